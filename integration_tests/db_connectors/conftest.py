@@ -1,5 +1,5 @@
 import pytest
-from utils import DebeziumContext, MongoDBContext, PostgresContext
+from utils import DebeziumContext, MongoDBContext, PostgresContext, RedisContext
 
 
 @pytest.fixture
@@ -15,3 +15,8 @@ def mongodb():
 @pytest.fixture
 def debezium():
     return DebeziumContext()
+
+
+@pytest.fixture
+def redis():
+    return RedisContext()
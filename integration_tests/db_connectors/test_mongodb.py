@@ -4,6 +4,7 @@ from utils import MONGODB_BASE_NAME, MONGODB_CONNECTION_STRING
 
 import pathway as pw
 from pathway.internals.parse_graph import G
+from pathway.tests.utils import wait_result_with_checker
 
 
 def test_mongodb(tmp_path, mongodb):
@@ -51,3 +52,70 @@ def test_mongodb(tmp_path, mongodb):
         {"name": "Water", "count": 600, "price": 0.5, "available": True},
     ]
     assert result == expected_result
+
+
+def read_output_changes(output_path) -> list[tuple[str, int, int]]:
+    changes = []
+    if not output_path.exists():
+        return changes
+    with open(output_path) as f:
+        for row in f:
+            data = json.loads(row)
+            changes.append((data["name"], data["count"], data["diff"]))
+    changes.sort()
+    return changes
+
+
+def test_mongodb_read_streaming_with_resume(tmp_path, mongodb):
+    class InputSchema(pw.Schema):
+        name: str
+        count: int
+
+    collection = mongodb.generate_collection_name()
+    output_path = tmp_path / "output.jsonl"
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    def run(checker) -> None:
+        G.clear()
+        table = pw.io.mongodb.read(
+            connection_string=MONGODB_CONNECTION_STRING,
+            database=MONGODB_BASE_NAME,
+            collection=collection,
+            schema=InputSchema,
+            autocommit_duration_ms=100,
+            persistent_id="documents",
+        )
+        pw.io.jsonlines.write(table, output_path)
+        wait_result_with_checker(
+            checker, 30, kwargs={"persistence_config": persistence_config}
+        )
+
+    mongodb.insert_document(collection, {"name": "Milk", "count": 500})
+    mongodb.insert_document(collection, {"name": "Water", "count": 600})
+
+    # The documents stored before the start are read from the snapshot, and the
+    # ones inserted afterwards from the change stream
+    is_snapshot_read = False
+
+    def snapshot_and_stream_checker() -> bool:
+        nonlocal is_snapshot_read
+        changes = read_output_changes(output_path)
+        if not is_snapshot_read:
+            if changes == [("Milk", 500, 1), ("Water", 600, 1)]:
+                is_snapshot_read = True
+                mongodb.insert_document(collection, {"name": "Bread", "count": 10})
+            return False
+        return changes == [("Bread", 10, 1), ("Milk", 500, 1), ("Water", 600, 1)]
+
+    run(snapshot_and_stream_checker)
+
+    # After the restart, the change stream is resumed from the persisted token, so
+    # only the changes made while the program was stopped are read
+    mongodb.insert_document(collection, {"name": "Juice", "count": 20})
+    mongodb.delete_document(collection, {"name": "Milk"})
+    run(
+        lambda: read_output_changes(output_path)
+        == [("Juice", 20, 1), ("Milk", 500, -1)]
+    )
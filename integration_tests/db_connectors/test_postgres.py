@@ -1,9 +1,12 @@
 import json
 
+import pytest
 from utils import POSTGRES_SETTINGS
 
 import pathway as pw
+from pathway.internals import api
 from pathway.internals.parse_graph import G
+from pathway.tests.utils import FileLinesNumberChecker, wait_result_with_checker
 
 
 def test_psql_output_stream(tmp_path, postgres):
@@ -87,3 +90,128 @@ def test_psql_output_snapshot(tmp_path, postgres):
         {"name": "Water", "count": 600, "price": 0.5, "available": True},
     ]
     assert rows == expected_rows
+
+
+class ReplicatedSchema(pw.Schema):
+    id: int = pw.column_definition(primary_key=True)
+    name: str
+
+
+def read_output_changes(output_path) -> list[tuple[int, str, int]]:
+    changes = []
+    with open(output_path) as f:
+        for row in f:
+            data = json.loads(row)
+            changes.append((data["id"], data["name"], data["diff"]))
+    changes.sort()
+    return changes
+
+
+def test_psql_replication_read_and_resume(tmp_path, postgres):
+    table_name = postgres.create_table(ReplicatedSchema, used_for_output=False)
+    slot_name, publication_name = postgres.create_replication(table_name)
+    output_path = tmp_path / "output.jsonl"
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    def run(expected_changes: list[tuple[int, str, int]]) -> None:
+        G.clear()
+        table = pw.io.postgres.read(
+            POSTGRES_SETTINGS,
+            table_name,
+            ReplicatedSchema,
+            slot_name=slot_name,
+            publication_name=publication_name,
+            autocommit_duration_ms=100,
+            persistent_id="replicated",
+        )
+        pw.io.jsonlines.write(table, output_path)
+        wait_result_with_checker(
+            FileLinesNumberChecker(output_path, len(expected_changes)),
+            30,
+            kwargs={"persistence_config": persistence_config},
+        )
+        assert read_output_changes(output_path) == expected_changes
+
+    try:
+        postgres.insert_row(table_name, {"id": 1, "name": "one"})
+        postgres.insert_row(table_name, {"id": 2, "name": "two"})
+        run([(1, "one", 1), (2, "two", 1)])
+
+        # Only the changes made after the first run are read after the restart
+        postgres.insert_row(table_name, {"id": 3, "name": "three"})
+        postgres.cursor.execute(f"UPDATE {table_name} SET name = 'uno' WHERE id = 1")
+        postgres.cursor.execute(f"DELETE FROM {table_name} WHERE id = 2")
+        run([(1, "one", -1), (1, "uno", 1), (2, "two", -1), (3, "three", 1)])
+    finally:
+        postgres.drop_replication(slot_name, publication_name)
+
+
+def test_psql_replication_truncate(tmp_path, postgres):
+    table_name = postgres.create_table(ReplicatedSchema, used_for_output=False)
+    slot_name, publication_name = postgres.create_replication(table_name)
+
+    try:
+        postgres.insert_row(table_name, {"id": 1, "name": "one"})
+        postgres.cursor.execute(f"TRUNCATE {table_name}")
+
+        G.clear()
+        table = pw.io.postgres.read(
+            POSTGRES_SETTINGS,
+            table_name,
+            ReplicatedSchema,
+            slot_name=slot_name,
+            publication_name=publication_name,
+            autocommit_duration_ms=100,
+        )
+        pw.io.jsonlines.write(table, tmp_path / "output.jsonl")
+        with pytest.raises(api.EngineError, match="was truncated"):
+            pw.run()
+    finally:
+        postgres.drop_replication(slot_name, publication_name)
+
+
+@pytest.mark.parametrize("with_persistence", [False, True])
+def test_psql_replication_transaction_above_peek_limit(
+    tmp_path, postgres, with_persistence
+):
+    table_name = postgres.create_table(ReplicatedSchema, used_for_output=False)
+    slot_name, publication_name = postgres.create_replication(table_name)
+    output_path = tmp_path / "output.jsonl"
+    persistence_config = None
+    if with_persistence:
+        persistence_config = pw.persistence.Config(
+            pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+        )
+
+    try:
+        # The transaction has more changes than a single peek of the slot returns,
+        # so the transaction after it is read only if the slot is advanced past it
+        postgres.cursor.execute(
+            f"INSERT INTO {table_name} (id, name) "
+            "SELECT id, 'bulk' FROM generate_series(1, 1500) AS id"
+        )
+        postgres.insert_row(table_name, {"id": 1501, "name": "last"})
+
+        G.clear()
+        table = pw.io.postgres.read(
+            POSTGRES_SETTINGS,
+            table_name,
+            ReplicatedSchema,
+            slot_name=slot_name,
+            publication_name=publication_name,
+            autocommit_duration_ms=100,
+            persistent_id="replicated",
+        )
+        pw.io.jsonlines.write(table, output_path)
+        wait_result_with_checker(
+            FileLinesNumberChecker(output_path, 1501),
+            30,
+            kwargs={"persistence_config": persistence_config},
+        )
+        changes = read_output_changes(output_path)
+        assert changes[-1] == (1501, "last", 1)
+        assert changes[:-1] == [(key, "bulk", 1) for key in range(1, 1501)]
+    finally:
+        postgres.drop_replication(slot_name, publication_name)
@@ -0,0 +1,110 @@
+import json
+
+from utils import REDIS_CONNECTION_STRING
+
+import pathway as pw
+from pathway.internals.parse_graph import G
+from pathway.tests.utils import wait_result_with_checker
+
+
+def read_output_rows(output_path) -> list[tuple[str, int, int]]:
+    rows = []
+    if not output_path.exists():
+        return rows
+    with open(output_path) as f:
+        for row in f:
+            data = json.loads(row)
+            rows.append((data["user"], data["amount"], data["diff"]))
+    rows.sort()
+    return rows
+
+
+def test_redis_read_with_resume(tmp_path, redis):
+    class InputSchema(pw.Schema):
+        user: str
+        amount: int
+
+    stream_key = redis.generate_key("stream")
+    output_path = tmp_path / "output.jsonl"
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    def run(checker) -> None:
+        G.clear()
+        table = pw.io.redis.read(
+            REDIS_CONNECTION_STRING,
+            stream_key,
+            schema=InputSchema,
+            autocommit_duration_ms=100,
+            persistent_id="orders",
+        )
+        pw.io.jsonlines.write(table, output_path)
+        wait_result_with_checker(
+            checker, 30, kwargs={"persistence_config": persistence_config}
+        )
+
+    redis.add_entry(stream_key, {"user": "alice", "amount": "10"})
+    redis.add_entry(stream_key, {"user": "bob", "amount": "20"})
+
+    # The entries added after the start are read as well
+    is_first_batch_read = False
+
+    def first_run_checker() -> bool:
+        nonlocal is_first_batch_read
+        rows = read_output_rows(output_path)
+        if not is_first_batch_read:
+            if rows == [("alice", 10, 1), ("bob", 20, 1)]:
+                is_first_batch_read = True
+                redis.add_entry(stream_key, {"user": "carol", "amount": "30"})
+            return False
+        return rows == [("alice", 10, 1), ("bob", 20, 1), ("carol", 30, 1)]
+
+    run(first_run_checker)
+
+    # The persisted entries are acknowledged in the consumer group, so after
+    # the restart only the entries added while the program was stopped are read
+    assert redis.pending_entries_count(stream_key, "pathway") == 0
+    redis.add_entry(stream_key, {"user": "dave", "amount": "40"})
+    run(lambda: read_output_rows(output_path) == [("dave", 40, 1)])
+    assert redis.pending_entries_count(stream_key, "pathway") == 0
+
+
+def test_redis_write_snapshot(tmp_path, redis):
+    table = pw.debug.table_from_markdown(
+        """
+        user  | amount | __time__ | __diff__
+        alice | 10     | 2        | 1
+        bob   | 20     | 2        | 1
+        carol | 30     | 2        | 1
+        alice | 10     | 4        | -1
+        alice | 15     | 4        | 1
+        bob   | 20     | 6        | -1
+        """
+    )
+    hash_prefix = redis.generate_key("hash") + ":"
+    json_prefix = redis.generate_key("json") + ":"
+    pw.io.redis.write_snapshot(
+        table, REDIS_CONNECTION_STRING, primary_key=["user"], key_prefix=hash_prefix
+    )
+    pw.io.redis.write_snapshot(
+        table,
+        REDIS_CONNECTION_STRING,
+        primary_key=["user"],
+        key_prefix=json_prefix,
+        value_type="json",
+    )
+    pw.run()
+
+    # The updated row is overwritten and the removed one is deleted
+    assert redis.get_hashes(hash_prefix) == {
+        f"{hash_prefix}alice": {"user": "alice", "amount": "15"},
+        f"{hash_prefix}carol": {"user": "carol", "amount": "30"},
+    }
+    documents = {
+        key: json.loads(value) for key, value in redis.get_strings(json_prefix).items()
+    }
+    assert documents == {
+        f"{json_prefix}alice": {"user": "alice", "amount": 15},
+        f"{json_prefix}carol": {"user": "carol", "amount": 30},
+    }
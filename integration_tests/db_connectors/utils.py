@@ -2,6 +2,7 @@ import time
 import uuid
 
 import psycopg2
+import redis
 import requests
 from pymongo import MongoClient
 
@@ -36,6 +37,8 @@ KAFKA_SETTINGS = {
 
 DEBEZIUM_CONNECTOR_URL = "http://debezium:8083/connectors"
 
+REDIS_CONNECTION_STRING = "redis://redis:6379/0"
+
 
 class PostgresContext:
 
@@ -105,6 +108,21 @@ class PostgresContext:
 
         return table_name
 
+    def create_replication(self, table_name: str) -> tuple[str, str]:
+        slot_name = f"slot_{table_name}"
+        publication_name = f"publication_{table_name}"
+        self.cursor.execute(
+            f"CREATE PUBLICATION {publication_name} FOR TABLE {table_name}"
+        )
+        self.cursor.execute(
+            "SELECT pg_create_logical_replication_slot(%s, 'pgoutput')", (slot_name,)
+        )
+        return slot_name, publication_name
+
+    def drop_replication(self, slot_name: str, publication_name: str) -> None:
+        self.cursor.execute("SELECT pg_drop_replication_slot(%s)", (slot_name,))
+        self.cursor.execute(f"DROP PUBLICATION {publication_name}")
+
     def get_table_contents(
         self, table_name: str, column_names: list[str]
     ) -> list[dict[str, str | int | bool | float]]:
@@ -151,6 +169,43 @@ class MongoDBContext:
         collection = db[collection_name]
         collection.insert_one(document)
 
+    def delete_document(
+        self, collection_name: str, filter: dict[str, int | bool | str | float]
+    ) -> None:
+        db = self.client[MONGODB_BASE_NAME]
+        collection = db[collection_name]
+        collection.delete_one(filter)
+
+
+class RedisContext:
+    client: redis.Redis
+
+    def __init__(self):
+        self.client = redis.Redis.from_url(
+            REDIS_CONNECTION_STRING, decode_responses=True
+        )
+
+    def generate_key(self, kind: str) -> str:
+        return f'{kind}_{str(uuid.uuid4()).replace("-", "")}'
+
+    def add_entry(self, stream_key: str, fields: dict[str, str]) -> None:
+        self.client.xadd(stream_key, fields)
+
+    def pending_entries_count(self, stream_key: str, consumer_group: str) -> int:
+        return self.client.xpending(stream_key, consumer_group)["pending"]
+
+    def get_hashes(self, key_prefix: str) -> dict[str, dict[str, str]]:
+        return {
+            key: self.client.hgetall(key)
+            for key in self.client.scan_iter(match=f"{key_prefix}*")
+        }
+
+    def get_strings(self, key_prefix: str) -> dict[str, str]:
+        return {
+            key: self.client.get(key)
+            for key in self.client.scan_iter(match=f"{key_prefix}*")
+        }
+
 
 class DebeziumContext:
 
@@ -0,0 +1,165 @@
+import hashlib
+import json
+import pathlib
+from uuid import uuid4
+
+import boto3
+import pandas as pd
+import pytest
+
+import pathway as pw
+from pathway.internals.parse_graph import G
+from pathway.tests.utils import CsvLinesNumberChecker, wait_result_with_checker
+
+KINESIS_ENDPOINT = "http://localstack:4566"
+KINESIS_REGION = "us-east-1"
+KPL_AGGREGATED_RECORD_MAGIC = b"\xf3\x89\x9a\xc2"
+
+
+@pytest.fixture(autouse=True)
+def localstack_credentials(monkeypatch):
+    monkeypatch.setenv("AWS_ACCESS_KEY_ID", "test")
+    monkeypatch.setenv("AWS_SECRET_ACCESS_KEY", "test")
+    monkeypatch.setenv("AWS_REGION", KINESIS_REGION)
+
+
+def kinesis_client():
+    return boto3.client(
+        "kinesis",
+        endpoint_url=KINESIS_ENDPOINT,
+        region_name=KINESIS_REGION,
+        aws_access_key_id="test",
+        aws_secret_access_key="test",
+    )
+
+
+def create_stream(n_shards: int) -> str:
+    stream_name = f"kinesis-{uuid4()}"
+    client = kinesis_client()
+    client.create_stream(StreamName=stream_name, ShardCount=n_shards)
+    client.get_waiter("stream_exists").wait(StreamName=stream_name)
+    return stream_name
+
+
+def put_records(stream_name: str, records: list[tuple[str, bytes]]) -> None:
+    client = kinesis_client()
+    for partition_key, data in records:
+        client.put_record(StreamName=stream_name, PartitionKey=partition_key, Data=data)
+
+
+def encode_varint(value: int) -> bytes:
+    result = bytearray()
+    while value >= 0x80:
+        result.append(value & 0x7F | 0x80)
+        value >>= 7
+    result.append(value)
+    return bytes(result)
+
+
+def encode_field(tag: int, value: int | bytes) -> bytes:
+    if isinstance(value, int):
+        return encode_varint(tag << 3) + encode_varint(value)
+    return encode_varint(tag << 3 | 2) + encode_varint(len(value)) + value
+
+
+def aggregated_record(
+    partition_keys: list[str], records: list[tuple[int, str]]
+) -> bytes:
+    """Builds a record in the same way as the Kinesis Producer Library does."""
+    message = b"".join(encode_field(1, key.encode()) for key in partition_keys)
+    for partition_key_index, data in records:
+        record = encode_field(1, partition_key_index) + encode_field(3, data.encode())
+        message += encode_field(3, record)
+    return KPL_AGGREGATED_RECORD_MAGIC + message + hashlib.md5(message).digest()
+
+
+def run_reader(
+    stream_name: str,
+    output_file: pathlib.Path,
+    n_expected: int,
+    persistence_config=None,
+) -> list[str]:
+    G.clear()
+    table = pw.io.kinesis.read(
+        stream_name,
+        endpoint=KINESIS_ENDPOINT,
+        format="plaintext",
+        autocommit_duration_ms=100,
+        persistent_id="1",
+    )
+    pw.io.csv.write(table, output_file)
+    wait_result_with_checker(
+        CsvLinesNumberChecker(output_file, n_expected),
+        60,
+        kwargs={"persistence_config": persistence_config},
+    )
+    return sorted(pd.read_csv(output_file)["data"])
+
+
+@pytest.mark.flaky(reruns=3)
+def test_kinesis_write_and_read(tmp_path: pathlib.Path):
+    stream_name = create_stream(2)
+    G.clear()
+    table = pw.debug.table_from_markdown(
+        """
+        user  | amount
+        alice | 10
+        bob   | 20
+        carol | 30
+        """
+    )
+    pw.io.kinesis.write(
+        table, stream_name, partition_key=table.user, endpoint=KINESIS_ENDPOINT
+    )
+    pw.run()
+
+    rows = run_reader(stream_name, tmp_path / "output.csv", 3)
+    assert sorted((row["user"], row["amount"]) for row in map(json.loads, rows)) == [
+        ("alice", 10),
+        ("bob", 20),
+        ("carol", 30),
+    ]
+
+
+@pytest.mark.flaky(reruns=3)
+def test_kinesis_resume_after_restart(tmp_path: pathlib.Path):
+    stream_name = create_stream(2)
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    put_records(stream_name, [("a", b"one"), ("b", b"two"), ("c", b"three")])
+    rows = run_reader(stream_name, tmp_path / "output_1.csv", 3, persistence_config)
+    assert rows == ["one", "three", "two"]
+
+    # Only the records put while the program was stopped are read
+    put_records(stream_name, [("d", b"four"), ("e", b"five")])
+    rows = run_reader(stream_name, tmp_path / "output_2.csv", 2, persistence_config)
+    assert rows == ["five", "four"]
+
+
+@pytest.mark.flaky(reruns=3)
+def test_kinesis_read_aggregated_records(tmp_path: pathlib.Path):
+    stream_name = create_stream(1)
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    put_records(
+        stream_name,
+        [
+            ("alice", aggregated_record(["alice", "bob"], [(0, "one"), (1, "two")])),
+            ("carol", b"three"),
+        ],
+    )
+    rows = run_reader(stream_name, tmp_path / "output_1.csv", 3, persistence_config)
+    assert rows == ["one", "three", "two"]
+
+    # The user records of an aggregated record get separate rows after a restart
+    # as well
+    put_records(
+        stream_name,
+        [("dave", aggregated_record(["dave"], [(0, "four"), (0, "five")]))],
+    )
+    rows = run_reader(stream_name, tmp_path / "output_2.csv", 2, persistence_config)
+    assert rows == ["five", "four"]
@@ -1,6 +1,9 @@
+import asyncio
 import pathlib
 from uuid import uuid4
 
+import nats
+import pandas as pd
 import pytest
 
 import pathway as pw
@@ -68,3 +71,93 @@ def test_nats_persistence(tmp_path: pathlib.Path):
     with open(input_file, "w") as f:
         f.write("one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\nnine")
     run(input_file, output_file, nats_topic, ["seven", "eight", "nine"], config)
+
+
+def create_jetstream_stream(stream_name: str, subjects: list[str]) -> None:
+    async def create():
+        client = await nats.connect(NATS_SERVER_URI)
+        await client.jetstream().add_stream(name=stream_name, subjects=subjects)
+        await client.close()
+
+    asyncio.run(create())
+
+
+def publish_to_jetstream(subject: str, messages: list[str]) -> None:
+    async def publish():
+        client = await nats.connect(NATS_SERVER_URI)
+        jetstream = client.jetstream()
+        for message in messages:
+            await jetstream.publish(subject, message.encode())
+        await client.close()
+
+    asyncio.run(publish())
+
+
+def jetstream_consumer_info(stream_name: str, durable_name: str):
+    async def get_info():
+        client = await nats.connect(NATS_SERVER_URI)
+        info = await client.jetstream().consumer_info(stream_name, durable_name)
+        await client.close()
+        return info
+
+    return asyncio.run(get_info())
+
+
+@pytest.mark.flaky(reruns=5)
+def test_nats_jetstream_ack_and_restart(tmp_path: pathlib.Path):
+    nats_topic = f"nats-{uuid4()}"
+    stream_name = f"stream-{uuid4()}"
+    durable_name = f"pathway-{uuid4()}"
+    create_jetstream_stream(stream_name, [f"{nats_topic}.>"])
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    def run_jetstream_reader(output_file: pathlib.Path, n_expected: int) -> list[str]:
+        G.clear()
+        table = pw.io.nats.read(
+            uri=NATS_SERVER_URI,
+            topic=f"{nats_topic}.>",
+            format="plaintext",
+            autocommit_duration_ms=100,
+            jetstream=stream_name,
+            durable_name=durable_name,
+            ack_wait_ms=60_000,
+            # Less than the number of messages, so the reading pauses until
+            # the messages read first are persisted and acknowledged
+            max_ack_pending=2,
+            persistent_id="1",
+        )
+        pw.io.csv.write(table, output_file)
+        wait_result_with_checker(
+            CsvLinesNumberChecker(output_file, n_expected),
+            30,
+            kwargs={"persistence_config": persistence_config},
+        )
+        return sorted(pd.read_csv(output_file)["data"])
+
+    # The messages published before the start are read
+    publish_to_jetstream(f"{nats_topic}.a", ["one", "two", "three"])
+    publish_to_jetstream(f"{nats_topic}.b", ["four", "five"])
+    assert run_jetstream_reader(tmp_path / "output_1.csv", 5) == [
+        "five",
+        "four",
+        "one",
+        "three",
+        "two",
+    ]
+
+    # All messages read are persisted and acknowledged
+    info = jetstream_consumer_info(stream_name, durable_name)
+    assert info.num_ack_pending == 0
+    assert info.num_pending == 0
+    assert info.config.max_ack_pending == 2
+    assert info.config.ack_wait == 60
+
+    # After the restart, only the new messages are read
+    publish_to_jetstream(f"{nats_topic}.a", ["six"])
+    publish_to_jetstream(f"{nats_topic}.c", ["seven"])
+    assert run_jetstream_reader(tmp_path / "output_2.csv", 2) == ["seven", "six"]
+    info = jetstream_consumer_info(stream_name, durable_name)
+    assert info.num_ack_pending == 0
+
@@ -0,0 +1,184 @@
+import json
+import pathlib
+from uuid import uuid4
+
+import pandas as pd
+import pytest
+from google.cloud import pubsub_v1  # type: ignore
+
+import pathway as pw
+from pathway.internals.parse_graph import G
+from pathway.tests.utils import CsvLinesNumberChecker, wait_result_with_checker
+
+PUBSUB_EMULATOR_HOST = "pubsub:8085"
+PROJECT_ID = "pathway-test"
+
+
+@pytest.fixture(autouse=True)
+def pubsub_emulator(monkeypatch):
+    monkeypatch.setenv("PUBSUB_EMULATOR_HOST", PUBSUB_EMULATOR_HOST)
+
+
+def create_topic_and_subscription() -> tuple[str, str]:
+    topic_id = f"pubsub-{uuid4()}"
+    subscription_id = f"{topic_id}-pathway"
+    publisher = pubsub_v1.PublisherClient()
+    topic_path = publisher.topic_path(PROJECT_ID, topic_id)
+    publisher.create_topic(request={"name": topic_path})
+    subscriber = pubsub_v1.SubscriberClient()
+    subscriber.create_subscription(
+        request={
+            "name": subscriber.subscription_path(PROJECT_ID, subscription_id),
+            "topic": topic_path,
+        }
+    )
+    return topic_id, subscription_id
+
+
+def publish_messages(
+    topic_id: str, messages: list[tuple[str, dict[str, str]]]
+) -> None:
+    publisher = pubsub_v1.PublisherClient()
+    topic_path = publisher.topic_path(PROJECT_ID, topic_id)
+    for data, attributes in messages:
+        publisher.publish(topic_path, data.encode(), **attributes).result()
+
+
+def pull_messages(subscription_id: str, n_messages: int) -> list:
+    subscriber = pubsub_v1.SubscriberClient()
+    subscription_path = subscriber.subscription_path(PROJECT_ID, subscription_id)
+    messages: list = []
+    while len(messages) < n_messages:
+        response = subscriber.pull(
+            request={"subscription": subscription_path, "max_messages": n_messages},
+            timeout=30,
+        )
+        messages.extend(message.message for message in response.received_messages)
+        if response.received_messages:
+            subscriber.acknowledge(
+                request={
+                    "subscription": subscription_path,
+                    "ack_ids": [
+                        message.ack_id for message in response.received_messages
+                    ],
+                }
+            )
+    return messages
+
+
+def run_reader(
+    subscription_id: str,
+    output_file: pathlib.Path,
+    n_expected: int,
+    persistence_config=None,
+) -> list[str]:
+    G.clear()
+    table = pw.io.pubsub.read(
+        PROJECT_ID,
+        subscription_id,
+        format="plaintext",
+        autocommit_duration_ms=100,
+        persistent_id="1",
+    )
+    pw.io.csv.write(table, output_file)
+    wait_result_with_checker(
+        CsvLinesNumberChecker(output_file, n_expected),
+        30,
+        kwargs={"persistence_config": persistence_config},
+    )
+    return sorted(pd.read_csv(output_file)["data"])
+
+
+@pytest.mark.flaky(reruns=3)
+def test_pubsub_read_attributes(tmp_path: pathlib.Path):
+    topic_id, subscription_id = create_topic_and_subscription()
+    publish_messages(
+        topic_id,
+        [
+            (json.dumps({"user": "alice", "amount": 10}), {"store": "berlin"}),
+            (json.dumps({"user": "bob", "amount": 20}), {}),
+        ],
+    )
+
+    class InputSchema(pw.Schema):
+        user: str
+        amount: int
+        store: str = pw.column_definition(default_value="unknown")
+
+    G.clear()
+    output_file = tmp_path / "output.csv"
+    table = pw.io.pubsub.read(
+        PROJECT_ID,
+        subscription_id,
+        format="json",
+        schema=InputSchema,
+        attribute_fields={"store": "store"},
+        autocommit_duration_ms=100,
+    )
+    pw.io.csv.write(table, output_file)
+    wait_result_with_checker(CsvLinesNumberChecker(output_file, 2), 30)
+
+    result = pd.read_csv(output_file).sort_values("user")
+    assert list(result["store"]) == ["berlin", "unknown"]
+
+
+@pytest.mark.flaky(reruns=3)
+def test_pubsub_resume_after_restart(tmp_path: pathlib.Path):
+    topic_id, subscription_id = create_topic_and_subscription()
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    publish_messages(topic_id, [("one", {}), ("two", {}), ("three", {})])
+    rows = run_reader(subscription_id, tmp_path / "output_1.csv", 3, persistence_config)
+    assert rows == ["one", "three", "two"]
+
+    # The persisted messages are acknowledged, so only the messages published
+    # while the program was stopped are read after the restart
+    publish_messages(topic_id, [("four", {}), ("five", {})])
+    rows = run_reader(subscription_id, tmp_path / "output_2.csv", 2, persistence_config)
+    assert rows == ["five", "four"]
+
+
+@pytest.mark.flaky(reruns=3)
+def test_pubsub_write_with_engine():
+    topic_id, subscription_id = create_topic_and_subscription()
+    G.clear()
+    table = pw.debug.table_from_markdown(
+        """
+        user  | amount
+        alice | 10
+        bob   | 20
+        """
+    )
+    pw.io.pubsub.write(
+        table,
+        None,
+        PROJECT_ID,
+        topic_id,
+        format="json",
+        ordering_key=table.user,
+    )
+    pw.run()
+
+    messages = pull_messages(subscription_id, 2)
+    rows = [json.loads(message.data) for message in messages]
+    assert sorted((row["user"], row["amount"], row["diff"]) for row in rows) == [
+        ("alice", 10, 1),
+        ("bob", 20, 1),
+    ]
+    assert sorted(message.ordering_key for message in messages) == ["alice", "bob"]
+    assert all(message.attributes["pathway_diff"] == "1" for message in messages)
+
+
+@pytest.mark.flaky(reruns=3)
+def test_pubsub_write_with_publisher():
+    topic_id, subscription_id = create_topic_and_subscription()
+    G.clear()
+    table = pw.debug.table_from_pandas(pd.DataFrame({"data": [b"one", b"two"]}))
+    pw.io.pubsub.write(table, pubsub_v1.PublisherClient(), PROJECT_ID, topic_id)
+    pw.run()
+
+    messages = pull_messages(subscription_id, 2)
+    assert sorted(message.data for message in messages) == [b"one", b"two"]
+    assert all(message.attributes["pathway_diff"] == "1" for message in messages)
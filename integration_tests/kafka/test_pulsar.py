@@ -0,0 +1,191 @@
+import pathlib
+from uuid import uuid4
+
+import pandas as pd
+import pulsar
+import pytest
+import requests
+
+import pathway as pw
+from pathway.internals.parse_graph import G
+from pathway.tests.utils import CsvLinesNumberChecker, wait_result_with_checker
+
+PULSAR_SERVICE_URL = "pulsar://pulsar:6650"
+PULSAR_ADMIN_URL = "http://pulsar:8080/admin/v2"
+
+
+def topic_name() -> str:
+    return f"persistent://public/default/pulsar-{uuid4()}"
+
+
+def create_partitioned_topic(topic: str, n_partitions: int) -> None:
+    path = topic.removeprefix("persistent://")
+    response = requests.put(
+        f"{PULSAR_ADMIN_URL}/persistent/{path}/partitions", json=n_partitions
+    )
+    response.raise_for_status()
+
+
+def publish_messages(
+    topic: str, messages: list[tuple[str, str]], hashing_scheme=None
+) -> None:
+    client = pulsar.Client(PULSAR_SERVICE_URL)
+    kwargs = {} if hashing_scheme is None else {"hashing_scheme": hashing_scheme}
+    producer = client.create_producer(topic, batching_enabled=False, **kwargs)
+    for key, message in messages:
+        producer.send(message.encode(), partition_key=key)
+    client.close()
+
+
+def consume_messages(
+    topic: str, subscription: str, n_messages: int
+) -> list[pulsar.Message]:
+    client = pulsar.Client(PULSAR_SERVICE_URL)
+    consumer = client.subscribe(
+        topic,
+        subscription,
+        consumer_type=pulsar.ConsumerType.Failover,
+        initial_position=pulsar.InitialPosition.Earliest,
+    )
+    messages = []
+    for _ in range(n_messages):
+        message = consumer.receive(timeout_millis=30_000)
+        consumer.acknowledge(message)
+        messages.append(message)
+    client.close()
+    return messages
+
+
+def run(input_file, output_file, topic, new_entries, persistence_config=None):
+    G.clear()
+    table = pw.io.plaintext.read(input_file, mode="static")
+    pw.io.pulsar.write(table, PULSAR_SERVICE_URL, topic, format="json")
+
+    class InputSchema(pw.Schema):
+        data: str
+
+    table_reread = pw.io.pulsar.read(
+        PULSAR_SERVICE_URL,
+        topic,
+        schema=InputSchema,
+        format="json",
+        autocommit_duration_ms=100,
+        persistent_id="1",
+    )
+    pw.io.csv.write(table_reread, output_file)
+
+    wait_result_with_checker(
+        CsvLinesNumberChecker(output_file, len(new_entries)),
+        30,
+        kwargs={"persistence_config": persistence_config},
+    )
+    assert sorted(pd.read_csv(output_file)["data"]) == sorted(new_entries)
+
+
+@pytest.mark.flaky(reruns=5)
+def test_pulsar_simple(tmp_path: pathlib.Path):
+    topic = topic_name()
+    create_partitioned_topic(topic, 3)
+    input_file = tmp_path / "input.txt"
+    output_file = tmp_path / "output.txt"
+
+    with open(input_file, "w") as f:
+        f.write("one\ntwo\nthree\nfour\n")
+    run(input_file, output_file, topic, ["one", "two", "three", "four"])
+
+
+@pytest.mark.flaky(reruns=5)
+def test_pulsar_persistence(tmp_path: pathlib.Path):
+    topic = topic_name()
+    create_partitioned_topic(topic, 3)
+    input_file = tmp_path / "input.txt"
+    output_file = tmp_path / "output.txt"
+
+    config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    with open(input_file, "w") as f:
+        f.write("one\ntwo\nthree\nfour\n")
+    run(input_file, output_file, topic, ["one", "two", "three", "four"], config)
+    with open(input_file, "w") as f:
+        f.write("one\ntwo\nthree\nfour\nfive\nsix\n")
+    run(input_file, output_file, topic, ["five", "six"], config)
+
+
+@pytest.mark.flaky(reruns=5)
+def test_pulsar_resume_from_persisted_message_id(tmp_path: pathlib.Path):
+    topic = topic_name()
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    def run_reader(output_file: pathlib.Path, n_expected: int) -> list[str]:
+        G.clear()
+        table = pw.io.pulsar.read(
+            PULSAR_SERVICE_URL,
+            topic,
+            format="plaintext",
+            autocommit_duration_ms=100,
+            persistent_id="1",
+        )
+        pw.io.csv.write(table, output_file)
+        wait_result_with_checker(
+            CsvLinesNumberChecker(output_file, n_expected),
+            30,
+            kwargs={"persistence_config": persistence_config},
+        )
+        return sorted(pd.read_csv(output_file)["data"])
+
+    publish_messages(topic, [("a", "one"), ("b", "two"), ("c", "three")])
+    assert run_reader(tmp_path / "output_1.csv", 3) == ["one", "three", "two"]
+
+    # Another consumer of the subscription moves its cursor past the new
+    # messages, but the reader seeks back to the persisted message ID
+    publish_messages(topic, [("d", "four"), ("e", "five")])
+    consume_messages(topic, "pathway", 2)
+    assert run_reader(tmp_path / "output_2.csv", 2) == ["five", "four"]
+
+
+@pytest.mark.flaky(reruns=5)
+def test_pulsar_key_routing_matches_java_client(tmp_path: pathlib.Path):
+    n_partitions = 5
+    keys = [f"user-{index}" for index in range(20)]
+
+    # The Python client can use the default hashing scheme of the Java client
+    reference_topic = topic_name()
+    create_partitioned_topic(reference_topic, n_partitions)
+    publish_messages(
+        reference_topic,
+        [(key, key) for key in keys],
+        hashing_scheme=pulsar.HashingScheme.JavaStringHash,
+    )
+    expected_partitions = {
+        message.partition_key(): message.topic_name()
+        for message in consume_messages(reference_topic, "check", len(keys))
+    }
+
+    topic = topic_name()
+    create_partitioned_topic(topic, n_partitions)
+    G.clear()
+    table = pw.debug.table_from_pandas(pd.DataFrame({"user": keys}))
+    pw.io.pulsar.write(
+        table,
+        PULSAR_SERVICE_URL,
+        topic,
+        format="plaintext",
+        key=table.user,
+        value=table.user,
+    )
+    pw.run()
+
+    partitions = {
+        message.partition_key(): message.topic_name()
+        for message in consume_messages(topic, "check", len(keys))
+    }
+    assert {
+        key: partition.removeprefix(topic) for key, partition in partitions.items()
+    } == {
+        key: partition.removeprefix(reference_topic)
+        for key, partition in expected_partitions.items()
+    }
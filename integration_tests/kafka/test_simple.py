@@ -4,6 +4,7 @@ import json
 import pathlib
 import threading
 import time
+from uuid import uuid4
 
 import pytest
 
@@ -464,6 +465,179 @@ def test_kafka_recovery(tmp_path: pathlib.Path, kafka_context: KafkaTestContext)
     )
 
 
+@pytest.mark.flaky(reruns=3)
+def test_kafka_transactional_output_recovery(
+    tmp_path: pathlib.Path, kafka_context: KafkaTestContext
+):
+    persistent_storage_path = tmp_path / "PStorage"
+
+    def read_committed_values() -> list[str]:
+        messages = kafka_context.read_topic(
+            kafka_context.output_topic, isolation_level="read_committed"
+        )
+        return [json.loads(message.value)["v"] for message in messages]
+
+    def run_with_several_sinks(n_expected: int, output_path: pathlib.Path) -> None:
+        G.clear()
+        table = pw.io.kafka.read(
+            rdkafka_settings=kafka_context.default_rdkafka_settings(),
+            topic=kafka_context.input_topic,
+            format="json",
+            value_columns=["v"],
+            primary_key=["k"],
+            autocommit_duration_ms=100,
+            persistent_id="1",
+        )
+        pw.io.kafka.write(
+            table,
+            rdkafka_settings=kafka_context.default_rdkafka_settings(),
+            topic_name=kafka_context.output_topic,
+            transactional_id="pathway-transactional-test",
+        )
+        pw.io.jsonlines.write(table, output_path)
+        pw.io.csv.write(table.filter(pw.this.v != ""), tmp_path / "filtered.csv")
+
+        def all_sinks_finished() -> bool:
+            return (
+                FileLinesNumberChecker(output_path, n_expected)()
+                and len(read_committed_values()) >= n_expected
+            )
+
+        wait_result_with_checker(
+            all_sinks_finished,
+            30,
+            kwargs={
+                "persistence_config": pw.persistence.Config(
+                    pw.persistence.Backend.filesystem(persistent_storage_path),
+                ),
+            },
+        )
+
+    kafka_context.fill(
+        [
+            json.dumps({"k": 0, "v": "foo"}),
+            json.dumps({"k": 1, "v": "bar"}),
+            json.dumps({"k": 2, "v": "baz"}),
+        ]
+    )
+    run_with_several_sinks(3, tmp_path / "output_1.jsonl")
+    assert sorted(read_committed_values()) == ["bar", "baz", "foo"]
+
+    # The program is stopped without finishing, so the output that has already
+    # been committed must not be produced again after the restart
+    kafka_context.fill(
+        [
+            json.dumps({"k": 3, "v": "foofoo"}),
+            json.dumps({"k": 4, "v": "barbar"}),
+        ]
+    )
+    run_with_several_sinks(2, tmp_path / "output_2.jsonl")
+    assert sorted(read_committed_values()) == [
+        "bar",
+        "barbar",
+        "baz",
+        "foo",
+        "foofoo",
+    ]
+
+
+@pytest.mark.flaky(reruns=3)
+def test_kafka_transactional_output_with_snapshot_interval(
+    tmp_path: pathlib.Path, kafka_context: KafkaTestContext
+):
+    # With the snapshot interval, the finalized time lags behind the output, so the
+    # messages written while a transaction waits for it go to the next transaction
+    def read_committed_values() -> list[str]:
+        messages = kafka_context.read_topic(
+            kafka_context.output_topic, isolation_level="read_committed"
+        )
+        return [json.loads(message.value)["v"] for message in messages]
+
+    def stream_inputs():
+        for batch_start in range(0, 100, 10):
+            kafka_context.fill(
+                json.dumps({"k": key, "v": f"value-{key}"})
+                for key in range(batch_start, batch_start + 10)
+            )
+            time.sleep(0.3)
+
+    G.clear()
+    table = pw.io.kafka.read(
+        rdkafka_settings=kafka_context.default_rdkafka_settings(),
+        topic=kafka_context.input_topic,
+        format="json",
+        value_columns=["v"],
+        primary_key=["k"],
+        autocommit_duration_ms=100,
+        persistent_id="1",
+    )
+    pw.io.kafka.write(
+        table,
+        rdkafka_settings=kafka_context.default_rdkafka_settings(),
+        topic_name=kafka_context.output_topic,
+        transactional_id="pathway-transactional-interval-test",
+    )
+    threading.Thread(target=stream_inputs, daemon=True).start()
+    wait_result_with_checker(
+        lambda: len(read_committed_values()) >= 100,
+        60,
+        kwargs={
+            "persistence_config": pw.persistence.Config(
+                pw.persistence.Backend.filesystem(tmp_path / "PStorage"),
+                snapshot_interval_ms=1000,
+            ),
+        },
+    )
+    assert sorted(read_committed_values()) == sorted(
+        f"value-{key}" for key in range(100)
+    )
+
+
+@pytest.mark.flaky(reruns=3)
+def test_kafka_topic_pattern(tmp_path: pathlib.Path, kafka_context: KafkaTestContext):
+    prefix = f"pattern-{uuid4()}"
+    kafka_context.create_extra_topic(f"{prefix}-1")
+    kafka_context.create_extra_topic(f"{prefix}-x")
+    kafka_context.create_extra_topic(f"old-{prefix}-2")
+    kafka_context.fill(["one"], topic=f"{prefix}-1")
+    kafka_context.fill(["skipped-x"], topic=f"{prefix}-x")
+    kafka_context.fill(["skipped-old"], topic=f"old-{prefix}-2")
+
+    # `\d` is a class of the Rust regex syntax, which POSIX regular expressions lack
+    table = pw.io.kafka.read(
+        rdkafka_settings=kafka_context.default_rdkafka_settings(),
+        topic_pattern=prefix + r"-\d+$",
+        format="plaintext",
+        autocommit_duration_ms=100,
+    )
+    output_path = tmp_path / "output.csv"
+    pw.io.csv.write(table, output_path)
+
+    def create_topic_later():
+        time.sleep(5)
+        kafka_context.create_extra_topic(f"{prefix}-2")
+        kafka_context.fill(["two"], topic=f"{prefix}-2")
+
+    thread = threading.Thread(target=create_topic_later, daemon=True)
+    thread.start()
+
+    # The topic created after the start is read after the next refresh of the list
+    wait_result_with_checker(
+        expect_csv_checker(
+            """
+            data
+            one
+            two
+            """,
+            output_path,
+            usecols=["data"],
+            index_col=["data"],
+        ),
+        90,
+    )
+    thread.join()
+
+
 @pytest.mark.flaky(reruns=3)
 def test_start_from_timestamp_ms_seek_to_middle(
     tmp_path: pathlib.Path, kafka_context: KafkaTestContext
@@ -577,3 +751,92 @@ def test_start_from_timestamp_ms_seek_to_end(
         ),
         30,
     )
+
+
+def read_jsonlines_data(path: pathlib.Path) -> list[str]:
+    if not path.exists():
+        return []
+    with open(path) as f:
+        return sorted(json.loads(row)["data"] for row in f)
+
+
+@pytest.mark.flaky(reruns=3)
+def test_kafka_static_mode(tmp_path: pathlib.Path, kafka_context: KafkaTestContext):
+    kafka_context.set_input_topic_partitions(3)
+    kafka_context.fill([str(i) for i in range(6)])
+
+    table = pw.io.kafka.read(
+        rdkafka_settings=kafka_context.default_rdkafka_settings(),
+        topic=kafka_context.input_topic,
+        format="plaintext",
+        mode="static",
+    )
+    output_path = tmp_path / "output.jsonl"
+    pw.io.jsonlines.write(table, output_path)
+
+    # The run finishes after every partition is read up to its end
+    pw.run()
+    assert read_jsonlines_data(output_path) == [str(i) for i in range(6)]
+
+
+@pytest.mark.flaky(reruns=3)
+def test_kafka_start_and_end_timestamps(
+    tmp_path: pathlib.Path, kafka_context: KafkaTestContext
+):
+    kafka_context.set_input_topic_partitions(2)
+    kafka_context.fill(["before-1", "before-2"])
+    time.sleep(2)
+    start_from_timestamp_ms = int(time.time() * 1000)
+    time.sleep(2)
+    kafka_context.fill(["inside-1", "inside-2", "inside-3"])
+    time.sleep(2)
+    end_at_timestamp_ms = int(time.time() * 1000)
+    time.sleep(2)
+    kafka_context.fill(["after-1", "after-2"])
+
+    table = pw.io.kafka.read(
+        rdkafka_settings=kafka_context.default_rdkafka_settings(),
+        topic=kafka_context.input_topic,
+        format="plaintext",
+        start_from_timestamp_ms=start_from_timestamp_ms,
+        end_at_timestamp_ms=end_at_timestamp_ms,
+    )
+    output_path = tmp_path / "output.jsonl"
+    pw.io.jsonlines.write(table, output_path)
+
+    pw.run()
+    assert read_jsonlines_data(output_path) == ["inside-1", "inside-2", "inside-3"]
+
+
+@pytest.mark.flaky(reruns=3)
+def test_kafka_static_mode_recovery(
+    tmp_path: pathlib.Path, kafka_context: KafkaTestContext
+):
+    kafka_context.set_input_topic_partitions(2)
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage"),
+    )
+
+    def run_static(output_path: pathlib.Path) -> list[str]:
+        G.clear()
+        table = pw.io.kafka.read(
+            rdkafka_settings=kafka_context.default_rdkafka_settings(),
+            topic=kafka_context.input_topic,
+            format="plaintext",
+            mode="static",
+            persistent_id="1",
+        )
+        pw.io.jsonlines.write(table, output_path)
+        pw.run(persistence_config=persistence_config)
+        return read_jsonlines_data(output_path)
+
+    kafka_context.fill(["a", "b", "c"])
+    assert run_static(tmp_path / "output_1.jsonl") == ["a", "b", "c"]
+
+    # The restarted read continues from the persisted offsets in every partition
+    kafka_context.fill(["d", "e"])
+    assert run_static(tmp_path / "output_2.jsonl") == ["d", "e"]
+
+    # Nothing new is read if there are no new messages
+    assert run_static(tmp_path / "output_3.jsonl") == []
+
@@ -15,6 +15,7 @@ class KafkaTestContext:
     _admin: KafkaAdminClient
     _input_topic: str
     _output_topic: str
+    _extra_topics: list[str]
 
     def __init__(self) -> None:
         self._producer = KafkaProducer(
@@ -25,6 +26,7 @@ class KafkaTestContext:
         )
         self._input_topic = f"integration-tests-{uuid4()}"
         self._output_topic = f"integration-tests-{uuid4()}"
+        self._extra_topics = []
         self._create_topic(self.input_topic)
         self._create_topic(self.output_topic)
 
@@ -36,27 +38,41 @@ class KafkaTestContext:
     def _delete_topic(self, name: str) -> None:
         self._admin.delete_topics(topics=[name])
 
-    def send(self, message: str | tuple[str, str]) -> None:
+    def create_extra_topic(self, name: str, num_partitions: int = 1) -> None:
+        self._create_topic(name, num_partitions)
+        self._extra_topics.append(name)
+
+    def send(self, message: str | tuple[str, str], topic: str | None = None) -> None:
         if isinstance(message, tuple):
             (key, value) = message
         else:
             (key, value) = str(uuid4()), message
-        self._producer.send(self.input_topic, key=key.encode(), value=value.encode())
+        self._producer.send(
+            topic or self.input_topic, key=key.encode(), value=value.encode()
+        )
 
     def set_input_topic_partitions(self, num_partitions: int):
         self._delete_topic(self._input_topic)
         self._create_topic(self._input_topic, num_partitions)
 
-    def fill(self, messages: Iterable[str | tuple[str, str]]) -> None:
+    def fill(
+        self, messages: Iterable[str | tuple[str, str]], topic: str | None = None
+    ) -> None:
         for msg in messages:
-            self.send(msg)
+            self.send(msg, topic)
         self._producer.flush()
 
-    def read_topic(self, topic, poll_timeout_ms: int = 1000) -> list[ConsumerRecord]:
+    def read_topic(
+        self,
+        topic,
+        poll_timeout_ms: int = 1000,
+        isolation_level: str = "read_uncommitted",
+    ) -> list[ConsumerRecord]:
         consumer = KafkaConsumer(
             topic,
             auto_offset_reset="earliest",
             bootstrap_servers=kafka_settings["bootstrap_servers"],
+            isolation_level=isolation_level,
         )
         messages = []
         while True:
@@ -88,6 +104,8 @@ class KafkaTestContext:
     def teardown(self) -> None:
         self._delete_topic(self.input_topic)
         self._delete_topic(self.output_topic)
+        for topic in self._extra_topics:
+            self._delete_topic(topic)
         self._producer.close()
         self._admin.close()
 
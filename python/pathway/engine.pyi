@@ -63,6 +63,8 @@ class ReadMethod(Enum):
 class DebeziumDBType(Enum):
     POSTGRES: DebeziumDBType
     MONGO_DB: DebeziumDBType
+    MY_SQL: DebeziumDBType
+    SQL_SERVER: DebeziumDBType
 
 class KeyGenerationPolicy(Enum):
     ALWAYS_AUTOGENERATE: KeyGenerationPolicy
@@ -779,6 +781,9 @@ class ElasticSearchAuth:
 class ElasticSearchParams:
     def __init__(self, *args, **kwargs): ...
 
+class WebhookSettings:
+    def __init__(self, *args, **kwargs): ...
+
 class PersistenceConfig:
     def __init__(self, *args, **kwargs): ...
 
@@ -2,6 +2,7 @@
 
 from pathway.io import (
     airbyte,
+    amqp,
     bigquery,
     csv,
     debezium,
@@ -9,24 +10,31 @@ from pathway.io import (
     elasticsearch,
     fs,
     gdrive,
+    grpc,
     http,
     iceberg,
     jsonlines,
     kafka,
+    kinesis,
     logstash,
     minio,
     mongodb,
+    mqtt,
+    mysql,
     nats,
     null,
     plaintext,
     postgres,
     pubsub,
+    pulsar,
     pyfilesystem,
     python,
+    redis,
     redpanda,
     s3,
     s3_csv,
     slack,
+    socket,
     sqlite,
 )
 from pathway.io._subscribe import OnChangeCallback, OnFinishCallback, subscribe
@@ -34,6 +42,7 @@ from pathway.io._utils import CsvParserSettings
 
 __all__ = [
     "airbyte",
+    "amqp",
     "bigquery",
     "csv",
     "CsvParserSettings",
@@ -52,6 +61,7 @@ __all__ = [
     "python",
     "OnChangeCallback",
     "OnFinishCallback",
+    "redis",
     "redpanda",
     "slack",
     "subscribe",
@@ -63,5 +73,11 @@ __all__ = [
     "deltalake",
     "iceberg",
     "mongodb",
+    "mqtt",
+    "mysql",
     "nats",
+    "pulsar",
+    "kinesis",
+    "grpc",
+    "socket",
 ]
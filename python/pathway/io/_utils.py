@@ -44,6 +44,8 @@ _DATA_FORMAT_MAPPING = {
     "binary": "identity",
     "plaintext_by_file": "identity",
     "plaintext_by_object": "identity",
+    "syslog": "syslog",
+    "parquet": "transparent",
 }
 
 _PATHWAY_TYPE_MAPPING: dict[PathwayType, dt.DType] = {
@@ -69,6 +71,8 @@ SUPPORTED_INPUT_FORMATS: set[str] = {
     "binary",
     "plaintext_by_file",
     "plaintext_by_object",
+    "syslog",
+    "parquet",
 }
 
 
@@ -80,6 +84,18 @@ class MetadataSchema(Schema):
     _metadata: dict
 
 
+class SyslogSchema(pw.Schema):
+    facility: int
+    severity: int
+    timestamp: pw.DateTimeUtc | None
+    hostname: str | None
+    app_name: str | None
+    procid: str | None
+    msgid: str | None
+    structured_data: pw.Json
+    message: str
+
+
 def get_data_format_type(format: str, supported_formats: set[str]):
     if format not in _DATA_FORMAT_MAPPING or format not in supported_formats:
         raise ValueError(f"data format `{format}` not supported")
@@ -122,6 +138,16 @@ def internal_read_method(format: str) -> ReadMethod:
     return ReadMethod.BY_LINE
 
 
+def internal_decompression(format: str, decompression: str) -> str:
+    if format == "parquet" and decompression != "none":
+        raise ValueError("Decompression is not supported for the parquet format")
+    # The objects read in full as bytes are decompressed only if the codec is
+    # given explicitly
+    if format == "binary" and decompression == "auto":
+        return "none"
+    return decompression
+
+
 class CsvParserSettings:
     """
     Class representing settings for the CSV parser.
@@ -276,9 +302,26 @@ def construct_schema_and_data_format(
     primary_key: list[str] | None = None,
     types: dict[str, PathwayType] | None = None,
     default_values: dict[str, Any] | None = None,
+    session_type: api.SessionType = api.SessionType.NATIVE,
+    header_fields: dict[str, str] | None = None,
+    timestamp_field: str | None = None,
+    attribute_fields: dict[str, str] | None = None,
+    publish_time_field: str | None = None,
     _stacklevel: int = 1,
 ) -> tuple[type[Schema], api.DataFormat]:
     data_format_type = get_data_format_type(format, SUPPORTED_INPUT_FORMATS)
+    if data_format_type != "jsonlines" and (
+        header_fields is not None or timestamp_field is not None
+    ):
+        raise ValueError(
+            "header_fields and timestamp_field are only supported for json format"
+        )
+    if data_format_type != "jsonlines" and (
+        attribute_fields is not None or publish_time_field is not None
+    ):
+        raise ValueError(
+            "attribute_fields and publish_time_field are only supported for json format"
+        )
 
     if data_format_type == "identity":
         kwargs = locals()
@@ -314,8 +357,12 @@ def construct_schema_and_data_format(
                 if autogenerate_key
                 else api.KeyGenerationPolicy.PREFER_MESSAGE_KEY
             ),
+            session_type=session_type,
         )
 
+    if data_format_type == "syslog" and schema is None and value_columns is None:
+        schema = SyslogSchema
+
     assert_schema_or_value_columns_not_none(schema, value_columns, data_format_type)
 
     if with_metadata:
@@ -337,6 +384,8 @@ def construct_schema_and_data_format(
     if data_format_type == "dsv":
         if json_field_paths is not None:
             raise ValueError("Unexpected argument for csv format: json_field_paths")
+        if session_type != api.SessionType.NATIVE:
+            raise ValueError("Upsert mode is not supported for csv format")
         return schema, api.DataFormat(
             **api_schema,
             format_type=data_format_type,
@@ -349,6 +398,35 @@ def construct_schema_and_data_format(
             **api_schema,
             format_type=data_format_type,
             column_paths=json_field_paths,
+            session_type=session_type,
+            header_fields=header_fields,
+            timestamp_field=timestamp_field,
+            attribute_fields=attribute_fields,
+            publish_time_field=publish_time_field,
+        )
+    elif data_format_type == "syslog":
+        if csv_settings is not None:
+            raise ValueError("Unexpected argument for syslog format: csv_settings")
+        if json_field_paths is not None:
+            raise ValueError("Unexpected argument for syslog format: json_field_paths")
+        if session_type != api.SessionType.NATIVE:
+            raise ValueError("Upsert mode is not supported for syslog format")
+        return schema, api.DataFormat(
+            **api_schema,
+            format_type=data_format_type,
+        )
+    elif data_format_type == "transparent":
+        if csv_settings is not None:
+            raise ValueError("Unexpected argument for parquet format: csv_settings")
+        if json_field_paths is not None:
+            raise ValueError(
+                "Unexpected argument for parquet format: json_field_paths"
+            )
+        if session_type != api.SessionType.NATIVE:
+            raise ValueError("Upsert mode is not supported for parquet format")
+        return schema, api.DataFormat(
+            **api_schema,
+            format_type=data_format_type,
         )
     else:
         raise ValueError(f"data format `{format}` not supported")
@@ -363,7 +441,10 @@ def construct_s3_data_storage(
     downloader_threads_count: int | None = None,
     csv_settings: CsvParserSettings | None = None,
     persistent_id: str | None = None,
+    expand_archives: bool = False,
+    decompression: str = "none",
 ):
+    decompression = internal_decompression(format, decompression)
     if format == "csv":
         return api.DataStorage(
             storage_type="s3_csv",
@@ -373,6 +454,18 @@ def construct_s3_data_storage(
             downloader_threads_count=downloader_threads_count,
             mode=internal_connector_mode(mode),
             persistent_id=persistent_id,
+            expand_archives=expand_archives,
+            decompression=decompression,
+        )
+    elif format == "parquet":
+        return api.DataStorage(
+            storage_type="s3_parquet",
+            path=path,
+            aws_s3_settings=rust_engine_s3_settings,
+            downloader_threads_count=downloader_threads_count,
+            mode=internal_connector_mode(mode),
+            persistent_id=persistent_id,
+            expand_archives=expand_archives,
         )
     else:
         return api.DataStorage(
@@ -383,6 +476,8 @@ def construct_s3_data_storage(
             read_method=internal_read_method(format),
             downloader_threads_count=downloader_threads_count,
             persistent_id=persistent_id,
+            expand_archives=expand_archives,
+            decompression=decompression,
         )
 
 
@@ -391,7 +486,6 @@ def check_raw_and_plaintext_only_kwargs_for_message_queues(f):
     def wrapper(*args, **kwargs):
         if kwargs.get("format") not in ("raw", "plaintext"):
             unexpected_params = [
-                "key",
                 "value",
                 "headers",
             ]
@@ -410,8 +504,10 @@ def check_raw_and_plaintext_only_kwargs_for_message_queues(f):
 class MessageQueueOutputFormat:
     _: KW_ONLY
     table: Table
-    key_field_index: int | None
+    key_fields: dict[str, int]
     header_fields: dict[str, int]
+    partition_field_index: int | None
+    topic_field_index: int | None
     data_format: api.DataFormat
 
     @classmethod
@@ -421,35 +517,57 @@ class MessageQueueOutputFormat:
         *,
         format: str = "json",
         delimiter: str = ",",
-        key: ColumnReference | None = None,
+        key: ColumnReference | list[ColumnReference] | None = None,
         value: ColumnReference | None = None,
         headers: Iterable[ColumnReference] | None = None,
+        partition: ColumnReference | None = None,
+        topic: ColumnReference | None = None,
     ) -> MessageQueueOutputFormat:
-        key_field_index = None
+        key_fields: dict[str, int] = {}
         header_fields: dict[str, int] = {}
-        if format == "json":
-            data_format = api.DataFormat(
-                format_type="jsonlines",
-                key_field_names=[],
-                value_fields=_format_output_value_fields(table),
-            )
-        elif format == "dsv":
-            data_format = api.DataFormat(
-                format_type="dsv",
-                key_field_names=[],
-                value_fields=_format_output_value_fields(table),
-                delimiter=delimiter,
-            )
+        partition_field_index = None
+        topic_field_index = None
+        if format == "json" or format == "dsv":
+            column_names = list(table._columns.keys())
+            if key is None:
+                key_columns = []
+            elif isinstance(key, list):
+                key_columns = key
+            else:
+                key_columns = [key]
+            for key_column in key_columns:
+                key_fields[key_column.name] = column_names.index(key_column.name)
+            if partition is not None:
+                partition_field_index = column_names.index(partition.name)
+            if topic is not None:
+                topic_field_index = column_names.index(topic.name)
+            if format == "json":
+                data_format = api.DataFormat(
+                    format_type="jsonlines",
+                    key_field_names=[],
+                    value_fields=_format_output_value_fields(table),
+                )
+            else:
+                data_format = api.DataFormat(
+                    format_type="dsv",
+                    key_field_names=[],
+                    value_fields=_format_output_value_fields(table),
+                    delimiter=delimiter,
+                )
         elif format == "raw" or format == "plaintext":
             value_field_index = None
             extracted_field_indices: dict[str, int] = {}
             columns_to_extract: list[ColumnReference] = []
             allowed_column_types = (dt.BYTES if format == "raw" else dt.STR, dt.ANY)
 
+            if isinstance(key, list):
+                raise ValueError(
+                    f"Only a single key column can be used in '{format}' format"
+                )
             if key is not None:
                 if value is None:
                     raise ValueError("'value' must be specified if 'key' is not None")
-                key_field_index = cls.add_column_reference_to_extract(
+                key_fields[key.name] = cls.add_column_reference_to_extract(
                     key, columns_to_extract, extracted_field_indices
                 )
             if value is not None:
@@ -473,6 +591,14 @@ class MessageQueueOutputFormat:
                     header_fields[header.name] = cls.add_column_reference_to_extract(
                         header, columns_to_extract, extracted_field_indices
                     )
+            if partition is not None:
+                partition_field_index = cls.add_column_reference_to_extract(
+                    partition, columns_to_extract, extracted_field_indices
+                )
+            if topic is not None:
+                topic_field_index = cls.add_column_reference_to_extract(
+                    topic, columns_to_extract, extracted_field_indices
+                )
 
             table = table.select(*columns_to_extract)
 
@@ -497,10 +623,20 @@ class MessageQueueOutputFormat:
         else:
             raise ValueError(f"Unsupported format: {format}")
 
+        if (
+            partition is not None
+            and table[partition._name]._column.dtype != dt.INT
+        ):
+            raise ValueError("The partition column should be of the type 'int'")
+        if topic is not None and table[topic._name]._column.dtype != dt.STR:
+            raise ValueError("The topic column should be of the type 'str'")
+
         return cls(
             table=table,
-            key_field_index=key_field_index,
+            key_fields=key_fields,
             header_fields=header_fields,
+            partition_field_index=partition_field_index,
+            topic_field_index=topic_field_index,
             data_format=data_format,
         )
 
@@ -40,8 +40,15 @@ def read(
         rdkafka_settings: Connection settings in the format of
             `librdkafka <https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md>`_.
         topic_name: Name of topic in Kafka to which the updates are streamed.
-        db_type: Type of the database from which events are streamed;
-        schema: Schema of the resulting table.
+        db_type: Type of the database from which events are streamed. The supported
+            types are ``DebeziumDBType.POSTGRES``, ``DebeziumDBType.MY_SQL``,
+            ``DebeziumDBType.SQL_SERVER`` and ``DebeziumDBType.MONGO_DB``.
+        schema: Schema of the resulting table. If the messages contain the ``schema``
+            section, the values of the Debezium logical types, such as
+            ``io.debezium.time.MicroTimestamp``, ``io.debezium.time.ZonedTimestamp``,
+            ``org.apache.kafka.connect.data.Decimal`` or ``io.debezium.data.Json``,
+            are converted into the types of the corresponding columns, for example,
+            ``pw.DateTimeNaive``, ``pw.DateTimeUtc``, ``pw.Duration``, ``float`` or ``pw.Json``.
         debug_data: Static data replacing original one when debug mode is active.
         autocommit_duration_ms:the maximum time between two commits. Every
             autocommit_duration_ms milliseconds, the updates received by the connector are
@@ -4,7 +4,7 @@ from __future__ import annotations
 
 import warnings
 from os import PathLike, fspath
-from typing import Any
+from typing import Any, Literal
 
 from pathway.internals import Schema, api, datasink, datasource
 from pathway.internals._io_helpers import _format_output_value_fields
@@ -17,6 +17,7 @@ from pathway.io._utils import (
     CsvParserSettings,
     construct_schema_and_data_format,
     internal_connector_mode,
+    internal_decompression,
     internal_read_method,
 )
 
@@ -37,6 +38,9 @@ def read(
     csv_settings: CsvParserSettings | None = None,
     json_field_paths: dict[str, str] | None = None,
     object_pattern: str = "*",
+    expand_archives: bool = False,
+    use_inotify: bool = False,
+    decompression: Literal["auto", "none", "gzip", "zstd", "bzip2", "xz"] = "none",
     with_metadata: bool = False,
     persistent_id: str | None = None,
     autocommit_duration_ms: int | None = 1500,
@@ -53,6 +57,9 @@ def read(
     directory are processed is determined according to the modification time of files
     within this folder: they will be processed by ascending order of the modification time.
 
+    Files compressed with gzip, zstd, bzip2 or xz can be decompressed on the fly, if
+    ``decompression`` is set.
+
     In case the format is "plaintext", the table will consist of a single column
     ``data`` with each cell containing a single line from the file.
 
@@ -62,11 +69,13 @@ def read(
 objects to be read. The connector will read the contents of all matching files as well \
 as recursively read the contents of all matching folders.
         format: Format of data to be read. Currently "csv", "json", "plaintext", \
-"plaintext_by_file" and "binary" formats are supported. The difference between \
+"plaintext_by_file", "binary" and "parquet" formats are supported. The difference between \
 "plaintext" and "plaintext_by_file" is how the input is tokenized: if the "plaintext" \
 option is chosen, it's split by the newlines. Otherwise, the files are split in full \
 and one row will correspond to one file. In case the "binary" format is specified, \
-the data is read as raw bytes without UTF-8 parsing.
+the data is read as raw bytes without UTF-8 parsing. The "parquet" format reads the rows \
+of Parquet files, committing them by row groups, so that the reading of a large file can \
+be resumed from the last committed row group after a restart.
         schema: Schema of the resulting table.
         mode: Denotes how the engine polls the new data from the source. Currently \
 "streaming" and "static" are supported. If set to "streaming" the engine will wait for \
@@ -85,6 +94,21 @@ of it in one commit. The default value is "streaming".
         object_pattern: Unix shell style pattern for filtering only certain files in the \
 directory. Ignored in case a path to a single file is specified. This value will be \
 deprecated soon, please use glob pattern in ``path`` instead.
+        expand_archives: If set to true, the tar and zip archives are read member by \
+member, as if the members were separate files. A member is addressed by the path of the \
+archive followed by ``!/`` and its path within the archive, for example \
+``bundle.zip!/a.csv``. When an archive is modified, only its changed members are reread.
+        use_inotify: If set to true, the changes of the files are detected by inotify \
+events instead of rescanning the whole directory on every poll, so they are picked up \
+with a lower latency. If some events are lost, the directory is rescanned in full. \
+Supported only on Linux.
+        decompression: How the files are decompressed: ``"none"`` to read them as \
+they are, ``"auto"`` to detect the compression by the file extension or by the contents \
+of the file, or the name of the codec of all files: ``"gzip"``, ``"zstd"``, ``"bzip2"`` \
+or ``"xz"``. The files are decompressed on the fly, and the rows are counted in the \
+decompressed contents, so the reading can be resumed after a restart. With ``"auto"``, \
+the files read in the "binary" format are kept as they are. Not supported for the \
+"parquet" format.
         with_metadata: When set to true, the connector will add an additional column \
 named ``_metadata`` to the table. This column will be a JSON field that will contain two \
 optional fields - ``created_at`` and ``modified_at``. These fields will have integral \
@@ -220,6 +244,7 @@ named ``path`` that will show the full path to the file from where a row was fil
     """
 
     path = fspath(path)
+    decompression = internal_decompression(format, decompression)
 
     if object_pattern != "*":
         warnings.warn(
@@ -237,6 +262,19 @@ named ``path`` that will show the full path to the file from where a row was fil
             mode=internal_connector_mode(mode),
             object_pattern=object_pattern,
             persistent_id=persistent_id,
+            expand_archives=expand_archives,
+            use_inotify=use_inotify,
+            decompression=decompression,
+        )
+    elif format == "parquet":
+        data_storage = api.DataStorage(
+            storage_type="parquet",
+            path=path,
+            mode=internal_connector_mode(mode),
+            object_pattern=object_pattern,
+            persistent_id=persistent_id,
+            expand_archives=expand_archives,
+            use_inotify=use_inotify,
         )
     else:
         data_storage = api.DataStorage(
@@ -246,6 +284,9 @@ named ``path`` that will show the full path to the file from where a row was fil
             read_method=internal_read_method(format),
             object_pattern=object_pattern,
             persistent_id=persistent_id,
+            expand_archives=expand_archives,
+            use_inotify=use_inotify,
+            decompression=decompression,
         )
 
     schema, data_format = construct_schema_and_data_format(
@@ -0,0 +1,136 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+from typing import Literal
+
+from pathway.internals import api, datasource
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.schema import Schema
+from pathway.internals.table import Table
+from pathway.internals.table_io import table_from_datasource
+from pathway.internals.trace import trace_user_frame
+from pathway.io._utils import construct_schema_and_data_format
+
+
+@check_arg_types
+@trace_user_frame
+def read(
+    host: str,
+    port: int,
+    table_name: str,
+    *,
+    schema: type[Schema] | None = None,
+    format: Literal["raw", "json"] = "json",
+    autocommit_duration_ms: int | None = 1500,
+    with_metadata: bool = False,
+    persistent_id: str | None = None,
+    debug_data=None,
+) -> Table:
+    """Starts a gRPC server, to which the clients stream the rows of the table.
+
+    The service is defined in the ``ingest.proto`` file, located in the directory of
+    this module, and the client stubs can be generated from it for any language. A
+    client opens the ``StreamRows`` stream and sends the ``RowBatch`` messages with the
+    ``table`` field equal to ``table_name``. The server answers each batch with a
+    ``BatchAck`` message, containing the ``batch_id`` of the batch, once the batch has
+    been accepted: all its rows get into the same timestamp of the computation. If
+    persistence is enabled, a batch is acknowledged only after it's persisted, so the
+    batches that aren't acknowledged can be safely sent again after a restart.
+
+    The rows can be sent in three ways:
+
+    * as the ``json`` payload: a JSON object with the values of the columns;
+    * as the ``fields`` structure of the type ``google.protobuf.Struct``, which is
+    handled in the same way as a JSON object;
+    * as the ``raw`` payload, which is put into the ``data`` column of the table as is
+    in the ``"raw"`` format. It can be, for example, a serialized protobuf message.
+
+    Each stream has a limit of batches waiting for acknowledgements, and the server
+    has a limited queue of the batches not yet taken by the connector, so the clients
+    that send faster than the pipeline processes are slowed down.
+
+    The server is run by a single Pathway worker, and different tables must be served
+    on different ports.
+
+    Args:
+        host: The host of the server, for example, ``"0.0.0.0"`` to accept the
+            connections from other machines.
+        port: The port of the server.
+        table_name: The name of the table, which the clients must specify in the
+            batches.
+        schema: The table schema, used only when the format is set to ``"json"``.
+        format: The format of the rows: ``"json"`` for the ``json`` and ``fields``
+            payloads, or ``"raw"`` for the ``raw`` ones.
+        autocommit_duration_ms: The maximum time interval (in milliseconds) between
+            commits. Each batch is committed separately in any case.
+        with_metadata: When set to ``True``, the connector will add an additional column
+            named ``_metadata`` to the table. This JSON field will contain the
+            ``peer_address`` of the client and the ``batch_id`` of the batch.
+        persistent_id: (unstable) An identifier, under which the state of the table will
+            be persisted or ``None``, if there is no need to persist the state of this table.
+        debug_data: Static data replacing original one when debug mode is active.
+
+    Returns:
+        Table: The table read.
+
+    Example:
+
+    The orders can be received on port ``50051`` as follows:
+
+    >>> import pathway as pw
+    >>> class OrderSchema(pw.Schema):
+    ...     user: str
+    ...     amount: float
+    >>> orders = pw.io.grpc.read("0.0.0.0", 50051, "orders", schema=OrderSchema)
+
+    Then a client sends the batches of rows, for example, in Python with the stubs
+    generated by ``grpcio-tools``:
+
+    >>> import json
+    >>> import grpc  # doctest: +SKIP
+    >>> import ingest_pb2, ingest_pb2_grpc  # doctest: +SKIP
+    >>> def batches():
+    ...     rows = [{"user": "alice", "amount": 10.5}, {"user": "bob", "amount": 3.0}]
+    ...     yield ingest_pb2.RowBatch(
+    ...         table="orders",
+    ...         batch_id=1,
+    ...         rows=[ingest_pb2.Row(json=json.dumps(row).encode()) for row in rows],
+    ...     )
+    >>> with grpc.insecure_channel("localhost:50051") as channel:  # doctest: +SKIP
+    ...     stub = ingest_pb2_grpc.IngestStub(channel)
+    ...     for ack in stub.StreamRows(batches()):
+    ...         print(ack.batch_id, ack.rows_accepted)
+    1 2
+    """
+
+    if ":" in host:
+        # An IPv6 address
+        host = f"[{host}]"
+    data_storage = api.DataStorage(
+        storage_type="grpc",
+        connection_string=f"{host}:{port}",
+        table_name=table_name,
+        persistent_id=persistent_id,
+        mode=api.ConnectorMode.STREAMING,
+    )
+    schema, data_format = construct_schema_and_data_format(
+        "binary" if format == "raw" else format,
+        with_metadata=with_metadata,
+        schema=schema,
+        csv_settings=None,
+        json_field_paths=None,
+    )
+    data_source_options = datasource.DataSourceOptions(
+        commit_duration_ms=autocommit_duration_ms
+    )
+    return table_from_datasource(
+        datasource.GenericDataSource(
+            datastorage=data_storage,
+            dataformat=data_format,
+            data_source_options=data_source_options,
+            schema=schema,
+            datasource_name="grpc",
+        ),
+        debug_datasource=datasource.debug_datasource(debug_data),
+    )
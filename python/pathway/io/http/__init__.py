@@ -21,6 +21,7 @@ from ._server import (
     rest_connector,
 )
 from ._streaming import HttpStreamingSubject
+from ._webhook import read_webhook, write_webhook
 
 
 @check_arg_types
@@ -299,4 +300,6 @@ __all__ = [
     "PathwayWebserver",
     "EndpointDocumentation",
     "EndpointExamples",
+    "read_webhook",
+    "write_webhook",
 ]
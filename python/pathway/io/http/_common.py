@@ -14,6 +14,9 @@ class RetryPolicy:
     """Class representing policy of delays or backoffs for the retries."""
 
     def __init__(self, first_delay_ms: int, backoff_factor: float, jitter_ms: int):
+        self.first_delay_ms = first_delay_ms
+        self.backoff_factor = backoff_factor
+        self.jitter_ms = jitter_ms
         self._next_retry_duration = first_delay_ms * 1e-3
         self._backoff_factor = backoff_factor
         self._jitter = jitter_ms * 1e-3
@@ -0,0 +1,251 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+from collections.abc import Sequence
+from typing import Literal
+
+from pathway.internals import api, datasink, datasource
+from pathway.internals._io_helpers import _format_output_value_fields
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.schema import Schema
+from pathway.internals.table import Table
+from pathway.internals.table_io import table_from_datasource
+from pathway.internals.trace import trace_user_frame
+from pathway.io._utils import construct_schema_and_data_format
+
+from ._common import RetryPolicy
+
+
+@check_arg_types
+@trace_user_frame
+def read_webhook(
+    host: str,
+    port: int,
+    *,
+    schema: type[Schema] | None = None,
+    routes: Sequence[str] = ("/",),
+    max_request_size: int | None = None,
+    wait_for_processing: bool = False,
+    autocommit_duration_ms: int | None = 1500,
+    json_field_paths: dict[str, str] | None = None,
+    with_metadata: bool = False,
+    persistent_id: str | None = None,
+    debug_data=None,
+) -> Table:
+    """Starts an HTTP server, which accepts the rows of the table in the bodies of
+    POST requests. Unlike :py:func:`pathway.io.http.rest_connector`, the server runs in
+    the engine, so the requests are handled without the Python interpreter, and each
+    request only adds the rows to the table without waiting for a result.
+
+    A request with the ``Content-Type: application/json`` header carries a single JSON
+    value: an array of objects gives a row for each of its elements, and an object gives
+    a single row. Any other request body is read as
+    `JSON Lines <https://jsonlines.org/>`_, where each non-empty line is an object with
+    the values of a row. The rows of a request get into the same timestamp of the
+    computation.
+
+    The server responds with the status ``200`` and a JSON object with the number of
+    ``rows_accepted`` once the rows have been passed to the computation. If
+    ``wait_for_processing`` is set, the response is sent only after all outputs of the
+    computation have processed the timestamp of the rows, so the client can read the
+    results right after the response. If persistence is enabled, the response is also
+    delayed until the rows are persisted. The requests to the routes that aren't served
+    get the status ``404``, the requests with the bodies exceeding ``max_request_size``
+    get ``413`` and the requests with the bodies that aren't valid JSON get ``400``.
+    The rows that don't match the schema are reported as the parsing errors of the
+    connector, as in the other connectors.
+
+    The server is run by a single Pathway worker, and different tables must be served
+    on different ports.
+
+    Args:
+        host: The host of the server, for example, ``"0.0.0.0"`` to accept the
+            connections from other machines.
+        port: The port of the server.
+        schema: The table schema.
+        routes: The paths of the URLs, which accept the rows. Each path must start with
+            ``"/"``.
+        max_request_size: The maximum size of a request body in bytes. Defaults to
+            16 MiB.
+        wait_for_processing: Whether to respond only after the rows have been processed
+            by all outputs of the computation.
+        autocommit_duration_ms: The maximum time interval (in milliseconds) between
+            commits. Each request is committed separately in any case.
+        json_field_paths: This allows mapping field names to paths within the JSON
+            structure. Use the format ``<field_name>: <path>`` where the path follows the
+            `JSON Pointer (RFC 6901) <https://www.rfc-editor.org/rfc/rfc6901>`_.
+        with_metadata: When set to ``True``, the connector will add an additional column
+            named ``_metadata`` to the table. This JSON field will contain the ``route``
+            of the request and the ``peer_address`` of the client.
+        persistent_id: (unstable) An identifier, under which the state of the table will
+            be persisted or ``None``, if there is no need to persist the state of this table.
+        debug_data: Static data replacing original one when debug mode is active.
+
+    Returns:
+        Table: The table read.
+
+    Example:
+
+    The events can be received on port ``8080`` at two routes as follows:
+
+    >>> import pathway as pw
+    >>> class EventSchema(pw.Schema):
+    ...     user: str
+    ...     action: str
+    >>> events = pw.io.http.read_webhook(
+    ...     "0.0.0.0",
+    ...     8080,
+    ...     schema=EventSchema,
+    ...     routes=["/events", "/v1/events"],
+    ... )
+
+    Then the rows can be sent, for example, with ``curl``:
+
+    .. code-block:: bash
+
+        curl -X POST http://localhost:8080/events \\
+            -H "Content-Type: application/json" \\
+            -d '[{"user": "alice", "action": "login"}, {"user": "bob", "action": "logout"}]'
+    """
+
+    if ":" in host:
+        # An IPv6 address
+        host = f"[{host}]"
+    data_storage = api.DataStorage(
+        storage_type="webhook",
+        connection_string=f"{host}:{port}",
+        routes=list(routes),
+        max_request_size=max_request_size,
+        wait_for_processing=wait_for_processing,
+        persistent_id=persistent_id,
+        mode=api.ConnectorMode.STREAMING,
+    )
+    schema, data_format = construct_schema_and_data_format(
+        "json",
+        with_metadata=with_metadata,
+        schema=schema,
+        csv_settings=None,
+        json_field_paths=json_field_paths,
+    )
+    data_source_options = datasource.DataSourceOptions(
+        commit_duration_ms=autocommit_duration_ms
+    )
+    return table_from_datasource(
+        datasource.GenericDataSource(
+            datastorage=data_storage,
+            dataformat=data_format,
+            data_source_options=data_source_options,
+            schema=schema,
+            datasource_name="webhook",
+        ),
+        debug_datasource=datasource.debug_datasource(debug_data),
+    )
+
+
+@check_arg_types
+@trace_user_frame
+def write_webhook(
+    table: Table,
+    url: str,
+    *,
+    format: Literal["jsonlines", "json"] = "jsonlines",
+    max_batch_size: int | None = None,
+    headers: dict[str, str] | None = None,
+    signing_secret: str | None = None,
+    signature_header: str = "X-Pathway-Signature",
+    n_retries: int = 3,
+    retry_policy: RetryPolicy = RetryPolicy.default(),
+    request_timeout_ms: int | None = None,
+) -> None:
+    """Sends the stream of updates from the table in batches of POST requests to an HTTP
+    endpoint. Unlike :py:func:`pathway.io.http.write`, which sends a request for each
+    change from Python, the requests are sent by the engine, and each of them contains
+    all changes of a batch, which go to the same URL.
+
+    Each change is a JSON object with the values of the row together with the ``time``
+    and ``diff`` fields. The changes of a request are sent as
+    `JSON Lines <https://jsonlines.org/>`_ with the ``application/x-ndjson`` content
+    type for the ``"jsonlines"`` format, or as a JSON array with the
+    ``application/json`` content type for the ``"json"`` format.
+
+    The URL can contain the wildcards ``{table.<column_name>}``, which are replaced with
+    the percent-encoded values of the column ``<column_name>`` in the row sent, and the
+    wildcards ``{table.time}`` and ``{table.diff}``, as in
+    :py:func:`pathway.io.http.write`. The rows are grouped into the requests by the URLs
+    built for them.
+
+    If ``signing_secret`` is given, each request has the header ``signature_header``
+    with the HMAC-SHA256 signature of the request body in the form
+    ``sha256=<hex digest>``, so the receiver can verify that the request comes from
+    the pipeline.
+
+    The requests failing with connection errors, timeouts, server errors or the status
+    ``429 Too Many Requests`` are retried with the backoff of ``retry_policy``. If a
+    response with the status ``429`` has the ``Retry-After`` header, the connector waits
+    for the time from the header instead of the backoff before the next attempt, but
+    not longer than the longest delay of the backoff, the one before the last retry.
+    The requests failing with the other statuses aren't retried.
+
+    Args:
+        table: The table for output.
+        url: The URL of the endpoint, which can contain the wildcards.
+        format: The format of the request bodies: ``"jsonlines"`` or ``"json"``.
+        max_batch_size: The maximum number of changes sent at once. If not set, all
+            changes of a batch produced by the engine are sent together.
+        headers: The headers of the requests.
+        signing_secret: The secret, which the requests are signed with.
+        signature_header: The name of the header with the signature.
+        n_retries: How many times to retry a failed request.
+        retry_policy: The policy of the delays between the retries.
+        request_timeout_ms: The timeout of a request, specified in milliseconds. In case
+            it's ``None``, no restrictions on request duration will be applied.
+
+    Example:
+
+    The totals of the users can be sent to the endpoints of the users, signed with a
+    secret shared with the receiver:
+
+    >>> import pathway as pw
+    >>> totals = pw.debug.table_from_markdown('''
+    ... user  | total
+    ... alice | 12.5
+    ... bob   | 40.0
+    ... ''')
+    >>> pw.io.http.write_webhook(
+    ...     totals,
+    ...     "https://partner.example.com/users/{table.user}/totals",
+    ...     signing_secret="my-secret",
+    ... )
+    """
+
+    data_storage = api.DataStorage(
+        storage_type="webhook",
+        webhook_settings=api.WebhookSettings(
+            url=url,
+            batch_format=format,
+            headers=headers or {},
+            signing_secret=signing_secret,
+            signature_header=signature_header,
+            max_retries=n_retries,
+            first_retry_delay_ms=retry_policy.first_delay_ms,
+            retry_backoff_factor=retry_policy.backoff_factor,
+            retry_jitter_ms=retry_policy.jitter_ms,
+            request_timeout_ms=request_timeout_ms,
+        ),
+        max_batch_size=max_batch_size,
+    )
+
+    data_format = api.DataFormat(
+        format_type="jsonlines",
+        key_field_names=[],
+        value_fields=_format_output_value_fields(table),
+    )
+
+    table.to(
+        datasink.GenericDataSink(
+            data_storage,
+            data_format,
+            datasink_name="webhook",
+        )
+    )
@@ -3,8 +3,7 @@
 from __future__ import annotations
 
 import uuid
-import warnings
-from typing import Any, Iterable
+from typing import Any, Iterable, Literal
 
 from pathway.internals import api, datasink, datasource
 from pathway.internals.api import PathwayType
@@ -19,6 +18,7 @@ from pathway.io._utils import (
     check_deprecated_kwargs,
     check_raw_and_plaintext_only_kwargs_for_message_queues,
     construct_schema_and_data_format,
+    internal_connector_mode,
 )
 
 
@@ -28,6 +28,7 @@ def read(
     rdkafka_settings: dict,
     topic: str | list[str] | None = None,
     *,
+    topic_pattern: str | None = None,
     schema: type[Schema] | None = None,
     format: str = "raw",
     debug_data=None,
@@ -36,8 +37,13 @@ def read(
     autogenerate_key: bool = False,
     with_metadata: bool = False,
     start_from_timestamp_ms: int | None = None,
+    mode: Literal["streaming", "static"] = "streaming",
+    end_at_timestamp_ms: int | None = None,
     parallel_readers: int | None = None,
     persistent_id: str | None = None,
+    upsert: bool = False,
+    header_fields: dict[str, str] | None = None,
+    timestamp_field: str | None = None,
     value_columns: list[str] | None = None,
     primary_key: list[str] | None = None,
     types: dict[str, PathwayType] | None = None,
@@ -61,7 +67,20 @@ def read(
     Args:
         rdkafka_settings: Connection settings in the format of `librdkafka
             <https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md>`_.
-        topic: Name of topic in Kafka from which the data should be read.
+        topic: Name of topic in Kafka from which the data should be read. A list of
+            topic names can be given to read several topics into a single table.
+        topic_pattern: A regular expression for the names of the topics to be read,
+            for example, ``"events\\..*"``. As in Kafka, the expression must match the
+            whole name of a topic, so ``"events"`` doesn't read ``"events_archive"``.
+            The expression uses the syntax of the Rust
+            `regex <https://docs.rs/regex/latest/regex/#syntax>`_ crate and is matched
+            by Pathway, which then subscribes to the list of the matching topics. The
+            list is updated every 30 seconds, so the topics created after the program
+            has started are read too. It can be used together with ``topic``. The
+            frontier of the persisted state is kept for each topic and partition, so
+            a restarted program resumes every topic from the position where it has
+            stopped. To know which topic a row comes from, use ``with_metadata`` and
+            the ``topic`` field of the ``_metadata`` column.
         schema: Schema of the resulting table.
         format: format of the input data, "raw", "plaintext", or "json".
         debug_data: Static data replacing original one when debug mode is active.
@@ -84,6 +103,15 @@ def read(
             correspond to the Kafka message that produced this row.
         start_from_timestamp_ms: If defined, the read starts from entries with the given
             timestamp in the past, specified in milliseconds.
+        mode: Denotes how the topics are read. In the ``"streaming"`` mode, the
+            connector waits for the new messages infinitely. In the ``"static"`` mode,
+            the connector reads each partition up to its high watermark as of the program
+            start, or up to the ``end_at_timestamp_ms``, and then finishes. In this mode
+            the partitions are assigned to a single reader, the consumer group is not
+            used for balancing them, and the topics created after the start are not read.
+        end_at_timestamp_ms: If defined, the read stops at the first entry of each
+            partition with the timestamp not less than the given one, specified in
+            milliseconds. This entry is not read. Implies the ``"static"`` mode.
         parallel_readers: number of copies of the reader to work in parallel. In case
             the number is not specified, min{pathway_threads, total number of partitions}
             will be taken. This number also can't be greater than the number of Pathway
@@ -94,6 +122,24 @@ def read(
             When a program restarts, it restores the state for all input tables according to what
             was saved for their ``persistent_id``. This way it's possible to configure the start of
             computations from the moment they were terminated last time.
+        upsert: If ``True``, the topic is read as a changelog of entities, for instance,
+            a log-compacted topic: the key of a message is the primary key of the row,
+            a message with a payload replaces the row with this key and a message without
+            payload (a tombstone) removes it. In the "raw" and "plaintext" formats, the whole
+            message key is used. In the "json" format, the message key is used as is if
+            the schema doesn't have a primary key, otherwise the key must be a JSON
+            object and the primary key fields are taken from it. Every message must
+            have a key in this mode.
+        header_fields: A mapping from the names of the columns to the names of the
+            message headers, which these columns are filled from, instead of the payload.
+            The header values are parsed according to the types of the columns, and
+            ``bytes`` columns get the raw values. If a message doesn't have the header,
+            the default value of the column is used, or ``None`` for an optional column.
+            Only supported for the "json" format.
+        timestamp_field: The name of the column, which is filled with the timestamp of
+            the message. The column can be of type ``pw.DateTimeUtc``,
+            ``pw.DateTimeNaive`` or ``int``, in the latter case it contains the number
+            of milliseconds since the UNIX epoch. Only supported for the "json" format.
         value_columns: Columns to extract for a table, required for format other than
             "raw". [will be deprecated soon]
         primary_key: In case the table should have a primary key generated according to
@@ -243,28 +289,32 @@ def read(
     # The data_storage is common to all kafka connectors
 
     if not topic:
-        topic_names = kwargs.get("topic_names")
-        if not topic_names:
+        topic = kwargs.get("topic_names")
+        if not topic and topic_pattern is None:
             raise ValueError("Missing topic name specification")
-        topic = topic_names[0]
-    if isinstance(topic, list):
-        warnings.warn(
-            "'topic' should be a str, not list. First element will be used.",
-            SyntaxWarning,
-            stacklevel=_stacklevel + 4,
-        )
-        topic = topic[0]
+    topic_names = [topic] if isinstance(topic, str) else topic
 
     check_deprecated_kwargs(kwargs, ["topic_names"], stacklevel=_stacklevel + 4)
+    if upsert and autogenerate_key:
+        raise ValueError(
+            "autogenerate_key can't be used in the upsert mode, "
+            "because the message key is the primary key"
+        )
 
     data_storage = api.DataStorage(
         storage_type="kafka",
         rdkafka_settings=rdkafka_settings,
-        topic=topic,
+        topic_names=topic_names,
+        topic_pattern=topic_pattern,
         parallel_readers=parallel_readers,
         persistent_id=persistent_id,
         start_from_timestamp_ms=start_from_timestamp_ms,
-        mode=api.ConnectorMode.STREAMING,
+        end_at_timestamp_ms=end_at_timestamp_ms,
+        mode=(
+            api.ConnectorMode.STATIC
+            if end_at_timestamp_ms is not None
+            else internal_connector_mode(mode)
+        ),
     )
     schema, data_format = construct_schema_and_data_format(
         "binary" if format == "raw" else format,
@@ -277,6 +327,9 @@ def read(
         primary_key=primary_key,
         types=types,
         default_values=default_values,
+        session_type=api.SessionType.UPSERT if upsert else api.SessionType.NATIVE,
+        header_fields=header_fields,
+        timestamp_field=timestamp_field,
         _stacklevel=5,
     )
     data_source_options = datasource.DataSourceOptions(
@@ -514,9 +567,12 @@ def write(
     *,
     format: str = "json",
     delimiter: str = ",",
-    key: ColumnReference | None = None,
+    key: ColumnReference | list[ColumnReference] | None = None,
     value: ColumnReference | None = None,
     headers: Iterable[ColumnReference] | None = None,
+    partitioner: Literal["default", "key_hash"] | ColumnReference = "default",
+    tombstones: bool = False,
+    transactional_id: str | None = None,
 ) -> None:
     """Write a table to a given topic on a Kafka instance.
 
@@ -552,8 +608,10 @@ def write(
         delimiter: field delimiter to be used in case of delimiter-separated values
             format.
         key: reference to the column that should be used as a key in the
-            produced message in 'plaintext' or 'raw' format. If left empty, an internal primary key will
-            be used.
+            produced message. If left empty, an internal primary key will be used. In 'json'
+            and 'dsv' formats, a list of columns can be given: then the key is a JSON object
+            with the values of these columns. A single key column must be of the string or
+            binary type, and its value is the key as it is.
         value: reference to the column that should be used as a value in
             the produced message in 'plaintext' or 'raw' format. It can be deduced automatically if the
             table has exactly one column. Otherwise it must be specified directly. It also has to be
@@ -562,6 +620,34 @@ def write(
             headers. These headers are named in the same way as fields that are forwarded and correspond
             to the string representations of the respective values encoded in UTF-8. If a binary
             column is requested, it will be produced "as is" in the respective header.
+        partitioner: defines the partition of each produced message. If "default", the
+            partition is chosen by librdkafka according to its ``partitioner`` setting. If
+            "key_hash", the partition is the murmur2 hash of the message key modulo the
+            number of partitions, as in the default partitioner of the Java client. If a
+            reference to an integer column is given, its value is used as the partition.
+            In all cases, the messages with the same key get into the same partition, so
+            their order is preserved.
+        tombstones: if ``True``, the table is written as a changelog for a log-compacted
+            topic: a row removal, which is not followed by an insertion of the row with
+            the same key within the same transaction, is produced as a message with an
+            empty payload (a tombstone), while a removal followed by an insertion is
+            produced only as the insertion. The key should be defined with the ``key``
+            parameter, otherwise the internal primary key is used.
+        transactional_id: if specified, the messages are produced with the transactional
+            producer, so the output is written exactly once. The messages are
+            produced into a transaction, which is committed once their time is
+            finalized, that is, once the persisted state guarantees that they won't be
+            produced again after a restart. This time is common for all outputs of a
+            worker, so it is held back by the slowest of them. While a transaction
+            waits for it, the new messages are kept in memory and produced into the
+            next transaction, so a transaction only has to outlast this wait, within
+            the ``transaction.timeout.ms`` of the producer. The transaction is
+            committed before the finalized time is saved. If the program stops
+            between the commit and the save, the messages of the last transaction are
+            produced again after a restart. Each Pathway worker uses its own
+            ``transactional.id``, which is the given value followed by the worker index,
+            so the producers of the previous runs are fenced. The consumers should use
+            the ``read_committed`` isolation level to skip uncommitted messages.
 
 
     Returns:
@@ -668,6 +754,7 @@ def write(
         key=key,
         value=value,
         headers=headers,
+        partition=partitioner if isinstance(partitioner, ColumnReference) else None,
     )
     table = output_format.table
 
@@ -675,8 +762,14 @@ def write(
         storage_type="kafka",
         rdkafka_settings=rdkafka_settings,
         topic=topic_name,
-        key_field_index=output_format.key_field_index,
+        key_fields=list(output_format.key_fields.items()),
         header_fields=[item for item in output_format.header_fields.items()],
+        partition_field_index=output_format.partition_field_index,
+        partition_by_key_hash=(
+            isinstance(partitioner, str) and partitioner == "key_hash"
+        ),
+        produce_tombstones=tombstones,
+        transactional_id=transactional_id,
     )
 
     table.to(
@@ -0,0 +1,247 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+from typing import Literal
+
+from pathway.internals import api, datasink, datasource
+from pathway.internals.expression import ColumnReference
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.schema import Schema
+from pathway.internals.table import Table
+from pathway.internals.table_io import table_from_datasource
+from pathway.internals.trace import trace_user_frame
+from pathway.io._utils import (
+    MessageQueueOutputFormat,
+    check_raw_and_plaintext_only_kwargs_for_message_queues,
+    construct_schema_and_data_format,
+)
+
+
+def _aws_settings(region: str | None, endpoint: str | None) -> api.AwsS3Settings | None:
+    if region is None and endpoint is None:
+        return None
+    return api.AwsS3Settings(region=region, endpoint=endpoint)
+
+
+@check_arg_types
+@trace_user_frame
+def read(
+    stream_name: str,
+    *,
+    region: str | None = None,
+    endpoint: str | None = None,
+    schema: type[Schema] | None = None,
+    format: Literal["plaintext", "raw", "json"] = "raw",
+    autocommit_duration_ms: int | None = 1500,
+    json_field_paths: dict[str, str] | None = None,
+    with_metadata: bool = False,
+    parallel_readers: int | None = None,
+    persistent_id: str | None = None,
+    debug_data=None,
+) -> Table:
+    """Reads the records of an
+    `Amazon Kinesis Data Streams <https://aws.amazon.com/kinesis/data-streams/>`_ stream.
+
+    It supports three formats: ``"plaintext"``, ``"raw"``, and ``"json"``.
+
+    * For the ``"raw"`` format, the payload is read as raw bytes and added directly to the
+    table.
+    * In the ``"plaintext"`` format, the payload decoded from UTF-8 and stored as plain text.
+
+    In both cases, the table will have an autogenerated primary key and a single ``"data"``
+    column representing the payload.
+
+    If you select the ``"json"`` format, the connector parses the record payload as JSON
+    and creates table columns based on the schema provided in the ``schema`` parameter. The
+    column values come from the corresponding JSON fields.
+
+    The shards of the stream are distributed among the Pathway workers. The stream is read
+    from the oldest record available. When the stream is resharded, a new shard is read
+    only after its parent shards have been read to the end, so the records with the same
+    partition key are read in the order they were written.
+
+    The records aggregated by the
+    `Kinesis Producer Library <https://github.com/awslabs/amazon-kinesis-producer>`_ are
+    split into the user records, and each of them becomes a separate row, with the
+    partition key of the user record in the metadata. A record that starts with the
+    aggregation magic bytes but has a wrong checksum is read as is.
+
+    If persistence is enabled, the position of the last record read from each shard is
+    stored in the persisted state, and after a restart the reading of each shard continues
+    right after it, including the case when only a part of the user records of an
+    aggregated record has been read.
+
+    The credentials are taken from the environment variables, such as
+    ``AWS_ACCESS_KEY_ID`` and ``AWS_SECRET_ACCESS_KEY``, or from the AWS profile.
+
+    Args:
+        stream_name: The name of the stream to read.
+        region: The AWS region of the stream. If it's not given, the region is taken
+            from the ``AWS_REGION`` environment variable or from the AWS profile.
+        endpoint: A custom endpoint, for instance, ``"http://localhost:4566"`` for
+            `LocalStack <https://www.localstack.cloud/>`_.
+        schema: The table schema, used only when the format is set to ``"json"``.
+        format: The input data format, which can be ``"raw"``, ``"plaintext"``, or
+            ``"json"``.
+        autocommit_duration_ms: The time interval (in milliseconds) between commits.
+            After this time, the updates received by the connector are committed and
+            added to Pathway's computation graph.
+        json_field_paths: For the ``"json"`` format, this allows mapping field names to
+            paths within the JSON structure. Use the format ``<field_name>: <path>``
+            where the path follows the
+            `JSON Pointer (RFC 6901) <https://www.rfc-editor.org/rfc/rfc6901>`_.
+        with_metadata: When set to ``True``, the connector will add an additional column
+            named ``_metadata`` to the table. This JSON field will contain the
+            ``stream_name``, the ``shard_id``, the ``sequence_number`` and the
+            ``partition_key`` of the record, and the optional
+            ``approximate_arrival_timestamp_millis``.
+        parallel_readers: The number of copies of the reader to work in parallel. If it's
+            not given, all Pathway engine threads read the stream.
+        persistent_id: (unstable) An identifier, under which the state of the table will
+            be persisted or ``None``, if there is no need to persist the state of this table.
+        debug_data: Static data replacing original one when debug mode is active.
+
+    Returns:
+        Table: The table read.
+
+    Example:
+
+    Assume that `LocalStack <https://www.localstack.cloud/>`_ is running locally on the
+    default port, ``4566``, and the producers put JSON records to the stream ``orders``.
+    They can be read as follows:
+
+    >>> import pathway as pw
+    >>> class InputSchema(pw.Schema):
+    ...     user: str
+    ...     amount: float
+    >>> table = pw.io.kinesis.read(
+    ...     "orders",
+    ...     region="us-east-1",
+    ...     endpoint="http://localhost:4566",
+    ...     format="json",
+    ...     schema=InputSchema,
+    ... )
+    """
+
+    data_storage = api.DataStorage(
+        storage_type="kinesis",
+        topic=stream_name,
+        aws_s3_settings=_aws_settings(region, endpoint),
+        parallel_readers=parallel_readers,
+        persistent_id=persistent_id,
+        mode=api.ConnectorMode.STREAMING,
+    )
+    schema, data_format = construct_schema_and_data_format(
+        "binary" if format == "raw" else format,
+        with_metadata=with_metadata,
+        schema=schema,
+        csv_settings=None,
+        json_field_paths=json_field_paths,
+    )
+    data_source_options = datasource.DataSourceOptions(
+        commit_duration_ms=autocommit_duration_ms
+    )
+    return table_from_datasource(
+        datasource.GenericDataSource(
+            datastorage=data_storage,
+            dataformat=data_format,
+            data_source_options=data_source_options,
+            schema=schema,
+            datasource_name="kinesis",
+        ),
+        debug_datasource=datasource.debug_datasource(debug_data),
+    )
+
+
+@check_raw_and_plaintext_only_kwargs_for_message_queues
+@check_arg_types
+@trace_user_frame
+def write(
+    table: Table,
+    stream_name: str,
+    *,
+    partition_key: ColumnReference | None = None,
+    region: str | None = None,
+    endpoint: str | None = None,
+    format: str = "json",
+    delimiter: str = ",",
+    value: ColumnReference | None = None,
+) -> None:
+    """Puts the rows of the table to an
+    `Amazon Kinesis Data Streams <https://aws.amazon.com/kinesis/data-streams/>`_ stream.
+
+    Each change of the table produces a record. In the ``"json"`` and ``"dsv"`` formats,
+    the record contains the values of the row together with the ``time`` and ``diff``
+    fields. In the ``"plaintext"`` and ``"raw"`` formats, the record is the value of a
+    single column.
+
+    The records are sent in batches with ``PutRecords``. If some records of a batch are
+    rejected, for instance, because the throughput of a shard is exceeded, these records
+    are sent again after a backoff. In this case, the order of the records with the same
+    partition key is not guaranteed.
+
+    The credentials are taken from the environment variables, such as
+    ``AWS_ACCESS_KEY_ID`` and ``AWS_SECRET_ACCESS_KEY``, or from the AWS profile.
+
+    Args:
+        table: The table for output.
+        stream_name: The name of the stream.
+        partition_key: The string or binary column whose value is the partition key of
+            a record. The records with the same partition key get into the same shard. If
+            it's not given, the internal primary key of the row is used.
+        region: The AWS region of the stream. If it's not given, the region is taken
+            from the ``AWS_REGION`` environment variable or from the AWS profile.
+        endpoint: A custom endpoint, for instance, ``"http://localhost:4566"`` for
+            `LocalStack <https://www.localstack.cloud/>`_.
+        format: The format of the records: ``"json"``, ``"dsv"``, ``"plaintext"`` or
+            ``"raw"``. If the ``"raw"`` format is selected, ``table`` must either contain
+            exactly one binary column or the column must be specified in the ``value``
+            parameter. Similarly, if ``"plaintext"`` is chosen, the column must be of the
+            string type.
+        delimiter: The field delimiter for the ``"dsv"`` format.
+        value: The column used as the record in the ``"plaintext"`` or ``"raw"`` format.
+            It can be omitted if the table has exactly one column.
+
+    Example:
+
+    Assume that `LocalStack <https://www.localstack.cloud/>`_ is running locally on the
+    default port, ``4566``. The events of the users can be put to the stream ``events`` so
+    that the events of each user get into the same shard:
+
+    >>> import pathway as pw
+    >>> events = pw.debug.table_from_markdown('''
+    ... user  | action
+    ... alice | login
+    ... bob   | logout
+    ... ''')
+    >>> pw.io.kinesis.write(
+    ...     events,
+    ...     "events",
+    ...     partition_key=events.user,
+    ...     region="us-east-1",
+    ...     endpoint="http://localhost:4566",
+    ... )
+    """
+
+    output_format = MessageQueueOutputFormat.construct(
+        table,
+        format=format,
+        delimiter=delimiter,
+        key=partition_key,
+        value=value,
+    )
+    table = output_format.table
+
+    data_storage = api.DataStorage(
+        storage_type="kinesis",
+        topic=stream_name,
+        aws_s3_settings=_aws_settings(region, endpoint),
+        key_fields=list(output_format.key_fields.items()),
+    )
+
+    table.to(
+        datasink.GenericDataSink(
+            data_storage, output_format.data_format, datasink_name="kinesis"
+        )
+    )
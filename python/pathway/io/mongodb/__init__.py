@@ -2,11 +2,99 @@
 
 from __future__ import annotations
 
-from pathway.internals import api, datasink
+from typing import Any
+
+from pathway.internals import api, datasink, datasource
 from pathway.internals._io_helpers import _format_output_value_fields
 from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.schema import Schema
 from pathway.internals.table import Table
+from pathway.internals.table_io import table_from_datasource
 from pathway.internals.trace import trace_user_frame
+from pathway.io._utils import internal_connector_mode, read_schema
+
+
+@check_arg_types
+@trace_user_frame
+def read(
+    *,
+    connection_string: str,
+    database: str,
+    collection: str,
+    schema: type[Schema],
+    mode: str = "streaming",
+    autocommit_duration_ms: int | None = 1500,
+    persistent_id: str | None = None,
+    debug_data: Any = None,
+) -> Table:
+    """Reads a collection from MongoDB.
+
+    The connector first reads the documents currently stored in the collection. Then,
+    in the ``"streaming"`` mode, it follows the collection's
+    `change stream <https://www.mongodb.com/docs/manual/changeStreams/>`_, so that
+    the insertions, updates and deletions of the documents are reflected in the
+    resulting table. Note that the change streams are only available for replica sets
+    and sharded clusters.
+
+    The rows of the table are identified by the ``_id`` field of the documents. The
+    fields of the documents are parsed according to the schema. Like in the writer,
+    the ``Duration`` values are expected to be given as an integer number of
+    milliseconds.
+
+    Args:
+        connection_string: The connection string for the MongoDB database. See the \
+`MongoDB documentation <https://www.mongodb.com/docs/manual/reference/connection-string/>`_ \
+for the details.
+        database: The name of the database to read from.
+        collection: The name of the collection to read.
+        schema: Schema of the resulting table.
+        mode: Denotes how the engine polls the new data from the source. Currently
+            ``"streaming"`` and ``"static"`` are supported. If set to ``"streaming"``,
+            the engine will follow the change stream of the collection after reading
+            its current state. The ``"static"`` mode will only read the documents
+            that are present in the collection at the start.
+        autocommit_duration_ms: The maximum time between two commits. Every
+            ``autocommit_duration_ms`` milliseconds, the updates received by the connector are
+            committed and pushed into Pathway's computation graph.
+        persistent_id: (unstable) An identifier, under which the state of the table
+            will be persisted or ``None``, if there is no need to persist the state of this table.
+            When a program restarts, it restores the state for all input tables according to what
+            was saved for their ``persistent_id``. The change stream is then resumed from
+            the last persisted change, without reading the collection again.
+        debug_data: Static data replacing original one when debug mode is active.
+
+    Returns:
+        Table: The table read.
+    """
+    schema, api_schema = read_schema(schema=schema)
+
+    data_storage = api.DataStorage(
+        storage_type="mongodb",
+        connection_string=connection_string,
+        database=database,
+        table_name=collection,
+        mode=internal_connector_mode(mode),
+        persistent_id=persistent_id,
+    )
+    data_format = api.DataFormat(
+        format_type="transparent",
+        session_type=api.SessionType.UPSERT,
+        **api_schema,
+    )
+
+    data_source_options = datasource.DataSourceOptions(
+        commit_duration_ms=autocommit_duration_ms
+    )
+    return table_from_datasource(
+        datasource.GenericDataSource(
+            datastorage=data_storage,
+            dataformat=data_format,
+            schema=schema,
+            data_source_options=data_source_options,
+            datasource_name="mongodb",
+        ),
+        debug_datasource=datasource.debug_datasource(debug_data),
+    )
 
 
 @check_arg_types
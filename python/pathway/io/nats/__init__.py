@@ -30,6 +30,10 @@ def read(
     json_field_paths: dict[str, str] | None = None,
     parallel_readers: int | None = None,
     persistent_id: str | None = None,
+    jetstream: str | None = None,
+    durable_name: str | None = None,
+    ack_wait_ms: int | None = None,
+    max_ack_pending: int = 1000,
     debug_data=None,
 ) -> Table:
     """Reads data from a specified NATS topic.
@@ -69,6 +73,25 @@ def read(
             When a program restarts, it restores the state for all input tables according to what
             was saved for their ``persistent_id``. This way it's possible to configure the start of
             computations from the moment they were terminated last time.
+        jetstream: The name of the JetStream stream to read the topic from. If set,
+            the messages are read by a durable pull consumer instead of a core NATS
+            subscription, so the messages published while the program isn't running
+            are read after it starts. The topic may contain wildcards. The stream
+            sequence of the last message read from each subject is stored in the
+            persisted state, and the messages are acknowledged only after they're
+            persisted. Without persistence, they are acknowledged right after reading.
+            In this mode, the messages are read by a single reader.
+        durable_name: The name of the durable JetStream consumer. The consumer is
+            created if it doesn't exist. Required if ``jetstream`` is set.
+        ack_wait_ms: The time in milliseconds, after which the server redelivers a
+            JetStream message that isn't acknowledged. With persistence, it should be
+            longer than the time it takes to persist the messages read, which depends
+            on ``autocommit_duration_ms`` and the snapshot interval of the persistence
+            config. If not specified, the server default of 30 seconds is used.
+        max_ack_pending: The maximum number of JetStream messages that are read, but not
+            acknowledged yet. When it's reached, the reading pauses until the persisted
+            messages are acknowledged. Both ``ack_wait_ms`` and ``max_ack_pending`` are
+            applied when the consumer is created, an existing consumer keeps its settings.
         debug_data: Static data replacing original one when debug mode is active.
 
     Returns:
@@ -89,6 +112,17 @@ def read(
     Keep in mind that NATS doesn't normally store messages. So, make sure to start your
     Pathway program before sending any messages.
 
+    If the messages are stored in a JetStream stream, for instance, the stream
+    ``"events"`` created by the ``nats-server -js`` instance, they can be read with a
+    durable consumer, so that no messages are lost between the program runs:
+
+    >>> table = pw.io.nats.read(
+    ...     "nats://127.0.0.1:4222",
+    ...     "data",
+    ...     jetstream="events",
+    ...     durable_name="pathway-reader",
+    ... )
+
     You can also parse messages as UTF-8 during reading by using the ``"format"`` parameter.
     Here's how the reading process would look:
 
@@ -119,12 +153,20 @@ def read(
     ``"phone"``. The ``"id"`` column will also act as the primary key for the Pathway table.
     """
 
+    if jetstream is not None and durable_name is None:
+        raise ValueError("durable_name must be specified for reading from JetStream")
+    if max_ack_pending <= 0:
+        raise ValueError("max_ack_pending must be positive")
     data_storage = api.DataStorage(
         storage_type="nats",
         path=uri,
         topic=topic,
         parallel_readers=parallel_readers,
         persistent_id=persistent_id,
+        jetstream_stream=jetstream,
+        durable_name=durable_name,
+        ack_wait_ms=ack_wait_ms,
+        max_ack_pending=max_ack_pending,
         mode=api.ConnectorMode.STREAMING,
     )
     schema, data_format = construct_schema_and_data_format(
@@ -2,17 +2,107 @@
 
 from __future__ import annotations
 
-from pathway.internals import api, datasink
+from typing import Any
+
+from pathway.internals import api, datasink, datasource
 from pathway.internals._io_helpers import _format_output_value_fields
 from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.schema import Schema
 from pathway.internals.table import Table
+from pathway.internals.table_io import table_from_datasource
 from pathway.internals.trace import trace_user_frame
+from pathway.io._utils import read_schema
 
 
 def _connection_string_from_settings(settings: dict):
     return " ".join(k + "=" + v for (k, v) in settings.items())
 
 
+@check_arg_types
+@trace_user_frame
+def read(
+    postgres_settings: dict,
+    table_name: str,
+    schema: type[Schema],
+    *,
+    slot_name: str,
+    publication_name: str,
+    autocommit_duration_ms: int | None = 1500,
+    persistent_id: str | None = None,
+    debug_data: Any = None,
+) -> Table:
+    """Reads the stream of changes of a table in Postgres via logical replication.
+
+    The changes are taken from the replication slot ``slot_name``, decoded with the
+    standard ``pgoutput`` plugin. The slot is created if it doesn't exist yet. The
+    publication ``publication_name`` must include the table being read, and the database
+    must be configured with ``wal_level = logical``. Note that only the changes made after
+    the creation of the slot are read.
+
+    If the schema defines a primary key, the changes are applied as upserts by this key.
+    Otherwise, the table in Postgres must have ``REPLICA IDENTITY FULL``, so that the
+    updates and deletions contain the previous versions of the rows.
+
+    A ``TRUNCATE`` of the table can't be represented as the deletions of its rows, since
+    the replication stream doesn't contain them. If the table is truncated, the reading
+    fails with an error.
+
+    Args:
+        postgres_settings: Components for the connection string for Postgres.
+        table_name: Name of the table to be read, optionally prefixed with the schema name.
+        schema: Schema of the resulting table.
+        slot_name: Name of the logical replication slot.
+        publication_name: Name of the publication, which contains the table.
+        autocommit_duration_ms: The maximum time between two commits. Every
+            autocommit_duration_ms milliseconds, the updates received by the connector are
+            committed and pushed into Pathway's computation graph.
+        persistent_id: (unstable) An identifier, under which the state of the table will
+            be persisted or ``None``, if there is no need to persist the state of this table.
+            When a program restarts, it restores the state for all input tables according to what
+            was saved for their ``persistent_id``. If set, the replication slot is advanced only
+            after the changes are persisted.
+        debug_data: Static data replacing original one when debug mode is active.
+
+    Returns:
+        Table: The table read.
+    """
+    schema, api_schema = read_schema(schema=schema)
+    session_type = (
+        api.SessionType.UPSERT
+        if schema.primary_key_columns()
+        else api.SessionType.NATIVE
+    )
+
+    data_storage = api.DataStorage(
+        storage_type="postgres_replication",
+        connection_string=_connection_string_from_settings(postgres_settings),
+        table_name=table_name,
+        slot_name=slot_name,
+        publication_name=publication_name,
+        persistent_id=persistent_id,
+        mode=api.ConnectorMode.STREAMING,
+    )
+    data_format = api.DataFormat(
+        format_type="transparent",
+        session_type=session_type,
+        **api_schema,
+    )
+
+    data_source_options = datasource.DataSourceOptions(
+        commit_duration_ms=autocommit_duration_ms
+    )
+    return table_from_datasource(
+        datasource.GenericDataSource(
+            datastorage=data_storage,
+            dataformat=data_format,
+            schema=schema,
+            data_source_options=data_source_options,
+            datasource_name="postgres",
+        ),
+        debug_datasource=datasource.debug_datasource(debug_data),
+    )
+
+
 @check_arg_types
 @trace_user_frame
 def write(
@@ -1,11 +1,158 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
 import logging
-from typing import Any
+from typing import Any, Iterable, Literal
 
 from google.cloud import pubsub_v1  # type: ignore
 
 import pathway.internals.dtype as dt
+from pathway.internals import api, datasink, datasource
 from pathway.internals.api import Pointer
+from pathway.internals.expression import ColumnReference
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.schema import Schema
+from pathway.internals.table import Table
+from pathway.internals.table_io import table_from_datasource
+from pathway.internals.trace import trace_user_frame
 from pathway.io._subscribe import subscribe
+from pathway.io._utils import MessageQueueOutputFormat, construct_schema_and_data_format
+
+
+@check_arg_types
+@trace_user_frame
+def read(
+    project_id: str,
+    subscription_id: str,
+    *,
+    schema: type[Schema] | None = None,
+    format: Literal["plaintext", "raw", "json"] = "raw",
+    autocommit_duration_ms: int | None = 1500,
+    json_field_paths: dict[str, str] | None = None,
+    with_metadata: bool = False,
+    attribute_fields: dict[str, str] | None = None,
+    publish_time_field: str | None = None,
+    credentials_file: str | None = None,
+    persistent_id: str | None = None,
+    debug_data=None,
+) -> Table:
+    """Reads data from a `Google Cloud Pub/Sub <https://cloud.google.com/pubsub>`_
+    subscription.
+
+    It supports three formats: ``"plaintext"``, ``"raw"``, and ``"json"``.
+
+    * For the ``"raw"`` format, the payload is read as raw bytes and added directly to the
+    table.
+    * In the ``"plaintext"`` format, the payload decoded from UTF-8 and stored as plain text.
+
+    In both cases, the table will have a ``"data"`` column representing the payload.
+
+    If you select the ``"json"`` format, the connector parses the message payload as JSON
+    and creates table columns based on the schema provided in the ``schema`` parameter. The
+    column values come from the corresponding JSON fields.
+
+    The subscription is read by several Pathway workers with a streaming pull, so the
+    messages are distributed among them by the server.
+
+    If persistence is enabled, the messages are acknowledged only after they're
+    persisted, so the messages that aren't persisted before a restart are redelivered.
+    Until then, the connector extends the ack deadlines of the messages. Without
+    persistence, the messages are acknowledged right after reading. In both cases, the
+    delivery is at-least-once, as the Pub/Sub service may redeliver the messages.
+
+    The connector authenticates with the service account key from ``credentials_file``,
+    or with the Application Default Credentials if it's not given. For local testing,
+    set the ``PUBSUB_EMULATOR_HOST`` environment variable to the address of the
+    `Pub/Sub emulator <https://cloud.google.com/pubsub/docs/emulator>`_: then the
+    connector connects to the emulator without authentication.
+
+    Args:
+        project_id: The ID of the Google Cloud project of the subscription.
+        subscription_id: The ID of the subscription to read.
+        schema: The table schema, used only when the format is set to ``"json"``.
+        format: The input data format, which can be ``"raw"``, ``"plaintext"``, or
+            ``"json"``.
+        autocommit_duration_ms: The time interval (in milliseconds) between commits.
+            After this time, the updates received by the connector are committed and
+            added to Pathway's computation graph.
+        json_field_paths: For the ``"json"`` format, this allows mapping field names to
+            paths within the JSON structure. Use the format ``<field_name>: <path>``
+            where the path follows the
+            `JSON Pointer (RFC 6901) <https://www.rfc-editor.org/rfc/rfc6901>`_.
+        with_metadata: When set to ``True``, the connector will add an additional column
+            named ``_metadata`` to the table. This JSON field will contain the
+            ``subscription``, the ``message_id``, the ``publish_time_millis``, the
+            optional ``ordering_key`` and ``delivery_attempt`` and the ``attributes`` of
+            the message.
+        attribute_fields: A mapping from the names of the columns to the names of the
+            message attributes, which these columns are filled from, instead of the
+            payload. The attribute values are parsed according to the types of the
+            columns. If a message doesn't have the attribute, the default value of the
+            column is used, or ``None`` for an optional column. Only supported for the
+            "json" format.
+        publish_time_field: The name of the column, which is filled with the publish time
+            of the message. The column can be of type ``pw.DateTimeUtc``,
+            ``pw.DateTimeNaive`` or ``int``, in the latter case it contains the number
+            of milliseconds since the UNIX epoch. Only supported for the "json" format.
+        credentials_file: The path to the JSON file with the service account key.
+        persistent_id: (unstable) An identifier, under which the state of the table will
+            be persisted or ``None``, if there is no need to persist the state of this table.
+        debug_data: Static data replacing original one when debug mode is active.
+
+    Returns:
+        Table: The table read.
+
+    Example:
+
+    Assume that the publishers send JSON messages with the orders to a topic, which has
+    a subscription ``orders-pathway`` in the project ``my-project``, and the ID of the
+    store is sent in the ``store`` attribute. The orders can be read as follows:
+
+    >>> import pathway as pw
+    >>> class InputSchema(pw.Schema):
+    ...     user: str
+    ...     amount: float
+    ...     store: str
+    >>> table = pw.io.pubsub.read(
+    ...     "my-project",
+    ...     "orders-pathway",
+    ...     format="json",
+    ...     schema=InputSchema,
+    ...     attribute_fields={"store": "store"},
+    ... )
+    """
+
+    data_storage = api.DataStorage(
+        storage_type="pubsub",
+        project_id=project_id,
+        subscription=subscription_id,
+        credentials_file=credentials_file,
+        persistent_id=persistent_id,
+        mode=api.ConnectorMode.STREAMING,
+    )
+    schema, data_format = construct_schema_and_data_format(
+        "binary" if format == "raw" else format,
+        with_metadata=with_metadata,
+        schema=schema,
+        csv_settings=None,
+        json_field_paths=json_field_paths,
+        attribute_fields=attribute_fields,
+        publish_time_field=publish_time_field,
+    )
+    data_source_options = datasource.DataSourceOptions(
+        commit_duration_ms=autocommit_duration_ms
+    )
+    return table_from_datasource(
+        datasource.GenericDataSource(
+            datastorage=data_storage,
+            dataformat=data_format,
+            data_source_options=data_source_options,
+            schema=schema,
+            datasource_name="pubsub",
+        ),
+        debug_datasource=datasource.debug_datasource(debug_data),
+    )
 
 
 class _OutputBuffer:
@@ -46,78 +193,172 @@ class _OutputBuffer:
         self._publish_futures = []
 
 
+def _write_with_publisher(
+    table: Table, publisher: pubsub_v1.PublisherClient, project_id: str, topic_id: str
+) -> None:
+    columns = list(table._columns.values())
+    if len(columns) != 1:
+        raise ValueError(
+            f"Unexpected number of columns in table: {len(table._columns)}"
+        )
+
+    allowed_column_types = (dt.BYTES, dt.ANY)
+    if columns[0].dtype not in allowed_column_types:
+        raise ValueError("The column should be of the type 'bytes'")
+
+    output_buffer = _OutputBuffer(publisher, project_id, topic_id)
+    subscribe(
+        table, on_change=output_buffer.on_change, on_time_end=output_buffer.on_time_end
+    )
+
+
+@check_arg_types
+@trace_user_frame
 def write(
-    table, publisher: pubsub_v1.PublisherClient, project_id: str, topic_id: str
+    table: Table,
+    publisher: pubsub_v1.PublisherClient | None,
+    project_id: str,
+    topic_id: str,
+    *,
+    format: str = "raw",
+    delimiter: str = ",",
+    value: ColumnReference | None = None,
+    ordering_key: ColumnReference | None = None,
+    attributes: Iterable[ColumnReference] | None = None,
+    credentials_file: str | None = None,
 ) -> None:
-    """Publish the ``table``'s stream of changes into the specified PubSub topic. Please note \
-that ``table`` must consist of a single column of the binary type. In addition, the connector \
-adds two attributes: ``pathway_time`` containing the logical time of the change and \
-``pathway_diff`` corresponding to the change type: either addition (``pathway_diff = 1``) \
-or deletion (``pathway_diff = -1``).
+    """Publishes the rows of the table to a `Google Cloud Pub/Sub
+    <https://cloud.google.com/pubsub>`_ topic.
+
+    Each change of the table produces a message with two attributes: ``pathway_time``,
+    corresponding to the processing time of the entry and ``pathway_diff`` that is
+    either 1 or -1. In the ``"raw"`` and ``"plaintext"`` formats, the payload is the
+    value of a single column, and the other columns can be passed in the
+    ``attributes``. In the ``"json"`` and ``"dsv"`` formats, the message contains the
+    values of the row together with the ``time`` and ``diff`` fields.
+
+    If the ``publisher`` is given, the messages are published with this client. In
+    this case, only the default ``"raw"`` format is supported, so the table must
+    consist of a single column of the binary type, and the other keyword arguments
+    can't be used.
+
+    Otherwise, if ``publisher`` is ``None``, the messages are published by the
+    Pathway engine, and after each batch of changes, the connector waits until the
+    service confirms all messages published. The authentication and the connection to
+    the emulator are the same as in :py:func:`pathway.io.pubsub.read`.
 
     Args:
-        table: The table to publish.
-        publisher: The configured ``pubsub_v1.PublisherClient`` object. You can refer to \
-the Google Cloud `documentation \
-<https://cloud.google.com/pubsub/docs/samples/pubsub-quickstart-publisher?hl=en>`_ for \
-the example of a simple publisher configuration. You can also see the examples for \
-`batching settings configuration \
-<https://cloud.google.com/pubsub/docs/samples/pubsub-publisher-batch-settings?hl=en>`_ or \
-`flow control configuration \
-<https://cloud.google.com/pubsub/docs/samples/pubsub-publisher-flow-control?hl=en>`_.
-        project_id: The ID of the project where the changes are published.
-        topic_id: The topic ID where the changes are published.
+        table: The table for output.
+        publisher: The configured ``pubsub_v1.PublisherClient`` object or ``None``, if
+            the messages should be published by the Pathway engine. You can refer to
+            the Google Cloud `documentation
+            <https://cloud.google.com/pubsub/docs/samples/pubsub-quickstart-publisher?hl=en>`_
+            for the example of a simple publisher configuration.
+        project_id: The ID of the Google Cloud project of the topic.
+        topic_id: The ID of the topic to publish the messages to.
+        format: The format of the messages: ``"raw"``, ``"plaintext"``, ``"json"`` or
+            ``"dsv"``. If the ``"raw"`` format is selected, ``table`` must either contain
+            exactly one binary column or the column must be specified in the ``value``
+            parameter. Similarly, if ``"plaintext"`` is chosen, the column must be of the
+            string type.
+        delimiter: The field delimiter for the ``"dsv"`` format.
+        value: The column used as the payload in the ``"plaintext"`` or ``"raw"`` format.
+            It can be omitted if the table has exactly one column.
+        ordering_key: The reference to the string or binary column that should be
+            used as the ordering key of the messages. The messages with the same
+            ordering key are delivered in the order of publishing to the subscriptions
+            with the message ordering enabled. If left empty, the messages are
+            published without an ordering key.
+        attributes: References to the table fields that must be provided as message
+            attributes. These attributes are named in the same way as fields that are
+            forwarded and contain the string representations of the respective values.
+            Only supported for the ``"plaintext"`` and ``"raw"`` formats.
+        credentials_file: The path to the JSON file with the service account key.
 
     Returns:
         None
 
     Example:
 
-    Consider that you have a table ``blobs``, consisting of a single column that has a binary
-    type. You would like to publish changes that happen to this table into a topic called
-    ``blobs`` in your Google Cloud pub/sub project.
-
-    For simplicity, let's consider that the project ID is stored in the ``project_id``
-    variable. The ``topic_id`` variable then would denote the name of the target topic
-    which is ``blobs`` in our case:
-
-    >>> project_id = "YOUR_PROJECT_ID"
-    >>> topic_id = "blobs"
-
-    Now, you need to create the publisher object of ``pubsub_v1.PublisherClient``
-    type. If you have the `service account
+    Consider that you have a table ``blobs``, consisting of a single column that has a
+    binary type, and the topic ``blobs`` exists in the project ``my-project``. If you
+    have the `service account
     <https://cloud.google.com/iam/docs/service-account-overview>`_ credentials stored
-    in a file ``./credentials.json``, it can be done with the following code:
+    in a file ``./credentials.json``, you can create a publisher and publish the
+    changes of the table with it:
 
+    >>> import pathway as pw
     >>> from google.cloud import pubsub_v1
     >>> publisher = pubsub_v1.PublisherClient.from_service_account_file(  # doctest: +SKIP
     ...     "./credentials.json"
     ... )
+    >>> pw.io.pubsub.write(table, publisher, "my-project", "blobs")  # doctest: +SKIP
 
-    If you don't have the topic created yet, you may want to create it first:
-
-    >>> topic_path = publisher.topic_path(project_id, topic_id)  # doctest: +SKIP
-    >>> topic = publisher.create_topic(request={"name": topic_path})  # doctest: +SKIP
+    The messages can also be published by the Pathway engine, which supports the other
+    formats. The totals of the users can be published as JSON with the user as the
+    ordering key as follows:
 
-    After that you can configure the table output with the following code:
-
-    >>> import pathway as pw
-    >>> pw.io.pubsub.write(table, publisher, project_id, topic_id)  # doctest: +SKIP
+    >>> totals = pw.debug.table_from_markdown('''
+    ... user  | total
+    ... alice | 12.5
+    ... bob   | 40.0
+    ... ''')
+    >>> pw.io.pubsub.write(
+    ...     totals,
+    ...     None,
+    ...     "my-project",
+    ...     "totals",
+    ...     format="json",
+    ...     ordering_key=totals.user,
+    ...     credentials_file="./credentials.json",
+    ... )
 
     At last, don't forget to add ``pw.run()`` to run your pipeline.
     """
 
-    columns = list(table._columns.values())
-    if len(columns) != 1:
-        raise ValueError(
-            f"Unexpected number of columns in table: {len(table._columns)}"
-        )
+    if publisher is not None:
+        custom_arguments = {
+            "format": format != "raw",
+            "delimiter": delimiter != ",",
+            "value": value is not None,
+            "ordering_key": ordering_key is not None,
+            "attributes": attributes is not None,
+            "credentials_file": credentials_file is not None,
+        }
+        for name, is_custom in custom_arguments.items():
+            if is_custom:
+                raise ValueError(
+                    f"Unsupported argument together with publisher: {name}"
+                )
+        _write_with_publisher(table, publisher, project_id, topic_id)
+        return
 
-    allowed_column_types = (dt.BYTES, dt.ANY)
-    if columns[0].dtype not in allowed_column_types:
-        raise ValueError("The column should be of the type 'bytes'")
+    if format not in ("raw", "plaintext"):
+        for name, argument in (("value", value), ("attributes", attributes)):
+            if argument is not None:
+                raise ValueError(f"Unsupported argument for {format} format: {name}")
 
-    output_buffer = _OutputBuffer(publisher, project_id, topic_id)
-    subscribe(
-        table, on_change=output_buffer.on_change, on_time_end=output_buffer.on_time_end
+    output_format = MessageQueueOutputFormat.construct(
+        table,
+        format=format,
+        delimiter=delimiter,
+        key=ordering_key,
+        value=value,
+        headers=attributes,
+    )
+    table = output_format.table
+
+    data_storage = api.DataStorage(
+        storage_type="pubsub",
+        project_id=project_id,
+        topic=topic_id,
+        credentials_file=credentials_file,
+        key_fields=list(output_format.key_fields.items()),
+        header_fields=list(output_format.header_fields.items()),
+    )
+
+    table.to(
+        datasink.GenericDataSink(
+            data_storage, output_format.data_format, datasink_name="pubsub"
+        )
     )
@@ -0,0 +1,259 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+from typing import Iterable, Literal
+
+from pathway.internals import api, datasink, datasource
+from pathway.internals.expression import ColumnReference
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.schema import Schema
+from pathway.internals.table import Table
+from pathway.internals.table_io import table_from_datasource
+from pathway.internals.trace import trace_user_frame
+from pathway.io._utils import (
+    MessageQueueOutputFormat,
+    check_raw_and_plaintext_only_kwargs_for_message_queues,
+    construct_schema_and_data_format,
+)
+
+
+@check_arg_types
+@trace_user_frame
+def read(
+    service_url: str,
+    topic: str | list[str],
+    *,
+    subscription: str = "pathway",
+    schema: type[Schema] | None = None,
+    format: Literal["plaintext", "raw", "json"] = "raw",
+    autocommit_duration_ms: int | None = 1500,
+    json_field_paths: dict[str, str] | None = None,
+    autogenerate_key: bool = False,
+    with_metadata: bool = False,
+    parallel_readers: int | None = None,
+    persistent_id: str | None = None,
+    upsert: bool = False,
+    debug_data=None,
+) -> Table:
+    """Reads data from one or several topics of `Apache Pulsar <https://pulsar.apache.org/>`_.
+
+    It supports three formats: ``"plaintext"``, ``"raw"``, and ``"json"``.
+
+    * For the ``"raw"`` format, the payload is read as raw bytes and added directly to the
+    table.
+    * In the ``"plaintext"`` format, the payload decoded from UTF-8 and stored as plain text.
+
+    In both cases, the table will have a ``"data"`` column representing the payload. The
+    primary key is taken from the message key if it's present, unless
+    ``autogenerate_key`` is set.
+
+    If you select the ``"json"`` format, the connector parses the message payload as JSON
+    and creates table columns based on the schema provided in the ``schema`` parameter. The
+    column values come from the corresponding JSON fields.
+
+    The topics are read with a failover subscription, so each partition of a topic is
+    read by a single Pathway worker. If the subscription doesn't exist, the topics are
+    read from the beginning.
+
+    If persistence is enabled, the ID of the last message read from each topic partition
+    is stored in the persisted state, and after a restart the reading continues right
+    after it. The messages are acknowledged only after they're persisted. Without
+    persistence, the messages are acknowledged right after reading.
+
+    Args:
+        service_url: The URL of the Pulsar service, for example,
+            ``"pulsar://localhost:6650"``. The ``pulsar+ssl`` scheme enables TLS.
+        topic: The name of the topic to read, or a list of such names. A partitioned
+            topic is read from all its partitions.
+        subscription: The name of the subscription.
+        schema: The table schema, used only when the format is set to ``"json"``.
+        format: The input data format, which can be ``"raw"``, ``"plaintext"``, or
+            ``"json"``.
+        autocommit_duration_ms: The time interval (in milliseconds) between commits.
+            After this time, the updates received by the connector are committed and
+            added to Pathway's computation graph.
+        json_field_paths: For the ``"json"`` format, this allows mapping field names to
+            paths within the JSON structure. Use the format ``<field_name>: <path>``
+            where the path follows the
+            `JSON Pointer (RFC 6901) <https://www.rfc-editor.org/rfc/rfc6901>`_.
+        autogenerate_key: If ``True``, Pathway automatically generates unique primary key
+            for the entries read. Otherwise it first tries to use the key from the message.
+            This parameter is used only if the ``format`` is "raw" or "plaintext".
+        with_metadata: When set to ``True``, the connector will add an additional column
+            named ``_metadata`` to the table. This JSON field will contain the ``topic``
+            partition, the ``key``, the ``publish_time_millis``, the optional
+            ``event_time_millis`` and the ``ledger_id``, ``entry_id`` and ``batch_index``
+            of the message ID.
+        parallel_readers: The number of copies of the reader to work in parallel. It can't
+            be greater than the number of Pathway engine threads, and will be reduced to
+            the number of engine threads, if it exceeds.
+        persistent_id: (unstable) An identifier, under which the state of the table will
+            be persisted or ``None``, if there is no need to persist the state of this table.
+        upsert: If ``True``, the topic is read as a changelog of entities, for instance,
+            a compacted topic: the key of a message is the primary key of the row, a
+            message with a payload replaces the row with this key and a message without
+            payload (a tombstone) removes it. Every message must have a key in this mode.
+        debug_data: Static data replacing original one when debug mode is active.
+
+    Returns:
+        Table: The table read.
+
+    Example:
+
+    Assume that Pulsar is running locally on the default port, ``6650``, and the
+    producers publish JSON messages to the topic ``orders``. They can be read as follows:
+
+    >>> import pathway as pw
+    >>> class InputSchema(pw.Schema):
+    ...     user: str
+    ...     amount: float
+    >>> table = pw.io.pulsar.read(
+    ...     "pulsar://127.0.0.1:6650",
+    ...     "persistent://public/default/orders",
+    ...     format="json",
+    ...     schema=InputSchema,
+    ... )
+    """
+
+    if upsert and autogenerate_key:
+        raise ValueError(
+            "autogenerate_key can't be used in the upsert mode, "
+            "because the message key is the primary key"
+        )
+
+    data_storage = api.DataStorage(
+        storage_type="pulsar",
+        connection_string=service_url,
+        topic=topic if isinstance(topic, str) else None,
+        topic_names=topic if isinstance(topic, list) else None,
+        subscription=subscription,
+        parallel_readers=parallel_readers,
+        persistent_id=persistent_id,
+        mode=api.ConnectorMode.STREAMING,
+    )
+    schema, data_format = construct_schema_and_data_format(
+        "binary" if format == "raw" else format,
+        with_metadata=with_metadata,
+        autogenerate_key=autogenerate_key,
+        schema=schema,
+        csv_settings=None,
+        json_field_paths=json_field_paths,
+        session_type=api.SessionType.UPSERT if upsert else api.SessionType.NATIVE,
+    )
+    data_source_options = datasource.DataSourceOptions(
+        commit_duration_ms=autocommit_duration_ms
+    )
+    return table_from_datasource(
+        datasource.GenericDataSource(
+            datastorage=data_storage,
+            dataformat=data_format,
+            data_source_options=data_source_options,
+            schema=schema,
+            datasource_name="pulsar",
+        ),
+        debug_datasource=datasource.debug_datasource(debug_data),
+    )
+
+
+@check_raw_and_plaintext_only_kwargs_for_message_queues
+@check_arg_types
+@trace_user_frame
+def write(
+    table: Table,
+    service_url: str,
+    topic: str,
+    *,
+    format: str = "json",
+    delimiter: str = ",",
+    key: ColumnReference | list[ColumnReference] | None = None,
+    value: ColumnReference | None = None,
+    headers: Iterable[ColumnReference] | None = None,
+    tombstones: bool = False,
+) -> None:
+    """Publishes the rows of the table to a topic of `Apache Pulsar <https://pulsar.apache.org/>`_.
+
+    Each change of the table produces a keyed message with two properties:
+    ``pathway_time``, corresponding to the processing time of the entry and
+    ``pathway_diff`` that is either 1 or -1. In the ``"json"`` and ``"dsv"`` formats, the
+    message contains the values of the row together with the ``time`` and ``diff``
+    fields. In the ``"plaintext"`` and ``"raw"`` formats, the payload is the value of a
+    single column, and the other columns can be passed in the ``headers``.
+
+    For a partitioned topic, the partition of a message is chosen by the hash of its key
+    in the same way as in the Java client with the default ``JavaStringHash`` hashing
+    scheme, so the messages with the same key get into the same partition as the ones
+    produced by such clients. After each batch of changes, the connector waits until the broker
+    confirms all messages published.
+
+    Args:
+        table: The table for output.
+        service_url: The URL of the Pulsar service, for example,
+            ``"pulsar://localhost:6650"``.
+        topic: The name of the topic to publish the messages to.
+        format: The format of the messages: ``"json"``, ``"dsv"``, ``"plaintext"`` or
+            ``"raw"``. If the ``"raw"`` format is selected, ``table`` must either contain
+            exactly one binary column or the column must be specified in the ``value``
+            parameter. Similarly, if ``"plaintext"`` is chosen, the column must be of the
+            string type.
+        delimiter: The field delimiter for the ``"dsv"`` format.
+        key: The reference to the column that should be used as the message key. If left
+            empty, an internal primary key will be used. In the ``"json"`` and ``"dsv"``
+            formats, a list of columns can be given: then the key is a JSON object with
+            the values of these columns. A single key column must be of the string or
+            binary type. The keys that aren't valid UTF-8 are encoded in base64.
+        value: The column used as the payload in the ``"plaintext"`` or ``"raw"`` format.
+            It can be omitted if the table has exactly one column.
+        headers: References to the table fields that must be provided as message
+            properties. These properties are named in the same way as fields that are
+            forwarded and contain the string representations of the respective values.
+        tombstones: If ``True``, the table is written as a changelog for a compacted
+            topic: a row removal, which is not followed by an insertion of the row with
+            the same key at the same processing time, is published as a message with an
+            empty payload (a tombstone), while a removal followed by an insertion is
+            published only as the insertion.
+
+    Example:
+
+    Assume that Pulsar is running locally on the default port, ``6650``. The current
+    totals of the users can be maintained in a compacted topic as follows:
+
+    >>> import pathway as pw
+    >>> totals = pw.debug.table_from_markdown('''
+    ... user  | total
+    ... alice | 12.5
+    ... bob   | 40.0
+    ... ''')
+    >>> pw.io.pulsar.write(
+    ...     totals,
+    ...     "pulsar://127.0.0.1:6650",
+    ...     "persistent://public/default/totals",
+    ...     key=totals.user,
+    ...     tombstones=True,
+    ... )
+    """
+
+    output_format = MessageQueueOutputFormat.construct(
+        table,
+        format=format,
+        delimiter=delimiter,
+        key=key,
+        value=value,
+        headers=headers,
+    )
+    table = output_format.table
+
+    data_storage = api.DataStorage(
+        storage_type="pulsar",
+        connection_string=service_url,
+        topic=topic,
+        key_fields=list(output_format.key_fields.items()),
+        header_fields=list(output_format.header_fields.items()),
+        produce_tombstones=tombstones,
+    )
+
+    table.to(
+        datasink.GenericDataSink(
+            data_storage, output_format.data_format, datasink_name="pulsar"
+        )
+    )
@@ -0,0 +1,189 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+from typing import Any, Literal
+
+from pathway.internals import api, datasink, datasource
+from pathway.internals._io_helpers import _format_output_value_fields
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.schema import Schema
+from pathway.internals.table import Table
+from pathway.internals.table_io import table_from_datasource
+from pathway.internals.trace import trace_user_frame
+from pathway.io._utils import read_schema
+
+
+@check_arg_types
+@trace_user_frame
+def read(
+    connection_string: str,
+    stream_key: str | list[str],
+    *,
+    schema: type[Schema],
+    consumer_group: str = "pathway",
+    consumer_name: str = "pathway-reader",
+    autocommit_duration_ms: int | None = 1500,
+    persistent_id: str | None = None,
+    debug_data: Any = None,
+) -> Table:
+    """Reads the entries of one or several Redis streams as a member of a consumer group.
+
+    The consumer group is created if it doesn't exist, in which case the streams are
+    read from the beginning. Otherwise, the reading continues from the last entry
+    delivered to the group. Each field of a stream entry fills the column with the same
+    name, and its value is parsed according to the type of the column: the booleans
+    are accepted as ``true``/``false`` or ``1``/``0``, the dates in the ISO 8601
+    format and the JSON columns as JSON documents. The absent fields get the default
+    values of the columns.
+
+    If persistence is enabled, the ID of the last entry read from each stream is
+    stored in the persisted state, and the entries are acknowledged in the consumer
+    group only after they're persisted. The entries read but not persisted before a
+    restart remain in the pending entries list of the consumer and are read again, so
+    the ``consumer_name`` must stay the same between the program runs. Without
+    persistence, the entries are acknowledged right after reading.
+
+    Args:
+        connection_string: The URL of the Redis server, for example,
+            ``"redis://localhost:6379/0"``.
+        stream_key: The key of the stream to read, or a list of such keys.
+        schema: Schema of the resulting table.
+        consumer_group: The name of the consumer group.
+        consumer_name: The name of the consumer within the group.
+        autocommit_duration_ms: The maximum time between two commits. Every
+            ``autocommit_duration_ms`` milliseconds, the updates received by the connector are
+            committed and pushed into Pathway's computation graph.
+        persistent_id: (unstable) An identifier, under which the state of the table
+            will be persisted or ``None``, if there is no need to persist the state of this table.
+        debug_data: Static data replacing original one when debug mode is active.
+
+    Returns:
+        Table: The table read.
+
+    Example:
+
+    Suppose that the entries like ``XADD orders * user alice amount 12.5`` are added
+    to the stream ``orders`` on a Redis server running locally. They can be read
+    as follows:
+
+    >>> import pathway as pw
+    >>> class OrderSchema(pw.Schema):
+    ...     user: str
+    ...     amount: float
+    >>> orders = pw.io.redis.read(
+    ...     "redis://localhost:6379/0",
+    ...     "orders",
+    ...     schema=OrderSchema,
+    ... )
+    """
+
+    schema, api_schema = read_schema(schema=schema)
+    data_storage = api.DataStorage(
+        storage_type="redis",
+        connection_string=connection_string,
+        topic=stream_key if isinstance(stream_key, str) else None,
+        topic_names=stream_key if isinstance(stream_key, list) else None,
+        consumer_group=consumer_group,
+        consumer_name=consumer_name,
+        mode=api.ConnectorMode.STREAMING,
+        persistent_id=persistent_id,
+    )
+    data_format = api.DataFormat(
+        format_type="transparent",
+        **api_schema,
+    )
+
+    data_source_options = datasource.DataSourceOptions(
+        commit_duration_ms=autocommit_duration_ms
+    )
+    return table_from_datasource(
+        datasource.GenericDataSource(
+            datastorage=data_storage,
+            dataformat=data_format,
+            data_source_options=data_source_options,
+            schema=schema,
+            datasource_name="redis",
+        ),
+        debug_datasource=datasource.debug_datasource(debug_data),
+    )
+
+
+@check_arg_types
+@trace_user_frame
+def write_snapshot(
+    table: Table,
+    connection_string: str,
+    primary_key: list[str],
+    *,
+    key_prefix: str = "",
+    value_type: Literal["hash", "json"] = "hash",
+) -> None:
+    """Maintains the current state of a table in Redis, storing each row under its
+    own key.
+
+    The key of a row consists of ``key_prefix`` followed by the values of the primary
+    key columns, separated by ``:``. Depending on ``value_type``, a row is stored
+    either as a hash with a field per column or as a string containing a JSON object.
+    In a hash, the strings and the bytes are stored as they are, while the other values
+    are stored in their JSON representation, and the ``None`` values are omitted.
+
+    When a row is added or updated, the key is overwritten, and when it's removed,
+    the key is deleted. The changes made at the same processing time are applied in a
+    single transaction.
+
+    Args:
+        table: The table to maintain in Redis.
+        connection_string: The URL of the Redis server, for example,
+            ``"redis://localhost:6379/0"``.
+        primary_key: The names of the columns whose values form the Redis key.
+            Each combination of their values must correspond to a single row.
+        key_prefix: The prefix of all keys, for example, ``"users:"``.
+        value_type: The way the rows are stored: ``"hash"`` or ``"json"``.
+
+    Example:
+
+    Consider a table with the total amount spent by each user:
+
+    >>> import pathway as pw
+    >>> totals = pw.debug.table_from_markdown('''
+    ... user  | total
+    ... alice | 12.5
+    ... bob   | 40.0
+    ... ''')
+
+    With the following code, the total of ``alice`` is available in the hash
+    ``totals:alice``, for instance, via ``HGET totals:alice total``, and it's updated
+    whenever the table changes:
+
+    >>> pw.io.redis.write_snapshot(
+    ...     totals,
+    ...     "redis://localhost:6379/0",
+    ...     primary_key=["user"],
+    ...     key_prefix="totals:",
+    ... )
+    """
+
+    column_names = list(table._columns.keys())
+    key_fields = []
+    for name in primary_key:
+        if name not in column_names:
+            raise ValueError(f"Primary key column {name!r} is not in the table")
+        key_fields.append((name, column_names.index(name)))
+
+    data_storage = api.DataStorage(
+        storage_type="redis",
+        connection_string=connection_string,
+        key_fields=key_fields,
+        key_prefix=key_prefix,
+        redis_value_type=value_type,
+    )
+    data_format = api.DataFormat(
+        format_type="identity",
+        key_field_names=[],
+        value_fields=_format_output_value_fields(table),
+    )
+
+    table.to(
+        datasink.GenericDataSink(data_storage, data_format, datasink_name="redis")
+    )
@@ -2,7 +2,7 @@
 
 from __future__ import annotations
 
-from typing import Any
+from typing import Any, Literal
 
 from pathway.internals import datasource
 from pathway.internals._io_helpers import AwsS3Settings
@@ -102,6 +102,8 @@ def read(
     csv_settings: CsvParserSettings | None = None,
     json_field_paths: dict[str, str] | None = None,
     downloader_threads_count: int | None = None,
+    expand_archives: bool = False,
+    decompression: Literal["auto", "none", "gzip", "zstd", "bzip2", "xz"] = "none",
     persistent_id: str | None = None,
     autocommit_duration_ms: int | None = 1500,
     debug_data: Any = None,
@@ -114,16 +116,20 @@ def read(
     the smaller the modification time is, the earlier the file will be passed to the
     engine.
 
+    Objects compressed with gzip, zstd, bzip2 or xz can be decompressed on the fly, if
+    ``decompression`` is set.
+
     Args:
         path: Path to an object or to a folder of objects in Amazon S3 bucket.
         aws_s3_settings: Connection parameters for the S3 account and the bucket.
         format: Format of data to be read. Currently ``csv``, ``json``, ``plaintext``,
-            ``plaintext_by_object`` and ``binary`` formats are supported. The difference
-            between ``plaintext`` and ``plaintext_by_object`` is how the input is
-            tokenized: if the ``plaintext`` option is chosen, it's split by the newlines.
-            Otherwise, the files are split in full and one row will correspond to one
-            file. In case the ``binary`` format is specified, the data is read as raw
-            bytes without UTF-8 parsing.
+            ``plaintext_by_object``, ``binary`` and ``parquet`` formats are supported.
+            The difference between ``plaintext`` and ``plaintext_by_object`` is how the
+            input is tokenized: if the ``plaintext`` option is chosen, it's split by the
+            newlines. Otherwise, the files are split in full and one row will correspond
+            to one file. In case the ``binary`` format is specified, the data is read as
+            raw bytes without UTF-8 parsing. The ``parquet`` format reads the rows of
+            Parquet objects, committing them by row groups.
         schema: Schema of the resulting table. Not required for ``plaintext_by_object``
             and ``binary`` formats: if they are chosen, the contents of the read objects
             are stored in the column ``data``.
@@ -147,6 +153,19 @@ def read(
             of the bucket under the given path. It defaults to the number of cores
             available on the machine. It is recommended to increase the number of
             threads if your bucket contains many small files.
+        expand_archives: If set to true, the tar and zip archives are read member by
+            member, as if the members were separate objects. A member is addressed by
+            the key of the archive followed by ``!/`` and its path within the archive,
+            for example ``bundle.zip!/a.csv``. When an archive is modified, only its
+            changed members are reread.
+        decompression: How the objects are decompressed: ``"none"`` to read them as
+            they are, ``"auto"`` to detect the compression by the object key extension
+            or by the contents of the object, or the name of the codec of all objects:
+            ``"gzip"``, ``"zstd"``, ``"bzip2"`` or ``"xz"``. The objects are
+            decompressed on the fly, and the rows are counted in the decompressed
+            contents, so the reading can be resumed after a restart. With ``"auto"``,
+            the objects read in the ``binary`` format are kept as they are. Not
+            supported for the ``parquet`` format.
         persistent_id: (unstable) An identifier, under which the state of the table
             will be persisted or ``None``, if there is no need to persist the state of this table.
             When a program restarts, it restores the state for all input tables according to what
@@ -274,6 +293,8 @@ def read(
         csv_settings=csv_settings,
         persistent_id=persistent_id,
         downloader_threads_count=downloader_threads_count,
+        expand_archives=expand_archives,
+        decompression=decompression,
     )
 
     schema, data_format = construct_schema_and_data_format(
@@ -327,12 +348,13 @@ def read_from_digital_ocean(
         path: Path to an object or to a folder of objects in S3 bucket.
         do_s3_settings: Connection parameters for the account and the bucket.
         format: Format of data to be read. Currently ``csv``, ``json``, ``plaintext``,
-            ``plaintext_by_object`` and ``binary`` formats are supported. The difference
-            between ``plaintext`` and ``plaintext_by_object`` is how the input is
-            tokenized: if the ``plaintext`` option is chosen, it's split by the newlines.
-            Otherwise, the files are split in full and one row will correspond to one
-            file. In case the ``binary`` format is specified, the data is read as raw
-            bytes without UTF-8 parsing.
+            ``plaintext_by_object``, ``binary`` and ``parquet`` formats are supported.
+            The difference between ``plaintext`` and ``plaintext_by_object`` is how the
+            input is tokenized: if the ``plaintext`` option is chosen, it's split by the
+            newlines. Otherwise, the files are split in full and one row will correspond
+            to one file. In case the ``binary`` format is specified, the data is read as
+            raw bytes without UTF-8 parsing. The ``parquet`` format reads the rows of
+            Parquet objects, committing them by row groups.
         schema: Schema of the resulting table. Not required for ``plaintext_by_object``
             and ``binary`` formats: if they are chosen, the contents of the read objects
             are stored in the column ``data``.
@@ -457,12 +479,13 @@ def read_from_wasabi(
         path: Path to an object or to a folder of objects in S3 bucket.
         wasabi_s3_settings: Connection parameters for the account and the bucket.
         format: Format of data to be read. Currently ``csv``, ``json``, ``plaintext``,
-            ``plaintext_by_object`` and ``binary`` formats are supported. The difference
-            between ``plaintext`` and ``plaintext_by_object`` is how the input is
-            tokenized: if the ``plaintext`` option is chosen, it's split by the newlines.
-            Otherwise, the files are split in full and one row will correspond to one
-            file. In case the ``binary`` format is specified, the data is read as raw
-            bytes without UTF-8 parsing.
+            ``plaintext_by_object``, ``binary`` and ``parquet`` formats are supported.
+            The difference between ``plaintext`` and ``plaintext_by_object`` is how the
+            input is tokenized: if the ``plaintext`` option is chosen, it's split by the
+            newlines. Otherwise, the files are split in full and one row will correspond
+            to one file. In case the ``binary`` format is specified, the data is read as
+            raw bytes without UTF-8 parsing. The ``parquet`` format reads the rows of
+            Parquet objects, committing them by row groups.
         schema: Schema of the resulting table. Not required for ``plaintext_by_object``
             and ``binary`` formats: if they are chosen, the contents of the read objects
             are stored in the column ``data``.
@@ -0,0 +1,154 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+from typing import Literal
+
+from pathway.internals import api, datasource
+from pathway.internals.runtime_type_check import check_arg_types
+from pathway.internals.schema import Schema
+from pathway.internals.table import Table
+from pathway.internals.table_io import table_from_datasource
+from pathway.internals.trace import trace_user_frame
+from pathway.io._utils import construct_schema_and_data_format
+
+
+@check_arg_types
+@trace_user_frame
+def read(
+    host: str,
+    port: int,
+    *,
+    protocol: Literal["tcp", "udp"] = "tcp",
+    schema: type[Schema] | None = None,
+    format: Literal["plaintext", "raw", "json", "syslog"] = "plaintext",
+    framing: Literal["lines", "length_prefixed", "octet_counting"] = "lines",
+    max_message_size: int | None = None,
+    autocommit_duration_ms: int | None = 1500,
+    json_field_paths: dict[str, str] | None = None,
+    with_metadata: bool = False,
+    persistent_id: str | None = None,
+    debug_data=None,
+) -> Table:
+    """Listens on a TCP or a UDP socket and reads the received messages. It allows,
+    for example, to receive the logs directly from the syslog daemons or the
+    applications, without writing them to files first.
+
+    A TCP connection is split into messages according to the ``framing``:
+
+    * ``"lines"``: each message is a line, terminated with ``\\n`` or ``\\r\\n``;
+    * ``"length_prefixed"``: each message is preceded by its length as a 4-byte
+      big-endian integer;
+    * ``"octet_counting"``: each message is preceded by its length as a decimal
+      number and a space, as in the syslog transmission over TCP
+      (`RFC 6587 <https://www.rfc-editor.org/rfc/rfc6587>`_).
+
+    If a connection violates the framing, or a message exceeds ``max_message_size``,
+    the connection is closed. For UDP, each datagram is a message, and the datagrams
+    larger than ``max_message_size`` are dropped.
+
+    The messages are parsed according to the ``format``:
+
+    * ``"plaintext"`` and ``"raw"``: the message is put into the ``data`` column as a
+      string or as bytes respectively;
+    * ``"json"``: the message is a JSON object, and the columns are filled from its
+      fields, as defined by the ``schema``;
+    * ``"syslog"``: the message is a syslog message in the format of
+      `RFC 5424 <https://www.rfc-editor.org/rfc/rfc5424>`_ or of
+      `RFC 3164 <https://www.rfc-editor.org/rfc/rfc3164>`_. By default the table has
+      the columns ``facility``, ``severity``, ``timestamp``, ``hostname``,
+      ``app_name``, ``procid``, ``msgid``, ``structured_data`` and ``message``, and a
+      custom ``schema`` can contain any of them, and also ``version``. The parts
+      absent in a message are ``None``. The ``timestamp`` column can be of type
+      ``pw.DateTimeUtc``, ``pw.DateTimeNaive`` (in UTC) or ``str``. As the RFC 3164
+      timestamps lack the year and the time zone, they are read in UTC in the year,
+      for which they are the closest to the current time.
+
+    The socket is listened to by a single Pathway worker. The messages aren't
+    acknowledged to the senders, so the messages received before a failure, but not
+    yet persisted, are lost.
+
+    Args:
+        host: The host to listen on, for example, ``"0.0.0.0"`` to accept the messages
+            from other machines.
+        port: The port to listen on.
+        protocol: The protocol of the socket, ``"tcp"`` or ``"udp"``.
+        schema: The table schema, used for the ``"json"`` and ``"syslog"`` formats.
+        format: The format of the messages: ``"plaintext"``, ``"raw"``, ``"json"`` or
+            ``"syslog"``.
+        framing: The framing of the messages in a TCP connection. It isn't used for
+            UDP.
+        max_message_size: The maximum size of a message in bytes. Defaults to 64 KiB.
+        autocommit_duration_ms: The time interval (in milliseconds) between commits.
+            After this time, the updates received by the connector are committed and
+            added to Pathway's computation graph.
+        json_field_paths: For the ``"json"`` format, this allows mapping field names to
+            paths within the JSON structure. Use the format ``<field_name>: <path>``
+            where the path follows the
+            `JSON Pointer (RFC 6901) <https://www.rfc-editor.org/rfc/rfc6901>`_.
+        with_metadata: When set to ``True``, the connector will add an additional column
+            named ``_metadata`` to the table. This JSON field will contain the
+            ``protocol`` and the ``source_address`` and ``source_port`` of the sender.
+        persistent_id: (unstable) An identifier, under which the state of the table will
+            be persisted or ``None``, if there is no need to persist the state of this table.
+        debug_data: Static data replacing original one when debug mode is active.
+
+    Returns:
+        Table: The table read.
+
+    Example:
+
+    Assume that rsyslog forwards the logs to the port ``5140`` over UDP with the
+    ``*.* @pathway-host:5140;RSYSLOG_SyslogProtocol23Format`` rule. The logs can be
+    read, together with the addresses of the senders, as follows:
+
+    >>> import pathway as pw
+    >>> logs = pw.io.socket.read(
+    ...     "0.0.0.0", 5140, protocol="udp", format="syslog", with_metadata=True
+    ... )
+
+    The errors and the more severe messages can then be selected by the severity:
+
+    >>> errors = logs.filter(logs.severity <= 3)
+
+    A TCP socket, to which the applications write JSON lines, can be read as follows:
+
+    >>> class EventSchema(pw.Schema):
+    ...     user: str
+    ...     action: str
+    >>> events = pw.io.socket.read("0.0.0.0", 9000, format="json", schema=EventSchema)
+    """
+
+    if ":" in host:
+        # An IPv6 address
+        host = f"[{host}]"
+    data_storage = api.DataStorage(
+        storage_type="socket",
+        connection_string=f"{host}:{port}",
+        protocol=protocol,
+        framing=framing,
+        max_message_size=max_message_size,
+        persistent_id=persistent_id,
+        mode=api.ConnectorMode.STREAMING,
+    )
+    schema, data_format = construct_schema_and_data_format(
+        "binary" if format == "raw" else format,
+        with_metadata=with_metadata,
+        schema=schema,
+        csv_settings=None,
+        json_field_paths=json_field_paths,
+    )
+    data_source_options = datasource.DataSourceOptions(
+        commit_duration_ms=autocommit_duration_ms
+    )
+    return table_from_datasource(
+        datasource.GenericDataSource(
+            datastorage=data_storage,
+            dataformat=data_format,
+            data_source_options=data_source_options,
+            schema=schema,
+            datasource_name="socket",
+        ),
+        debug_datasource=datasource.debug_datasource(debug_data),
+    )
+
@@ -0,0 +1,177 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import json
+import pathlib
+import threading
+
+import grpc
+import pandas as pd
+
+import pathway as pw
+from pathway.internals.parse_graph import G
+from pathway.tests.utils import (
+    CsvLinesNumberChecker,
+    needs_multiprocessing_fork,
+    wait_result_with_checker,
+)
+
+STREAM_ROWS_METHOD = "/pathway.ingest.v1.Ingest/StreamRows"
+
+
+def encode_varint(value: int) -> bytes:
+    result = bytearray()
+    while value >= 0x80:
+        result.append(value & 0x7F | 0x80)
+        value >>= 7
+    result.append(value)
+    return bytes(result)
+
+
+def encode_field(tag: int, value: int | bytes) -> bytes:
+    if isinstance(value, int):
+        return encode_varint(tag << 3) + encode_varint(value)
+    return encode_varint(tag << 3 | 2) + encode_varint(len(value)) + value
+
+
+def row_batch(table: str, batch_id: int, rows: list[dict]) -> bytes:
+    """Serializes the ``RowBatch`` message of ``ingest.proto`` with JSON rows."""
+    message = encode_field(1, table.encode()) + encode_field(2, batch_id)
+    for row in rows:
+        message += encode_field(3, encode_field(1, json.dumps(row).encode()))
+    return message
+
+
+def decode_varint(data: bytes, position: int) -> tuple[int, int]:
+    value, shift = 0, 0
+    while True:
+        byte = data[position]
+        position += 1
+        value |= (byte & 0x7F) << shift
+        shift += 7
+        if byte < 0x80:
+            return value, position
+
+
+def batch_ack(data: bytes) -> tuple[int, int]:
+    """Deserializes the ``BatchAck`` message into the batch ID and the rows count."""
+    fields = {1: 0, 2: 0}
+    position = 0
+    while position < len(data):
+        key, position = decode_varint(data, position)
+        fields[key >> 3], position = decode_varint(data, position)
+    return fields[1], fields[2]
+
+
+def stream_batches(
+    port: int, batches: list[bytes], acks: list[tuple[int, int]]
+) -> threading.Thread:
+    """Streams the batches to the server in a separate thread and collects the
+    acknowledgements into ``acks``."""
+
+    def target():
+        with grpc.insecure_channel(f"localhost:{port}") as channel:
+            grpc.channel_ready_future(channel).result(timeout=30)
+            stream_rows = channel.stream_stream(
+                STREAM_ROWS_METHOD,
+                request_serializer=lambda message: message,
+                response_deserializer=batch_ack,
+            )
+            acks.extend(stream_rows(iter(batches)))
+
+    thread = threading.Thread(target=target, daemon=True)
+    thread.start()
+    return thread
+
+
+class InputSchema(pw.Schema):
+    user: str
+    amount: int
+
+
+def read_orders(port: int, output_path: pathlib.Path) -> None:
+    G.clear()
+    table = pw.io.grpc.read(
+        "localhost",
+        port,
+        "orders",
+        schema=InputSchema,
+        autocommit_duration_ms=100,
+        persistent_id="orders",
+    )
+    pw.io.csv.write(table, output_path)
+
+
+def read_rows(output_path: pathlib.Path) -> list[tuple[str, int]]:
+    result = pd.read_csv(output_path)
+    return sorted(zip(result["user"], result["amount"]))
+
+
+@needs_multiprocessing_fork
+def test_grpc_read(tmp_path: pathlib.Path, port: int):
+    output_path = tmp_path / "output.csv"
+    read_orders(port, output_path)
+
+    acks: list[tuple[int, int]] = []
+    stream_batches(
+        port,
+        [
+            row_batch(
+                "orders",
+                1,
+                [{"user": "alice", "amount": 10}, {"user": "bob", "amount": 20}],
+            ),
+            row_batch("orders", 2, [{"user": "carol", "amount": 30}]),
+        ],
+        acks,
+    )
+    rows_checker = CsvLinesNumberChecker(output_path, 3)
+    wait_result_with_checker(lambda: rows_checker() and len(acks) == 2, 30)
+
+    assert read_rows(output_path) == [("alice", 10), ("bob", 20), ("carol", 30)]
+    assert acks == [(1, 2), (2, 1)]
+
+
+@needs_multiprocessing_fork
+def test_grpc_resume_after_restart(tmp_path: pathlib.Path, port: int):
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    # The batches are acknowledged only after they're persisted
+    output_path = tmp_path / "output_1.csv"
+    read_orders(port, output_path)
+    acks: list[tuple[int, int]] = []
+    stream_batches(
+        port,
+        [
+            row_batch("orders", 1, [{"user": "alice", "amount": 10}]),
+            row_batch("orders", 2, [{"user": "bob", "amount": 20}]),
+        ],
+        acks,
+    )
+    rows_checker = CsvLinesNumberChecker(output_path, 2)
+    wait_result_with_checker(
+        lambda: rows_checker() and len(acks) == 2,
+        30,
+        kwargs={"persistence_config": persistence_config},
+    )
+    assert read_rows(output_path) == [("alice", 10), ("bob", 20)]
+
+    # After the restart, the batches are counted from the persisted state, so a
+    # new batch is acknowledged once it's persisted as well
+    output_path = tmp_path / "output_2.csv"
+    read_orders(port, output_path)
+    acks = []
+    stream_batches(
+        port, [row_batch("orders", 3, [{"user": "carol", "amount": 30}])], acks
+    )
+    rows_checker = CsvLinesNumberChecker(output_path, 1)
+    wait_result_with_checker(
+        lambda: rows_checker() and len(acks) == 1,
+        30,
+        kwargs={"persistence_config": persistence_config},
+    )
+    assert read_rows(output_path) == [("carol", 30)]
+    assert acks == [(3, 1)]
@@ -1,5 +1,6 @@
 # Copyright © 2024 Pathway
 
+import gzip
 import json
 import os
 import pathlib
@@ -1476,6 +1477,34 @@ def test_binary_data_in_subscribe(tmp_path: pathlib.Path):
     ]
 
 
+def test_fs_read_decompression(tmp_path: pathlib.Path):
+    input_path = tmp_path / "input.txt.gz"
+    compressed = gzip.compress(b"abc\ndef\n")
+    input_path.write_bytes(compressed)
+
+    def read_rows(format: str, **kwargs) -> list:
+        G.clear()
+        table = pw.io.fs.read(input_path, format=format, mode="static", **kwargs)
+        rows = []
+        pw.io.subscribe(
+            table, on_change=lambda key, row, time, is_addition: rows.append(row)
+        )
+        run()
+        return rows
+
+    # The files are read as they are by default
+    assert read_rows("binary") == [{"data": compressed}]
+    assert sorted(
+        row["data"] for row in read_rows("plaintext", decompression="auto")
+    ) == ["abc", "def"]
+    # The binary files are decompressed only if the codec is given explicitly
+    assert read_rows("binary", decompression="auto") == [{"data": compressed}]
+    assert read_rows("binary", decompression="gzip") == [{"data": b"abc\ndef\n"}]
+
+    with pytest.raises(ValueError, match="not supported for the parquet format"):
+        pw.io.fs.read(input_path, format="parquet", decompression="auto")
+
+
 def test_bool_values_parsing_in_csv(tmp_path: pathlib.Path):
     input_path = tmp_path / "input.csv"
     output_path = tmp_path / "output.csv"
@@ -0,0 +1,170 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import json
+import pathlib
+import socket
+import threading
+import time
+
+import pandas as pd
+
+import pathway as pw
+from pathway.internals.parse_graph import G
+from pathway.tests.utils import (
+    CsvLinesNumberChecker,
+    needs_multiprocessing_fork,
+    wait_result_with_checker,
+)
+
+
+def send_over_tcp(port: int, data: bytes) -> threading.Thread:
+    """Sends the data over a TCP connection in a separate thread, once the server is
+    up."""
+
+    def target():
+        for _ in range(300):
+            try:
+                connection = socket.create_connection(("127.0.0.1", port))
+                break
+            except ConnectionRefusedError:
+                time.sleep(0.1)
+        with connection:
+            connection.sendall(data)
+
+    thread = threading.Thread(target=target, daemon=True)
+    thread.start()
+    return thread
+
+
+def send_over_udp(port: int, datagrams: list[bytes]) -> threading.Thread:
+    """Sends the datagrams in a separate thread, once the server is up. As the
+    datagrams sent before are lost, empty datagrams are sent first, until they stop
+    being refused."""
+
+    def target():
+        with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as client:
+            client.connect(("127.0.0.1", port))
+            client.settimeout(0.1)
+            for _ in range(300):
+                client.send(b"")
+                try:
+                    client.recv(1)
+                except ConnectionRefusedError:
+                    time.sleep(0.1)
+                except TimeoutError:
+                    break
+            for datagram in datagrams:
+                client.send(datagram)
+
+    thread = threading.Thread(target=target, daemon=True)
+    thread.start()
+    return thread
+
+
+def read_data(output_path: pathlib.Path) -> list[str]:
+    return sorted(pd.read_csv(output_path)["data"])
+
+
+@needs_multiprocessing_fork
+def test_socket_read_tcp(tmp_path: pathlib.Path, port: int):
+    output_path = tmp_path / "output.csv"
+    G.clear()
+    table = pw.io.socket.read("127.0.0.1", port, autocommit_duration_ms=100)
+    pw.io.csv.write(table, output_path)
+
+    send_over_tcp(port, b"one\r\ntwo\nthree\n")
+    wait_result_with_checker(CsvLinesNumberChecker(output_path, 3), 30)
+    assert read_data(output_path) == ["one", "three", "two"]
+
+
+@needs_multiprocessing_fork
+def test_socket_read_udp_with_metadata(tmp_path: pathlib.Path, port: int):
+    output_path = tmp_path / "output.csv"
+    G.clear()
+    table = pw.io.socket.read(
+        "127.0.0.1",
+        port,
+        protocol="udp",
+        with_metadata=True,
+        autocommit_duration_ms=100,
+    )
+    # The empty datagrams sent while waiting for the server are skipped
+    table = table.filter(table.data != "")
+    pw.io.csv.write(table, output_path)
+
+    send_over_udp(port, [b"one", b"two three"])
+    wait_result_with_checker(CsvLinesNumberChecker(output_path, 2), 30)
+    result = pd.read_csv(output_path)
+    assert sorted(result["data"]) == ["one", "two three"]
+    assert all(
+        json.loads(metadata)["protocol"] == "udp" for metadata in result["_metadata"]
+    )
+
+
+@needs_multiprocessing_fork
+def test_socket_read_syslog(tmp_path: pathlib.Path, port: int):
+    output_path = tmp_path / "output.csv"
+
+    class InputSchema(pw.Schema):
+        severity: int
+        version: int | None
+        hostname: str | None
+        message: str
+
+    G.clear()
+    table = pw.io.socket.read(
+        "127.0.0.1",
+        port,
+        format="syslog",
+        framing="octet_counting",
+        schema=InputSchema,
+        autocommit_duration_ms=100,
+    )
+    pw.io.csv.write(table, output_path)
+
+    messages = [
+        b"<11>1 2024-01-02T03:04:05Z web-1 nginx - - - upstream timed out",
+        b"<34>Oct 11 22:14:15 mymachine su: 'su root' failed",
+        b"<13>1 disk is full",
+    ]
+    send_over_tcp(port, b"".join(b"%d %s" % (len(m), m) for m in messages))
+    wait_result_with_checker(CsvLinesNumberChecker(output_path, 3), 30)
+
+    result = pd.read_csv(output_path).sort_values("severity")
+    assert list(result["severity"]) == [2, 3, 5]
+    assert list(result["hostname"].fillna("")) == ["mymachine", "web-1", ""]
+    assert list(result["version"].fillna(0)) == [0, 1, 0]
+    assert list(result["message"]) == [
+        "'su root' failed",
+        "upstream timed out",
+        "1 disk is full",
+    ]
+
+
+@needs_multiprocessing_fork
+def test_socket_resume_after_restart(tmp_path: pathlib.Path, port: int):
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+
+    def run(output_path: pathlib.Path, data: bytes, n_expected: int) -> list[str]:
+        G.clear()
+        table = pw.io.socket.read(
+            "127.0.0.1", port, autocommit_duration_ms=100, persistent_id="logs"
+        )
+        pw.io.csv.write(table, output_path)
+        send_over_tcp(port, data)
+        wait_result_with_checker(
+            CsvLinesNumberChecker(output_path, n_expected),
+            30,
+            kwargs={"persistence_config": persistence_config},
+        )
+        return read_data(output_path)
+
+    assert run(tmp_path / "output_1.csv", b"one\ntwo\n", 2) == ["one", "two"]
+
+    # After the restart, the messages are counted from the persisted state and
+    # the new messages are read
+    assert run(tmp_path / "output_2.csv", b"three\n", 1) == ["three"]
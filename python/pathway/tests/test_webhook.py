@@ -0,0 +1,279 @@
+# Copyright © 2024 Pathway
+
+from __future__ import annotations
+
+import hashlib
+import hmac
+import http.server
+import json
+import pathlib
+import threading
+import time
+
+import pandas as pd
+import requests
+
+import pathway as pw
+from pathway.internals.parse_graph import G
+from pathway.tests.utils import (
+    CsvLinesNumberChecker,
+    needs_multiprocessing_fork,
+    wait_result_with_checker,
+)
+
+
+def post_requests(
+    url: str, bodies: list[tuple[str, str]], responses: list[tuple[int, dict]]
+) -> threading.Thread:
+    """Sends the bodies with the given content types one by one in a separate thread,
+    once the server is up, and collects the statuses and the bodies of the
+    responses into ``responses``."""
+
+    def target():
+        for content_type, body in bodies:
+            for _ in range(300):
+                try:
+                    response = requests.post(
+                        url, data=body, headers={"Content-Type": content_type}
+                    )
+                    break
+                except requests.ConnectionError:
+                    time.sleep(0.1)
+            responses.append((response.status_code, response.json()))
+
+    thread = threading.Thread(target=target, daemon=True)
+    thread.start()
+    return thread
+
+
+def serve_requests(
+    port: int,
+    responses: list[tuple[int, dict[str, str]]],
+    received: list[tuple[str, dict[str, str], bytes]],
+) -> http.server.HTTPServer:
+    """Runs a server in a separate thread, which answers the requests with the given
+    statuses and headers one by one, then with ``200``, and collects the paths, the
+    headers and the bodies of the requests into ``received``."""
+
+    class Handler(http.server.BaseHTTPRequestHandler):
+        def do_POST(self):
+            body = self.rfile.read(int(self.headers["Content-Length"]))
+            received.append((self.path, dict(self.headers), body))
+            status, headers = responses.pop(0) if responses else (200, {})
+            self.send_response(status)
+            for name, value in headers.items():
+                self.send_header(name, value)
+            self.send_header("Content-Length", "0")
+            self.end_headers()
+
+        def log_message(self, *args):
+            pass
+
+    server = http.server.HTTPServer(("127.0.0.1", port), Handler)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    return server
+
+
+class InputSchema(pw.Schema):
+    user: str
+    amount: int
+
+
+def read_orders(port: int, output_path: pathlib.Path, **kwargs) -> None:
+    G.clear()
+    table = pw.io.http.read_webhook(
+        "127.0.0.1",
+        port,
+        schema=InputSchema,
+        routes=["/orders"],
+        autocommit_duration_ms=100,
+        persistent_id="orders",
+        **kwargs,
+    )
+    pw.io.csv.write(table, output_path)
+
+
+def read_rows(output_path: pathlib.Path) -> list[tuple[str, int]]:
+    result = pd.read_csv(output_path)
+    return sorted(zip(result["user"], result["amount"]))
+
+
+@needs_multiprocessing_fork
+def test_webhook_read(tmp_path: pathlib.Path, port: int):
+    output_path = tmp_path / "output.csv"
+    read_orders(port, output_path, wait_for_processing=True)
+
+    responses: list[tuple[int, dict]] = []
+    post_requests(
+        f"http://127.0.0.1:{port}/orders",
+        [
+            (
+                "application/json",
+                json.dumps(
+                    [{"user": "alice", "amount": 10}, {"user": "bob", "amount": 20}]
+                ),
+            ),
+            ("application/x-ndjson", '{"user": "carol", "amount": 30}\n'),
+            ("application/json", "[{"),
+        ],
+        responses,
+    )
+    rows_checker = CsvLinesNumberChecker(output_path, 3)
+    wait_result_with_checker(lambda: rows_checker() and len(responses) == 3, 30)
+
+    assert read_rows(output_path) == [("alice", 10), ("bob", 20), ("carol", 30)]
+    assert [status for status, _ in responses] == [200, 200, 400]
+    assert [body for _, body in responses[:2]] == [
+        {"rows_accepted": 2},
+        {"rows_accepted": 1},
+    ]
+
+
+@needs_multiprocessing_fork
+def test_webhook_unknown_route(tmp_path: pathlib.Path, port: int):
+    output_path = tmp_path / "output.csv"
+    read_orders(port, output_path)
+
+    responses: list[tuple[int, dict]] = []
+    post_requests(
+        f"http://127.0.0.1:{port}/users",
+        [("application/x-ndjson", '{"user": "alice", "amount": 10}')],
+        responses,
+    )
+    wait_result_with_checker(lambda: len(responses) == 1, 30)
+    assert [status for status, _ in responses] == [404]
+
+
+@needs_multiprocessing_fork
+def test_webhook_resume_after_restart(tmp_path: pathlib.Path, port: int):
+    persistence_config = pw.persistence.Config(
+        pw.persistence.Backend.filesystem(tmp_path / "PStorage")
+    )
+    url = f"http://127.0.0.1:{port}/orders"
+
+    # The requests are answered only after their rows are persisted
+    output_path = tmp_path / "output_1.csv"
+    read_orders(port, output_path)
+    responses: list[tuple[int, dict]] = []
+    post_requests(
+        url,
+        [
+            ("application/x-ndjson", '{"user": "alice", "amount": 10}'),
+            ("application/x-ndjson", '{"user": "bob", "amount": 20}'),
+        ],
+        responses,
+    )
+    rows_checker = CsvLinesNumberChecker(output_path, 2)
+    wait_result_with_checker(
+        lambda: rows_checker() and len(responses) == 2,
+        30,
+        kwargs={"persistence_config": persistence_config},
+    )
+    assert read_rows(output_path) == [("alice", 10), ("bob", 20)]
+
+    # After the restart, the requests are counted from the persisted state, so a
+    # new request is answered once it's persisted as well
+    output_path = tmp_path / "output_2.csv"
+    read_orders(port, output_path)
+    responses = []
+    post_requests(
+        url, [("application/x-ndjson", '{"user": "carol", "amount": 30}')], responses
+    )
+    rows_checker = CsvLinesNumberChecker(output_path, 1)
+    wait_result_with_checker(
+        lambda: rows_checker() and len(responses) == 1,
+        30,
+        kwargs={"persistence_config": persistence_config},
+    )
+    assert read_rows(output_path) == [("carol", 30)]
+    assert responses == [(200, {"rows_accepted": 1})]
+
+
+def test_webhook_write(port: int):
+    received: list[tuple[str, dict[str, str], bytes]] = []
+    server = serve_requests(port, [], received)
+    G.clear()
+    table = pw.debug.table_from_markdown(
+        """
+        user  | amount
+        alice | 10
+        bob   | 20
+        alice | 30
+        """
+    )
+    pw.io.http.write_webhook(
+        table,
+        f"http://127.0.0.1:{port}/users/{{table.user}}",
+        format="json",
+        signing_secret="secret",
+    )
+    pw.run()
+    server.shutdown()
+
+    # The rows going to the same URL are sent in one signed request
+    assert sorted(path for path, _, _ in received) == ["/users/alice", "/users/bob"]
+    for path, headers, body in received:
+        assert headers["Content-Type"] == "application/json"
+        signature = hmac.new(b"secret", body, hashlib.sha256).hexdigest()
+        assert headers["X-Pathway-Signature"] == f"sha256={signature}"
+        rows = json.loads(body)
+        assert all(row["user"] == path.removeprefix("/users/") for row in rows)
+        assert all(row["diff"] == 1 for row in rows)
+    amounts = [row["amount"] for _, _, body in received for row in json.loads(body)]
+    assert sorted(amounts) == [10, 20, 30]
+
+
+def test_webhook_write_waits_for_retry_after(port: int):
+    received: list[tuple[str, dict[str, str], bytes]] = []
+    server = serve_requests(port, [(429, {"Retry-After": "1"})], received)
+    G.clear()
+    table = pw.debug.table_from_markdown(
+        """
+        user  | amount
+        alice | 10
+        """
+    )
+    # The time from Retry-After is waited for instead of the long backoff
+    pw.io.http.write_webhook(
+        table,
+        f"http://127.0.0.1:{port}/orders",
+        retry_policy=pw.io.http.RetryPolicy(
+            first_delay_ms=60_000, backoff_factor=1, jitter_ms=1
+        ),
+    )
+    started_at = time.monotonic()
+    pw.run()
+    elapsed = time.monotonic() - started_at
+    server.shutdown()
+
+    assert len(received) == 2
+    assert received[0][2] == received[1][2]
+    assert 1 <= elapsed < 30
+
+
+def test_webhook_write_limits_retry_after(port: int):
+    received: list[tuple[str, dict[str, str], bytes]] = []
+    server = serve_requests(port, [(429, {"Retry-After": "3600"})], received)
+    G.clear()
+    table = pw.debug.table_from_markdown(
+        """
+        user  | amount
+        alice | 10
+        """
+    )
+    # The server can't make the connector wait longer than the longest backoff
+    pw.io.http.write_webhook(
+        table,
+        f"http://127.0.0.1:{port}/orders",
+        n_retries=1,
+        retry_policy=pw.io.http.RetryPolicy(
+            first_delay_ms=100, backoff_factor=1, jitter_ms=1
+        ),
+    )
+    started_at = time.monotonic()
+    pw.run()
+    elapsed = time.monotonic() - started_at
+    server.shutdown()
+
+    assert len(received) == 2
+    assert elapsed < 30
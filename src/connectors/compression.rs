@@ -0,0 +1,145 @@
+// Copyright © 2024 Pathway
+
+//! Transparent decompression of the objects read by `PosixLikeReader`. It's
+//! enabled explicitly, either with the codec of all objects or with the codec
+//! detected by the extension of the object path or, if the path has no known
+//! extension, by the magic bytes at the beginning of the contents.
+
+use log::warn;
+use std::io::{Cursor, Read};
+
+use bzip2::read::MultiBzDecoder;
+use flate2::read::MultiGzDecoder;
+use xz2::read::XzDecoder;
+use zstd::stream::read::Decoder as ZstdDecoder;
+
+const GZIP_MAGIC_BYTES: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC_BYTES: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+const BZIP2_MAGIC_BYTES: &[u8] = b"BZh";
+const BZIP2_BLOCK_MAGIC_BYTES: &[u8] = &[0x31, 0x41, 0x59, 0x26, 0x53, 0x59];
+const XZ_MAGIC_BYTES: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0x00];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Codec {
+    Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+/// Defines which objects `PosixLikeReader` decompresses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Decompression {
+    /// The objects are read as they are.
+    #[default]
+    None,
+
+    /// The codec of each object is detected, and the uncompressed objects are
+    /// read as they are.
+    Auto,
+
+    /// All objects are compressed with the given codec.
+    Codec(Codec),
+}
+
+impl Codec {
+    const ALL: [Codec; 4] = [Codec::Gzip, Codec::Zstd, Codec::Bzip2, Codec::Xz];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+            Self::Bzip2 => "bzip2",
+            Self::Xz => "xz",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|codec| codec.name() == name)
+    }
+
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Gzip => &["gz", "gzip"],
+            Self::Zstd => &["zst", "zstd"],
+            Self::Bzip2 => &["bz2"],
+            Self::Xz => &["xz"],
+        }
+    }
+
+    pub fn from_path(path: &[u8]) -> Option<Self> {
+        let path = String::from_utf8_lossy(path);
+        let (_, extension) = path.rsplit_once('.')?;
+        let extension = extension.to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|codec| codec.extensions().contains(&extension.as_str()))
+    }
+
+    pub fn from_magic_bytes(contents: &[u8]) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|codec| codec.has_magic_bytes(contents))
+    }
+
+    fn has_magic_bytes(self, contents: &[u8]) -> bool {
+        match self {
+            Self::Gzip => contents.starts_with(GZIP_MAGIC_BYTES),
+            Self::Zstd => contents.starts_with(ZSTD_MAGIC_BYTES),
+            Self::Xz => contents.starts_with(XZ_MAGIC_BYTES),
+            Self::Bzip2 => {
+                // The stream header is short and may occur in plain text, so
+                // the magic bytes of the first block are checked too. The byte
+                // in between is the block size, from '1' to '9'.
+                contents.starts_with(BZIP2_MAGIC_BYTES)
+                    && contents
+                        .get(BZIP2_MAGIC_BYTES.len())
+                        .is_some_and(|block_size| (b'1'..=b'9').contains(block_size))
+                    && contents[BZIP2_MAGIC_BYTES.len() + 1..].starts_with(BZIP2_BLOCK_MAGIC_BYTES)
+            }
+        }
+    }
+
+    /// Detects the codec of an object. The extension is trusted only if the
+    /// contents start with the magic bytes of the codec, so that a mislabeled
+    /// uncompressed object is still read as it is.
+    pub fn detect(path: &[u8], contents: &[u8]) -> Option<Self> {
+        match Self::from_path(path) {
+            Some(codec) if codec.has_magic_bytes(contents) => Some(codec),
+            Some(codec) => {
+                if !contents.is_empty() {
+                    warn!(
+                        "Object {} has the extension of {} but isn't compressed with it. It will be read as is.",
+                        String::from_utf8_lossy(path),
+                        codec.name(),
+                    );
+                }
+                None
+            }
+            None => Self::from_magic_bytes(contents),
+        }
+    }
+}
+
+/// Returns a reader of the object contents, which decompresses them on the
+/// fly if the object is compressed and the decompression is enabled.
+pub fn decompressing_reader(
+    path: &[u8],
+    contents: Vec<u8>,
+    decompression: Decompression,
+) -> Result<Box<dyn Read + Send + 'static>, std::io::Error> {
+    let codec = match decompression {
+        Decompression::None => None,
+        Decompression::Auto => Codec::detect(path, &contents),
+        Decompression::Codec(codec) => Some(codec),
+    };
+    let source = Cursor::new(contents);
+    let reader: Box<dyn Read + Send + 'static> = match codec {
+        None => Box::new(source),
+        Some(Codec::Gzip) => Box::new(MultiGzDecoder::new(source)),
+        Some(Codec::Zstd) => Box::new(ZstdDecoder::new(source)?),
+        Some(Codec::Bzip2) => Box::new(MultiBzDecoder::new(source)),
+        Some(Codec::Xz) => Box::new(XzDecoder::new_multi_decoder(source)),
+    };
+    Ok(reader)
+}
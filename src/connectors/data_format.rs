@@ -11,16 +11,21 @@ use std::mem::take;
 use std::str::{from_utf8, Utf8Error};
 
 use crate::connectors::metadata::SourceMetadata;
+use crate::connectors::syslog::{Error as SyslogParseError, Message as SyslogMessage};
 use crate::connectors::ReaderContext::{Diff, Empty, KeyValue, RawBytes, TokenizedEntries};
 use crate::connectors::{DataEventType, Offset, ReaderContext, SessionType, SnapshotEvent};
 use crate::engine::error::{limit_length, DynError, DynResult, STANDARD_OBJECT_LENGTH_LIMIT};
 use crate::engine::time::DateTime;
-use crate::engine::{Error, Key, Result, Timestamp, Type, Value};
+use crate::engine::{
+    DateTimeNaive, DateTimeUtc, Duration, Error, Key, Result, Timestamp, Type, Value,
+};
 
 use async_nats::header::HeaderMap as NatsHeaders;
 use base64::engine::general_purpose::STANDARD as base64encoder;
 use base64::Engine;
+use chrono::Utc;
 use itertools::{chain, Itertools};
+use lapin::types::{AMQPValue, FieldTable as AmqpFieldTable, LongString as AmqpLongString};
 use log::error;
 use mongodb::bson::{
     bson, spec::BinarySubtype as BsonBinarySubtype, Binary as BsonBinaryContents,
@@ -188,6 +193,9 @@ pub enum ParseError {
     #[error("received message doesn't have payload")]
     EmptyKafkaPayload,
 
+    #[error("received message doesn't have key, which is required in the upsert mode")]
+    EmptyKafkaKey,
+
     #[error("internal error, reader context is not supported in this parser")]
     UnsupportedReaderContext,
 
@@ -202,6 +210,12 @@ pub enum ParseError {
 
     #[error("no value for {field_name:?} field and no default specified")]
     NoDefault { field_name: String },
+
+    #[error("received message {message:?} is not a valid syslog message: {error}")]
+    FailedToParseSyslog {
+        message: String,
+        error: SyslogParseError,
+    },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -395,6 +409,33 @@ impl FormatterContext {
         headers
     }
 
+    /// Builds the key of a message from the given fields. A single field must be a
+    /// string or binary one and is used as it is, while several fields are serialized
+    /// into a JSON object. If there are no key fields, the key of the row is used.
+    pub fn construct_message_key(
+        &self,
+        key_fields: &[(String, usize)],
+    ) -> Result<Vec<u8>, FormatterError> {
+        match key_fields {
+            [] => Ok(self.key.0.to_le_bytes().to_vec()),
+            [(_, position)] => match &self.values[*position] {
+                Value::Bytes(bytes) => Ok(bytes.to_vec()),
+                Value::String(string) => Ok(string.as_bytes().to_vec()),
+                other => Err(FormatterError::IncorrectKeyFieldType(other.clone())),
+            },
+            _ => {
+                let mut key = serde_json::Map::with_capacity(key_fields.len());
+                for (name, position) in key_fields {
+                    key.insert(
+                        name.clone(),
+                        serialize_value_to_json(&self.values[*position])?,
+                    );
+                }
+                Ok(JsonValue::Object(key).to_string().into_bytes())
+            }
+        }
+    }
+
     pub fn construct_kafka_headers(&self, header_fields: &Vec<(String, usize)>) -> KafkaHeaders {
         let raw_headers = self.construct_message_headers(header_fields, false);
         let mut kafka_headers = KafkaHeaders::new_with_capacity(raw_headers.len());
@@ -419,6 +460,40 @@ impl FormatterContext {
         }
         nats_headers
     }
+
+    pub fn construct_pulsar_properties(
+        &self,
+        header_fields: &Vec<(String, usize)>,
+    ) -> HashMap<String, String> {
+        self.construct_message_headers(header_fields, true)
+            .into_iter()
+            .map(|header| {
+                let value = String::from_utf8(header.value)
+                    .expect("all prepared headers must be UTF-8 serializable");
+                (header.key, value)
+            })
+            .collect()
+    }
+
+    /// Pub/Sub attributes are string pairs, the same as Pulsar properties.
+    pub fn construct_pubsub_attributes(
+        &self,
+        header_fields: &Vec<(String, usize)>,
+    ) -> HashMap<String, String> {
+        self.construct_pulsar_properties(header_fields)
+    }
+
+    pub fn construct_amqp_headers(&self, header_fields: &Vec<(String, usize)>) -> AmqpFieldTable {
+        let raw_headers = self.construct_message_headers(header_fields, false);
+        let mut amqp_headers = AmqpFieldTable::default();
+        for header in raw_headers {
+            amqp_headers.insert(
+                header.key.into(),
+                AMQPValue::LongString(AmqpLongString::from(header.value)),
+            );
+        }
+        amqp_headers
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -447,6 +522,9 @@ pub enum FormatterError {
 
     #[error("unexpected formatter context type")]
     UnexpectedContextType,
+
+    #[error("value {0} can't be used as a key because it's neither 'bytes' nor 'string'")]
+    IncorrectKeyFieldType(Value),
 }
 
 pub trait Formatter: Send {
@@ -862,6 +940,22 @@ impl Parser for IdentityParser {
                 value_from_bytes(raw_bytes, self.parse_utf8),
                 Ok(None),
             ),
+            KeyValue((key, value)) if matches!(self.session_type, SessionType::Upsert) => {
+                // In the upsert mode, the message without payload is a deletion of its key
+                let key = self.key_generation_policy.generate(key, self.parse_utf8);
+                if key.is_none() {
+                    return Err(ParseError::EmptyKafkaKey.into());
+                }
+                match value {
+                    Some(bytes) => (
+                        DataEventType::Upsert,
+                        key,
+                        value_from_bytes(bytes, self.parse_utf8),
+                        Ok(None),
+                    ),
+                    None => return Ok(vec![ParsedEventWithErrors::Upsert((key, None))]),
+                }
+            }
             KeyValue((key, value)) => match value {
                 Some(bytes) => (
                     DataEventType::Insert,
@@ -1048,6 +1142,8 @@ impl Formatter for SingleColumnFormatter {
 pub enum DebeziumDBType {
     Postgres,
     MongoDB,
+    MySQL,
+    SqlServer,
 }
 
 pub struct DebeziumMessageParser {
@@ -1055,6 +1151,190 @@ pub struct DebeziumMessageParser {
     value_field_names: Vec<String>,
     separator: String, // how key-value pair is separated
     db_type: DebeziumDBType,
+    schema: HashMap<String, InnerSchemaField>,
+}
+
+/// The semantic type of a field, which is specified in the `schema` section of a
+/// Debezium message when the JSON converter has `schemas.enable` set.
+#[derive(Clone, Debug)]
+struct DebeziumLogicalType {
+    name: String,
+    parameters: JsonValue,
+}
+
+impl DebeziumLogicalType {
+    fn decimal_scale(&self) -> Option<u32> {
+        // Kafka Connect serializes the parameters as strings
+        match &self.parameters["scale"] {
+            JsonValue::String(scale) => scale.parse().ok(),
+            JsonValue::Number(scale) => scale.as_u64()?.try_into().ok(),
+            _ => None,
+        }
+    }
+}
+
+type DebeziumLogicalTypes = HashMap<String, DebeziumLogicalType>;
+
+fn debezium_logical_types_from_fields(fields: &JsonValue) -> DebeziumLogicalTypes {
+    let mut logical_types = HashMap::new();
+    let Some(fields) = fields.as_array() else {
+        return logical_types;
+    };
+    for field in fields {
+        if let (Some(field_name), Some(name)) = (field["field"].as_str(), field["name"].as_str()) {
+            logical_types.insert(
+                field_name.to_string(),
+                DebeziumLogicalType {
+                    name: name.to_string(),
+                    parameters: field["parameters"].clone(),
+                },
+            );
+        }
+    }
+    logical_types
+}
+
+/// Extracts the logical types of the row fields from the `schema` section of the
+/// value message, where they are described in the `before` and `after` structures.
+fn debezium_logical_types_from_envelope(schema: &JsonValue) -> DebeziumLogicalTypes {
+    let mut logical_types = HashMap::new();
+    let Some(envelope_fields) = schema["fields"].as_array() else {
+        return logical_types;
+    };
+    for envelope_field in envelope_fields {
+        if matches!(envelope_field["field"].as_str(), Some("before" | "after")) {
+            logical_types.extend(debezium_logical_types_from_fields(
+                &envelope_field["fields"],
+            ));
+        }
+    }
+    logical_types
+}
+
+/// Decodes the unscaled value of `org.apache.kafka.connect.data.Decimal`, which
+/// is a big-endian two's-complement integer, encoded in base64.
+fn parse_debezium_unscaled_decimal(value: &JsonValue) -> Option<i128> {
+    let bytes = base64encoder.decode(value.as_str()?).ok()?;
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+    let fill = if bytes[0] & 0x80 == 0 { 0 } else { 0xff };
+    let mut buffer = [fill; 16];
+    buffer[16 - bytes.len()..].copy_from_slice(&bytes);
+    Some(i128::from_be_bytes(buffer))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn debezium_decimal_to_value(unscaled: i128, scale: u32, dtype: &Type) -> Option<Value> {
+    match dtype {
+        Type::Float | Type::Any => Some(Value::from(
+            unscaled as f64 / 10_f64.powi(scale.try_into().ok()?),
+        )),
+        Type::Int if scale == 0 => i64::try_from(unscaled).ok().map(Value::from),
+        Type::String => {
+            let digits = unscaled.unsigned_abs().to_string();
+            let scale: usize = scale.try_into().ok()?;
+            let digits = format!("{digits:0>width$}", width = scale + 1);
+            let (integer_part, fractional_part) = digits.split_at(digits.len() - scale);
+            let sign = if unscaled < 0 { "-" } else { "" };
+            if fractional_part.is_empty() {
+                Some(Value::from(format!("{sign}{integer_part}").as_str()))
+            } else {
+                Some(Value::from(
+                    format!("{sign}{integer_part}.{fractional_part}").as_str(),
+                ))
+            }
+        }
+        _ => None,
+    }
+}
+
+fn debezium_timestamp_to_value(timestamp: i64, unit: &str, dtype: &Type) -> Option<Value> {
+    match dtype {
+        Type::DateTimeNaive | Type::Any => DateTimeNaive::from_timestamp(timestamp, unit)
+            .ok()
+            .map(Value::from),
+        Type::DateTimeUtc => DateTimeUtc::from_timestamp(timestamp, unit)
+            .ok()
+            .map(Value::from),
+        _ => None,
+    }
+}
+
+fn debezium_duration_to_value(duration: i64, unit: &str, dtype: &Type) -> Option<Value> {
+    match dtype {
+        Type::Duration | Type::Any => Duration::new_with_unit(duration, unit)
+            .ok()
+            .map(Value::from),
+        _ => None,
+    }
+}
+
+/// Parses a field according to its Debezium logical type. Falls back to the
+/// plain JSON parsing if there is no logical type, or if it can't be converted
+/// into the requested type.
+fn parse_value_from_debezium_json(
+    value: &JsonValue,
+    logical_type: Option<&DebeziumLogicalType>,
+    dtype: &Type,
+) -> Option<Value> {
+    let Some(logical_type) = logical_type else {
+        return parse_value_from_json(value, dtype);
+    };
+    if value.is_null() {
+        return parse_value_from_json(value, dtype);
+    }
+    let inner_dtype = dtype.unoptionalize();
+    let parsed = match logical_type.name.as_str() {
+        "io.debezium.time.Date" | "org.apache.kafka.connect.data.Date" => {
+            // The number of days since the epoch
+            let days = value.as_i64()?;
+            debezium_timestamp_to_value(days.checked_mul(86_400)?, "s", inner_dtype)
+        }
+        "io.debezium.time.Time" | "org.apache.kafka.connect.data.Time" => {
+            debezium_duration_to_value(value.as_i64()?, "ms", inner_dtype)
+        }
+        "io.debezium.time.MicroTime" | "io.debezium.time.MicroDuration" => {
+            debezium_duration_to_value(value.as_i64()?, "us", inner_dtype)
+        }
+        "io.debezium.time.NanoTime" => {
+            debezium_duration_to_value(value.as_i64()?, "ns", inner_dtype)
+        }
+        "io.debezium.time.Timestamp" | "org.apache.kafka.connect.data.Timestamp" => {
+            debezium_timestamp_to_value(value.as_i64()?, "ms", inner_dtype)
+        }
+        "io.debezium.time.MicroTimestamp" => {
+            debezium_timestamp_to_value(value.as_i64()?, "us", inner_dtype)
+        }
+        "io.debezium.time.NanoTimestamp" => {
+            debezium_timestamp_to_value(value.as_i64()?, "ns", inner_dtype)
+        }
+        "io.debezium.time.ZonedTimestamp" => {
+            let parsed = chrono::DateTime::parse_from_rfc3339(value.as_str()?).ok()?;
+            match inner_dtype {
+                Type::DateTimeUtc | Type::Any => Some(Value::from(DateTimeUtc::from(parsed))),
+                Type::DateTimeNaive => Some(Value::from(DateTimeNaive::from(parsed.naive_utc()))),
+                _ => None,
+            }
+        }
+        "org.apache.kafka.connect.data.Decimal" => {
+            let unscaled = parse_debezium_unscaled_decimal(value)?;
+            debezium_decimal_to_value(unscaled, logical_type.decimal_scale()?, inner_dtype)
+        }
+        "io.debezium.data.VariableScaleDecimal" => {
+            let unscaled = parse_debezium_unscaled_decimal(&value["value"])?;
+            let scale = value["scale"].as_u64()?.try_into().ok()?;
+            debezium_decimal_to_value(unscaled, scale, inner_dtype)
+        }
+        "io.debezium.data.Json" => match inner_dtype {
+            Type::Json | Type::Any => serde_json::from_str::<JsonValue>(value.as_str()?)
+                .ok()
+                .map(Value::from),
+            _ => None,
+        },
+        _ => None,
+    };
+    parsed.or_else(|| parse_value_from_json(value, dtype))
 }
 
 fn parse_list_from_json(values: &[JsonValue], dtype: &Type) -> Option<Value> {
@@ -1099,7 +1379,7 @@ fn parse_value_from_json(value: &JsonValue, dtype: &Type) -> Option<Value> {
     }
 }
 
-fn serialize_value_to_json(value: &Value) -> Result<JsonValue, FormatterError> {
+pub fn serialize_value_to_json(value: &Value) -> Result<JsonValue, FormatterError> {
     match value {
         Value::None => Ok(JsonValue::Null),
         Value::Int(i) => Ok(json!(i)),
@@ -1153,6 +1433,7 @@ fn values_by_names_from_json(
     field_absence_is_error: bool,
     schema: &HashMap<String, InnerSchemaField>,
     metadata_column_value: &Value,
+    message_metadata_values: &HashMap<String, MessageMetadataValue>,
 ) -> ValueFieldsWithErrors {
     let mut parsed_values = Vec::with_capacity(field_names.len());
     for value_field in field_names {
@@ -1166,6 +1447,8 @@ fn values_by_names_from_json(
 
         let value = if value_field == METADATA_FIELD_NAME {
             Ok(metadata_column_value.clone())
+        } else if let Some(value) = message_metadata_values.get(value_field) {
+            message_metadata_field_value(value_field, value, schema.get(value_field))
         } else if let Some(path) = column_paths.get(value_field) {
             if let Some(value) = payload.pointer(path) {
                 parse_value_from_json(value, dtype).ok_or_else(|| {
@@ -1224,15 +1507,65 @@ impl DebeziumMessageParser {
         value_field_names: Vec<String>,
         separator: String,
         db_type: DebeziumDBType,
+        schema: HashMap<String, InnerSchemaField>,
     ) -> DebeziumMessageParser {
         DebeziumMessageParser {
             key_field_names,
             value_field_names,
             separator,
             db_type,
+            schema,
         }
     }
 
+    fn values_by_names(
+        &self,
+        payload: &JsonValue,
+        field_names: &[String],
+        logical_types: &DebeziumLogicalTypes,
+    ) -> ValueFieldsWithErrors {
+        field_names
+            .iter()
+            .map(|field_name| {
+                let (default_value, dtype) = match self.schema.get(field_name) {
+                    Some(schema_item) => (schema_item.default.as_ref(), &schema_item.type_),
+                    None => (None, &Type::Any),
+                };
+                match payload.get(field_name) {
+                    Some(value) => {
+                        parse_value_from_debezium_json(value, logical_types.get(field_name), dtype)
+                            .ok_or_else(|| {
+                                ParseError::FailedToParseFromJson {
+                                    field_name: field_name.to_string(),
+                                    payload: value.clone(),
+                                    type_: dtype.clone(),
+                                }
+                                .into()
+                            })
+                    }
+                    None => match default_value {
+                        Some(default) => Ok(default.clone()),
+                        None => Err(ParseError::FailedToExtractJsonField {
+                            field_name: field_name.to_string(),
+                            path: None,
+                            payload: payload.clone(),
+                        }
+                        .into()),
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn parse_key(&self, key: &JsonValue) -> KeyFieldsWithErrors {
+        let logical_types = debezium_logical_types_from_fields(&key["schema"]["fields"]);
+        self.key_field_names.as_ref().map(|names| {
+            self.values_by_names(&key["payload"], names, &logical_types)
+                .into_iter()
+                .collect()
+        })
+    }
+
     pub fn standard_separator() -> String {
         "        ".to_string()
     }
@@ -1241,6 +1574,7 @@ impl DebeziumMessageParser {
         &mut self,
         key: &JsonValue,
         value: &JsonValue,
+        logical_types: &DebeziumLogicalTypes,
         event: DataEventType,
     ) -> Result<ParsedEventWithErrors, ParseError> {
         // in case of MongoDB, the message is always string
@@ -1255,27 +1589,9 @@ impl DebeziumMessageParser {
             }
         };
 
-        let key = self.key_field_names.as_ref().map(|names| {
-            values_by_names_from_json(
-                key,
-                names,
-                &HashMap::new(),
-                true,
-                &HashMap::new(),
-                &Value::None,
-            )
-            .into_iter()
-            .collect()
-        });
-
-        let parsed_values = values_by_names_from_json(
-            &prepared_value,
-            &self.value_field_names,
-            &HashMap::new(),
-            true,
-            &HashMap::new(),
-            &Value::None,
-        );
+        let key = self.parse_key(key);
+        let parsed_values =
+            self.values_by_names(&prepared_value, &self.value_field_names, logical_types);
 
         match event {
             DataEventType::Insert => Ok(ParsedEventWithErrors::Insert((key, parsed_values))),
@@ -1284,52 +1600,55 @@ impl DebeziumMessageParser {
         }
     }
 
-    fn parse_read_or_create(&mut self, key: &JsonValue, value: &JsonValue) -> ParseResult {
+    fn parse_read_or_create(
+        &mut self,
+        key: &JsonValue,
+        value: &JsonValue,
+        logical_types: &DebeziumLogicalTypes,
+    ) -> ParseResult {
         let event = match self.db_type {
-            DebeziumDBType::Postgres => {
-                self.parse_event(key, &value["after"], DataEventType::Insert)?
+            DebeziumDBType::Postgres | DebeziumDBType::MySQL | DebeziumDBType::SqlServer => {
+                self.parse_event(key, &value["after"], logical_types, DataEventType::Insert)?
             }
             DebeziumDBType::MongoDB => {
-                self.parse_event(key, &value["after"], DataEventType::Upsert)?
+                self.parse_event(key, &value["after"], logical_types, DataEventType::Upsert)?
             }
         };
         Ok(vec![event])
     }
 
-    fn parse_delete(&mut self, key: &JsonValue, value: &JsonValue) -> ParseResult {
+    fn parse_delete(
+        &mut self,
+        key: &JsonValue,
+        value: &JsonValue,
+        logical_types: &DebeziumLogicalTypes,
+    ) -> ParseResult {
         let event = match self.db_type {
-            DebeziumDBType::Postgres => {
-                self.parse_event(key, &value["before"], DataEventType::Delete)?
-            }
-            DebeziumDBType::MongoDB => {
-                let key = self.key_field_names.as_ref().map(|names| {
-                    values_by_names_from_json(
-                        key,
-                        names,
-                        &HashMap::new(),
-                        true,
-                        &HashMap::new(),
-                        &Value::None,
-                    )
-                    .into_iter()
-                    .collect()
-                });
-                ParsedEventWithErrors::Upsert((key, None))
+            DebeziumDBType::Postgres | DebeziumDBType::MySQL | DebeziumDBType::SqlServer => {
+                self.parse_event(key, &value["before"], logical_types, DataEventType::Delete)?
             }
+            DebeziumDBType::MongoDB => ParsedEventWithErrors::Upsert((self.parse_key(key), None)),
         };
         Ok(vec![event])
     }
 
-    fn parse_update(&mut self, key: &JsonValue, value: &JsonValue) -> ParseResult {
+    fn parse_update(
+        &mut self,
+        key: &JsonValue,
+        value: &JsonValue,
+        logical_types: &DebeziumLogicalTypes,
+    ) -> ParseResult {
         match self.db_type {
-            DebeziumDBType::Postgres => {
+            DebeziumDBType::Postgres | DebeziumDBType::MySQL | DebeziumDBType::SqlServer => {
                 let event_before =
-                    self.parse_event(key, &value["before"], DataEventType::Delete)?;
-                let event_after = self.parse_event(key, &value["after"], DataEventType::Insert)?;
+                    self.parse_event(key, &value["before"], logical_types, DataEventType::Delete)?;
+                let event_after =
+                    self.parse_event(key, &value["after"], logical_types, DataEventType::Insert)?;
                 Ok(vec![event_before, event_after])
             }
             DebeziumDBType::MongoDB => {
-                let event_after = self.parse_event(key, &value["after"], DataEventType::Upsert)?;
+                let event_after =
+                    self.parse_event(key, &value["after"], logical_types, DataEventType::Upsert)?;
                 Ok(vec![event_after])
             }
         }
@@ -1402,13 +1721,16 @@ impl Parser for DebeziumMessageParser {
             .into());
         }
 
-        match &change_payload["payload"]["op"] {
+        let logical_types = change_payload
+            .get("schema")
+            .map(debezium_logical_types_from_envelope)
+            .unwrap_or_default();
+        let payload = &change_payload["payload"];
+        match &payload["op"] {
             JsonValue::String(op) => match op.as_ref() {
-                "r" | "c" => {
-                    self.parse_read_or_create(&change_key["payload"], &change_payload["payload"])
-                }
-                "u" => self.parse_update(&change_key["payload"], &change_payload["payload"]),
-                "d" => self.parse_delete(&change_key["payload"], &change_payload["payload"]),
+                "r" | "c" => self.parse_read_or_create(&change_key, payload, &logical_types),
+                "u" => self.parse_update(&change_key, payload, &logical_types),
+                "d" => self.parse_delete(&change_key, payload, &logical_types),
                 _ => Err(ParseError::UnsupportedDebeziumOperation(op.to_string()).into()),
             },
             _ => Err(ParseError::DebeziumFormatViolated(
@@ -1426,7 +1748,9 @@ impl Parser for DebeziumMessageParser {
 
     fn session_type(&self) -> SessionType {
         match self.db_type {
-            DebeziumDBType::Postgres => SessionType::Native,
+            DebeziumDBType::Postgres | DebeziumDBType::MySQL | DebeziumDBType::SqlServer => {
+                SessionType::Native
+            }
 
             // MongoDB events don't contain the previous state of the record
             // therefore we can only do the upsert with the same key and the
@@ -1436,6 +1760,127 @@ impl Parser for DebeziumMessageParser {
     }
 }
 
+/// A field of the table, which is filled from the metadata of a message
+/// rather than from its payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MessageMetadataField {
+    KafkaHeader(String),
+    KafkaTimestamp,
+    PubSubAttribute(String),
+    PubSubPublishTime,
+}
+
+type MessageMetadataValue = Option<Result<Value, Box<ConversionError>>>;
+
+impl MessageMetadataField {
+    /// Returns the value of the field with the type from the schema, or `None`,
+    /// if the message doesn't have the respective header or timestamp.
+    fn extract(
+        &self,
+        metadata: &SourceMetadata,
+        field_name: &str,
+        type_: &Type,
+    ) -> MessageMetadataValue {
+        match (self, metadata) {
+            (Self::KafkaHeader(name), SourceMetadata::Kafka(metadata)) => Some(Self::parse_header(
+                metadata.header(name)?,
+                field_name,
+                type_,
+            )),
+            (Self::KafkaTimestamp, SourceMetadata::Kafka(metadata)) => Some(Self::parse_timestamp(
+                metadata.timestamp_millis()?,
+                field_name,
+                type_,
+            )),
+            (Self::PubSubAttribute(name), SourceMetadata::PubSub(metadata)) => Some(
+                Self::parse_header(metadata.attribute(name)?.as_bytes(), field_name, type_),
+            ),
+            (Self::PubSubPublishTime, SourceMetadata::PubSub(metadata)) => Some(
+                Self::parse_timestamp(metadata.publish_time_millis()?, field_name, type_),
+            ),
+            _ => None,
+        }
+    }
+
+    fn parse_header(
+        value: &[u8],
+        field_name: &str,
+        type_: &Type,
+    ) -> Result<Value, Box<ConversionError>> {
+        if matches!(type_.unoptionalize(), Type::Bytes) {
+            return Ok(Value::Bytes(value.into()));
+        }
+        let Ok(value) = from_utf8(value) else {
+            return Err(Box::new(conversion_error(
+                format!("{value:?}"),
+                field_name,
+                type_,
+            )));
+        };
+        let schema_item = InnerSchemaField::new(type_.clone(), None);
+        parse_with_type(value, &schema_item, field_name)
+            .map_err(|_| Box::new(conversion_error(format!("{value:?}"), field_name, type_)))
+    }
+
+    fn parse_timestamp(
+        timestamp_millis: i64,
+        field_name: &str,
+        type_: &Type,
+    ) -> Result<Value, Box<ConversionError>> {
+        let value = match type_.unoptionalize() {
+            Type::Int | Type::Any => Ok(Value::Int(timestamp_millis)),
+            Type::DateTimeUtc => {
+                DateTimeUtc::from_timestamp(timestamp_millis, "ms").map(Value::from)
+            }
+            Type::DateTimeNaive => {
+                DateTimeNaive::from_timestamp(timestamp_millis, "ms").map(Value::from)
+            }
+            _ => {
+                return Err(Box::new(conversion_error(
+                    timestamp_millis.to_string(),
+                    field_name,
+                    type_,
+                )))
+            }
+        };
+        value.map_err(|_| {
+            Box::new(conversion_error(
+                timestamp_millis.to_string(),
+                field_name,
+                type_,
+            ))
+        })
+    }
+}
+
+fn conversion_error(value_repr: String, field_name: &str, type_: &Type) -> ConversionError {
+    ConversionError {
+        value_repr,
+        field_name: field_name.to_string(),
+        type_: type_.clone(),
+    }
+}
+
+/// Returns the value of a field taken from the message metadata. If the message
+/// doesn't have it, the default value is used, or `None` for an optional field.
+fn message_metadata_field_value(
+    field_name: &str,
+    value: &MessageMetadataValue,
+    schema_item: Option<&InnerSchemaField>,
+) -> DynResult<Value> {
+    match (value, schema_item) {
+        (Some(value), _) => Ok(value.clone()?),
+        (None, Some(schema_item)) => {
+            if schema_item.default.is_none() && matches!(schema_item.type_, Type::Optional(_)) {
+                Ok(Value::None)
+            } else {
+                schema_item.maybe_use_default(field_name, None)
+            }
+        }
+        (None, None) => Ok(Value::None),
+    }
+}
+
 pub struct JsonLinesParser {
     key_field_names: Option<Vec<String>>,
     value_field_names: Vec<String>,
@@ -1444,6 +1889,8 @@ pub struct JsonLinesParser {
     schema: HashMap<String, InnerSchemaField>,
     metadata_column_value: Value,
     session_type: SessionType,
+    message_metadata_fields: Vec<(String, MessageMetadataField)>,
+    message_metadata_values: HashMap<String, MessageMetadataValue>,
 }
 
 impl JsonLinesParser {
@@ -1464,8 +1911,55 @@ impl JsonLinesParser {
             schema,
             metadata_column_value: Value::None,
             session_type,
+            message_metadata_fields: Vec::new(),
+            message_metadata_values: HashMap::new(),
         })
     }
+
+    /// Makes the parser fill the given fields from the metadata of the messages,
+    /// such as Kafka headers, instead of the JSON payload.
+    pub fn with_message_metadata_fields(
+        mut self,
+        message_metadata_fields: Vec<(String, MessageMetadataField)>,
+    ) -> Result<Self> {
+        for (name, _) in &message_metadata_fields {
+            if !self.schema.contains_key(name) {
+                return Err(Error::FieldNotInSchema {
+                    name: name.clone(),
+                    schema_keys: self.schema.keys().cloned().collect(),
+                });
+            }
+        }
+        self.message_metadata_fields = message_metadata_fields;
+        Ok(self)
+    }
+
+    /// Converts the key of a message into the primary key. If there are no key
+    /// fields, the whole key is used, as with `KeyGenerationPolicy::PreferMessageKey`.
+    /// Otherwise the key is expected to be a JSON object, containing the key fields.
+    fn parse_message_key(&self, key: Option<&[u8]>) -> DynResult<DynResult<Vec<Value>>> {
+        let Some(key) = key else {
+            return Err(ParseError::EmptyKafkaKey.into());
+        };
+        let Some(key_field_names) = &self.key_field_names else {
+            return Ok(value_from_bytes(key, true).map(|key| vec![key]));
+        };
+        let key = prepare_plaintext_string(key)?;
+        let Ok(key) = serde_json::from_str::<JsonValue>(&key) else {
+            return Err(ParseError::FailedToParseJson(key).into());
+        };
+        Ok(values_by_names_from_json(
+            &key,
+            key_field_names,
+            &self.column_paths,
+            self.field_absence_is_error,
+            &self.schema,
+            &self.metadata_column_value,
+            &self.message_metadata_values,
+        )
+        .into_iter()
+        .collect())
+    }
 }
 
 impl Parser for JsonLinesParser {
@@ -1475,6 +1969,15 @@ impl Parser for JsonLinesParser {
                 let line = prepare_plaintext_string(line)?;
                 (*event, None, line)
             }
+            KeyValue((key, value)) if matches!(self.session_type, SessionType::Upsert) => {
+                // In the upsert mode, the message key is the primary key and
+                // the message without payload is a deletion of this key
+                let key = Some(self.parse_message_key(key.as_deref())?);
+                let Some(line) = value else {
+                    return Ok(vec![ParsedEventWithErrors::Upsert((key, None))]);
+                };
+                (DataEventType::Upsert, key, prepare_plaintext_string(line)?)
+            }
             KeyValue((_key, value)) => {
                 if let Some(line) = value {
                     let line = prepare_plaintext_string(line)?;
@@ -1511,6 +2014,7 @@ impl Parser for JsonLinesParser {
                     self.field_absence_is_error,
                     &self.schema,
                     &self.metadata_column_value,
+                    &self.message_metadata_values,
                 )
                 .into_iter()
                 .collect(),
@@ -1525,6 +2029,7 @@ impl Parser for JsonLinesParser {
             self.field_absence_is_error,
             &self.schema,
             &self.metadata_column_value,
+            &self.message_metadata_values,
         );
 
         let event = ParsedEventWithErrors::new(self.session_type, data_event, key, values);
@@ -1535,6 +2040,14 @@ impl Parser for JsonLinesParser {
     fn on_new_source_started(&mut self, metadata: &SourceMetadata) {
         let metadata_serialized: JsonValue = metadata.serialize();
         self.metadata_column_value = metadata_serialized.into();
+        self.message_metadata_values = self
+            .message_metadata_fields
+            .iter()
+            .map(|(name, field)| {
+                let value = field.extract(metadata, name, &self.schema[name].type_);
+                (name.clone(), value)
+            })
+            .collect();
     }
 
     fn column_count(&self) -> usize {
@@ -1546,6 +2059,105 @@ impl Parser for JsonLinesParser {
     }
 }
 
+/// Parses the syslog messages, both in the format of RFC 5424 and of RFC 3164.
+/// The columns are filled from the parts of the message with the same names:
+/// `facility`, `severity`, `version`, `timestamp`, `hostname`, `app_name`,
+/// `procid`, `msgid`, `structured_data` and `message`. The parts that the
+/// message doesn't have are `None`.
+///
+/// The `timestamp` can be read into a string column in the RFC 3339 format,
+/// or into a datetime column, in which case a naive datetime is in UTC.
+pub struct SyslogParser {
+    value_field_names: Vec<String>,
+    schema: HashMap<String, InnerSchemaField>,
+    metadata_column_value: Value,
+}
+
+impl SyslogParser {
+    pub fn new(
+        value_field_names: Vec<String>,
+        schema: HashMap<String, InnerSchemaField>,
+    ) -> Result<SyslogParser> {
+        ensure_all_fields_in_schema(&None, &value_field_names, &schema)?;
+        Ok(SyslogParser {
+            value_field_names,
+            schema,
+            metadata_column_value: Value::None,
+        })
+    }
+
+    fn timestamp_value(&self, message: &SyslogMessage) -> Option<Value> {
+        let schema_item = self.schema.get("timestamp")?;
+        let timestamp = message.timestamp?.timestamp_nanos_opt()?;
+        match schema_item.type_.unoptionalize() {
+            Type::DateTimeUtc => Some(Value::from(DateTimeUtc::new(timestamp))),
+            Type::DateTimeNaive => Some(Value::from(DateTimeNaive::new(timestamp))),
+            _ => None,
+        }
+    }
+}
+
+impl Parser for SyslogParser {
+    fn parse(&mut self, data: &ReaderContext) -> ParseResult {
+        let (data_event, line) = match data {
+            RawBytes(event, line) => (*event, prepare_plaintext_string(line)?),
+            KeyValue((_key, Some(line))) => {
+                (DataEventType::Insert, prepare_plaintext_string(line)?)
+            }
+            KeyValue((_key, None)) => return Err(ParseError::EmptyKafkaPayload.into()),
+            Diff(_) | TokenizedEntries(..) => {
+                return Err(ParseError::UnsupportedReaderContext.into());
+            }
+            Empty => return Ok(vec![]),
+        };
+
+        if line.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let message = match SyslogMessage::parse(&line, Utc::now()) {
+            Ok(message) => message,
+            Err(error) => {
+                return Err(ParseError::FailedToParseSyslog {
+                    message: line,
+                    error,
+                }
+                .into())
+            }
+        };
+
+        let mut values = values_by_names_from_json(
+            &message.to_json(),
+            &self.value_field_names,
+            &HashMap::new(),
+            false,
+            &self.schema,
+            &self.metadata_column_value,
+            &HashMap::new(),
+        );
+        if let Some(timestamp) = self.timestamp_value(&message) {
+            for (field_name, value) in zip(&self.value_field_names, &mut values) {
+                if field_name == "timestamp" {
+                    *value = Ok(timestamp.clone());
+                }
+            }
+        }
+
+        let event = ParsedEventWithErrors::new(SessionType::Native, data_event, None, values);
+
+        Ok(vec![event])
+    }
+
+    fn on_new_source_started(&mut self, metadata: &SourceMetadata) {
+        let metadata_serialized: JsonValue = metadata.serialize();
+        self.metadata_column_value = metadata_serialized.into();
+    }
+
+    fn column_count(&self) -> usize {
+        self.value_field_names.len()
+    }
+}
+
 /// Receives values directly from a Reader and passes them
 /// further only making adjustments according to the schema.
 ///
@@ -1555,6 +2167,7 @@ pub struct TransparentParser {
     value_field_names: Vec<String>,
     schema: HashMap<String, InnerSchemaField>,
     session_type: SessionType,
+    metadata_column_value: Value,
 }
 
 impl TransparentParser {
@@ -1570,6 +2183,7 @@ impl TransparentParser {
             value_field_names,
             schema,
             session_type,
+            metadata_column_value: Value::None,
         })
     }
 }
@@ -1600,8 +2214,12 @@ impl Parser for TransparentParser {
             .value_field_names
             .iter()
             .map(|name| {
+                let value = values.get(name).cloned();
+                if value.is_none() && name == METADATA_FIELD_NAME {
+                    return Ok(self.metadata_column_value.clone());
+                }
                 self.schema[name] // ensure_all_fields_in_schema in new() makes sure that all keys are in the schema
-                    .maybe_use_default(name, values.get(name).cloned())
+                    .maybe_use_default(name, value)
             })
             .collect();
 
@@ -1610,7 +2228,10 @@ impl Parser for TransparentParser {
         Ok(vec![event])
     }
 
-    fn on_new_source_started(&mut self, _metadata: &SourceMetadata) {}
+    fn on_new_source_started(&mut self, metadata: &SourceMetadata) {
+        let metadata_serialized: JsonValue = metadata.serialize();
+        self.metadata_column_value = metadata_serialized.into();
+    }
 
     fn column_count(&self) -> usize {
         self.value_field_names.len()
@@ -1972,6 +2593,73 @@ fn serialize_value_to_bson(value: &Value) -> Result<BsonValue, FormatterError> {
     }
 }
 
+fn parse_list_from_bson(values: &[BsonValue], dtype: &Type) -> Option<Value> {
+    let mut list = Vec::with_capacity(values.len());
+    for value in values {
+        list.push(parse_value_from_bson(value, dtype)?);
+    }
+    Some(Value::from(list))
+}
+
+fn parse_tuple_from_bson(values: &[BsonValue], dtypes: &[Type]) -> Option<Value> {
+    if values.len() != dtypes.len() {
+        return None;
+    }
+    let mut tuple = Vec::with_capacity(values.len());
+    for (value, dtype) in values.iter().zip_eq(dtypes.iter()) {
+        tuple.push(parse_value_from_bson(value, dtype)?);
+    }
+    Some(Value::from(tuple))
+}
+
+/// Converts a BSON value into a value of the requested type. The conversion is
+/// consistent with `serialize_value_to_bson`: the date-times and durations are
+/// expected in milliseconds. `ObjectId` can be read as a string, in which case
+/// its hex representation is used.
+pub fn parse_value_from_bson(value: &BsonValue, dtype: &Type) -> Option<Value> {
+    match (dtype, value) {
+        (Type::Optional(_) | Type::Any, BsonValue::Null | BsonValue::Undefined) => {
+            Some(Value::None)
+        }
+        (Type::Optional(arg), value) => parse_value_from_bson(value, arg),
+        (Type::Json | Type::Any, BsonValue::Document(_)) | (Type::Json, _) => {
+            Some(Value::from(value.clone().into_relaxed_extjson()))
+        }
+        (Type::String | Type::Any, BsonValue::String(s) | BsonValue::Symbol(s)) => {
+            Some(Value::from(s.as_str()))
+        }
+        (Type::String | Type::Any, BsonValue::ObjectId(oid)) => {
+            Some(Value::from(oid.to_hex().as_str()))
+        }
+        (Type::Bool | Type::Any, BsonValue::Boolean(b)) => Some(Value::Bool(*b)),
+        (Type::Int | Type::Any, BsonValue::Int32(i)) => Some(Value::Int((*i).into())),
+        (Type::Int | Type::Any, BsonValue::Int64(i)) => Some(Value::Int(*i)),
+        (Type::Float, BsonValue::Int32(i)) => Some(Value::from(f64::from(*i))),
+        (Type::Float | Type::Any, BsonValue::Double(f)) => Some(Value::from(*f)),
+        (Type::Duration, BsonValue::Int32(ms)) => Duration::new_with_unit((*ms).into(), "ms")
+            .ok()
+            .map(Value::from),
+        (Type::Duration, BsonValue::Int64(ms)) => {
+            Duration::new_with_unit(*ms, "ms").ok().map(Value::from)
+        }
+        (Type::DateTimeNaive, BsonValue::DateTime(dt)) => {
+            DateTimeNaive::from_timestamp(dt.timestamp_millis(), "ms")
+                .ok()
+                .map(Value::from)
+        }
+        (Type::DateTimeUtc | Type::Any, BsonValue::DateTime(dt)) => {
+            DateTimeUtc::from_timestamp(dt.timestamp_millis(), "ms")
+                .ok()
+                .map(Value::from)
+        }
+        (Type::Bytes | Type::Any, BsonValue::Binary(b)) => Some(Value::from(b.bytes.as_slice())),
+        (Type::Tuple(dtypes), BsonValue::Array(v)) => parse_tuple_from_bson(v, dtypes),
+        (Type::List(arg), BsonValue::Array(v)) => parse_list_from_bson(v, arg),
+        (Type::Any, BsonValue::Array(v)) => parse_list_from_bson(v, &Type::Any),
+        _ => None,
+    }
+}
+
 pub struct BsonFormatter {
     value_field_names: Vec<String>,
 }
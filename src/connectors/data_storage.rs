@@ -7,6 +7,8 @@ use s3::error::S3Error;
 use std::any::type_name;
 use std::borrow::Borrow;
 use std::borrow::Cow;
+use std::cmp::min;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
@@ -17,11 +19,14 @@ use std::io::BufWriter;
 use std::io::Write;
 use std::mem::take;
 use std::str::{from_utf8, Utf8Error};
-use std::sync::Arc;
-use std::thread::sleep;
-use std::time::Duration;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{sleep, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
 
 use arcstr::ArcStr;
+use base64::engine::general_purpose::STANDARD as base64encoder;
+use base64::Engine;
 use deltalake::arrow::datatypes::DataType as ArrowDataType;
 use deltalake::arrow::error::ArrowError;
 use deltalake::datafusion::parquet::record::Field as ParquetValue;
@@ -32,20 +37,74 @@ use iceberg::Error as IcebergError;
 use itertools::Itertools;
 use log::{error, info, warn};
 use postgres::types::ToSql;
+use regex::Regex;
+use reqwest::blocking::Client as HttpClient;
+use reqwest::header::{
+    HeaderMap as HttpHeaderMap, HeaderName as HttpHeaderName, HeaderValue as HttpHeaderValue,
+    CONTENT_TYPE as HTTP_CONTENT_TYPE, RETRY_AFTER as HTTP_RETRY_AFTER,
+};
+use reqwest::StatusCode as HttpStatusCode;
 use tokio::runtime::Runtime as TokioRuntime;
+use tokio::sync::mpsc::Receiver as TokioMpscReceiver;
+use tokio::sync::oneshot::Sender as TokioOneshotSender;
 
 use crate::async_runtime::create_async_tokio_runtime;
-use crate::connectors::data_format::{FormatterContext, FormatterError, COMMIT_LITERAL};
-use crate::connectors::data_tokenize::{BufReaderTokenizer, CsvTokenizer};
-use crate::connectors::metadata::{KafkaMetadata, SQLiteMetadata, SourceMetadata};
+use crate::connectors::data_format::{
+    parse_value_from_bson, serialize_value_to_json, FormatterContext, FormatterError,
+    COMMIT_LITERAL,
+};
+use crate::connectors::data_tokenize::{BufReaderTokenizer, CsvTokenizer, ParquetTokenizer};
+use crate::connectors::grpc::{
+    IngestServerHandle as GrpcIngestServerHandle, IngestedBatch as GrpcIngestedBatch,
+};
+use crate::connectors::kinesis::{
+    compare_record_positions as compare_kinesis_record_positions,
+    deaggregate_records as deaggregate_kinesis_records,
+    ShardDescription as KinesisShardDescription, ShardTracker as KinesisShardTracker,
+    UserRecord as KinesisUserRecord,
+};
+use crate::connectors::metadata::{
+    AmqpMetadata, GrpcMetadata, KafkaMetadata, KinesisMetadata, MqttMetadata, PubSubMetadata,
+    PulsarMetadata, SQLiteMetadata, SocketMetadata, SourceMetadata, WebhookMetadata,
+};
+use crate::connectors::mqtt::{
+    Client as MqttClient, Connection as MqttConnection, Error as MqttError,
+    Notification as MqttNotification, QualityOfService as MqttQoS,
+};
+use crate::connectors::mysql_binlog::{
+    binlog_file_index, format_source_id, parse_binlog_value, Error as MysqlBinlogError, GtidSet,
+};
 use crate::connectors::offset::EMPTY_OFFSET;
+use crate::connectors::pgoutput::{
+    format_lsn, parse_lsn, parse_text_value, Error as PgoutputError, Lsn,
+    Message as PgoutputMessage, Relation, RelationId, ReplicaIdentity, Tuple, TupleColumn,
+};
 use crate::connectors::posix_like::PosixLikeReader;
+use crate::connectors::pulsar::{
+    key_partition as pulsar_key_partition, message_key as pulsar_message_key,
+    MessageId as PulsarMessageId,
+};
+use crate::connectors::redis_stream::{
+    format_field_value as format_redis_value, parse_field_value as parse_redis_value,
+    EntryId as RedisStreamEntryId, Error as RedisStreamError,
+};
 use crate::connectors::scanner::s3::S3CommandName;
 use crate::connectors::scanner::{FilesystemScanner, S3Scanner};
-use crate::connectors::{Offset, OffsetKey, OffsetValue};
+use crate::connectors::socket::{
+    ListenerHandle as SocketListenerHandle, Protocol as SocketProtocol,
+    ReceivedMessage as SocketReceivedMessage,
+};
+use crate::connectors::webhook::{
+    parse_retry_after, sign_body, BatchFormat as WebhookBatchFormat,
+    IngestServerHandle as WebhookIngestServerHandle, ReceivedRequest as WebhookReceivedRequest,
+    UrlTemplate as WebhookUrlTemplate,
+};
+use crate::connectors::{Offset, OffsetKey, OffsetValue, SessionType};
 use crate::engine::error::limit_length;
 use crate::engine::error::DynResult;
 use crate::engine::error::STANDARD_OBJECT_LENGTH_LIMIT;
+use crate::engine::Timestamp;
+use crate::engine::TotalFrontier;
 use crate::engine::Type;
 use crate::engine::Value;
 use crate::persistence::backends::Error as PersistenceBackendError;
@@ -55,24 +114,86 @@ use crate::persistence::{ExternalPersistentId, PersistentId};
 use crate::python_api::extract_value;
 use crate::python_api::threads::PythonThreadState;
 use crate::python_api::PythonSubject;
+use crate::retry::{execute_with_retries_and_delays, RetryConfig};
 
 use async_nats::client::FlushError as NatsFlushError;
 use async_nats::client::PublishError as NatsPublishError;
+use async_nats::jetstream::consumer::pull::{
+    MessagesError as NatsJetStreamMessagesError, Stream as NatsJetStreamMessages,
+};
+use async_nats::jetstream::Message as NatsJetStreamMessage;
 use async_nats::Client as NatsClient;
+use async_nats::Error as NatsError;
 use async_nats::Subscriber as NatsSubscriber;
+use aws_sdk_kinesis::operation::get_records::GetRecordsError as KinesisGetRecordsError;
+use aws_sdk_kinesis::primitives::Blob as KinesisBlob;
+use aws_sdk_kinesis::types::{
+    PutRecordsRequestEntry as KinesisRecordEntry, Record as KinesisRecord,
+    ShardIteratorType as KinesisShardIteratorType,
+};
+use aws_sdk_kinesis::{Client as KinesisClient, Error as KinesisError};
 use bincode::ErrorKind as BincodeError;
 use elasticsearch::{BulkParts, Elasticsearch};
 use glob::PatternError as GlobPatternError;
+use google_cloud_gax::grpc::Status as PubSubStatus;
+use google_cloud_googleapis::pubsub::v1::{
+    ModifyAckDeadlineRequest as PubSubModifyAckDeadlineRequest, PubsubMessage,
+};
+use google_cloud_pubsub::publisher::{Awaiter as PubSubAwaiter, Publisher as PubSubPublisher};
+use google_cloud_pubsub::subscription::{
+    MessageStream as PubSubMessageStream, Subscription as PubSubSubscription,
+};
+use lapin::acker::Acker as AmqpAcker;
+use lapin::options::{BasicAckOptions, BasicPublishOptions};
+use lapin::publisher_confirm::{Confirmation as AmqpConfirmation, PublisherConfirm};
+use lapin::{
+    BasicProperties as AmqpProperties, Channel as AmqpChannel, Consumer as AmqpConsumer,
+    Error as AmqpError,
+};
+use mongodb::bson::de::Error as BsonDeserializationError;
+use mongodb::bson::ser::Error as BsonSerializationError;
 use mongodb::bson::Document as BsonDocument;
+use mongodb::change_stream::event::{
+    ChangeStreamEvent as MongoChangeStreamEvent, OperationType as MongoOperationType,
+    ResumeToken as MongoResumeToken,
+};
 use mongodb::error::Error as MongoError;
+use mongodb::options::FullDocumentType;
+use mongodb::sync::ChangeStream as MongoChangeStream;
 use mongodb::sync::Collection as MongoCollection;
+use mongodb::sync::Cursor as MongoCursor;
+use mysql::binlog::events::{Event as MysqlBinlogEvent, EventData as MysqlBinlogEventData};
+use mysql::binlog::row::BinlogRow;
+use mysql::binlog::value::BinlogValue;
+use mysql::prelude::Queryable;
+use mysql::BinlogRequest as MysqlBinlogRequest;
+use mysql::BinlogStream as MysqlBinlogStream;
+use mysql::Conn as MysqlConnection;
+use mysql::Error as MysqlError;
+use mysql::Opts as MysqlOpts;
+use mysql::Row as MysqlRow;
 use postgres::Client as PsqlClient;
+use postgres::Error as PsqlError;
+use pulsar::error::ConsumerError as PulsarConsumerError;
+use pulsar::producer::{Message as PulsarMessage, SendFuture as PulsarSendFuture};
+use pulsar::proto::MessageIdData as PulsarMessageIdData;
+use pulsar::{
+    Consumer as PulsarConsumer, Error as PulsarError, MultiTopicProducer as PulsarProducer, Pulsar,
+    TokioExecutor,
+};
 use pyo3::prelude::*;
 use rdkafka::consumer::{BaseConsumer, Consumer, DefaultConsumerContext};
 use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::message::OwnedHeaders as KafkaHeaders;
+use rdkafka::metadata::MetadataTopic;
 use rdkafka::producer::{BaseRecord, DefaultProducerContext, Producer, ThreadedProducer};
 use rdkafka::topic_partition_list::Offset as KafkaOffset;
 use rdkafka::Message;
+use redis::streams::{
+    StreamId as RedisStreamEntry, StreamReadOptions as RedisStreamReadOptions,
+    StreamReadReply as RedisStreamReadReply,
+};
+use redis::{Commands as RedisCommands, Connection as RedisConnection, RedisError};
 use rusqlite::types::ValueRef as SqliteValue;
 use rusqlite::Connection as SqliteConnection;
 use rusqlite::Error as SqliteError;
@@ -243,6 +364,9 @@ pub enum ReadError {
     #[error("no objects to read")]
     NoObjectsToRead,
 
+    #[error("inotify is supported only for the filesystem sources on Linux")]
+    InotifyUnsupported,
+
     #[error("invalid special value: {0}")]
     InvalidSpecialValue(String),
 
@@ -251,6 +375,87 @@ pub enum ReadError {
 
     #[error("deletion vectors in delta tables are not supported")]
     DeltaDeletionVectorsNotSupported,
+
+    #[error(transparent)]
+    Psql(#[from] PsqlError),
+
+    #[error(transparent)]
+    Pgoutput(#[from] PgoutputError),
+
+    #[error("replication slot {0:?} is not a logical replication slot")]
+    PsqlNotLogicalReplicationSlot(String),
+
+    #[error("relation {0} was not described by the replication stream")]
+    PsqlUnknownRelation(RelationId),
+
+    #[error("table {0:?} must have REPLICA IDENTITY FULL to track updates and deletions in non-upsert mode")]
+    PsqlReplicaIdentityNotFull(String),
+
+    #[error("table {0:?} was truncated, which can't be propagated as the deletions of its rows")]
+    PsqlTableTruncated(String),
+
+    #[error(transparent)]
+    MongoDB(#[from] MongoError),
+
+    #[error(transparent)]
+    BsonSerialization(#[from] BsonSerializationError),
+
+    #[error(transparent)]
+    BsonDeserialization(#[from] BsonDeserializationError),
+
+    #[error(transparent)]
+    Mysql(#[from] MysqlError),
+
+    #[error(transparent)]
+    MysqlBinlog(#[from] MysqlBinlogError),
+
+    #[error("table {0:?} doesn't exist or has no columns")]
+    MysqlTableNotFound(String),
+
+    #[error("server variable {0} must be set to {1:?} to read the changes from the binlog, but it is {2:?}")]
+    MysqlBinlogSetting(&'static str, &'static str, String),
+
+    #[error("binlog stream was closed by the server")]
+    MysqlBinlogStreamClosed,
+
+    #[error(transparent)]
+    NatsJetStreamMessages(#[from] NatsJetStreamMessagesError),
+
+    #[error("failed to get the metadata of a NATS JetStream message: {0}")]
+    NatsJetStreamMessageInfo(NatsError),
+
+    #[error("failed to acknowledge a NATS JetStream message: {0}")]
+    NatsJetStreamAck(NatsError),
+
+    #[error(transparent)]
+    Mqtt(#[from] MqttError),
+
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+
+    #[error(transparent)]
+    RedisStream(#[from] RedisStreamError),
+
+    #[error(transparent)]
+    Amqp(#[from] AmqpError),
+
+    #[error(transparent)]
+    Pulsar(#[from] PulsarError),
+
+    #[error(transparent)]
+    PulsarConsumer(#[from] PulsarConsumerError),
+
+    #[error(transparent)]
+    Kinesis(Box<KinesisError>),
+
+    #[error(transparent)]
+    PubSub(#[from] PubSubStatus),
+}
+
+impl From<KinesisError> for ReadError {
+    fn from(error: KinesisError) -> Self {
+        Self::Kinesis(Box::new(error))
+    }
 }
 
 #[derive(Debug, thiserror::Error, Clone, Eq, PartialEq)]
@@ -275,6 +480,19 @@ pub enum StorageType {
     Nats,
     PosixLike,
     Iceberg,
+    PsqlReplication,
+    MongoDB,
+    MysqlBinlog,
+    NatsJetStream,
+    Mqtt,
+    RedisStreams,
+    Amqp,
+    Pulsar,
+    Kinesis,
+    PubSub,
+    Grpc,
+    Webhook,
+    Socket,
 }
 
 impl StorageType {
@@ -295,6 +513,19 @@ impl StorageType {
             StorageType::DeltaLake => DeltaTableReader::merge_two_frontiers(lhs, rhs),
             StorageType::Nats => NatsReader::merge_two_frontiers(lhs, rhs),
             StorageType::Iceberg => IcebergReader::merge_two_frontiers(lhs, rhs),
+            StorageType::PsqlReplication => PsqlReplicationReader::merge_two_frontiers(lhs, rhs),
+            StorageType::MongoDB => MongoReader::merge_two_frontiers(lhs, rhs),
+            StorageType::MysqlBinlog => MysqlBinlogReader::merge_two_frontiers(lhs, rhs),
+            StorageType::NatsJetStream => NatsJetStreamReader::merge_two_frontiers(lhs, rhs),
+            StorageType::Mqtt => MqttReader::merge_two_frontiers(lhs, rhs),
+            StorageType::RedisStreams => RedisStreamsReader::merge_two_frontiers(lhs, rhs),
+            StorageType::Amqp => AmqpReader::merge_two_frontiers(lhs, rhs),
+            StorageType::Pulsar => PulsarReader::merge_two_frontiers(lhs, rhs),
+            StorageType::Kinesis => KinesisReader::merge_two_frontiers(lhs, rhs),
+            StorageType::PubSub => PubSubReader::merge_two_frontiers(lhs, rhs),
+            StorageType::Grpc => GrpcReader::merge_two_frontiers(lhs, rhs),
+            StorageType::Webhook => WebhookReader::merge_two_frontiers(lhs, rhs),
+            StorageType::Socket => SocketReader::merge_two_frontiers(lhs, rhs),
         }
     }
 }
@@ -333,6 +564,23 @@ pub fn new_csv_filesystem_reader(
     )
 }
 
+pub fn new_parquet_filesystem_reader<S: ::std::hash::BuildHasher>(
+    path: &str,
+    column_types: HashMap<String, Type, S>,
+    streaming_mode: ConnectorMode,
+    persistent_id: Option<PersistentId>,
+    object_pattern: &str,
+) -> Result<PosixLikeReader, ReadError> {
+    let scanner = FilesystemScanner::new(path, object_pattern)?;
+    let tokenizer = ParquetTokenizer::new(column_types.into_iter().collect());
+    PosixLikeReader::new(
+        Box::new(scanner),
+        Box::new(tokenizer),
+        streaming_mode,
+        persistent_id,
+    )
+}
+
 pub fn new_s3_generic_reader(
     bucket: S3Bucket,
     objects_prefix: impl Into<String>,
@@ -369,6 +617,24 @@ pub fn new_s3_csv_reader(
     )
 }
 
+pub fn new_s3_parquet_reader<S: ::std::hash::BuildHasher>(
+    bucket: S3Bucket,
+    objects_prefix: impl Into<String>,
+    column_types: HashMap<String, Type, S>,
+    streaming_mode: ConnectorMode,
+    persistent_id: Option<PersistentId>,
+    downloader_threads_count: usize,
+) -> Result<PosixLikeReader, ReadError> {
+    let scanner = S3Scanner::new(bucket, objects_prefix, downloader_threads_count)?;
+    let tokenizer = ParquetTokenizer::new(column_types.into_iter().collect());
+    PosixLikeReader::new(
+        Box::new(scanner),
+        Box::new(tokenizer),
+        streaming_mode,
+        persistent_id,
+    )
+}
+
 pub trait Reader {
     fn read(&mut self) -> Result<ReadResult, ReadError>;
 
@@ -385,6 +651,24 @@ pub trait Reader {
         Ok(())
     }
 
+    /// Provides the frontiers which are guaranteed to be restored after a restart.
+    /// A reader that acknowledges the consumed data in the source should do it
+    /// only for the data within these frontiers. The receiver is only provided
+    /// if the input persistence is enabled.
+    fn set_persisted_frontier_receiver(&mut self, _receiver: Receiver<OffsetAntichain>) {}
+
+    /// Whether the reader needs to know which of the data it has read is
+    /// already processed by all outputs of the computation.
+    fn processed_frontier_needed(&self) -> bool {
+        false
+    }
+
+    /// Provides the frontiers of the data that has been processed by all
+    /// outputs. The receiver is only provided if the reader has requested it
+    /// with `processed_frontier_needed`.
+    fn set_processed_frontier_receiver(&mut self, _receiver: Receiver<OffsetAntichain>) {}
+
+    #[allow(clippy::too_many_lines)]
     fn merge_two_frontiers(lhs: &OffsetAntichain, rhs: &OffsetAntichain) -> OffsetAntichain
     where
         Self: Sized,
@@ -465,6 +749,129 @@ pub trait Reader {
                             result.advance_offset(offset_key.clone(), other_value.clone());
                         }
                     }
+                    (
+                        OffsetValue::PsqlReplicationPosition {
+                            transaction_lsn: offset_lsn,
+                            entries_read_within_transaction: offset_position,
+                        },
+                        OffsetValue::PsqlReplicationPosition {
+                            transaction_lsn: other_lsn,
+                            entries_read_within_transaction: other_position,
+                        },
+                    ) => {
+                        if (other_lsn, other_position) > (offset_lsn, offset_position) {
+                            result.advance_offset(offset_key.clone(), other_value.clone());
+                        }
+                    }
+                    (
+                        OffsetValue::MysqlBinlogPosition { .. },
+                        OffsetValue::MysqlBinlogPosition { .. },
+                    ) => {
+                        if MysqlBinlogReader::position_order_key(other_value)
+                            > MysqlBinlogReader::position_order_key(offset_value)
+                        {
+                            result.advance_offset(offset_key.clone(), other_value.clone());
+                        }
+                    }
+                    (
+                        OffsetValue::NatsStreamSequence(offset_sequence),
+                        OffsetValue::NatsStreamSequence(other_sequence),
+                    ) => {
+                        if other_sequence > offset_sequence {
+                            result.advance_offset(offset_key.clone(), other_value.clone());
+                        }
+                    }
+                    (
+                        OffsetValue::RedisStreamEntryId {
+                            milliseconds: offset_milliseconds,
+                            sequence: offset_sequence,
+                        },
+                        OffsetValue::RedisStreamEntryId {
+                            milliseconds: other_milliseconds,
+                            sequence: other_sequence,
+                        },
+                    ) => {
+                        if (other_milliseconds, other_sequence)
+                            > (offset_milliseconds, offset_sequence)
+                        {
+                            result.advance_offset(offset_key.clone(), other_value.clone());
+                        }
+                    }
+                    (
+                        OffsetValue::MqttReadEntriesCount(offset_count),
+                        OffsetValue::MqttReadEntriesCount(other_count),
+                    )
+                    | (
+                        OffsetValue::AmqpReadEntriesCount(offset_count),
+                        OffsetValue::AmqpReadEntriesCount(other_count),
+                    )
+                    | (
+                        OffsetValue::PubSubReadMessagesCount(offset_count),
+                        OffsetValue::PubSubReadMessagesCount(other_count),
+                    )
+                    | (
+                        OffsetValue::GrpcReadBatchesCount(offset_count),
+                        OffsetValue::GrpcReadBatchesCount(other_count),
+                    )
+                    | (
+                        OffsetValue::WebhookReadRequestsCount(offset_count),
+                        OffsetValue::WebhookReadRequestsCount(other_count),
+                    )
+                    | (
+                        OffsetValue::SocketReadMessagesCount(offset_count),
+                        OffsetValue::SocketReadMessagesCount(other_count),
+                    ) => {
+                        if other_count > offset_count {
+                            result.advance_offset(offset_key.clone(), other_value.clone());
+                        }
+                    }
+                    (
+                        OffsetValue::PulsarMessageId {
+                            ledger_id: offset_ledger_id,
+                            entry_id: offset_entry_id,
+                            batch_index: offset_batch_index,
+                        },
+                        OffsetValue::PulsarMessageId {
+                            ledger_id: other_ledger_id,
+                            entry_id: other_entry_id,
+                            batch_index: other_batch_index,
+                        },
+                    ) => {
+                        if (other_ledger_id, other_entry_id, other_batch_index)
+                            > (offset_ledger_id, offset_entry_id, offset_batch_index)
+                        {
+                            result.advance_offset(offset_key.clone(), other_value.clone());
+                        }
+                    }
+                    (
+                        OffsetValue::KinesisRecordPosition {
+                            sequence_number: offset_sequence_number,
+                            sub_sequence_number: offset_sub_sequence_number,
+                        },
+                        OffsetValue::KinesisRecordPosition {
+                            sequence_number: other_sequence_number,
+                            sub_sequence_number: other_sub_sequence_number,
+                        },
+                    ) => {
+                        if compare_kinesis_record_positions(
+                            (other_sequence_number, *other_sub_sequence_number),
+                            (offset_sequence_number, *offset_sub_sequence_number),
+                        )
+                        .is_gt()
+                        {
+                            result.advance_offset(offset_key.clone(), other_value.clone());
+                        }
+                    }
+                    (
+                        OffsetValue::MongoDbResumeToken(offset_token),
+                        OffsetValue::MongoDbResumeToken(other_token),
+                    ) => {
+                        if MongoReader::resume_token_order_key(other_token)
+                            > MongoReader::resume_token_order_key(offset_token)
+                        {
+                            result.advance_offset(offset_key.clone(), other_value.clone());
+                        }
+                    }
                     (_, _) => {
                         error!("Incomparable offsets in the frontier: {offset_value:?} and {other_value:?}");
                     }
@@ -507,6 +914,10 @@ pub trait ReaderBuilder: Send + 'static {
     fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>);
 
     fn storage_type(&self) -> StorageType;
+
+    fn processed_frontier_needed(&self) -> bool {
+        false
+    }
 }
 
 impl<T> ReaderBuilder for T
@@ -528,6 +939,10 @@ where
     fn storage_type(&self) -> StorageType {
         Reader::storage_type(self)
     }
+
+    fn processed_frontier_needed(&self) -> bool {
+        Reader::processed_frontier_needed(self)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -572,8 +987,11 @@ pub enum WriteError {
     #[error("integer value {0} out of range")]
     IntOutOfRange(i64),
 
-    #[error("value {0} can't be used as a key because it's neither 'bytes' nor 'string'")]
-    IncorrectKeyFieldType(Value),
+    #[error("value {0} can't be used as a partition because it's not an integer")]
+    IncorrectPartitionFieldType(Value),
+
+    #[error("kafka topic {0:?} doesn't exist or has no partitions")]
+    KafkaTopicWithoutPartitions(String),
 
     #[error("unsupported type: {0:?}")]
     UnsupportedType(Type),
@@ -595,6 +1013,64 @@ pub enum WriteError {
 
     #[error(transparent)]
     MongoDB(#[from] MongoError),
+
+    #[error(transparent)]
+    Mqtt(#[from] MqttError),
+
+    #[error("value {0} can't be used as an MQTT topic because it's not a string")]
+    IncorrectMqttTopicFieldType(Value),
+
+    #[error("the connection to the MQTT broker was lost before it confirmed all messages: {0}")]
+    MqttConnectionLost(String),
+
+    #[error("the MQTT broker hasn't confirmed {0} messages within {1:?}")]
+    MqttDeliveryTimeout(usize, Duration),
+
+    #[error(transparent)]
+    Redis(#[from] RedisError),
+
+    #[error(transparent)]
+    Amqp(#[from] AmqpError),
+
+    #[error("the AMQP broker has rejected a published message")]
+    AmqpPublishRejected,
+
+    #[error("value {0} can't be used as an AMQP routing key because it's not a string")]
+    IncorrectAmqpRoutingKeyFieldType(Value),
+
+    #[error(transparent)]
+    Pulsar(#[from] PulsarError),
+
+    #[error(transparent)]
+    Kinesis(Box<KinesisError>),
+
+    #[error(
+        "Kinesis has rejected {count} records, the first error: {error_code}: {error_message}"
+    )]
+    KinesisRecordsRejected {
+        count: usize,
+        error_code: String,
+        error_message: String,
+    },
+
+    #[error(transparent)]
+    PubSub(#[from] PubSubStatus),
+
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+
+    #[error("request to {url} failed with status {status}: {response}")]
+    HttpRequestFailed {
+        url: String,
+        status: HttpStatusCode,
+        response: String,
+    },
+}
+
+impl From<KinesisError> for WriteError {
+    fn from(error: KinesisError) -> Self {
+        Self::Kinesis(Box::new(error))
+    }
 }
 
 pub trait Writer: Send {
@@ -604,6 +1080,16 @@ pub trait Writer: Send {
         Ok(())
     }
 
+    /// Called after `flush` with the time, before which the output of this sink
+    /// won't be produced again after a restart. The time is saved only after this
+    /// method succeeds. Sinks with transactions commit the output before this time here.
+    fn on_time_finalized(
+        &mut self,
+        _finalized_time: TotalFrontier<Timestamp>,
+    ) -> Result<(), WriteError> {
+        Ok(())
+    }
+
     fn retriable(&self) -> bool {
         false
     }
@@ -670,14 +1156,172 @@ impl Writer for FileWriter {
     }
 }
 
+/// The topics read by a Kafka reader: the topics given by their names and the
+/// topics, whose whole names match the pattern, as in Kafka's own subscriptions.
+/// The pattern is matched with the `regex` crate and the resulting list of topics
+/// is subscribed to, because librdkafka would interpret the pattern with a
+/// different syntax.
+#[derive(Debug)]
+pub struct KafkaTopicSubscription {
+    topic_names: Vec<String>,
+    topic_pattern: Option<Regex>,
+}
+
+impl KafkaTopicSubscription {
+    pub fn new(
+        topic_names: Vec<String>,
+        topic_pattern: Option<&str>,
+    ) -> Result<Self, regex::Error> {
+        let topic_pattern = topic_pattern
+            .map(|topic_pattern| Regex::new(&format!("^(?:{topic_pattern})$")))
+            .transpose()?;
+        Ok(Self {
+            topic_names,
+            topic_pattern,
+        })
+    }
+
+    pub fn topic_names(&self) -> &[String] {
+        &self.topic_names
+    }
+
+    pub fn has_pattern(&self) -> bool {
+        self.topic_pattern.is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.topic_names.is_empty() && self.topic_pattern.is_none()
+    }
+
+    pub fn matches_pattern(&self, topic: &str) -> bool {
+        self.topic_pattern
+            .as_ref()
+            .is_some_and(|topic_pattern| topic_pattern.is_match(topic))
+    }
+
+    /// Returns the topics to subscribe to: the given topic names, followed by the
+    /// sorted names of the existing topics that match the pattern.
+    pub fn resolve<'a>(&self, existing_topics: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let mut matched_topics: Vec<String> = existing_topics
+            .into_iter()
+            .filter(|topic| {
+                self.matches_pattern(topic) && !self.topic_names.iter().any(|name| name == topic)
+            })
+            .map(ToString::to_string)
+            .collect();
+        matched_topics.sort();
+        matched_topics.dedup();
+        self.topic_names
+            .iter()
+            .cloned()
+            .chain(matched_topics)
+            .collect()
+    }
+}
+
+/// The partitions and the offsets of a bounded Kafka read.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct KafkaBoundedRead {
+    /// The partitions to be assigned to the consumer with their start offsets.
+    /// If there is no start offset, the committed offset of the consumer group is used.
+    pub assignment: Vec<(String, i32, Option<i64>)>,
+
+    pub stop_offsets: KafkaStopOffsets,
+}
+
+impl KafkaBoundedRead {
+    /// Plans the read of the given partitions up to the end offsets. The partitions
+    /// that have nothing to read before the end offset aren't assigned at all.
+    pub fn new(
+        watermarks: &HashMap<(String, i32), (i64, i64)>,
+        start_offsets: &HashMap<(String, i32), i64>,
+        end_offsets: HashMap<(String, i32), i64>,
+    ) -> Self {
+        let mut result = Self::default();
+        for ((topic, partition), end_offset) in end_offsets.into_iter().sorted() {
+            let (low, _) = watermarks[&(topic.clone(), partition)];
+            let start_offset = start_offsets.get(&(topic.clone(), partition)).copied();
+            if end_offset <= low.max(start_offset.unwrap_or(low)) {
+                info!("Partition {partition} of topic {topic} has nothing to read before the end position.");
+                continue;
+            }
+            result
+                .stop_offsets
+                .offsets
+                .insert((topic.as_str().into(), partition), end_offset);
+            result.assignment.push((topic, partition, start_offset));
+        }
+        result
+    }
+}
+
+/// The offsets, before which the partitions that are not finished yet must be read
+/// in the bounded mode.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct KafkaStopOffsets {
+    offsets: HashMap<(ArcStr, i32), i64>,
+}
+
+impl KafkaStopOffsets {
+    pub fn is_finished(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    pub fn is_partition_finished(&self, topic_partition: &(ArcStr, i32)) -> bool {
+        !self.offsets.contains_key(topic_partition)
+    }
+
+    /// Returns `true` if the message at the given offset must be read. The partition
+    /// is finished once its last message before the stop offset is read or once
+    /// a message at or after the stop offset comes.
+    pub fn accept_message(&mut self, topic_partition: &(ArcStr, i32), offset: i64) -> bool {
+        let Some(stop_offset) = self.offsets.get(topic_partition) else {
+            // The partition is already read up to the end
+            return false;
+        };
+        if offset >= *stop_offset {
+            self.offsets.remove(topic_partition);
+            return false;
+        }
+        if offset + 1 >= *stop_offset {
+            self.offsets.remove(topic_partition);
+        }
+        true
+    }
+
+    /// Finishes the partition if the consumer is already positioned at or after
+    /// the stop offset.
+    pub fn on_consumer_position(&mut self, topic_partition: &(ArcStr, i32), position: i64) {
+        if self
+            .offsets
+            .get(topic_partition)
+            .is_some_and(|stop_offset| position >= *stop_offset)
+        {
+            self.offsets.remove(topic_partition);
+        }
+    }
+}
+
 pub struct KafkaReader {
     consumer: BaseConsumer<DefaultConsumerContext>,
     persistent_id: Option<PersistentId>,
-    topic: ArcStr,
-    positions_for_seek: HashMap<i32, KafkaOffset>,
+    positions_for_seek: HashMap<(ArcStr, i32), KafkaOffset>,
     deferred_read_result: Option<ReadResult>,
+
+    // In the bounded mode: the offsets, before which the partitions must be read
+    stop_offsets: Option<KafkaStopOffsets>,
+
+    // In the streaming mode with a topic pattern: the subscription, which is
+    // resolved again periodically to pick up the newly created topics
+    pattern_subscription: Option<KafkaTopicSubscription>,
+    subscribed_topics: Option<Vec<String>>,
+    next_subscription_refresh: Instant,
 }
 
+const KAFKA_BOUNDED_READ_POLL_TIMEOUT: Duration = Duration::from_secs(1);
+const KAFKA_TOPIC_PATTERN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+const KAFKA_METADATA_TIMEOUT: Duration = Duration::from_secs(30);
+
 impl Reader for KafkaReader {
     fn read(&mut self) -> Result<ReadResult, ReadError> {
         if let Some(deferred_read_result) = take(&mut self.deferred_read_result) {
@@ -685,15 +1329,47 @@ impl Reader for KafkaReader {
         }
 
         loop {
-            let kafka_message = self
-                .consumer
-                .poll(Timeout::Never)
-                .expect("poll should never timeout")?;
+            if self
+                .stop_offsets
+                .as_ref()
+                .is_some_and(KafkaStopOffsets::is_finished)
+            {
+                return Ok(ReadResult::Finished);
+            }
+
+            let kafka_message = if self.stop_offsets.is_some() {
+                let Some(kafka_message) = self.consumer.poll(KAFKA_BOUNDED_READ_POLL_TIMEOUT)
+                else {
+                    self.remove_finished_partitions()?;
+                    continue;
+                };
+                kafka_message?
+            } else if self.pattern_subscription.is_some() {
+                let now = Instant::now();
+                if now >= self.next_subscription_refresh {
+                    self.refresh_pattern_subscription()?;
+                    continue;
+                }
+                let Some(kafka_message) = self
+                    .consumer
+                    .poll(self.next_subscription_refresh.duration_since(now))
+                else {
+                    continue;
+                };
+                kafka_message?
+            } else {
+                self.consumer
+                    .poll(Timeout::Never)
+                    .expect("poll should never timeout")?
+            };
             let message_key = kafka_message.key().map(<[u8]>::to_vec);
             let message_payload = kafka_message.payload().map(<[u8]>::to_vec);
+            let topic_partition = (
+                ArcStr::from(kafka_message.topic()),
+                kafka_message.partition(),
+            );
 
-            if let Some(lazy_seek_offset) = self.positions_for_seek.get(&kafka_message.partition())
-            {
+            if let Some(lazy_seek_offset) = self.positions_for_seek.get(&topic_partition) {
                 info!(
                     "Performing Kafka topic seek for ({}, {}) to {:?}",
                     kafka_message.topic(),
@@ -714,13 +1390,20 @@ impl Reader for KafkaReader {
                         lazy_seek_offset,
                     );
                 } else {
-                    self.positions_for_seek.remove(&kafka_message.partition());
+                    self.positions_for_seek.remove(&topic_partition);
                 }
                 continue;
             }
 
+            if let Some(stop_offsets) = &mut self.stop_offsets {
+                if !stop_offsets.accept_message(&topic_partition, kafka_message.offset()) {
+                    continue;
+                }
+            }
+
             let offset = {
-                let offset_key = OffsetKey::Kafka(self.topic.clone(), kafka_message.partition());
+                let (topic, partition) = topic_partition;
+                let offset_key = OffsetKey::Kafka(topic, partition);
                 let offset_value = OffsetValue::KafkaOffset(kafka_message.offset());
                 (offset_key, offset_value)
             };
@@ -740,14 +1423,6 @@ impl Reader for KafkaReader {
                 continue;
             };
             if let OffsetKey::Kafka(topic, partition) = offset_key {
-                if self.topic != *topic {
-                    warn!(
-                        "Unexpected topic name. Expected: {}, Got: {topic}",
-                        self.topic
-                    );
-                    continue;
-                }
-
                 /*
                     Note: we can't do seek straight away, because it works only for
                     assigned partitions.
@@ -756,8 +1431,10 @@ impl Reader for KafkaReader {
                     to be done on behalf of rdkafka client, taking account of other
                     members in its' consumer group.
                 */
-                self.positions_for_seek
-                    .insert(*partition, KafkaOffset::Offset(*position + 1));
+                self.positions_for_seek.insert(
+                    (topic.clone(), *partition),
+                    KafkaOffset::Offset(*position + 1),
+                );
             } else {
                 error!("Unexpected offset in Kafka frontier: ({offset_key:?}, {offset_value:?})");
             }
@@ -786,27 +1463,75 @@ impl Reader for KafkaReader {
 impl KafkaReader {
     pub fn new(
         consumer: BaseConsumer<DefaultConsumerContext>,
-        topic: String,
         persistent_id: Option<PersistentId>,
-        positions_for_seek: HashMap<i32, KafkaOffset>,
+        positions_for_seek: HashMap<(ArcStr, i32), KafkaOffset>,
+        stop_offsets: Option<KafkaStopOffsets>,
+        pattern_subscription: Option<KafkaTopicSubscription>,
     ) -> KafkaReader {
         KafkaReader {
             consumer,
             persistent_id,
-            topic: topic.into(),
             positions_for_seek,
             deferred_read_result: None,
+            stop_offsets,
+            pattern_subscription,
+            subscribed_topics: None,
+            next_subscription_refresh: Instant::now(),
         }
     }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum ConnectorMode {
-    Static,
-    Streaming,
-}
 
-impl ConnectorMode {
+    /// Subscribes to the topics matching the subscription if they have changed
+    /// since the last refresh.
+    fn refresh_pattern_subscription(&mut self) -> Result<(), ReadError> {
+        let Some(pattern_subscription) = &self.pattern_subscription else {
+            return Ok(());
+        };
+        let metadata = self.consumer.fetch_metadata(None, KAFKA_METADATA_TIMEOUT)?;
+        let topics =
+            pattern_subscription.resolve(metadata.topics().iter().map(MetadataTopic::name));
+        self.next_subscription_refresh = Instant::now() + KAFKA_TOPIC_PATTERN_REFRESH_INTERVAL;
+        if self.subscribed_topics.as_ref() == Some(&topics) {
+            return Ok(());
+        }
+
+        info!("Subscribing to Kafka topics {topics:?}");
+        if topics.is_empty() {
+            self.consumer.unsubscribe();
+        } else {
+            let topic_names: Vec<&str> = topics.iter().map(String::as_str).collect();
+            self.consumer.subscribe(&topic_names)?;
+        }
+        self.subscribed_topics = Some(topics);
+        Ok(())
+    }
+
+    /// Removes the partitions, where the consumer is already positioned at or after
+    /// the stop offset. It happens if the partition ends with the transaction
+    /// markers or if the committed offset of the consumer group is already beyond
+    /// the stop offset, so no more messages will come from it.
+    fn remove_finished_partitions(&mut self) -> Result<(), ReadError> {
+        let Some(stop_offsets) = &mut self.stop_offsets else {
+            return Ok(());
+        };
+        let positions = self.consumer.position()?;
+        for element in positions.elements() {
+            let KafkaOffset::Offset(position) = element.offset() else {
+                continue;
+            };
+            let topic_partition = (ArcStr::from(element.topic()), element.partition());
+            stop_offsets.on_consumer_position(&topic_partition, position);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectorMode {
+    Static,
+    Streaming,
+}
+
+impl ConnectorMode {
     pub fn is_polling_enabled(&self) -> bool {
         match self {
             ConnectorMode::Static => false,
@@ -1236,446 +1961,4348 @@ impl Writer for PsqlWriter {
     }
 }
 
-pub struct KafkaWriter {
-    producer: ThreadedProducer<DefaultProducerContext>,
-    topic: String,
-    header_fields: Vec<(String, usize)>,
-    key_field_index: Option<usize>,
+const PSQL_REPLICATION_PEEK_LIMIT: i32 = 1024;
+
+/// Reads the changes of a `PostgreSQL` table from a logical replication slot,
+/// decoded with the `pgoutput` plugin.
+///
+/// The changes are peeked from the slot, and the slot is advanced only when it's
+/// safe: right away if there is no persistence, and once the corresponding frontier
+/// is persisted otherwise. Since the slot may therefore return the same transactions
+/// several times, the position of the last read change is tracked and the changes
+/// up to it are skipped.
+///
+/// The slot is advanced to the end of the last confirmed transaction, so that the
+/// server doesn't decode it again. A transaction is confirmed when its changes are
+/// persisted, or right away if it has no changes of the table.
+pub struct PsqlReplicationReader {
+    client: PsqlClient,
+    slot_name: String,
+    publication_name: String,
+    table_name: String,
+    schema: HashMap<String, Type>,
+    session_type: SessionType,
+    persistent_id: Option<PersistentId>,
+
+    relations: HashMap<RelationId, Relation>,
+    last_read_position: Option<(Lsn, u64)>,
+    confirmed_lsn: Lsn,
+    persisted_lsn: Option<Lsn>,
+    // The starting and the ending positions of the read transactions that aren't
+    // confirmed yet, and whether they have changes of the table
+    unconfirmed_transactions: VecDeque<(Lsn, Lsn, bool)>,
+    persisted_frontier_receiver: Option<Receiver<OffsetAntichain>>,
+    queued_updates: VecDeque<ReadResult>,
 }
 
-impl KafkaWriter {
+impl PsqlReplicationReader {
     pub fn new(
-        producer: ThreadedProducer<DefaultProducerContext>,
-        topic: String,
-        header_fields: Vec<(String, usize)>,
-        key_field_index: Option<usize>,
-    ) -> KafkaWriter {
-        KafkaWriter {
-            producer,
-            topic,
-            header_fields,
-            key_field_index,
-        }
-    }
-}
+        mut client: PsqlClient,
+        slot_name: String,
+        publication_name: String,
+        table_name: String,
+        schema: HashMap<String, Type>,
+        session_type: SessionType,
+        persistent_id: Option<PersistentId>,
+    ) -> Result<PsqlReplicationReader, ReadError> {
+        let slot_rows = client.query(
+            "SELECT confirmed_flush_lsn::text FROM pg_replication_slots WHERE slot_name = $1",
+            &[&slot_name],
+        )?;
+        let confirmed_lsn: Option<String> = if let Some(row) = slot_rows.first() {
+            row.get(0)
+        } else {
+            info!("Creating logical replication slot {slot_name:?}");
+            let row = client.query_one(
+                "SELECT lsn::text FROM pg_create_logical_replication_slot($1, 'pgoutput')",
+                &[&slot_name],
+            )?;
+            row.get(0)
+        };
+        let confirmed_lsn = confirmed_lsn
+            .ok_or_else(|| ReadError::PsqlNotLogicalReplicationSlot(slot_name.clone()))?;
 
-impl Drop for KafkaWriter {
-    fn drop(&mut self) {
-        self.producer.flush(None).expect("kafka commit should work");
+        Ok(Self {
+            client,
+            slot_name,
+            publication_name,
+            table_name,
+            schema,
+            session_type,
+            persistent_id,
+
+            relations: HashMap::new(),
+            last_read_position: None,
+            confirmed_lsn: parse_lsn(&confirmed_lsn)?,
+            persisted_lsn: None,
+            unconfirmed_transactions: VecDeque::new(),
+            persisted_frontier_receiver: None,
+            queued_updates: VecDeque::new(),
+        })
     }
-}
 
-impl Writer for KafkaWriter {
-    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
-        let key_as_bytes = match self.key_field_index {
-            Some(index) => match &data.values[index] {
-                Value::Bytes(bytes) => bytes.to_vec(),
-                Value::String(string) => string.as_bytes().to_vec(),
-                _ => {
-                    return Err(WriteError::IncorrectKeyFieldType(
-                        data.values[index].clone(),
-                    ))
-                }
-            },
-            None => data.key.0.to_le_bytes().to_vec(),
+    fn convert_to_value(
+        value: &TupleColumn,
+        field_name: &str,
+        dtype: &Type,
+    ) -> Result<Value, Box<ConversionError>> {
+        let converted = match value {
+            TupleColumn::Null if dtype.can_be_none() => Some(Value::None),
+            TupleColumn::Text(raw_value) => parse_text_value(raw_value, dtype),
+            TupleColumn::Null | TupleColumn::UnchangedToast => None,
         };
+        converted.ok_or_else(|| {
+            let value_repr = limit_length(format!("{value:?}"), STANDARD_OBJECT_LENGTH_LIMIT);
+            Box::new(ConversionError {
+                value_repr,
+                field_name: field_name.to_owned(),
+                type_: dtype.clone(),
+            })
+        })
+    }
 
-        let headers = data.construct_kafka_headers(&self.header_fields);
-        for payload in data.payloads {
-            let payload = payload.into_raw_bytes()?;
-            let mut entry = BaseRecord::<Vec<u8>, Vec<u8>>::to(&self.topic)
-                .payload(&payload)
-                .headers(headers.clone())
-                .key(&key_as_bytes);
-            loop {
-                match self.producer.send(entry) {
-                    Ok(()) => break,
-                    Err((
-                        KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull),
-                        unsent_entry,
-                    )) => {
-                        self.producer.poll(Duration::from_millis(10));
-                        entry = unsent_entry;
-                        continue;
-                    }
-                    Err((e, _unsent_entry)) => return Err(WriteError::Kafka(e)),
+    /// Unchanged TOAST values are not sent by the server, so they are taken from
+    /// the old version of the row, if it's available.
+    fn tuple_to_values(
+        &self,
+        relation: &Relation,
+        tuple: &Tuple,
+        old_tuple: Option<&Tuple>,
+    ) -> ValuesMap {
+        let mut values = HashMap::with_capacity(self.schema.len());
+        for (column_idx, (column, value)) in relation.columns.iter().zip(tuple).enumerate() {
+            let Some(dtype) = self.schema.get(&column.name) else {
+                continue;
+            };
+            let value = match (value, old_tuple) {
+                (TupleColumn::UnchangedToast, Some(old_tuple)) => {
+                    old_tuple.get(column_idx).unwrap_or(value)
                 }
-            }
+                _ => value,
+            };
+            values.insert(
+                column.name.clone(),
+                Self::convert_to_value(value, &column.name, dtype),
+            );
         }
-        Ok(())
+        values.into()
     }
 
-    fn retriable(&self) -> bool {
-        true
+    fn queue_change(&mut self, event: DataEventType, values: ValuesMap, offset: &Offset) {
+        let event = match (self.session_type, event) {
+            (SessionType::Upsert, DataEventType::Insert) => DataEventType::Upsert,
+            (_, event) => event,
+        };
+        self.queued_updates.push_back(ReadResult::Data(
+            ReaderContext::from_diff(event, None, values),
+            offset.clone(),
+        ));
     }
 
-    fn single_threaded(&self) -> bool {
-        false
-    }
-}
+    fn queue_message(
+        &mut self,
+        message: PgoutputMessage,
+        transaction_lsn: Lsn,
+        entries_read_within_transaction: &mut u64,
+    ) -> Result<(), ReadError> {
+        let (relation_id, old_tuple, new_tuple) = match message {
+            PgoutputMessage::Relation(relation) => {
+                self.relations.insert(relation.id, relation);
+                return Ok(());
+            }
+            PgoutputMessage::Truncate { relation_ids } => {
+                let is_tracked_table_truncated = relation_ids.iter().any(|relation_id| {
+                    self.relations
+                        .get(relation_id)
+                        .is_some_and(|relation| relation.matches(&self.table_name))
+                });
+                if is_tracked_table_truncated {
+                    return Err(ReadError::PsqlTableTruncated(self.table_name.clone()));
+                }
+                return Ok(());
+            }
+            PgoutputMessage::Insert {
+                relation_id,
+                new_tuple,
+            } => (relation_id, None, Some(new_tuple)),
+            PgoutputMessage::Update {
+                relation_id,
+                old_tuple,
+                new_tuple,
+            } => (relation_id, old_tuple, Some(new_tuple)),
+            PgoutputMessage::Delete {
+                relation_id,
+                old_tuple,
+            } => (relation_id, Some(old_tuple), None),
+            _ => return Ok(()),
+        };
 
-pub struct ElasticSearchWriter {
-    client: Elasticsearch,
-    index_name: String,
-    max_batch_size: Option<usize>,
+        let relation = self
+            .relations
+            .get(&relation_id)
+            .ok_or(ReadError::PsqlUnknownRelation(relation_id))?;
+        if !relation.matches(&self.table_name) {
+            return Ok(());
+        }
+        let is_full_row_required = matches!(self.session_type, SessionType::Native)
+            && relation.replica_identity != ReplicaIdentity::Full
+            && (old_tuple.is_some() || new_tuple.is_none());
+        if is_full_row_required {
+            return Err(ReadError::PsqlReplicaIdentityNotFull(
+                self.table_name.clone(),
+            ));
+        }
 
-    docs_buffer: Vec<Vec<u8>>,
-}
+        *entries_read_within_transaction += 1;
+        let position = (transaction_lsn, *entries_read_within_transaction);
+        if self
+            .last_read_position
+            .is_some_and(|last_read_position| position <= last_read_position)
+        {
+            return Ok(());
+        }
+        self.last_read_position = Some(position);
 
-impl ElasticSearchWriter {
-    pub fn new(client: Elasticsearch, index_name: String, max_batch_size: Option<usize>) -> Self {
-        ElasticSearchWriter {
-            client,
-            index_name,
-            max_batch_size,
-            docs_buffer: Vec::new(),
+        let offset = (
+            OffsetKey::Empty,
+            OffsetValue::PsqlReplicationPosition {
+                transaction_lsn,
+                entries_read_within_transaction: *entries_read_within_transaction,
+            },
+        );
+        let old_values = old_tuple
+            .as_ref()
+            .map(|old_tuple| self.tuple_to_values(relation, old_tuple, None));
+        let new_values = new_tuple
+            .as_ref()
+            .map(|new_tuple| self.tuple_to_values(relation, new_tuple, old_tuple.as_ref()));
+        if let Some(old_values) = old_values {
+            self.queue_change(DataEventType::Delete, old_values, &offset);
+        }
+        if let Some(new_values) = new_values {
+            self.queue_change(DataEventType::Insert, new_values, &offset);
         }
+
+        Ok(())
     }
-}
 
-impl Writer for ElasticSearchWriter {
-    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
-        for payload in data.payloads {
-            self.docs_buffer.push(b"{\"index\": {}}".to_vec());
-            self.docs_buffer.push(payload.into_raw_bytes()?);
+    fn load_changes(&mut self) -> Result<(), ReadError> {
+        let rows = self.client.query(
+            "SELECT data FROM pg_logical_slot_peek_binary_changes($1, NULL, $2, 'proto_version', '1', 'publication_names', $3)",
+            &[&self.slot_name, &PSQL_REPLICATION_PEEK_LIMIT, &self.publication_name],
+        )?;
+
+        let mut transaction_lsn = None;
+        let mut entries_read_within_transaction = 0;
+        for row in rows {
+            let data: Vec<u8> = row.get(0);
+            match PgoutputMessage::parse(&data)? {
+                PgoutputMessage::Begin { final_lsn } => {
+                    transaction_lsn = Some(final_lsn);
+                    entries_read_within_transaction = 0;
+                }
+                PgoutputMessage::Commit { end_lsn, .. } => {
+                    if let Some(final_lsn) = transaction_lsn.take() {
+                        self.add_unconfirmed_transaction(
+                            final_lsn,
+                            end_lsn,
+                            entries_read_within_transaction > 0,
+                        );
+                    }
+                }
+                message => self.queue_message(
+                    message,
+                    transaction_lsn.ok_or(ReadError::MalformedData)?,
+                    &mut entries_read_within_transaction,
+                )?,
+            }
         }
 
-        if let Some(max_batch_size) = self.max_batch_size {
-            if self.docs_buffer.len() / 2 >= max_batch_size {
-                self.flush(true)?;
+        if !self.queued_updates.is_empty() {
+            self.queued_updates.push_front(ReadResult::FinishedSource {
+                commit_allowed: false,
+            });
+            self.queued_updates.push_back(ReadResult::FinishedSource {
+                commit_allowed: true,
+            });
+        }
+        if self.persisted_frontier_receiver.is_none() {
+            if let Some((_, end_lsn, _)) = self.unconfirmed_transactions.back() {
+                self.advance_slot(*end_lsn)?;
             }
+            self.unconfirmed_transactions.clear();
         }
 
         Ok(())
     }
 
-    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
-        if self.docs_buffer.is_empty() {
+    fn add_unconfirmed_transaction(&mut self, final_lsn: Lsn, end_lsn: Lsn, has_changes: bool) {
+        // Until the slot is advanced, the same transactions are returned by each peek
+        let is_new = self
+            .unconfirmed_transactions
+            .back()
+            .map_or(true, |(last_final_lsn, _, _)| *last_final_lsn < final_lsn);
+        if is_new {
+            self.unconfirmed_transactions
+                .push_back((final_lsn, end_lsn, has_changes));
+        }
+    }
+
+    fn advance_slot(&mut self, lsn: Lsn) -> Result<(), ReadError> {
+        if lsn <= self.confirmed_lsn {
             return Ok(());
         }
-        create_async_tokio_runtime()?.block_on(async {
-            self.client
-                .bulk(BulkParts::Index(&self.index_name))
-                .body(take(&mut self.docs_buffer))
-                .send()
-                .await
-                .map_err(WriteError::Elasticsearch)?
-                .error_for_status_code()
-                .map_err(WriteError::Elasticsearch)?;
+        self.client.execute(
+            "SELECT pg_replication_slot_advance($1, $2::text::pg_lsn)",
+            &[&self.slot_name, &format_lsn(lsn)],
+        )?;
+        self.confirmed_lsn = lsn;
+        Ok(())
+    }
 
-            Ok(())
-        })
+    fn advance_slot_to_persisted_frontier(&mut self) -> Result<(), ReadError> {
+        let Some(receiver) = &self.persisted_frontier_receiver else {
+            return Ok(());
+        };
+        for frontier in receiver.try_iter() {
+            if let Some(OffsetValue::PsqlReplicationPosition {
+                transaction_lsn, ..
+            }) = frontier.get_offset(&OffsetKey::Empty)
+            {
+                self.persisted_lsn = Some(*transaction_lsn);
+            }
+        }
+
+        let mut confirmed_end_lsn = None;
+        while let Some(&(final_lsn, end_lsn, has_changes)) = self.unconfirmed_transactions.front() {
+            let is_persisted = self
+                .persisted_lsn
+                .is_some_and(|persisted_lsn| final_lsn <= persisted_lsn);
+            if has_changes && !is_persisted {
+                break;
+            }
+            confirmed_end_lsn = Some(end_lsn);
+            self.unconfirmed_transactions.pop_front();
+        }
+        if let Some(lsn) = confirmed_end_lsn {
+            self.advance_slot(lsn)?;
+        }
+        Ok(())
     }
 
-    fn single_threaded(&self) -> bool {
-        false
+    fn wait_period() -> Duration {
+        Duration::from_millis(500)
     }
 }
 
-#[derive(Default, Debug)]
-pub struct NullWriter;
+impl Reader for PsqlReplicationReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        loop {
+            if let Some(queued_update) = self.queued_updates.pop_front() {
+                return Ok(queued_update);
+            }
 
-impl NullWriter {
-    pub fn new() -> Self {
-        Self
+            self.advance_slot_to_persisted_frontier()?;
+            self.load_changes()?;
+            if self.queued_updates.is_empty() {
+                sleep(Self::wait_period());
+            }
+        }
     }
-}
 
-impl Writer for NullWriter {
-    fn write(&mut self, _data: FormatterContext) -> Result<(), WriteError> {
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        let offset_value = frontier.get_offset(&OffsetKey::Empty);
+        if let Some(offset) = offset_value {
+            if let OffsetValue::PsqlReplicationPosition {
+                transaction_lsn,
+                entries_read_within_transaction,
+            } = offset
+            {
+                self.last_read_position =
+                    Some((*transaction_lsn, *entries_read_within_transaction));
+                self.persisted_lsn = Some(*transaction_lsn);
+                self.advance_slot(*transaction_lsn)?;
+            } else {
+                error!("Unexpected offset type for PostgreSQL replication reader: {offset:?}");
+            }
+        }
         Ok(())
     }
 
-    fn single_threaded(&self) -> bool {
-        false
+    fn set_persisted_frontier_receiver(&mut self, receiver: Receiver<OffsetAntichain>) {
+        self.persisted_frontier_receiver = Some(receiver);
     }
-}
 
-const SQLITE_DATA_VERSION_PRAGMA: &str = "data_version";
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
 
-pub struct SqliteReader {
-    connection: SqliteConnection,
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::PsqlReplication
+    }
+}
+
+/// Reads the changes of a `MySQL` table from the row-based binary log, connecting
+/// to the server as a replica.
+///
+/// The position is tracked as the binlog coordinates of the beginning of the current
+/// transaction and the number of the table changes read within it. If GTIDs are
+/// enabled on the server, the set of the transactions executed before the current one
+/// is tracked as well and the replication is resumed from it, since unlike the binlog
+/// coordinates it doesn't depend on the particular server.
+pub struct MysqlBinlogReader {
+    client: MysqlConnection,
+    connection_options: MysqlOpts,
+    server_id: u32,
+    database_name: String,
     table_name: String,
-    schema: Vec<(String, Type)>,
+    schema: HashMap<String, Type>,
+    session_type: SessionType,
+    persistent_id: Option<PersistentId>,
 
-    last_saved_data_version: Option<i64>,
-    stored_state: HashMap<i64, ValuesMap>,
+    binlog_stream: Option<MysqlBinlogStream>,
+    column_names: Vec<String>,
+    primary_key_indices: Vec<usize>,
+    file_name: ArcStr,
+    transaction_position: u64,
+    executed_gtid_set: Option<GtidSet>,
+    executed_gtid_set_repr: Option<ArcStr>,
+    current_gtid: Option<(String, u64)>,
+    entries_read_within_transaction: u64,
+    entries_to_skip: u64,
+    is_transaction_started: bool,
     queued_updates: VecDeque<ReadResult>,
 }
 
-impl SqliteReader {
+impl MysqlBinlogReader {
     pub fn new(
-        connection: SqliteConnection,
+        connection_options: MysqlOpts,
+        server_id: u32,
+        database_name: String,
         table_name: String,
-        schema: Vec<(String, Type)>,
-    ) -> Self {
-        Self {
-            connection,
+        schema: HashMap<String, Type>,
+        session_type: SessionType,
+        persistent_id: Option<PersistentId>,
+    ) -> Result<MysqlBinlogReader, ReadError> {
+        let mut client = MysqlConnection::new(connection_options.clone())?;
+
+        let (binlog_format, binlog_row_image): (String, String) = client
+            .query_first("SELECT @@GLOBAL.binlog_format, @@GLOBAL.binlog_row_image")?
+            .ok_or(ReadError::MalformedData)?;
+        if !binlog_format.eq_ignore_ascii_case("ROW") {
+            return Err(ReadError::MysqlBinlogSetting(
+                "binlog_format",
+                "ROW",
+                binlog_format,
+            ));
+        }
+        if !binlog_row_image.eq_ignore_ascii_case("FULL") {
+            return Err(ReadError::MysqlBinlogSetting(
+                "binlog_row_image",
+                "FULL",
+                binlog_row_image,
+            ));
+        }
+
+        // The binlog is read starting from the current position of the server
+        let binary_logs: Vec<MysqlRow> = client.query("SHOW BINARY LOGS")?;
+        let (file_name, transaction_position) = binary_logs
+            .last()
+            .and_then(|row| Some((row.get::<String, _>(0)?, row.get::<u64, _>(1)?)))
+            .ok_or(ReadError::MalformedData)?;
+
+        // MariaDB has a different GTID implementation, so it's read by the binlog coordinates
+        let gtid_mode: Option<String> = client
+            .query_first("SELECT @@GLOBAL.gtid_mode")
+            .unwrap_or_default();
+        let executed_gtid_set = if gtid_mode.is_some_and(|mode| mode.eq_ignore_ascii_case("ON")) {
+            let gtid_executed: String = client
+                .query_first("SELECT @@GLOBAL.gtid_executed")?
+                .unwrap_or_default();
+            Some(gtid_executed.parse::<GtidSet>()?)
+        } else {
+            None
+        };
+
+        let mut reader = Self {
+            client,
+            connection_options,
+            server_id,
+            database_name,
             table_name,
             schema,
+            session_type,
+            persistent_id,
 
-            last_saved_data_version: None,
+            binlog_stream: None,
+            column_names: Vec::new(),
+            primary_key_indices: Vec::new(),
+            file_name: file_name.into(),
+            transaction_position,
+            executed_gtid_set_repr: executed_gtid_set
+                .as_ref()
+                .map(|gtid_set| gtid_set.to_string().into()),
+            executed_gtid_set,
+            current_gtid: None,
+            entries_read_within_transaction: 0,
+            entries_to_skip: 0,
+            is_transaction_started: false,
             queued_updates: VecDeque::new(),
-            stored_state: HashMap::new(),
+        };
+        reader.load_columns()?;
+        Ok(reader)
+    }
+
+    /// The binlog doesn't contain the column names unless `binlog_row_metadata`
+    /// is `FULL`, so they are taken from the current table definition.
+    fn load_columns(&mut self) -> Result<(), ReadError> {
+        let columns: Vec<(String, String)> = self.client.exec(
+            "SELECT COLUMN_NAME, COLUMN_KEY FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION",
+            (&self.database_name, &self.table_name),
+        )?;
+        if columns.is_empty() {
+            return Err(ReadError::MysqlTableNotFound(format!(
+                "{}.{}",
+                self.database_name, self.table_name
+            )));
         }
+        self.primary_key_indices = columns
+            .iter()
+            .positions(|(_, column_key)| column_key == "PRI")
+            .collect();
+        self.column_names = columns.into_iter().map(|(name, _)| name).collect();
+        Ok(())
     }
 
-    /// Data version is required to check if there was an update in the database.
-    /// There are also hooks, but they only work for changes happened in the same
-    /// connection.
-    /// More details why hooks don't help here: <https://sqlite.org/forum/forumpost/3174b39eeb79b6a4>
-    pub fn data_version(&self) -> i64 {
-        let version: ::rusqlite::Result<i64> = self.connection.pragma_query_value(
-            Some(::rusqlite::DatabaseName::Main),
-            SQLITE_DATA_VERSION_PRAGMA,
-            |row| row.get(0),
-        );
-        version.expect("pragma.data_version request should not fail")
+    fn start_binlog_stream(&self) -> Result<MysqlBinlogStream, ReadError> {
+        let connection = MysqlConnection::new(self.connection_options.clone())?;
+        let mut request = MysqlBinlogRequest::new(self.server_id)
+            .with_filename(self.file_name.as_bytes().to_vec())
+            .with_pos(self.transaction_position);
+        if let Some(gtid_set) = &self.executed_gtid_set {
+            request = request.with_use_gtid(true).with_sids(gtid_set.to_sids());
+        }
+        Ok(connection.get_binlog_stream(request)?)
     }
 
-    /// Convert raw `SQLite` field into one of internal value types
-    /// There are only five supported types: null, integer, real, text, blob
-    /// See also: <https://www.sqlite.org/datatype3.html>
     fn convert_to_value(
-        value: SqliteValue<'_>,
+        value: Option<&BinlogValue>,
         field_name: &str,
         dtype: &Type,
     ) -> Result<Value, Box<ConversionError>> {
-        let value = match (dtype, value) {
-            (Type::Optional(_) | Type::Any, SqliteValue::Null) => Some(Value::None),
-            (Type::Optional(arg), value) => Self::convert_to_value(value, field_name, arg).ok(),
-            (Type::Int | Type::Any, SqliteValue::Integer(val)) => Some(Value::Int(val)),
-            (Type::Float | Type::Any, SqliteValue::Real(val)) => Some(Value::Float(val.into())),
-            (Type::String | Type::Any, SqliteValue::Text(val)) => from_utf8(val)
-                .ok()
-                .map(|parsed_string| Value::String(parsed_string.into())),
-            (Type::Json, SqliteValue::Text(val)) => from_utf8(val)
-                .ok()
-                .and_then(|parsed_string| {
-                    serde_json::from_str::<serde_json::Value>(parsed_string).ok()
+        value
+            .and_then(|value| parse_binlog_value(value, dtype))
+            .ok_or_else(|| {
+                let value_repr = limit_length(format!("{value:?}"), STANDARD_OBJECT_LENGTH_LIMIT);
+                Box::new(ConversionError {
+                    value_repr,
+                    field_name: field_name.to_owned(),
+                    type_: dtype.clone(),
                 })
-                .map(Value::from),
-            (Type::Bytes | Type::Any, SqliteValue::Blob(val)) => Some(Value::Bytes(val.into())),
-            _ => None,
-        };
-        if let Some(value) = value {
-            Ok(value)
-        } else {
-            let value_repr = limit_length(format!("{value:?}"), STANDARD_OBJECT_LENGTH_LIMIT);
-            Err(Box::new(ConversionError {
-                value_repr,
-                field_name: field_name.to_owned(),
-                type_: dtype.clone(),
-            }))
+            })
+    }
+
+    fn row_to_values(&mut self, row: &BinlogRow) -> Result<ValuesMap, ReadError> {
+        if row.len() != self.column_names.len() {
+            // The table definition has changed since the columns were loaded
+            self.load_columns()?;
+        }
+        let mut values = HashMap::with_capacity(self.schema.len());
+        for (column_idx, column_name) in self.column_names.iter().enumerate() {
+            let Some(dtype) = self.schema.get(column_name) else {
+                continue;
+            };
+            values.insert(
+                column_name.clone(),
+                Self::convert_to_value(row.as_ref(column_idx), column_name, dtype),
+            );
         }
+        Ok(values.into())
     }
 
-    fn load_table(&mut self) -> Result<(), ReadError> {
-        let column_names: Vec<&str> = self
-            .schema
-            .iter()
-            .map(|(name, _dtype)| name.as_str())
-            .collect();
-        let query = format!(
-            "SELECT {},_rowid_ FROM {}",
-            column_names.join(","),
-            self.table_name
-        );
+    fn is_primary_key_changed(&self, old_row: &BinlogRow, new_row: &BinlogRow) -> bool {
+        self.primary_key_indices.is_empty()
+            || self
+                .primary_key_indices
+                .iter()
+                .any(|idx| old_row.as_ref(*idx) != new_row.as_ref(*idx))
+    }
 
-        let mut statement = self.connection.prepare(&query)?;
-        let mut rows = statement.query([])?;
+    fn queue_change(&mut self, event: DataEventType, values: ValuesMap, offset: &Offset) {
+        let event = match (self.session_type, event) {
+            (SessionType::Upsert, DataEventType::Insert) => DataEventType::Upsert,
+            (_, event) => event,
+        };
+        self.queued_updates.push_back(ReadResult::Data(
+            ReaderContext::from_diff(event, None, values),
+            offset.clone(),
+        ));
+    }
 
-        let mut present_rowids = HashSet::new();
-        while let Some(row) = rows.next()? {
-            let rowid: i64 = row.get(self.schema.len())?;
-            let mut values = HashMap::with_capacity(self.schema.len());
-            for (column_idx, (column_name, column_dtype)) in self.schema.iter().enumerate() {
-                let value =
-                    Self::convert_to_value(row.get_ref(column_idx)?, column_name, column_dtype);
-                values.insert(column_name.clone(), value);
-            }
-            let values: ValuesMap = values.into();
-            self.stored_state
-                .entry(rowid)
-                .and_modify(|current_values| {
-                    if current_values != &values {
-                        let key = vec![Value::Int(rowid)];
-                        self.queued_updates.push_back(ReadResult::Data(
-                            ReaderContext::from_diff(
-                                DataEventType::Delete,
-                                Some(key.clone()),
-                                take(current_values),
-                            ),
-                            EMPTY_OFFSET,
-                        ));
-                        self.queued_updates.push_back(ReadResult::Data(
-                            ReaderContext::from_diff(
-                                DataEventType::Insert,
-                                Some(key),
-                                values.clone(),
-                            ),
-                            EMPTY_OFFSET,
-                        ));
-                        current_values.clone_from(&values);
-                    }
-                })
-                .or_insert_with(|| {
-                    let key = vec![Value::Int(rowid)];
-                    self.queued_updates.push_back(ReadResult::Data(
-                        ReaderContext::from_diff(DataEventType::Insert, Some(key), values.clone()),
-                        EMPTY_OFFSET,
-                    ));
-                    values
-                });
-            present_rowids.insert(rowid);
+    fn queue_row(
+        &mut self,
+        old_row: Option<BinlogRow>,
+        new_row: Option<BinlogRow>,
+    ) -> Result<(), ReadError> {
+        self.entries_read_within_transaction += 1;
+        if self.entries_read_within_transaction <= self.entries_to_skip {
+            return Ok(());
+        }
+        if !self.is_transaction_started {
+            self.is_transaction_started = true;
+            self.queued_updates.push_back(ReadResult::FinishedSource {
+                commit_allowed: false,
+            });
         }
 
-        self.stored_state.retain(|rowid, values| {
-            if present_rowids.contains(rowid) {
-                true
-            } else {
-                let key = vec![Value::Int(*rowid)];
-                self.queued_updates.push_back(ReadResult::Data(
-                    ReaderContext::from_diff(DataEventType::Delete, Some(key), take(values)),
-                    EMPTY_OFFSET,
-                ));
-                false
+        let offset = (
+            OffsetKey::Empty,
+            OffsetValue::MysqlBinlogPosition {
+                file_name: self.file_name.clone(),
+                transaction_position: self.transaction_position,
+                gtid_set: self.executed_gtid_set_repr.clone(),
+                entries_read_within_transaction: self.entries_read_within_transaction,
+            },
+        );
+        let is_upsert_possible = match (&old_row, &new_row) {
+            (Some(old_row), Some(new_row)) => {
+                matches!(self.session_type, SessionType::Upsert)
+                    && !self.is_primary_key_changed(old_row, new_row)
             }
-        });
+            _ => false,
+        };
+        if let Some(old_row) = old_row.filter(|_| !is_upsert_possible) {
+            let values = self.row_to_values(&old_row)?;
+            self.queue_change(DataEventType::Delete, values, &offset);
+        }
+        if let Some(new_row) = new_row {
+            let values = self.row_to_values(&new_row)?;
+            self.queue_change(DataEventType::Insert, values, &offset);
+        }
+        Ok(())
+    }
 
-        if !self.queued_updates.is_empty() {
+    /// Transactions end with `XID_EVENT` or with a `COMMIT` query in case of
+    /// non-transactional storage engines. DDL statements are committed implicitly.
+    fn on_transaction_end(&mut self, next_event_position: u64) {
+        if let (Some(gtid_set), Some((source_id, transaction_id))) =
+            (&mut self.executed_gtid_set, self.current_gtid.take())
+        {
+            gtid_set.add(&source_id, transaction_id);
+            self.executed_gtid_set_repr = Some(gtid_set.to_string().into());
+        }
+        if next_event_position != 0 {
+            self.transaction_position = next_event_position;
+        }
+        self.entries_read_within_transaction = 0;
+        self.entries_to_skip = 0;
+        if self.is_transaction_started {
+            self.is_transaction_started = false;
             self.queued_updates.push_back(ReadResult::FinishedSource {
                 commit_allowed: true,
             });
         }
+    }
 
+    fn handle_event(&mut self, event: &MysqlBinlogEvent) -> Result<(), ReadError> {
+        let next_event_position = u64::from(event.header().log_pos());
+        let Some(event_data) = event.read_data()? else {
+            return Ok(());
+        };
+        match event_data {
+            MysqlBinlogEventData::RotateEvent(rotate_event) => {
+                self.file_name = rotate_event.name().as_ref().into();
+                self.transaction_position = rotate_event.position();
+            }
+            MysqlBinlogEventData::GtidEvent(gtid_event) => {
+                self.current_gtid = Some((format_source_id(gtid_event.sid()), gtid_event.gno()));
+            }
+            MysqlBinlogEventData::RowsEvent(rows_event) => {
+                let table_map_event = self
+                    .binlog_stream
+                    .as_ref()
+                    .and_then(|stream| stream.get_tme(rows_event.table_id()))
+                    .filter(|table_map_event| {
+                        table_map_event.database_name() == self.database_name
+                            && table_map_event.table_name() == self.table_name
+                    })
+                    .cloned();
+                if let Some(table_map_event) = table_map_event {
+                    for row in rows_event.rows(&table_map_event) {
+                        let (old_row, new_row) = row?;
+                        self.queue_row(old_row, new_row)?;
+                    }
+                }
+            }
+            MysqlBinlogEventData::XidEvent(_) => self.on_transaction_end(next_event_position),
+            MysqlBinlogEventData::QueryEvent(query_event) => {
+                if query_event.query() != "BEGIN" {
+                    self.on_transaction_end(next_event_position);
+                }
+            }
+            _ => {}
+        }
         Ok(())
     }
 
-    fn wait_period() -> Duration {
-        Duration::from_millis(500)
+    fn position_order_key(offset: &OffsetValue) -> Option<(u64, u64, u64, u64)> {
+        let OffsetValue::MysqlBinlogPosition {
+            file_name,
+            transaction_position,
+            gtid_set,
+            entries_read_within_transaction,
+        } = offset
+        else {
+            return None;
+        };
+        let transactions_count = gtid_set
+            .as_ref()
+            .and_then(|gtid_set| gtid_set.parse::<GtidSet>().ok())
+            .map_or(0, |gtid_set| gtid_set.transactions_count());
+        Some((
+            transactions_count,
+            binlog_file_index(file_name).unwrap_or_default(),
+            *transaction_position,
+            *entries_read_within_transaction,
+        ))
     }
 }
 
-impl Reader for SqliteReader {
-    fn seek(&mut self, _frontier: &OffsetAntichain) -> Result<(), ReadError> {
-        todo!("seek is not supported for Sqlite source: persistent history of changes unavailable")
-    }
-
+impl Reader for MysqlBinlogReader {
     fn read(&mut self) -> Result<ReadResult, ReadError> {
         loop {
             if let Some(queued_update) = self.queued_updates.pop_front() {
                 return Ok(queued_update);
             }
 
-            let current_data_version = self.data_version();
-            if self.last_saved_data_version != Some(current_data_version) {
-                self.load_table()?;
-                self.last_saved_data_version = Some(current_data_version);
-                return Ok(ReadResult::NewSource(
-                    SQLiteMetadata::new(current_data_version).into(),
-                ));
+            if self.binlog_stream.is_none() {
+                self.binlog_stream = Some(self.start_binlog_stream()?);
+            }
+            let event = self
+                .binlog_stream
+                .as_mut()
+                .expect("binlog stream must be started")
+                .next()
+                .ok_or(ReadError::MysqlBinlogStreamClosed)??;
+            self.handle_event(&event)?;
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        let offset_value = frontier.get_offset(&OffsetKey::Empty);
+        if let Some(offset) = offset_value {
+            if let OffsetValue::MysqlBinlogPosition {
+                file_name,
+                transaction_position,
+                gtid_set,
+                entries_read_within_transaction,
+            } = offset
+            {
+                self.file_name = file_name.clone();
+                self.transaction_position = *transaction_position;
+                self.executed_gtid_set = gtid_set
+                    .as_ref()
+                    .map(|gtid_set| gtid_set.parse::<GtidSet>())
+                    .transpose()?;
+                self.executed_gtid_set_repr.clone_from(gtid_set);
+                self.current_gtid = None;
+                self.entries_read_within_transaction = 0;
+                self.entries_to_skip = *entries_read_within_transaction;
+                self.binlog_stream = None;
+            } else {
+                error!("Unexpected offset type for MySQL binlog reader: {offset:?}");
+            }
+        }
+        Ok(())
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::MysqlBinlog
+    }
+}
+
+pub const KAFKA_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Defines how the partition of a produced Kafka message is chosen.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KafkaPartitioner {
+    /// The partition is chosen by librdkafka according to its `partitioner` setting.
+    Default,
+
+    /// The partition is the murmur2 hash of the message key modulo the number of
+    /// partitions, which is the same as in the default partitioner of the Java client.
+    KeyHash,
+
+    /// The partition is taken from the integer column with the given index.
+    Column(usize),
+}
+
+/// The hash used by the default partitioner of the Java Kafka client.
+#[allow(clippy::cast_possible_wrap)]
+pub fn kafka_murmur2(data: &[u8]) -> i32 {
+    const SEED: u32 = 0x9747_b28c;
+    const M: u32 = 0x5bd1_e995;
+    const R: u32 = 24;
+
+    let length = u32::try_from(data.len()).expect("message key is too long");
+    let mut h = SEED ^ length;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes(chunk.try_into().unwrap());
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M);
+        h ^= k;
+    }
+    let tail = chunks.remainder();
+    if tail.len() >= 3 {
+        h ^= u32::from(tail[2]) << 16;
+    }
+    if tail.len() >= 2 {
+        h ^= u32::from(tail[1]) << 8;
+    }
+    if !tail.is_empty() {
+        h ^= u32::from(tail[0]);
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h as i32
+}
+
+/// Returns the partition for the given key in the same way as the Java client does.
+pub fn kafka_key_hash_partition(key: &[u8], partition_count: i32) -> i32 {
+    (kafka_murmur2(key) & 0x7fff_ffff) % partition_count
+}
+
+struct KafkaTombstone {
+    headers: KafkaHeaders,
+    partition: Option<i32>,
+}
+
+/// A message of the transactional mode, which waits until the open transaction
+/// is committed.
+struct KafkaUncommittedMessage {
+    time: Timestamp,
+    key: Vec<u8>,
+    payload: Option<Vec<u8>>,
+    headers: KafkaHeaders,
+    partition: Option<i32>,
+}
+
+pub struct KafkaWriter {
+    producer: ThreadedProducer<DefaultProducerContext>,
+    topic: String,
+    header_fields: Vec<(String, usize)>,
+    key_fields: Vec<(String, usize)>,
+    partitioner: KafkaPartitioner,
+    produce_tombstones: bool,
+    transactional: bool,
+
+    // The greatest time of the messages in the open transaction, if there is one
+    transaction_time: Option<Timestamp>,
+    // Whether the open transaction waits for its time to be finalized, so that
+    // the new messages are kept until it's committed
+    is_transaction_sealed: bool,
+    uncommitted_messages: VecDeque<KafkaUncommittedMessage>,
+    partition_count: Option<i32>,
+    current_time: Option<Timestamp>,
+    upserted_keys: HashSet<Vec<u8>>,
+    pending_tombstones: HashMap<Vec<u8>, KafkaTombstone>,
+}
+
+impl KafkaWriter {
+    pub fn new(
+        producer: ThreadedProducer<DefaultProducerContext>,
+        topic: String,
+        header_fields: Vec<(String, usize)>,
+        key_fields: Vec<(String, usize)>,
+        partitioner: KafkaPartitioner,
+        produce_tombstones: bool,
+        transactional: bool,
+    ) -> KafkaWriter {
+        KafkaWriter {
+            producer,
+            topic,
+            header_fields,
+            key_fields,
+            partitioner,
+            produce_tombstones,
+            transactional,
+            transaction_time: None,
+            is_transaction_sealed: false,
+            uncommitted_messages: VecDeque::new(),
+            partition_count: None,
+            current_time: None,
+            upserted_keys: HashSet::new(),
+            pending_tombstones: HashMap::new(),
+        }
+    }
+
+    fn partition(&mut self, key: &[u8], values: &[Value]) -> Result<Option<i32>, WriteError> {
+        match self.partitioner {
+            KafkaPartitioner::Default => Ok(None),
+            KafkaPartitioner::KeyHash => {
+                let partition_count = if let Some(partition_count) = self.partition_count {
+                    partition_count
+                } else {
+                    let partition_count = self.fetch_partition_count()?;
+                    self.partition_count = Some(partition_count);
+                    partition_count
+                };
+                Ok(Some(kafka_key_hash_partition(key, partition_count)))
+            }
+            KafkaPartitioner::Column(index) => match &values[index] {
+                Value::Int(partition) => Ok(Some(
+                    (*partition)
+                        .try_into()
+                        .map_err(|_| WriteError::IntOutOfRange(*partition))?,
+                )),
+                other => Err(WriteError::IncorrectPartitionFieldType(other.clone())),
+            },
+        }
+    }
+
+    fn fetch_partition_count(&self) -> Result<i32, WriteError> {
+        let metadata = self
+            .producer
+            .client()
+            .fetch_metadata(Some(&self.topic), Duration::from_secs(30))?;
+        let partition_count = metadata
+            .topics()
+            .iter()
+            .find(|topic| topic.name() == self.topic)
+            .map_or(0, |topic| topic.partitions().len());
+        if partition_count == 0 {
+            return Err(WriteError::KafkaTopicWithoutPartitions(self.topic.clone()));
+        }
+        Ok(partition_count
+            .try_into()
+            .expect("partition count must fit into i32"))
+    }
+
+    /// In the transactional mode, the messages are produced into the open transaction,
+    /// which is committed once their times are finalized, so that a transaction never
+    /// contains the output that is produced again after a restart. While the open
+    /// transaction waits for that, the new messages are kept, so that the transaction
+    /// doesn't grow and is eventually committed.
+    fn send_or_keep(
+        &mut self,
+        time: Timestamp,
+        key: Vec<u8>,
+        payload: Option<Vec<u8>>,
+        headers: KafkaHeaders,
+        partition: Option<i32>,
+    ) -> Result<(), WriteError> {
+        if !self.transactional {
+            return self.send(&key, payload.as_deref(), headers, partition);
+        }
+        if self.is_transaction_sealed {
+            self.uncommitted_messages
+                .push_back(KafkaUncommittedMessage {
+                    time,
+                    key,
+                    payload,
+                    headers,
+                    partition,
+                });
+            return Ok(());
+        }
+        self.send_in_transaction(time, &key, payload.as_deref(), headers, partition)
+    }
+
+    fn send_in_transaction(
+        &mut self,
+        time: Timestamp,
+        key: &[u8],
+        payload: Option<&[u8]>,
+        headers: KafkaHeaders,
+        partition: Option<i32>,
+    ) -> Result<(), WriteError> {
+        if self.transaction_time.is_none() {
+            self.producer.begin_transaction()?;
+        }
+        self.send(key, payload, headers, partition)?;
+        self.transaction_time = self.transaction_time.max(Some(time));
+        Ok(())
+    }
+
+    /// Commits the open transaction, if the times of all its messages are before
+    /// `finalized_time`, and produces the kept messages into the next one.
+    fn commit_finalized_messages(
+        &mut self,
+        finalized_time: TotalFrontier<Timestamp>,
+    ) -> Result<(), WriteError> {
+        loop {
+            if let Some(transaction_time) = self.transaction_time {
+                if !finalized_time.is_time_done(&transaction_time) {
+                    self.is_transaction_sealed = true;
+                    return Ok(());
+                }
+                self.transaction_time = None;
+                self.is_transaction_sealed = false;
+                if let Err(e) = self.producer.commit_transaction(KAFKA_TRANSACTION_TIMEOUT) {
+                    // The finalized time isn't saved, so these messages are produced again after a restart
+                    if let Err(abort_error) =
+                        self.producer.abort_transaction(KAFKA_TRANSACTION_TIMEOUT)
+                    {
+                        error!("Failed to abort Kafka transaction: {abort_error}");
+                    }
+                    return Err(WriteError::Kafka(e));
+                }
+            }
+            if self.uncommitted_messages.is_empty() {
+                return Ok(());
+            }
+            while let Some(message) = self.uncommitted_messages.pop_front() {
+                self.send_in_transaction(
+                    message.time,
+                    &message.key,
+                    message.payload.as_deref(),
+                    message.headers,
+                    message.partition,
+                )?;
+            }
+        }
+    }
+
+    fn send(
+        &self,
+        key: &[u8],
+        payload: Option<&[u8]>,
+        headers: KafkaHeaders,
+        partition: Option<i32>,
+    ) -> Result<(), WriteError> {
+        let mut entry = BaseRecord::<[u8], [u8]>::to(&self.topic)
+            .headers(headers)
+            .key(key);
+        if let Some(payload) = payload {
+            entry = entry.payload(payload);
+        }
+        if let Some(partition) = partition {
+            entry = entry.partition(partition);
+        }
+        loop {
+            match self.producer.send(entry) {
+                Ok(()) => break,
+                Err((KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull), unsent_entry)) => {
+                    self.producer.poll(Duration::from_millis(10));
+                    entry = unsent_entry;
+                    continue;
+                }
+                Err((e, _unsent_entry)) => return Err(WriteError::Kafka(e)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the tombstones for the keys that were removed, but not reinserted
+    /// within the same time.
+    fn send_pending_tombstones(&mut self) -> Result<(), WriteError> {
+        let Some(time) = self.current_time else {
+            return Ok(());
+        };
+        for (key, tombstone) in take(&mut self.pending_tombstones) {
+            self.send_or_keep(time, key, None, tombstone.headers, tombstone.partition)?;
+        }
+        self.upserted_keys.clear();
+        Ok(())
+    }
+}
+
+impl Drop for KafkaWriter {
+    fn drop(&mut self) {
+        self.producer.flush(None).expect("kafka commit should work");
+    }
+}
+
+impl Writer for KafkaWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        if self.produce_tombstones && self.current_time != Some(data.time) {
+            self.send_pending_tombstones()?;
+            self.current_time = Some(data.time);
+        }
+
+        let key = data.construct_message_key(&self.key_fields)?;
+        let partition = self.partition(&key, &data.values)?;
+        let headers = data.construct_kafka_headers(&self.header_fields);
+
+        if self.produce_tombstones {
+            if data.diff < 0 {
+                // The key may still be reinserted within the same time
+                if !self.upserted_keys.contains(&key) {
+                    self.pending_tombstones
+                        .insert(key, KafkaTombstone { headers, partition });
+                }
+                return Ok(());
+            }
+            self.pending_tombstones.remove(&key);
+            self.upserted_keys.insert(key.clone());
+        }
+
+        for payload in data.payloads {
+            let payload = payload.into_raw_bytes()?;
+            self.send_or_keep(
+                data.time,
+                key.clone(),
+                Some(payload),
+                headers.clone(),
+                partition,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
+        if self.produce_tombstones {
+            self.send_pending_tombstones()?;
+        }
+        Ok(())
+    }
+
+    fn on_time_finalized(
+        &mut self,
+        finalized_time: TotalFrontier<Timestamp>,
+    ) -> Result<(), WriteError> {
+        self.commit_finalized_messages(finalized_time)
+    }
+
+    fn retriable(&self) -> bool {
+        true
+    }
+
+    fn single_threaded(&self) -> bool {
+        false
+    }
+}
+
+pub struct ElasticSearchWriter {
+    client: Elasticsearch,
+    index_name: String,
+    max_batch_size: Option<usize>,
+
+    docs_buffer: Vec<Vec<u8>>,
+}
+
+impl ElasticSearchWriter {
+    pub fn new(client: Elasticsearch, index_name: String, max_batch_size: Option<usize>) -> Self {
+        ElasticSearchWriter {
+            client,
+            index_name,
+            max_batch_size,
+            docs_buffer: Vec::new(),
+        }
+    }
+}
+
+impl Writer for ElasticSearchWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        for payload in data.payloads {
+            self.docs_buffer.push(b"{\"index\": {}}".to_vec());
+            self.docs_buffer.push(payload.into_raw_bytes()?);
+        }
+
+        if let Some(max_batch_size) = self.max_batch_size {
+            if self.docs_buffer.len() / 2 >= max_batch_size {
+                self.flush(true)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
+        if self.docs_buffer.is_empty() {
+            return Ok(());
+        }
+        create_async_tokio_runtime()?.block_on(async {
+            self.client
+                .bulk(BulkParts::Index(&self.index_name))
+                .body(take(&mut self.docs_buffer))
+                .send()
+                .await
+                .map_err(WriteError::Elasticsearch)?
+                .error_for_status_code()
+                .map_err(WriteError::Elasticsearch)?;
+
+            Ok(())
+        })
+    }
+
+    fn single_threaded(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct NullWriter;
+
+impl NullWriter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Writer for NullWriter {
+    fn write(&mut self, _data: FormatterContext) -> Result<(), WriteError> {
+        Ok(())
+    }
+
+    fn single_threaded(&self) -> bool {
+        false
+    }
+}
+
+const SQLITE_DATA_VERSION_PRAGMA: &str = "data_version";
+
+pub struct SqliteReader {
+    connection: SqliteConnection,
+    table_name: String,
+    schema: Vec<(String, Type)>,
+
+    last_saved_data_version: Option<i64>,
+    stored_state: HashMap<i64, ValuesMap>,
+    queued_updates: VecDeque<ReadResult>,
+}
+
+impl SqliteReader {
+    pub fn new(
+        connection: SqliteConnection,
+        table_name: String,
+        schema: Vec<(String, Type)>,
+    ) -> Self {
+        Self {
+            connection,
+            table_name,
+            schema,
+
+            last_saved_data_version: None,
+            queued_updates: VecDeque::new(),
+            stored_state: HashMap::new(),
+        }
+    }
+
+    /// Data version is required to check if there was an update in the database.
+    /// There are also hooks, but they only work for changes happened in the same
+    /// connection.
+    /// More details why hooks don't help here: <https://sqlite.org/forum/forumpost/3174b39eeb79b6a4>
+    pub fn data_version(&self) -> i64 {
+        let version: ::rusqlite::Result<i64> = self.connection.pragma_query_value(
+            Some(::rusqlite::DatabaseName::Main),
+            SQLITE_DATA_VERSION_PRAGMA,
+            |row| row.get(0),
+        );
+        version.expect("pragma.data_version request should not fail")
+    }
+
+    /// Convert raw `SQLite` field into one of internal value types
+    /// There are only five supported types: null, integer, real, text, blob
+    /// See also: <https://www.sqlite.org/datatype3.html>
+    fn convert_to_value(
+        value: SqliteValue<'_>,
+        field_name: &str,
+        dtype: &Type,
+    ) -> Result<Value, Box<ConversionError>> {
+        let value = match (dtype, value) {
+            (Type::Optional(_) | Type::Any, SqliteValue::Null) => Some(Value::None),
+            (Type::Optional(arg), value) => Self::convert_to_value(value, field_name, arg).ok(),
+            (Type::Int | Type::Any, SqliteValue::Integer(val)) => Some(Value::Int(val)),
+            (Type::Float | Type::Any, SqliteValue::Real(val)) => Some(Value::Float(val.into())),
+            (Type::String | Type::Any, SqliteValue::Text(val)) => from_utf8(val)
+                .ok()
+                .map(|parsed_string| Value::String(parsed_string.into())),
+            (Type::Json, SqliteValue::Text(val)) => from_utf8(val)
+                .ok()
+                .and_then(|parsed_string| {
+                    serde_json::from_str::<serde_json::Value>(parsed_string).ok()
+                })
+                .map(Value::from),
+            (Type::Bytes | Type::Any, SqliteValue::Blob(val)) => Some(Value::Bytes(val.into())),
+            _ => None,
+        };
+        if let Some(value) = value {
+            Ok(value)
+        } else {
+            let value_repr = limit_length(format!("{value:?}"), STANDARD_OBJECT_LENGTH_LIMIT);
+            Err(Box::new(ConversionError {
+                value_repr,
+                field_name: field_name.to_owned(),
+                type_: dtype.clone(),
+            }))
+        }
+    }
+
+    fn load_table(&mut self) -> Result<(), ReadError> {
+        let column_names: Vec<&str> = self
+            .schema
+            .iter()
+            .map(|(name, _dtype)| name.as_str())
+            .collect();
+        let query = format!(
+            "SELECT {},_rowid_ FROM {}",
+            column_names.join(","),
+            self.table_name
+        );
+
+        let mut statement = self.connection.prepare(&query)?;
+        let mut rows = statement.query([])?;
+
+        let mut present_rowids = HashSet::new();
+        while let Some(row) = rows.next()? {
+            let rowid: i64 = row.get(self.schema.len())?;
+            let mut values = HashMap::with_capacity(self.schema.len());
+            for (column_idx, (column_name, column_dtype)) in self.schema.iter().enumerate() {
+                let value =
+                    Self::convert_to_value(row.get_ref(column_idx)?, column_name, column_dtype);
+                values.insert(column_name.clone(), value);
+            }
+            let values: ValuesMap = values.into();
+            self.stored_state
+                .entry(rowid)
+                .and_modify(|current_values| {
+                    if current_values != &values {
+                        let key = vec![Value::Int(rowid)];
+                        self.queued_updates.push_back(ReadResult::Data(
+                            ReaderContext::from_diff(
+                                DataEventType::Delete,
+                                Some(key.clone()),
+                                take(current_values),
+                            ),
+                            EMPTY_OFFSET,
+                        ));
+                        self.queued_updates.push_back(ReadResult::Data(
+                            ReaderContext::from_diff(
+                                DataEventType::Insert,
+                                Some(key),
+                                values.clone(),
+                            ),
+                            EMPTY_OFFSET,
+                        ));
+                        current_values.clone_from(&values);
+                    }
+                })
+                .or_insert_with(|| {
+                    let key = vec![Value::Int(rowid)];
+                    self.queued_updates.push_back(ReadResult::Data(
+                        ReaderContext::from_diff(DataEventType::Insert, Some(key), values.clone()),
+                        EMPTY_OFFSET,
+                    ));
+                    values
+                });
+            present_rowids.insert(rowid);
+        }
+
+        self.stored_state.retain(|rowid, values| {
+            if present_rowids.contains(rowid) {
+                true
+            } else {
+                let key = vec![Value::Int(*rowid)];
+                self.queued_updates.push_back(ReadResult::Data(
+                    ReaderContext::from_diff(DataEventType::Delete, Some(key), take(values)),
+                    EMPTY_OFFSET,
+                ));
+                false
+            }
+        });
+
+        if !self.queued_updates.is_empty() {
+            self.queued_updates.push_back(ReadResult::FinishedSource {
+                commit_allowed: true,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn wait_period() -> Duration {
+        Duration::from_millis(500)
+    }
+}
+
+impl Reader for SqliteReader {
+    fn seek(&mut self, _frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        todo!("seek is not supported for Sqlite source: persistent history of changes unavailable")
+    }
+
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        loop {
+            if let Some(queued_update) = self.queued_updates.pop_front() {
+                return Ok(queued_update);
+            }
+
+            let current_data_version = self.data_version();
+            if self.last_saved_data_version != Some(current_data_version) {
+                self.load_table()?;
+                self.last_saved_data_version = Some(current_data_version);
+                return Ok(ReadResult::NewSource(
+                    SQLiteMetadata::new(current_data_version).into(),
+                ));
+            }
+            // Sleep to avoid non-stop pragma requests of a table
+            // that did not change
+            sleep(Self::wait_period());
+        }
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::Sqlite
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        None
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        if persistent_id.is_some() {
+            unimplemented!("persistence is not supported for Sqlite data source")
+        }
+    }
+}
+
+pub struct MongoWriter {
+    collection: MongoCollection<BsonDocument>,
+    buffer: Vec<BsonDocument>,
+    max_batch_size: Option<usize>,
+}
+
+impl MongoWriter {
+    pub fn new(collection: MongoCollection<BsonDocument>, max_batch_size: Option<usize>) -> Self {
+        Self {
+            collection,
+            max_batch_size,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl Writer for MongoWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        for payload in data.payloads {
+            self.buffer.push(payload.into_bson_document()?);
+        }
+        if let Some(max_batch_size) = self.max_batch_size {
+            if self.buffer.len() >= max_batch_size {
+                self.flush(true)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let command = self.collection.insert_many(take(&mut self.buffer));
+        let _ = command.run()?;
+        Ok(())
+    }
+}
+
+/// Reads a `MongoDB` collection: first the snapshot of its current state and
+/// then the changes from the collection's change stream.
+///
+/// The change stream is opened before the snapshot is taken, so that the changes
+/// done while the snapshot is being read aren't lost. Since the events are keyed by
+/// the document `_id`, reading such changes again doesn't change the result.
+pub struct MongoReader {
+    collection: MongoCollection<BsonDocument>,
+    schema: HashMap<String, Type>,
+    mode: ConnectorMode,
+    persistent_id: Option<PersistentId>,
+
+    is_snapshot_required: bool,
+    is_started: bool,
+    resume_token: Option<MongoResumeToken>,
+    snapshot_cursor: Option<MongoCursor<BsonDocument>>,
+    change_stream: Option<MongoChangeStream<MongoChangeStreamEvent<BsonDocument>>>,
+}
+
+impl MongoReader {
+    pub fn new(
+        collection: MongoCollection<BsonDocument>,
+        schema: HashMap<String, Type>,
+        mode: ConnectorMode,
+        persistent_id: Option<PersistentId>,
+    ) -> Self {
+        Self {
+            collection,
+            schema,
+            mode,
+            persistent_id,
+
+            is_snapshot_required: true,
+            is_started: false,
+            resume_token: None,
+            snapshot_cursor: None,
+            change_stream: None,
+        }
+    }
+
+    fn start(&mut self) -> Result<(), ReadError> {
+        if self.mode.is_polling_enabled() {
+            let change_stream = self
+                .collection
+                .watch()
+                .full_document(FullDocumentType::UpdateLookup)
+                .resume_after(self.resume_token.clone())
+                .max_await_time(Self::max_await_time())
+                .run()?;
+            self.resume_token = change_stream.resume_token();
+            self.change_stream = Some(change_stream);
+        }
+        if self.is_snapshot_required {
+            self.snapshot_cursor = Some(self.collection.find(BsonDocument::new()).run()?);
+        }
+        self.is_started = true;
+        Ok(())
+    }
+
+    /// The time for which the server holds a request for the new changes if
+    /// there are none, so that the stream isn't polled in a busy loop.
+    fn max_await_time() -> Duration {
+        Duration::from_millis(500)
+    }
+
+    /// The resume tokens are ordered by their `_data` field, which is the
+    /// hex-encoded position of the event in the oplog.
+    fn resume_token_order_key(serialized_token: &[u8]) -> Option<String> {
+        let token: BsonDocument = mongodb::bson::from_slice(serialized_token).ok()?;
+        token.get_str("_data").ok().map(ToOwned::to_owned)
+    }
+
+    fn document_key(document: &BsonDocument) -> Option<Vec<Value>> {
+        let id = document.get("_id")?;
+        parse_value_from_bson(id, &Type::Any).map(|id| vec![id])
+    }
+
+    fn convert_document(&self, document: &BsonDocument) -> ValuesMap {
+        let mut values = HashMap::with_capacity(self.schema.len());
+        for (field_name, dtype) in &self.schema {
+            let Some(value) = document.get(field_name) else {
+                continue;
+            };
+            let converted = parse_value_from_bson(value, dtype).ok_or_else(|| {
+                let value_repr = limit_length(format!("{value:?}"), STANDARD_OBJECT_LENGTH_LIMIT);
+                Box::new(ConversionError {
+                    value_repr,
+                    field_name: field_name.clone(),
+                    type_: dtype.clone(),
+                })
+            });
+            values.insert(field_name.clone(), converted);
+        }
+        values.into()
+    }
+
+    fn current_offset(&self) -> Result<Offset, ReadError> {
+        let Some(resume_token) = &self.resume_token else {
+            return Ok(EMPTY_OFFSET);
+        };
+        let serialized_token = mongodb::bson::to_vec(resume_token)?;
+        Ok((
+            OffsetKey::Empty,
+            OffsetValue::MongoDbResumeToken(serialized_token.into()),
+        ))
+    }
+
+    fn read_snapshot_entry(&mut self) -> Result<Option<ReadResult>, ReadError> {
+        let Some(snapshot_cursor) = &mut self.snapshot_cursor else {
+            return Ok(None);
+        };
+        if let Some(document) = snapshot_cursor.next() {
+            let document = document?;
+            let context = ReaderContext::from_diff(
+                DataEventType::Upsert,
+                Self::document_key(&document),
+                self.convert_document(&document),
+            );
+            Ok(Some(ReadResult::Data(context, self.current_offset()?)))
+        } else {
+            self.snapshot_cursor = None;
+            self.is_snapshot_required = false;
+            Ok(Some(ReadResult::FinishedSource {
+                commit_allowed: true,
+            }))
+        }
+    }
+
+    fn read_change_stream_entry(&mut self) -> Result<Option<ReadResult>, ReadError> {
+        let Some(change_stream) = &mut self.change_stream else {
+            return Ok(Some(ReadResult::Finished));
+        };
+        let Some(event) = change_stream.next_if_any()? else {
+            if change_stream.is_alive() {
+                return Ok(None);
+            }
+            info!("MongoDB change stream is closed");
+            return Ok(Some(ReadResult::Finished));
+        };
+        self.resume_token = Some(event.id);
+
+        let (data_event, document) = match event.operation_type {
+            MongoOperationType::Insert
+            | MongoOperationType::Update
+            | MongoOperationType::Replace => {
+                // The document may be already deleted when the full version is looked up.
+                // In this case the deletion event follows.
+                let Some(document) = event.full_document else {
+                    return Ok(None);
+                };
+                (DataEventType::Upsert, document)
+            }
+            MongoOperationType::Delete => {
+                let Some(document_key) = event.document_key else {
+                    return Ok(None);
+                };
+                (DataEventType::Delete, document_key)
+            }
+            MongoOperationType::Drop
+            | MongoOperationType::Rename
+            | MongoOperationType::DropDatabase
+            | MongoOperationType::Invalidate => {
+                warn!(
+                    "The collection is no longer available for tracking: {:?} event received",
+                    event.operation_type
+                );
+                return Ok(None);
+            }
+            _ => return Ok(None),
+        };
+
+        let values = if data_event == DataEventType::Delete {
+            ValuesMap::default()
+        } else {
+            self.convert_document(&document)
+        };
+        let context = ReaderContext::from_diff(data_event, Self::document_key(&document), values);
+        Ok(Some(ReadResult::Data(context, self.current_offset()?)))
+    }
+}
+
+impl Reader for MongoReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        if !self.is_started {
+            self.start()?;
+            if self.snapshot_cursor.is_some() {
+                return Ok(ReadResult::FinishedSource {
+                    commit_allowed: false,
+                });
+            }
+        }
+        if let Some(entry) = self.read_snapshot_entry()? {
+            return Ok(entry);
+        }
+        loop {
+            if let Some(entry) = self.read_change_stream_entry()? {
+                return Ok(entry);
+            }
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        if !self.mode.is_polling_enabled() {
+            // Without the change stream, the snapshot is the only source of data
+            return Ok(());
+        }
+        let offset_value = frontier.get_offset(&OffsetKey::Empty);
+        if let Some(offset) = offset_value {
+            if let OffsetValue::MongoDbResumeToken(serialized_token) = offset {
+                self.resume_token = Some(mongodb::bson::from_slice(serialized_token)?);
+                self.is_snapshot_required = false;
+            } else {
+                error!("Unexpected offset type for MongoDB reader: {offset:?}");
+            }
+        }
+        Ok(())
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::MongoDB
+    }
+}
+
+pub struct NatsReader {
+    runtime: TokioRuntime,
+    subscriber: NatsSubscriber,
+    worker_index: usize,
+    persistent_id: Option<PersistentId>,
+    total_entries_read: usize,
+}
+
+impl Reader for NatsReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        if let Some(message) = self.runtime.block_on(async {
+            self.subscriber
+                .next()
+                .await
+                .map(|message| message.payload.to_vec())
+        }) {
+            let payload = ReaderContext::from_raw_bytes(DataEventType::Insert, message);
+            self.total_entries_read += 1;
+            let offset = (
+                OffsetKey::Nats(self.worker_index),
+                OffsetValue::NatsReadEntriesCount(self.total_entries_read),
+            );
+            Ok(ReadResult::Data(payload, offset))
+        } else {
+            Ok(ReadResult::Finished)
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        let offset_value = frontier.get_offset(&OffsetKey::Nats(self.worker_index));
+        if let Some(offset) = offset_value {
+            if let OffsetValue::NatsReadEntriesCount(last_run_entries_read) = offset {
+                self.total_entries_read = *last_run_entries_read;
+            } else {
+                error!("Unexpected offset type for NATS reader: {offset:?}");
+            }
+        }
+        Ok(())
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::Nats
+    }
+
+    fn max_allowed_consecutive_errors(&self) -> usize {
+        32
+    }
+}
+
+impl NatsReader {
+    pub fn new(
+        runtime: TokioRuntime,
+        subscriber: NatsSubscriber,
+        worker_index: usize,
+        persistent_id: Option<PersistentId>,
+    ) -> NatsReader {
+        NatsReader {
+            runtime,
+            subscriber,
+            worker_index,
+            persistent_id,
+            total_entries_read: 0,
+        }
+    }
+}
+
+/// Reads a `JetStream` stream via a durable pull consumer. The offset is the
+/// stream sequence of the last message read from each subject.
+///
+/// A message is acknowledged only after its stream sequence gets into the
+/// persisted frontier, so the messages that aren't persisted yet are redelivered
+/// by the server after a restart. The redelivered messages that are already in
+/// the frontier are skipped. Without persistence, the messages are acknowledged
+/// as soon as they are read.
+///
+/// At most `max_ack_pending` messages wait for the acknowledgement. When there
+/// are that many, the reader doesn't take new messages until the persisted ones
+/// are acknowledged.
+pub struct NatsJetStreamReader {
+    runtime: TokioRuntime,
+    messages: NatsJetStreamMessages,
+    persistent_id: Option<PersistentId>,
+    max_ack_pending: usize,
+    last_read_sequences: HashMap<ArcStr, u64>,
+    persisted_sequences: HashMap<ArcStr, u64>,
+
+    // The messages waiting for the acknowledgement by their stream sequences. A message
+    // that is redelivered before it's acknowledged replaces its previous delivery.
+    pending_acks: BTreeMap<u64, (ArcStr, NatsJetStreamMessage)>,
+    persisted_frontier_receiver: Option<Receiver<OffsetAntichain>>,
+}
+
+const NATS_JETSTREAM_ACK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+pub const NATS_JETSTREAM_DEFAULT_MAX_ACK_PENDING: usize = 1000;
+
+impl NatsJetStreamReader {
+    pub fn new(
+        runtime: TokioRuntime,
+        messages: NatsJetStreamMessages,
+        persistent_id: Option<PersistentId>,
+        max_ack_pending: usize,
+    ) -> NatsJetStreamReader {
+        NatsJetStreamReader {
+            runtime,
+            messages,
+            persistent_id,
+            max_ack_pending,
+            last_read_sequences: HashMap::new(),
+            persisted_sequences: HashMap::new(),
+            pending_acks: BTreeMap::new(),
+            persisted_frontier_receiver: None,
+        }
+    }
+
+    /// Takes the persisted frontiers that have arrived. If `timeout` is given, waits
+    /// for a frontier for at most that long.
+    fn receive_persisted_frontiers(&mut self, timeout: Option<Duration>) {
+        let Some(receiver) = &self.persisted_frontier_receiver else {
+            return;
+        };
+        let first_frontier = match timeout {
+            Some(timeout) => match receiver.recv_timeout(timeout) {
+                Ok(frontier) => Some(frontier),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => {
+                    sleep(timeout);
+                    None
+                }
+            },
+            None => None,
+        };
+        for frontier in first_frontier.into_iter().chain(receiver.try_iter()) {
+            for (offset_key, offset_value) in &frontier {
+                if let (
+                    OffsetKey::NatsSubject(subject),
+                    OffsetValue::NatsStreamSequence(sequence),
+                ) = (offset_key, offset_value)
+                {
+                    self.persisted_sequences.insert(subject.clone(), *sequence);
+                }
+            }
+        }
+    }
+
+    fn ack_persisted_messages(&mut self) -> Result<(), ReadError> {
+        let is_persistence_enabled = self.persisted_frontier_receiver.is_some();
+        let mut acked_sequences = Vec::new();
+        for (sequence, (subject, message)) in &self.pending_acks {
+            let is_persisted = !is_persistence_enabled
+                || self
+                    .persisted_sequences
+                    .get(subject)
+                    .is_some_and(|persisted_sequence| sequence <= persisted_sequence);
+            if is_persisted {
+                self.runtime
+                    .block_on(message.ack())
+                    .map_err(ReadError::NatsJetStreamAck)?;
+                acked_sequences.push(*sequence);
+            }
+        }
+        for sequence in acked_sequences {
+            self.pending_acks.remove(&sequence);
+        }
+        Ok(())
+    }
+}
+
+impl Reader for NatsJetStreamReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        loop {
+            if self.pending_acks.len() >= self.max_ack_pending {
+                self.receive_persisted_frontiers(Some(NATS_JETSTREAM_ACK_CHECK_INTERVAL));
+                self.ack_persisted_messages()?;
+                continue;
+            }
+            self.receive_persisted_frontiers(None);
+            self.ack_persisted_messages()?;
+            // The waiting is limited, so that the messages get acknowledged
+            // even if there are no new ones
+            let next_message = self.runtime.block_on(async {
+                tokio::time::timeout(NATS_JETSTREAM_ACK_CHECK_INTERVAL, self.messages.next()).await
+            });
+            let Ok(next_message) = next_message else {
+                continue;
+            };
+            let Some(message) = next_message else {
+                return Ok(ReadResult::Finished);
+            };
+            let message = message?;
+            let sequence = message
+                .info()
+                .map_err(ReadError::NatsJetStreamMessageInfo)?
+                .stream_sequence;
+            let subject = ArcStr::from(message.subject.as_str());
+            let payload = message.payload.to_vec();
+
+            let is_already_read = self
+                .last_read_sequences
+                .get(&subject)
+                .is_some_and(|last_read_sequence| sequence <= *last_read_sequence);
+            self.pending_acks
+                .insert(sequence, (subject.clone(), message));
+            if is_already_read {
+                // A redelivery of a message that was read before
+                continue;
+            }
+            self.last_read_sequences.insert(subject.clone(), sequence);
+
+            let offset = (
+                OffsetKey::NatsSubject(subject),
+                OffsetValue::NatsStreamSequence(sequence),
+            );
+            let payload = ReaderContext::from_raw_bytes(DataEventType::Insert, payload);
+            return Ok(ReadResult::Data(payload, offset));
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        for (offset_key, offset_value) in frontier {
+            if let (OffsetKey::NatsSubject(subject), OffsetValue::NatsStreamSequence(sequence)) =
+                (offset_key, offset_value)
+            {
+                self.last_read_sequences.insert(subject.clone(), *sequence);
+            } else {
+                error!("Unexpected offset in NATS JetStream frontier: ({offset_key:?}, {offset_value:?})");
+            }
+        }
+        Ok(())
+    }
+
+    fn set_persisted_frontier_receiver(&mut self, receiver: Receiver<OffsetAntichain>) {
+        self.persisted_frontier_receiver = Some(receiver);
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::NatsJetStream
+    }
+
+    fn max_allowed_consecutive_errors(&self) -> usize {
+        32
+    }
+}
+
+pub struct NatsWriter {
+    runtime: TokioRuntime,
+    client: NatsClient,
+    topic: String,
+    header_fields: Vec<(String, usize)>,
+}
+
+impl Writer for NatsWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        self.runtime.block_on(async {
+            let last_payload_index = data.payloads.len() - 1;
+            let mut common_headers = data.construct_nats_headers(&self.header_fields);
+            for (index, payload) in data.payloads.into_iter().enumerate() {
+                // Avoid copying data on the last iteration, reuse the existing headers
+                let headers = {
+                    if index == last_payload_index {
+                        take(&mut common_headers)
+                    } else {
+                        common_headers.clone()
+                    }
+                };
+                let payload = payload.into_raw_bytes()?;
+                self.client
+                    .publish_with_headers(self.topic.clone(), headers, payload.into())
+                    .await
+                    .map_err(WriteError::NatsPublish)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
+        self.runtime
+            .block_on(async { self.client.flush().await })
+            .map_err(WriteError::NatsFlush)
+    }
+
+    fn retriable(&self) -> bool {
+        true
+    }
+
+    fn single_threaded(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for NatsWriter {
+    fn drop(&mut self) {
+        self.flush(true).expect("failed to send the final messages");
+    }
+}
+
+impl NatsWriter {
+    pub fn new(
+        runtime: TokioRuntime,
+        client: NatsClient,
+        topic: String,
+        header_fields: Vec<(String, usize)>,
+    ) -> Self {
+        NatsWriter {
+            runtime,
+            client,
+            topic,
+            header_fields,
+        }
+    }
+}
+
+/// The pause before reporting a failed connection attempt, so that the allowed
+/// number of consecutive errors covers a reasonable outage of the broker.
+const MQTT_RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reads the messages from the topics matching a filter, which may contain
+/// the `+` and `#` wildcards. The subscription is renewed every time the broker
+/// reports that it doesn't keep the session, so it survives the reconnects.
+pub struct MqttReader {
+    client: MqttClient,
+    connection: MqttConnection,
+    topic: String,
+    qos: MqttQoS,
+    persistent_id: Option<PersistentId>,
+    total_entries_read: usize,
+    deferred_read_result: Option<ReadResult>,
+}
+
+impl MqttReader {
+    pub fn new(
+        client: MqttClient,
+        connection: MqttConnection,
+        topic: String,
+        qos: MqttQoS,
+        persistent_id: Option<PersistentId>,
+    ) -> MqttReader {
+        MqttReader {
+            client,
+            connection,
+            topic,
+            qos,
+            persistent_id,
+            total_entries_read: 0,
+            deferred_read_result: None,
+        }
+    }
+}
+
+impl Reader for MqttReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        if let Some(deferred_read_result) = self.deferred_read_result.take() {
+            return Ok(deferred_read_result);
+        }
+
+        loop {
+            let notification = match self.connection.next_notification() {
+                Ok(notification) => notification,
+                Err(e) => {
+                    sleep(MQTT_RECONNECT_INTERVAL);
+                    return Err(e.into());
+                }
+            };
+            match notification {
+                MqttNotification::Connected { session_present } => {
+                    if !session_present {
+                        self.client.subscribe(&self.topic, self.qos)?;
+                    }
+                }
+                MqttNotification::Message(message) => {
+                    self.total_entries_read += 1;
+                    let offset = (
+                        OffsetKey::Mqtt,
+                        OffsetValue::MqttReadEntriesCount(self.total_entries_read),
+                    );
+                    let payload =
+                        ReaderContext::from_raw_bytes(DataEventType::Insert, message.payload);
+                    self.deferred_read_result = Some(ReadResult::Data(payload, offset));
+                    let metadata =
+                        MqttMetadata::new(message.topic, message.qos.level(), message.retain);
+                    return Ok(ReadResult::NewSource(metadata.into()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        let offset_value = frontier.get_offset(&OffsetKey::Mqtt);
+        if let Some(offset) = offset_value {
+            if let OffsetValue::MqttReadEntriesCount(last_run_entries_read) = offset {
+                self.total_entries_read = *last_run_entries_read;
+            } else {
+                error!("Unexpected offset type for MQTT reader: {offset:?}");
+            }
+        }
+        Ok(())
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::Mqtt
+    }
+
+    fn max_allowed_consecutive_errors(&self) -> usize {
+        32
+    }
+}
+
+/// The default time, for which the writer waits until the broker confirms the messages.
+pub const MQTT_FLUSH_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The state of the connection of a writer, which is updated by its connection thread.
+#[derive(Debug, Default)]
+struct MqttDeliveryState {
+    /// The number of messages confirmed by the broker. For the at most once
+    /// delivery a message is considered confirmed once it has been written to the network.
+    delivered: usize,
+
+    /// The last connection error, if the connection hasn't been restored since then.
+    connection_error: Option<String>,
+
+    /// Set when the writer is dropped, so that the connection thread stops
+    /// trying to reconnect.
+    is_stopped: bool,
+}
+
+type SharedMqttDeliveryState = Arc<(Mutex<MqttDeliveryState>, Condvar)>;
+
+/// Publishes the rows either to a fixed topic or to the topic taken from a
+/// column of the row. The connection is driven by a separate thread, and
+/// `flush` waits until the broker confirms all messages published so far.
+/// The waiting fails if the connection is lost or if the confirmations don't
+/// come within `flush_timeout`.
+pub struct MqttWriter {
+    client: MqttClient,
+    topic: String,
+    topic_column_index: Option<usize>,
+    qos: MqttQoS,
+    retain: bool,
+    flush_timeout: Duration,
+    total_messages_published: usize,
+    delivery_state: SharedMqttDeliveryState,
+    connection_thread: Option<JoinHandle<()>>,
+}
+
+impl MqttWriter {
+    pub fn new(
+        client: MqttClient,
+        connection: MqttConnection,
+        topic: String,
+        topic_column_index: Option<usize>,
+        qos: MqttQoS,
+        retain: bool,
+        flush_timeout: Duration,
+    ) -> Result<Self, WriteError> {
+        let delivery_state: SharedMqttDeliveryState =
+            Arc::new((Mutex::new(MqttDeliveryState::default()), Condvar::new()));
+        let connection_thread = std::thread::Builder::new()
+            .name("pathway:mqtt_writer_connection".to_string())
+            .spawn({
+                let delivery_state = delivery_state.clone();
+                move || Self::drive_connection(connection, qos, &delivery_state)
+            })?;
+        Ok(MqttWriter {
+            client,
+            topic,
+            topic_column_index,
+            qos,
+            retain,
+            flush_timeout,
+            total_messages_published: 0,
+            delivery_state,
+            connection_thread: Some(connection_thread),
+        })
+    }
+
+    fn drive_connection(
+        mut connection: MqttConnection,
+        qos: MqttQoS,
+        delivery_state: &SharedMqttDeliveryState,
+    ) {
+        let (state, condvar) = &**delivery_state;
+        loop {
+            let notification = connection.next_notification();
+            let mut state = state.lock().unwrap();
+            match notification {
+                Ok(MqttNotification::Connected { .. }) => state.connection_error = None,
+                Ok(MqttNotification::PublishSent) if qos == MqttQoS::AtMostOnce => {
+                    state.delivered += 1;
+                }
+                Ok(MqttNotification::PublishAcknowledged) => state.delivered += 1,
+                Ok(MqttNotification::Disconnected) => break,
+                Err(MqttError::ConnectionClosed) => {
+                    state.connection_error = Some(MqttError::ConnectionClosed.to_string());
+                    condvar.notify_all();
+                    break;
+                }
+                Ok(_) => continue,
+                Err(e) => {
+                    error!("MQTT connection error, reconnecting: {e}");
+                    state.connection_error = Some(e.to_string());
+                    condvar.notify_all();
+                    if state.is_stopped {
+                        break;
+                    }
+                    drop(state);
+                    sleep(MQTT_RECONNECT_INTERVAL);
+                    continue;
+                }
+            }
+            condvar.notify_all();
+        }
+    }
+
+    fn message_topic(&self, data: &FormatterContext) -> Result<String, WriteError> {
+        let Some(topic_column_index) = self.topic_column_index else {
+            return Ok(self.topic.clone());
+        };
+        match &data.values[topic_column_index] {
+            Value::String(topic) => Ok(topic.to_string()),
+            value => Err(WriteError::IncorrectMqttTopicFieldType(value.clone())),
+        }
+    }
+}
+
+impl Writer for MqttWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        let topic = self.message_topic(&data)?;
+        for payload in data.payloads {
+            let payload = payload.into_raw_bytes()?;
+            self.client
+                .publish(topic.clone(), self.qos, self.retain, payload)?;
+            self.total_messages_published += 1;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
+        let (state, condvar) = &*self.delivery_state;
+        let (state, wait_result) = condvar
+            .wait_timeout_while(state.lock().unwrap(), self.flush_timeout, |state| {
+                state.delivered < self.total_messages_published && state.connection_error.is_none()
+            })
+            .unwrap();
+        if state.delivered >= self.total_messages_published {
+            return Ok(());
+        }
+        if let Some(connection_error) = &state.connection_error {
+            return Err(WriteError::MqttConnectionLost(connection_error.clone()));
+        }
+        assert!(wait_result.timed_out());
+        Err(WriteError::MqttDeliveryTimeout(
+            self.total_messages_published - state.delivered,
+            self.flush_timeout,
+        ))
+    }
+
+    fn retriable(&self) -> bool {
+        true
+    }
+
+    fn single_threaded(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for MqttWriter {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush(true) {
+            error!("Failed to send the final MQTT messages: {e}");
+        }
+        self.delivery_state.0.lock().unwrap().is_stopped = true;
+        if let Err(e) = self.client.disconnect() {
+            error!("Failed to disconnect from the MQTT broker: {e}");
+        }
+        if let Some(connection_thread) = self.connection_thread.take() {
+            connection_thread
+                .join()
+                .expect("MQTT connection thread failed");
+        }
+    }
+}
+
+const REDIS_STREAMS_BATCH_SIZE: usize = 1024;
+
+/// The waiting for the new entries is limited, so that the entries get
+/// acknowledged even if there are no new ones.
+const REDIS_STREAMS_BLOCK_TIMEOUT_MS: usize = 1000;
+
+/// Reads Redis streams as a consumer of a consumer group. The offset is the ID
+/// of the last entry read from each stream.
+///
+/// An entry is acknowledged only after its ID gets into the persisted frontier,
+/// so the entries that aren't persisted yet stay in the pending entries list of
+/// the consumer and are read again after a restart. The entries that are already
+/// in the frontier are skipped. Without persistence, the entries are
+/// acknowledged as soon as they are read.
+pub struct RedisStreamsReader {
+    connection: RedisConnection,
+    stream_keys: Vec<String>,
+    group_name: String,
+    consumer_name: String,
+    schema: HashMap<String, Type>,
+    persistent_id: Option<PersistentId>,
+
+    // Before reading the new entries, the entries that were delivered to this
+    // consumer, but weren't acknowledged, are read from its pending entries list
+    pending_list_positions: Option<Vec<String>>,
+    last_read_ids: HashMap<ArcStr, RedisStreamEntryId>,
+    queued_entries: VecDeque<(ArcStr, RedisStreamEntry)>,
+    pending_acks: VecDeque<(ArcStr, RedisStreamEntryId)>,
+    persisted_frontier_receiver: Option<Receiver<OffsetAntichain>>,
+}
+
+impl RedisStreamsReader {
+    /// Creates the consumer group for each stream, if it doesn't exist yet. A new
+    /// group starts from the beginning of the stream.
+    pub fn new(
+        mut connection: RedisConnection,
+        stream_keys: Vec<String>,
+        group_name: String,
+        consumer_name: String,
+        schema: HashMap<String, Type>,
+        persistent_id: Option<PersistentId>,
+    ) -> Result<RedisStreamsReader, ReadError> {
+        for stream_key in &stream_keys {
+            let created: Result<(), RedisError> =
+                connection.xgroup_create_mkstream(stream_key, &group_name, "0");
+            match created {
+                Err(e) if e.code() != Some("BUSYGROUP") => return Err(e.into()),
+                _ => {}
+            }
+        }
+        let pending_list_positions = Some(vec!["0".to_string(); stream_keys.len()]);
+        Ok(RedisStreamsReader {
+            connection,
+            stream_keys,
+            group_name,
+            consumer_name,
+            schema,
+            persistent_id,
+            pending_list_positions,
+            last_read_ids: HashMap::new(),
+            queued_entries: VecDeque::new(),
+            pending_acks: VecDeque::new(),
+            persisted_frontier_receiver: None,
+        })
+    }
+
+    fn read_entries(&mut self) -> Result<(), ReadError> {
+        let options = RedisStreamReadOptions::default()
+            .group(&self.group_name, &self.consumer_name)
+            .count(REDIS_STREAMS_BATCH_SIZE);
+        let Some(pending_list_positions) = &mut self.pending_list_positions else {
+            let new_entries_ids = vec![">"; self.stream_keys.len()];
+            let options = options.block(REDIS_STREAMS_BLOCK_TIMEOUT_MS);
+            let reply: Option<RedisStreamReadReply> =
+                self.connection
+                    .xread_options(&self.stream_keys, &new_entries_ids, &options)?;
+            for stream in reply.into_iter().flat_map(|reply| reply.keys) {
+                let stream_key = ArcStr::from(stream.key);
+                for entry in stream.ids {
+                    self.queued_entries.push_back((stream_key.clone(), entry));
+                }
+            }
+            return Ok(());
+        };
+
+        let reply: Option<RedisStreamReadReply> =
+            self.connection
+                .xread_options(&self.stream_keys, pending_list_positions, &options)?;
+        let mut has_pending_entries = false;
+        for stream in reply.into_iter().flat_map(|reply| reply.keys) {
+            let Some(last_entry) = stream.ids.last() else {
+                continue;
+            };
+            has_pending_entries = true;
+            if let Some(index) = self.stream_keys.iter().position(|key| *key == stream.key) {
+                pending_list_positions[index].clone_from(&last_entry.id);
+            }
+            let stream_key = ArcStr::from(stream.key);
+            for entry in stream.ids {
+                self.queued_entries.push_back((stream_key.clone(), entry));
+            }
+        }
+        if !has_pending_entries {
+            self.pending_list_positions = None;
+        }
+        Ok(())
+    }
+
+    fn entry_values(&self, entry: &RedisStreamEntry) -> ValuesMap {
+        let mut values = HashMap::with_capacity(self.schema.len());
+        for (field_name, dtype) in &self.schema {
+            // The absent fields are filled with the default values by the parser
+            let Some(raw_value) = entry.map.get(field_name) else {
+                continue;
+            };
+            let value = redis::from_redis_value::<Vec<u8>>(raw_value)
+                .ok()
+                .and_then(|raw_value| parse_redis_value(&raw_value, dtype))
+                .ok_or_else(|| {
+                    let value_repr =
+                        limit_length(format!("{raw_value:?}"), STANDARD_OBJECT_LENGTH_LIMIT);
+                    Box::new(ConversionError {
+                        value_repr,
+                        field_name: field_name.clone(),
+                        type_: dtype.clone(),
+                    })
+                });
+            values.insert(field_name.clone(), value);
+        }
+        values.into()
+    }
+
+    fn ack_persisted_entries(&mut self) -> Result<(), ReadError> {
+        let Some(receiver) = &self.persisted_frontier_receiver else {
+            return self.ack_entries(|_, _| true);
+        };
+        let mut persisted_ids = HashMap::new();
+        for frontier in receiver.try_iter() {
+            for (offset_key, offset_value) in &frontier {
+                if let (
+                    OffsetKey::RedisStream(stream_key),
+                    OffsetValue::RedisStreamEntryId {
+                        milliseconds,
+                        sequence,
+                    },
+                ) = (offset_key, offset_value)
+                {
+                    let entry_id = RedisStreamEntryId {
+                        milliseconds: *milliseconds,
+                        sequence: *sequence,
+                    };
+                    persisted_ids.insert(stream_key.clone(), entry_id);
+                }
+            }
+        }
+        if persisted_ids.is_empty() {
+            return Ok(());
+        }
+        self.ack_entries(|stream_key, entry_id| {
+            persisted_ids
+                .get(stream_key)
+                .is_some_and(|persisted_id| entry_id <= *persisted_id)
+        })
+    }
+
+    fn ack_entries(
+        &mut self,
+        is_persisted: impl Fn(&ArcStr, RedisStreamEntryId) -> bool,
+    ) -> Result<(), ReadError> {
+        let mut ids_to_ack: HashMap<ArcStr, Vec<String>> = HashMap::new();
+        let mut still_pending = VecDeque::with_capacity(self.pending_acks.len());
+        for (stream_key, entry_id) in take(&mut self.pending_acks) {
+            if is_persisted(&stream_key, entry_id) {
+                ids_to_ack
+                    .entry(stream_key)
+                    .or_default()
+                    .push(entry_id.to_string());
+            } else {
+                still_pending.push_back((stream_key, entry_id));
+            }
+        }
+        self.pending_acks = still_pending;
+        for (stream_key, ids) in ids_to_ack {
+            let _: usize = self
+                .connection
+                .xack(stream_key.as_str(), &self.group_name, &ids)?;
+        }
+        Ok(())
+    }
+}
+
+impl Reader for RedisStreamsReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        loop {
+            self.ack_persisted_entries()?;
+            let Some((stream_key, entry)) = self.queued_entries.pop_front() else {
+                self.read_entries()?;
+                continue;
+            };
+            let entry_id: RedisStreamEntryId = entry.id.parse()?;
+            let is_already_read = self
+                .last_read_ids
+                .get(&stream_key)
+                .is_some_and(|last_read_id| entry_id <= *last_read_id);
+            self.pending_acks.push_back((stream_key.clone(), entry_id));
+            if is_already_read {
+                // An entry from the pending entries list, which was read before
+                continue;
+            }
+            self.last_read_ids.insert(stream_key.clone(), entry_id);
+
+            let offset = (
+                OffsetKey::RedisStream(stream_key),
+                OffsetValue::RedisStreamEntryId {
+                    milliseconds: entry_id.milliseconds,
+                    sequence: entry_id.sequence,
+                },
+            );
+            let values = self.entry_values(&entry);
+            return Ok(ReadResult::Data(
+                ReaderContext::from_diff(DataEventType::Insert, None, values),
+                offset,
+            ));
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        for (offset_key, offset_value) in frontier {
+            if let (
+                OffsetKey::RedisStream(stream_key),
+                OffsetValue::RedisStreamEntryId {
+                    milliseconds,
+                    sequence,
+                },
+            ) = (offset_key, offset_value)
+            {
+                let entry_id = RedisStreamEntryId {
+                    milliseconds: *milliseconds,
+                    sequence: *sequence,
+                };
+                self.last_read_ids.insert(stream_key.clone(), entry_id);
+            } else {
+                error!("Unexpected offset in Redis Streams frontier: ({offset_key:?}, {offset_value:?})");
+            }
+        }
+        Ok(())
+    }
+
+    fn set_persisted_frontier_receiver(&mut self, receiver: Receiver<OffsetAntichain>) {
+        self.persisted_frontier_receiver = Some(receiver);
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::RedisStreams
+    }
+
+    fn max_allowed_consecutive_errors(&self) -> usize {
+        32
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RedisValueType {
+    /// Each row is stored as a hash, with a field per column.
+    Hash,
+
+    /// Each row is stored as a string containing a JSON object.
+    Json,
+}
+
+/// Maintains the snapshot of a table in Redis: each row is stored under the key
+/// built from the key prefix and the values of the primary key columns.
+///
+/// The changes are applied in a transaction on each flush. Within a batch, an
+/// update consists of the deletion of the old row and the insertion of the new
+/// one, which may come in any order, so an insertion takes precedence over a
+/// deletion of the same key.
+pub struct RedisSnapshotWriter {
+    connection: RedisConnection,
+    key_prefix: String,
+    key_field_positions: Vec<usize>,
+    value_field_names: Vec<String>,
+    value_type: RedisValueType,
+    pending_changes: HashMap<Vec<u8>, Option<Vec<Value>>>,
+}
+
+impl RedisSnapshotWriter {
+    pub fn new(
+        connection: RedisConnection,
+        key_prefix: String,
+        key_field_positions: Vec<usize>,
+        value_field_names: Vec<String>,
+        value_type: RedisValueType,
+    ) -> RedisSnapshotWriter {
+        RedisSnapshotWriter {
+            connection,
+            key_prefix,
+            key_field_positions,
+            value_field_names,
+            value_type,
+            pending_changes: HashMap::new(),
+        }
+    }
+
+    fn redis_key(&self, data: &FormatterContext) -> Result<Vec<u8>, WriteError> {
+        let mut key = self.key_prefix.as_bytes().to_vec();
+        if self.key_field_positions.is_empty() {
+            key.extend_from_slice(data.key.to_string().as_bytes());
+        }
+        for (index, position) in self.key_field_positions.iter().enumerate() {
+            if index > 0 {
+                key.push(b':');
+            }
+            if let Some(value) = format_redis_value(&data.values[*position])? {
+                key.extend_from_slice(&value);
+            }
+        }
+        Ok(key)
+    }
+
+    fn add_row_commands(
+        &self,
+        pipeline: &mut redis::Pipeline,
+        key: &[u8],
+        values: &[Value],
+    ) -> Result<(), WriteError> {
+        match self.value_type {
+            RedisValueType::Hash => {
+                let mut fields = Vec::with_capacity(values.len());
+                for (name, value) in self.value_field_names.iter().zip(values) {
+                    if let Some(value) = format_redis_value(value)? {
+                        fields.push((name.as_str(), value));
+                    }
+                }
+                if !fields.is_empty() {
+                    pipeline.hset_multiple(key, &fields).ignore();
+                }
+            }
+            RedisValueType::Json => {
+                let mut document = serde_json::Map::with_capacity(values.len());
+                for (name, value) in self.value_field_names.iter().zip(values) {
+                    document.insert(name.clone(), serialize_value_to_json(value)?);
+                }
+                pipeline
+                    .set(key, serde_json::Value::Object(document).to_string())
+                    .ignore();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Writer for RedisSnapshotWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        let key = self.redis_key(&data)?;
+        if data.diff > 0 {
+            self.pending_changes.insert(key, Some(data.values));
+        } else {
+            self.pending_changes.entry(key).or_insert(None);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
+        if self.pending_changes.is_empty() {
+            return Ok(());
+        }
+        let mut pipeline = redis::pipe();
+        pipeline.atomic();
+        for (key, values) in take(&mut self.pending_changes) {
+            pipeline.del(&key).ignore();
+            if let Some(values) = values {
+                self.add_row_commands(&mut pipeline, &key, &values)?;
+            }
+        }
+        pipeline.query::<()>(&mut self.connection)?;
+        Ok(())
+    }
+}
+
+const AMQP_ACK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The maximum number of unacknowledged messages delivered to a reader.
+pub const AMQP_PREFETCH_COUNT: u16 = 1024;
+
+/// Consumes an AMQP 0-9-1 queue with manual acknowledgements. The queue may
+/// be consumed by several workers, and the offset is the number of messages
+/// read by each of them.
+///
+/// A message is acknowledged only after it gets into the persisted frontier,
+/// so the messages that aren't persisted yet are redelivered by the broker
+/// after a restart. Without persistence, the messages are acknowledged as soon
+/// as they are read.
+pub struct AmqpReader {
+    runtime: TokioRuntime,
+    consumer: AmqpConsumer,
+    worker_index: usize,
+    persistent_id: Option<PersistentId>,
+    total_entries_read: usize,
+    pending_acks: VecDeque<(usize, AmqpAcker)>,
+    persisted_frontier_receiver: Option<Receiver<OffsetAntichain>>,
+    deferred_read_result: Option<ReadResult>,
+}
+
+impl AmqpReader {
+    pub fn new(
+        runtime: TokioRuntime,
+        consumer: AmqpConsumer,
+        worker_index: usize,
+        persistent_id: Option<PersistentId>,
+    ) -> AmqpReader {
+        AmqpReader {
+            runtime,
+            consumer,
+            worker_index,
+            persistent_id,
+            total_entries_read: 0,
+            pending_acks: VecDeque::new(),
+            persisted_frontier_receiver: None,
+            deferred_read_result: None,
+        }
+    }
+
+    fn ack_persisted_messages(&mut self) -> Result<(), ReadError> {
+        let Some(receiver) = &self.persisted_frontier_receiver else {
+            return self.ack_messages(usize::MAX);
+        };
+        let offset_key = OffsetKey::Amqp(self.worker_index);
+        let mut persisted_entries_count = None;
+        for frontier in receiver.try_iter() {
+            if let Some(OffsetValue::AmqpReadEntriesCount(count)) = frontier.get_offset(&offset_key)
+            {
+                persisted_entries_count = Some(*count);
+            }
+        }
+        match persisted_entries_count {
+            Some(count) => self.ack_messages(count),
+            None => Ok(()),
+        }
+    }
+
+    /// Acknowledges the messages up to the given number of the messages read.
+    /// The delivery tags grow within a channel, so a single acknowledgement
+    /// with the `multiple` flag covers all of them.
+    fn ack_messages(&mut self, persisted_entries_count: usize) -> Result<(), ReadError> {
+        let mut last_persisted_acker = None;
+        while let Some((entry_number, _)) = self.pending_acks.front() {
+            if *entry_number > persisted_entries_count {
+                break;
+            }
+            last_persisted_acker = self.pending_acks.pop_front().map(|(_, acker)| acker);
+        }
+        if let Some(acker) = last_persisted_acker {
+            self.runtime
+                .block_on(acker.ack(BasicAckOptions { multiple: true }))?;
+        }
+        Ok(())
+    }
+}
+
+impl Reader for AmqpReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        if let Some(deferred_read_result) = self.deferred_read_result.take() {
+            return Ok(deferred_read_result);
+        }
+
+        loop {
+            self.ack_persisted_messages()?;
+            // The waiting is limited, so that the messages get acknowledged
+            // even if there are no new ones
+            let next_delivery = self.runtime.block_on(async {
+                tokio::time::timeout(AMQP_ACK_CHECK_INTERVAL, self.consumer.next()).await
+            });
+            let Ok(next_delivery) = next_delivery else {
+                continue;
+            };
+            let Some(delivery) = next_delivery else {
+                return Ok(ReadResult::Finished);
+            };
+            let delivery = delivery?;
+
+            self.total_entries_read += 1;
+            self.pending_acks
+                .push_back((self.total_entries_read, delivery.acker));
+            let offset = (
+                OffsetKey::Amqp(self.worker_index),
+                OffsetValue::AmqpReadEntriesCount(self.total_entries_read),
+            );
+            let payload = ReaderContext::from_raw_bytes(DataEventType::Insert, delivery.data);
+            self.deferred_read_result = Some(ReadResult::Data(payload, offset));
+            let metadata = AmqpMetadata::new(
+                delivery.exchange.to_string(),
+                delivery.routing_key.to_string(),
+                delivery.redelivered,
+            );
+            return Ok(ReadResult::NewSource(metadata.into()));
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        let offset_value = frontier.get_offset(&OffsetKey::Amqp(self.worker_index));
+        if let Some(offset) = offset_value {
+            if let OffsetValue::AmqpReadEntriesCount(last_run_entries_read) = offset {
+                self.total_entries_read = *last_run_entries_read;
+            } else {
+                error!("Unexpected offset type for AMQP reader: {offset:?}");
+            }
+        }
+        Ok(())
+    }
+
+    fn set_persisted_frontier_receiver(&mut self, receiver: Receiver<OffsetAntichain>) {
+        self.persisted_frontier_receiver = Some(receiver);
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::Amqp
+    }
+
+    fn max_allowed_consecutive_errors(&self) -> usize {
+        32
+    }
+}
+
+/// The delivery mode of the messages that survive a restart of the broker.
+const AMQP_PERSISTENT_DELIVERY_MODE: u8 = 2;
+
+/// Publishes the rows to an exchange, either with a fixed routing key or with
+/// the routing key taken from a column of the row. The channel is in the
+/// confirm mode, and `flush` waits until the broker confirms all messages
+/// published so far.
+pub struct AmqpWriter {
+    runtime: TokioRuntime,
+    channel: AmqpChannel,
+    exchange: String,
+    routing_key: String,
+    routing_key_column_index: Option<usize>,
+    header_fields: Vec<(String, usize)>,
+    pending_confirms: Vec<PublisherConfirm>,
+}
+
+impl AmqpWriter {
+    pub fn new(
+        runtime: TokioRuntime,
+        channel: AmqpChannel,
+        exchange: String,
+        routing_key: String,
+        routing_key_column_index: Option<usize>,
+        header_fields: Vec<(String, usize)>,
+    ) -> Self {
+        AmqpWriter {
+            runtime,
+            channel,
+            exchange,
+            routing_key,
+            routing_key_column_index,
+            header_fields,
+            pending_confirms: Vec::new(),
+        }
+    }
+
+    fn message_routing_key(&self, data: &FormatterContext) -> Result<String, WriteError> {
+        let Some(routing_key_column_index) = self.routing_key_column_index else {
+            return Ok(self.routing_key.clone());
+        };
+        match &data.values[routing_key_column_index] {
+            Value::String(routing_key) => Ok(routing_key.to_string()),
+            value => Err(WriteError::IncorrectAmqpRoutingKeyFieldType(value.clone())),
+        }
+    }
+}
+
+impl Writer for AmqpWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        let routing_key = self.message_routing_key(&data)?;
+        let properties = AmqpProperties::default()
+            .with_delivery_mode(AMQP_PERSISTENT_DELIVERY_MODE)
+            .with_headers(data.construct_amqp_headers(&self.header_fields));
+        self.runtime.block_on(async {
+            for payload in data.payloads {
+                let payload = payload.into_raw_bytes()?;
+                let confirm = self
+                    .channel
+                    .basic_publish(
+                        &self.exchange,
+                        &routing_key,
+                        BasicPublishOptions::default(),
+                        &payload,
+                        properties.clone(),
+                    )
+                    .await?;
+                self.pending_confirms.push(confirm);
+            }
+            Ok(())
+        })
+    }
+
+    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
+        self.runtime.block_on(async {
+            for confirm in take(&mut self.pending_confirms) {
+                if let AmqpConfirmation::Nack(_) = confirm.await? {
+                    return Err(WriteError::AmqpPublishRejected);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn retriable(&self) -> bool {
+        true
+    }
+
+    fn single_threaded(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for AmqpWriter {
+    fn drop(&mut self) {
+        self.flush(true).expect("failed to send the final messages");
+    }
+}
+
+const PULSAR_ACK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reads the Pulsar topics via a failover subscription, so that each topic
+/// partition is consumed by a single reader at a time. The offset is the ID of
+/// the last message read from each partition.
+///
+/// The subscription cursor of a partition is moved only after the message gets
+/// into the persisted frontier, so the messages that aren't persisted yet are
+/// redelivered after a restart. On a restart, the cursor of each partition in
+/// the frontier is also moved to the persisted message ID, so the reading
+/// resumes from there even if the subscription has advanced further. The
+/// redelivered messages that are already in the frontier are skipped. Without
+/// persistence, the messages are acknowledged as soon as they are read.
+pub struct PulsarReader {
+    runtime: TokioRuntime,
+    client: Pulsar<TokioExecutor>,
+    consumer: PulsarConsumer<Vec<u8>, TokioExecutor>,
+    persistent_id: Option<PersistentId>,
+    last_read_ids: HashMap<ArcStr, PulsarMessageId>,
+    pending_acks: HashMap<ArcStr, VecDeque<(PulsarMessageId, PulsarMessageIdData)>>,
+    persisted_frontier_receiver: Option<Receiver<OffsetAntichain>>,
+    deferred_read_result: Option<ReadResult>,
+}
+
+impl PulsarReader {
+    pub fn new(
+        runtime: TokioRuntime,
+        client: Pulsar<TokioExecutor>,
+        consumer: PulsarConsumer<Vec<u8>, TokioExecutor>,
+        persistent_id: Option<PersistentId>,
+    ) -> PulsarReader {
+        PulsarReader {
+            runtime,
+            client,
+            consumer,
+            persistent_id,
+            last_read_ids: HashMap::new(),
+            pending_acks: HashMap::new(),
+            persisted_frontier_receiver: None,
+            deferred_read_result: None,
+        }
+    }
+
+    fn ack_persisted_messages(&mut self) -> Result<(), ReadError> {
+        let Some(receiver) = &self.persisted_frontier_receiver else {
+            return self.ack_messages(|_, _| true);
+        };
+        let mut persisted_ids = HashMap::new();
+        for frontier in receiver.try_iter() {
+            for (offset_key, offset_value) in &frontier {
+                if let (
+                    OffsetKey::Pulsar(topic),
+                    OffsetValue::PulsarMessageId {
+                        ledger_id,
+                        entry_id,
+                        batch_index,
+                    },
+                ) = (offset_key, offset_value)
+                {
+                    let message_id = PulsarMessageId {
+                        ledger_id: *ledger_id,
+                        entry_id: *entry_id,
+                        batch_index: *batch_index,
+                    };
+                    persisted_ids.insert(topic.clone(), message_id);
+                }
+            }
+        }
+        if persisted_ids.is_empty() {
+            return Ok(());
+        }
+        self.ack_messages(|topic, message_id| {
+            persisted_ids
+                .get(topic)
+                .is_some_and(|persisted_id| message_id <= *persisted_id)
+        })
+    }
+
+    /// Moves the subscription cursor of each partition to the last message
+    /// satisfying `is_persisted`, acknowledging all preceding messages too.
+    fn ack_messages(
+        &mut self,
+        is_persisted: impl Fn(&ArcStr, PulsarMessageId) -> bool,
+    ) -> Result<(), ReadError> {
+        for (topic, pending_acks) in &mut self.pending_acks {
+            let mut last_persisted_id = None;
+            while let Some((message_id, _)) = pending_acks.front() {
+                if !is_persisted(topic, *message_id) {
+                    break;
+                }
+                last_persisted_id = pending_acks.pop_front().map(|(_, id)| id);
+            }
+            if let Some(id) = last_persisted_id {
+                self.runtime
+                    .block_on(self.consumer.cumulative_ack_with_id(topic, id))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Reader for PulsarReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        if let Some(deferred_read_result) = self.deferred_read_result.take() {
+            return Ok(deferred_read_result);
+        }
+
+        loop {
+            self.ack_persisted_messages()?;
+            // The waiting is limited, so that the messages get acknowledged
+            // even if there are no new ones
+            let next_message = self.runtime.block_on(async {
+                tokio::time::timeout(PULSAR_ACK_CHECK_INTERVAL, self.consumer.next()).await
+            });
+            let Ok(next_message) = next_message else {
+                continue;
+            };
+            let Some(message) = next_message else {
+                return Ok(ReadResult::Finished);
+            };
+            let message = message?;
+            let topic = ArcStr::from(message.topic.as_str());
+            let message_id = PulsarMessageId::from(message.message_id());
+
+            let is_already_read = self
+                .last_read_ids
+                .get(&topic)
+                .is_some_and(|last_read_id| message_id <= *last_read_id);
+            self.pending_acks
+                .entry(topic.clone())
+                .or_default()
+                .push_back((message_id, message.message_id().clone()));
+            if is_already_read {
+                // A redelivery of a message that was read before
+                continue;
+            }
+            self.last_read_ids.insert(topic.clone(), message_id);
+
+            let metadata = message.metadata();
+            let metadata = PulsarMetadata::new(
+                message.topic.clone(),
+                metadata.partition_key.clone(),
+                metadata.publish_time,
+                metadata.event_time,
+                message_id.ledger_id,
+                message_id.entry_id,
+                message_id.batch_index,
+            );
+            let offset = (
+                OffsetKey::Pulsar(topic),
+                OffsetValue::PulsarMessageId {
+                    ledger_id: message_id.ledger_id,
+                    entry_id: message_id.entry_id,
+                    batch_index: message_id.batch_index,
+                },
+            );
+            let key = message.key().map(String::into_bytes);
+            // A message with an empty payload removes its key from a compacted topic,
+            // so it's passed on as a tombstone, like in Kafka
+            let data = message.payload.data;
+            let payload = ReaderContext::from_key_value(key, (!data.is_empty()).then_some(data));
+            self.deferred_read_result = Some(ReadResult::Data(payload, offset));
+            return Ok(ReadResult::NewSource(metadata.into()));
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        for (offset_key, offset_value) in frontier {
+            if let (
+                OffsetKey::Pulsar(topic),
+                OffsetValue::PulsarMessageId {
+                    ledger_id,
+                    entry_id,
+                    batch_index,
+                },
+            ) = (offset_key, offset_value)
+            {
+                let message_id = PulsarMessageId {
+                    ledger_id: *ledger_id,
+                    entry_id: *entry_id,
+                    batch_index: *batch_index,
+                };
+                self.last_read_ids.insert(topic.clone(), message_id);
+            } else {
+                error!("Unexpected offset in Pulsar frontier: ({offset_key:?}, {offset_value:?})");
+            }
+        }
+
+        // The seek is done by the broker, so the messages before the persisted
+        // ones aren't delivered again. The messages of the same batch are
+        // still delivered, since the broker can only seek to an entry, and
+        // they are skipped in `read`.
+        let subscribed_topics = self.consumer.topics();
+        for (topic, message_id) in &self.last_read_ids {
+            if !subscribed_topics.iter().any(|name| name == topic.as_str()) {
+                continue;
+            }
+            let message_id = PulsarMessageIdData {
+                ledger_id: message_id.ledger_id,
+                entry_id: message_id.entry_id,
+                batch_index: (message_id.batch_index >= 0).then_some(message_id.batch_index),
+                ..Default::default()
+            };
+            self.runtime.block_on(self.consumer.seek(
+                Some(vec![topic.to_string()]),
+                Some(message_id),
+                None,
+                self.client.clone(),
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn set_persisted_frontier_receiver(&mut self, receiver: Receiver<OffsetAntichain>) {
+        self.persisted_frontier_receiver = Some(receiver);
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::Pulsar
+    }
+
+    fn max_allowed_consecutive_errors(&self) -> usize {
+        32
+    }
+}
+
+/// Publishes the rows to a Pulsar topic. Every message has a key, which is
+/// built from the key fields in the same way as the Kafka message key, so that
+/// the topic can be compacted. For a partitioned topic, the partition is chosen
+/// by the key as in the Java client, and `flush` waits until the broker
+/// confirms all messages published so far.
+///
+/// If tombstones are enabled, a row removal that isn't followed by an
+/// insertion with the same key at the same time is published as a message with
+/// an empty payload, like in `KafkaWriter`.
+pub struct PulsarWriter {
+    runtime: TokioRuntime,
+    producer: PulsarProducer<TokioExecutor>,
+    partition_topics: Vec<String>,
+    header_fields: Vec<(String, usize)>,
+    key_fields: Vec<(String, usize)>,
+    produce_tombstones: bool,
+
+    current_time: Option<Timestamp>,
+    upserted_keys: HashSet<String>,
+    pending_tombstones: HashMap<String, HashMap<String, String>>,
+    pending_receipts: Vec<PulsarSendFuture>,
+}
+
+impl PulsarWriter {
+    pub fn new(
+        runtime: TokioRuntime,
+        producer: PulsarProducer<TokioExecutor>,
+        partition_topics: Vec<String>,
+        header_fields: Vec<(String, usize)>,
+        key_fields: Vec<(String, usize)>,
+        produce_tombstones: bool,
+    ) -> Self {
+        assert!(
+            !partition_topics.is_empty(),
+            "a topic must have at least one partition"
+        );
+        PulsarWriter {
+            runtime,
+            producer,
+            partition_topics,
+            header_fields,
+            key_fields,
+            produce_tombstones,
+            current_time: None,
+            upserted_keys: HashSet::new(),
+            pending_tombstones: HashMap::new(),
+            pending_receipts: Vec::new(),
+        }
+    }
+
+    fn send(
+        &mut self,
+        key: String,
+        payload: Vec<u8>,
+        properties: HashMap<String, String>,
+    ) -> Result<(), WriteError> {
+        let partition = pulsar_key_partition(&key, self.partition_topics.len());
+        let topic = self.partition_topics[partition].clone();
+        let message = PulsarMessage {
+            payload,
+            properties,
+            partition_key: Some(key),
+            ..Default::default()
+        };
+        let receipt = self
+            .runtime
+            .block_on(self.producer.send_non_blocking(topic, message))?;
+        self.pending_receipts.push(receipt);
+        Ok(())
+    }
+
+    fn send_pending_tombstones(&mut self) -> Result<(), WriteError> {
+        for (key, properties) in take(&mut self.pending_tombstones) {
+            self.send(key, Vec::new(), properties)?;
+        }
+        self.upserted_keys.clear();
+        Ok(())
+    }
+}
+
+impl Writer for PulsarWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        if self.produce_tombstones && self.current_time != Some(data.time) {
+            self.send_pending_tombstones()?;
+            self.current_time = Some(data.time);
+        }
+
+        let key = pulsar_message_key(data.construct_message_key(&self.key_fields)?);
+        let properties = data.construct_pulsar_properties(&self.header_fields);
+
+        if self.produce_tombstones {
+            if data.diff < 0 {
+                // The key may still be reinserted within the same time
+                if !self.upserted_keys.contains(&key) {
+                    self.pending_tombstones.insert(key, properties);
+                }
+                return Ok(());
+            }
+            self.pending_tombstones.remove(&key);
+            self.upserted_keys.insert(key.clone());
+        }
+
+        for payload in data.payloads {
+            self.send(key.clone(), payload.into_raw_bytes()?, properties.clone())?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
+        if self.produce_tombstones {
+            self.send_pending_tombstones()?;
+        }
+        self.runtime.block_on(async {
+            for receipt in take(&mut self.pending_receipts) {
+                receipt.await?;
+            }
+            Ok(())
+        })
+    }
+
+    fn retriable(&self) -> bool {
+        true
+    }
+
+    fn single_threaded(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for PulsarWriter {
+    fn drop(&mut self) {
+        self.flush(true).expect("failed to send the final messages");
+    }
+}
+
+/// The interval between the checks for the new shards of a Kinesis stream.
+const KINESIS_SHARD_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The time to wait when all shards have been read to the end. Kinesis allows
+/// up to five `GetRecords` calls per second for a shard.
+const KINESIS_IDLE_INTERVAL: Duration = Duration::from_secs(1);
+
+const KINESIS_GET_RECORDS_LIMIT: i32 = 10_000;
+
+/// Reads the shards of a Kinesis stream, which are assigned to this reader,
+/// with the shard iterators. The offset is the position of the last user
+/// record read from each shard: the sequence number of the Kinesis record and,
+/// if the record has been aggregated by the Kinesis Producer Library and not
+/// all its user records have been read, the sub-sequence number of the user
+/// record within it.
+///
+/// When the stream is resharded, the child shards are started only after their
+/// parents are read to the end, so no records are lost and the records with
+/// the same partition key are read in order. After a restart, the reading of
+/// each shard continues after the position stored in the frontier.
+pub struct KinesisReader {
+    runtime: TokioRuntime,
+    client: KinesisClient,
+    stream_name: String,
+    persistent_id: Option<PersistentId>,
+    shard_tracker: KinesisShardTracker,
+    last_read_positions: HashMap<ArcStr, (ArcStr, Option<u64>)>,
+    shard_iterators: VecDeque<(ArcStr, String)>,
+    next_shard_refresh: Instant,
+    idle_shards_in_row: usize,
+    pending_read_results: VecDeque<ReadResult>,
+}
+
+impl KinesisReader {
+    pub fn new(
+        runtime: TokioRuntime,
+        client: KinesisClient,
+        stream_name: String,
+        reader_index: usize,
+        reader_count: usize,
+        persistent_id: Option<PersistentId>,
+    ) -> KinesisReader {
+        KinesisReader {
+            runtime,
+            client,
+            stream_name,
+            persistent_id,
+            shard_tracker: KinesisShardTracker::new(reader_index, reader_count),
+            last_read_positions: HashMap::new(),
+            shard_iterators: VecDeque::new(),
+            next_shard_refresh: Instant::now(),
+            idle_shards_in_row: 0,
+            pending_read_results: VecDeque::new(),
+        }
+    }
+
+    fn refresh_shards(&mut self) -> Result<(), ReadError> {
+        let mut shards = Vec::new();
+        let mut next_token: Option<String> = None;
+        loop {
+            // The stream name can't be passed together with the pagination token
+            let request = match next_token.take() {
+                Some(token) => self.client.list_shards().next_token(token),
+                None => self.client.list_shards().stream_name(&self.stream_name),
+            };
+            let response = self
+                .runtime
+                .block_on(request.send())
+                .map_err(KinesisError::from)?;
+            shards.extend(response.shards().iter().map(|shard| {
+                KinesisShardDescription {
+                    shard_id: shard.shard_id().to_string(),
+                    parent_shard_ids: shard
+                        .parent_shard_id()
+                        .into_iter()
+                        .chain(shard.adjacent_parent_shard_id())
+                        .map(str::to_string)
+                        .collect(),
+                }
+            }));
+            match response.next_token() {
+                Some(token) => next_token = Some(token.to_string()),
+                None => break,
+            }
+        }
+
+        for shard_id in self.shard_tracker.ready_shards(&shards) {
+            let shard_id = ArcStr::from(shard_id);
+            self.start_shard(shard_id)?;
+        }
+        self.next_shard_refresh = Instant::now() + KINESIS_SHARD_REFRESH_INTERVAL;
+        Ok(())
+    }
+
+    fn start_shard(&mut self, shard_id: ArcStr) -> Result<(), ReadError> {
+        let request = self
+            .client
+            .get_shard_iterator()
+            .stream_name(&self.stream_name)
+            .shard_id(shard_id.as_str());
+        let request = match self.last_read_positions.get(&shard_id) {
+            // An aggregated record, which has been read partially, is read again,
+            // and its user records that have been read already are skipped
+            Some((sequence_number, Some(_))) => request
+                .shard_iterator_type(KinesisShardIteratorType::AtSequenceNumber)
+                .starting_sequence_number(sequence_number.as_str()),
+            Some((sequence_number, None)) => request
+                .shard_iterator_type(KinesisShardIteratorType::AfterSequenceNumber)
+                .starting_sequence_number(sequence_number.as_str()),
+            None => request.shard_iterator_type(KinesisShardIteratorType::TrimHorizon),
+        };
+        let response = self
+            .runtime
+            .block_on(request.send())
+            .map_err(KinesisError::from)?;
+        if let Some(shard_iterator) = response.shard_iterator() {
+            self.shard_iterators
+                .push_back((shard_id, shard_iterator.to_string()));
+        } else {
+            self.finish_shard(&shard_id);
+        }
+        Ok(())
+    }
+
+    fn finish_shard(&mut self, shard_id: &str) {
+        self.shard_tracker.finish(shard_id);
+        // The children of the shard can be started right away
+        self.next_shard_refresh = Instant::now();
+    }
+
+    /// Queues the user records of the Kinesis records, which are read from the
+    /// shard, skipping the ones that have been read already.
+    fn queue_records(&mut self, shard_id: &ArcStr, records: &[KinesisRecord]) {
+        for record in records {
+            let sequence_number = ArcStr::from(record.sequence_number());
+            let user_records =
+                deaggregate_kinesis_records(record.data().as_ref()).unwrap_or_else(|| {
+                    vec![KinesisUserRecord {
+                        partition_key: record.partition_key().to_string(),
+                        data: record.data().as_ref().to_vec(),
+                    }]
+                });
+            let n_user_records = user_records.len();
+            let first_unread_user_record = match self.last_read_positions.get(shard_id) {
+                Some((last_sequence_number, Some(last_sub_sequence_number)))
+                    if *last_sequence_number == sequence_number =>
+                {
+                    usize::try_from(*last_sub_sequence_number).map_or(usize::MAX, |index| index + 1)
+                }
+                _ => 0,
+            };
+            for (index, user_record) in user_records
+                .into_iter()
+                .enumerate()
+                .skip(first_unread_user_record)
+            {
+                // The position of the last user record is the whole Kinesis record
+                let sub_sequence_number = (index + 1 < n_user_records).then_some(index as u64);
+                let metadata = KinesisMetadata::new(
+                    self.stream_name.clone(),
+                    shard_id.to_string(),
+                    sequence_number.to_string(),
+                    user_record.partition_key,
+                    record
+                        .approximate_arrival_timestamp()
+                        .and_then(|timestamp| timestamp.to_millis().ok()),
+                );
+                let offset = (
+                    OffsetKey::Kinesis(shard_id.clone()),
+                    OffsetValue::KinesisRecordPosition {
+                        sequence_number: sequence_number.clone(),
+                        sub_sequence_number,
+                    },
+                );
+                self.pending_read_results
+                    .push_back(ReadResult::NewSource(metadata.into()));
+                self.pending_read_results.push_back(ReadResult::Data(
+                    ReaderContext::from_raw_bytes(DataEventType::Insert, user_record.data),
+                    offset,
+                ));
+            }
+            self.last_read_positions
+                .insert(shard_id.clone(), (sequence_number, None));
+        }
+    }
+}
+
+impl Reader for KinesisReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        loop {
+            if let Some(read_result) = self.pending_read_results.pop_front() {
+                return Ok(read_result);
+            }
+            if Instant::now() >= self.next_shard_refresh {
+                self.refresh_shards()?;
+            }
+            if self.idle_shards_in_row >= self.shard_iterators.len() {
+                self.idle_shards_in_row = 0;
+                sleep(KINESIS_IDLE_INTERVAL);
+                continue;
+            }
+
+            let (shard_id, shard_iterator) = self
+                .shard_iterators
+                .pop_front()
+                .expect("there must be a shard that is being read");
+            let response = self.runtime.block_on(
+                self.client
+                    .get_records()
+                    .shard_iterator(shard_iterator)
+                    .limit(KINESIS_GET_RECORDS_LIMIT)
+                    .send(),
+            );
+            let response = match response {
+                Ok(response) => response,
+                Err(e)
+                    if e.as_service_error()
+                        .is_some_and(KinesisGetRecordsError::is_expired_iterator_exception) =>
+                {
+                    // The iterators are valid for five minutes only
+                    self.start_shard(shard_id)?;
+                    continue;
+                }
+                Err(e)
+                    if e.as_service_error().is_some_and(
+                        KinesisGetRecordsError::is_provisioned_throughput_exceeded_exception,
+                    ) =>
+                {
+                    self.start_shard(shard_id)?;
+                    self.idle_shards_in_row += 1;
+                    continue;
+                }
+                Err(e) => return Err(KinesisError::from(e).into()),
+            };
+
+            self.queue_records(&shard_id, response.records());
+
+            let is_caught_up = response.millis_behind_latest().unwrap_or(0) == 0;
+            if response.records().is_empty() && is_caught_up {
+                self.idle_shards_in_row += 1;
+            } else {
+                self.idle_shards_in_row = 0;
+            }
+
+            if let Some(next_shard_iterator) = response.next_shard_iterator() {
+                self.shard_iterators
+                    .push_back((shard_id, next_shard_iterator.to_string()));
+            } else {
+                // The shard is closed after resharding, and all its records have been read
+                self.finish_shard(&shard_id);
+            }
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        for (offset_key, offset_value) in frontier {
+            if let (
+                OffsetKey::Kinesis(shard_id),
+                OffsetValue::KinesisRecordPosition {
+                    sequence_number,
+                    sub_sequence_number,
+                },
+            ) = (offset_key, offset_value)
+            {
+                self.last_read_positions.insert(
+                    shard_id.clone(),
+                    (sequence_number.clone(), *sub_sequence_number),
+                );
+            } else {
+                error!("Unexpected offset in Kinesis frontier: ({offset_key:?}, {offset_value:?})");
+            }
+        }
+        Ok(())
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::Kinesis
+    }
+
+    fn max_allowed_consecutive_errors(&self) -> usize {
+        32
+    }
+}
+
+/// `PutRecords` accepts up to 500 records, 5 MiB in total, in a single call.
+const KINESIS_MAX_BATCH_RECORDS: usize = 500;
+const KINESIS_MAX_BATCH_SIZE: usize = 5 * 1024 * 1024;
+
+const KINESIS_MAX_PUT_ATTEMPTS: usize = 8;
+
+/// Writes the rows to a Kinesis stream with `PutRecords`. The records are
+/// buffered until `flush` or until a full batch is collected. If some of the
+/// records of a batch fail, for instance, because the throughput of a shard
+/// is exceeded, only these records are sent again after a backoff.
+///
+/// The partition key is the value of the given column or the row key if the
+/// column isn't given.
+pub struct KinesisWriter {
+    runtime: TokioRuntime,
+    client: KinesisClient,
+    stream_name: String,
+    key_fields: Vec<(String, usize)>,
+    pending_records: Vec<KinesisRecordEntry>,
+    pending_size: usize,
+}
+
+impl KinesisWriter {
+    pub fn new(
+        runtime: TokioRuntime,
+        client: KinesisClient,
+        stream_name: String,
+        key_fields: Vec<(String, usize)>,
+    ) -> KinesisWriter {
+        KinesisWriter {
+            runtime,
+            client,
+            stream_name,
+            key_fields,
+            pending_records: Vec::new(),
+            pending_size: 0,
+        }
+    }
+
+    fn partition_key(&self, data: &FormatterContext) -> Result<String, WriteError> {
+        if self.key_fields.is_empty() {
+            return Ok(data.key.to_string());
+        }
+        let key = data.construct_message_key(&self.key_fields)?;
+        Ok(String::from_utf8(key).unwrap_or_else(|e| base64encoder.encode(e.as_bytes())))
+    }
+
+    fn put_records(&mut self) -> Result<(), WriteError> {
+        let mut records = take(&mut self.pending_records);
+        self.pending_size = 0;
+        let mut retry_config = RetryConfig::default();
+        for attempt in 1..=KINESIS_MAX_PUT_ATTEMPTS {
+            if records.is_empty() {
+                break;
+            }
+            let response = self
+                .runtime
+                .block_on(
+                    self.client
+                        .put_records()
+                        .stream_name(&self.stream_name)
+                        .set_records(Some(records.clone()))
+                        .send(),
+                )
+                .map_err(KinesisError::from)?;
+            if response.failed_record_count().unwrap_or(0) == 0 {
+                return Ok(());
+            }
+
+            let mut first_error = None;
+            records = records
+                .into_iter()
+                .zip(response.records())
+                .filter_map(|(record, result)| {
+                    let error_code = result.error_code()?;
+                    first_error.get_or_insert_with(|| {
+                        (
+                            error_code.to_string(),
+                            result.error_message().unwrap_or_default().to_string(),
+                        )
+                    });
+                    Some(record)
+                })
+                .collect();
+            if attempt == KINESIS_MAX_PUT_ATTEMPTS {
+                let (error_code, error_message) = first_error.unwrap_or_default();
+                return Err(WriteError::KinesisRecordsRejected {
+                    count: records.len(),
+                    error_code,
+                    error_message,
+                });
+            }
+            warn!(
+                "Kinesis has rejected {} records, retrying them (attempt {attempt})",
+                records.len()
+            );
+            retry_config.sleep_after_error();
+        }
+        Ok(())
+    }
+}
+
+impl Writer for KinesisWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        let partition_key = self.partition_key(&data)?;
+        for payload in data.payloads {
+            let payload = payload.into_raw_bytes()?;
+            let record_size = payload.len() + partition_key.len();
+            if self.pending_records.len() == KINESIS_MAX_BATCH_RECORDS
+                || self.pending_size + record_size > KINESIS_MAX_BATCH_SIZE
+            {
+                self.put_records()?;
+            }
+            let record = KinesisRecordEntry::builder()
+                .data(KinesisBlob::new(payload))
+                .partition_key(partition_key.clone())
+                .build()
+                .expect("the data and the partition key must be set");
+            self.pending_records.push(record);
+            self.pending_size += record_size;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
+        self.put_records()
+    }
+
+    fn retriable(&self) -> bool {
+        true
+    }
+
+    fn single_threaded(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for KinesisWriter {
+    fn drop(&mut self) {
+        self.flush(true).expect("failed to send the final records");
+    }
+}
+
+/// The ack deadline, in seconds, of the messages delivered to a Pub/Sub reader.
+pub const PUBSUB_ACK_DEADLINE_SECONDS: i32 = 60;
+
+/// The interval between the extensions of the ack deadlines of the messages
+/// that are read but not yet acknowledged. It is well below the deadline, so
+/// that a slow request doesn't let the messages expire.
+const PUBSUB_ACK_DEADLINE_EXTENSION_INTERVAL: Duration = Duration::from_secs(20);
+
+/// The maximum number of unacknowledged messages delivered to a reader.
+pub const PUBSUB_MAX_OUTSTANDING_MESSAGES: i64 = 1024;
+
+/// The maximum number of ack IDs sent in a single request, as in the official
+/// client libraries.
+const PUBSUB_MAX_ACK_IDS_PER_REQUEST: usize = 2500;
+
+const PUBSUB_ACK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reads the messages from a Pub/Sub subscription with a streaming pull. The
+/// subscription may be read by several workers, and the offset is the number
+/// of messages read by each of them.
+///
+/// A message is acknowledged only after it gets into the persisted frontier,
+/// so the messages that aren't persisted yet are redelivered after a restart.
+/// Until then, the reader keeps extending their ack deadlines, so that they
+/// aren't redelivered to another subscriber while a minibatch is in flight.
+/// Without persistence, the messages are acknowledged as soon as they are
+/// read.
+pub struct PubSubReader {
+    runtime: TokioRuntime,
+    subscription: PubSubSubscription,
+    stream: PubSubMessageStream,
+    worker_index: usize,
+    persistent_id: Option<PersistentId>,
+    total_entries_read: usize,
+    pending_acks: VecDeque<(usize, String)>,
+    last_deadline_extension: Instant,
+    persisted_frontier_receiver: Option<Receiver<OffsetAntichain>>,
+    deferred_read_result: Option<ReadResult>,
+}
+
+impl PubSubReader {
+    pub fn new(
+        runtime: TokioRuntime,
+        subscription: PubSubSubscription,
+        stream: PubSubMessageStream,
+        worker_index: usize,
+        persistent_id: Option<PersistentId>,
+    ) -> PubSubReader {
+        PubSubReader {
+            runtime,
+            subscription,
+            stream,
+            worker_index,
+            persistent_id,
+            total_entries_read: 0,
+            pending_acks: VecDeque::new(),
+            last_deadline_extension: Instant::now(),
+            persisted_frontier_receiver: None,
+            deferred_read_result: None,
+        }
+    }
+
+    fn ack_persisted_messages(&mut self) -> Result<(), ReadError> {
+        let Some(receiver) = &self.persisted_frontier_receiver else {
+            return self.ack_messages(usize::MAX);
+        };
+        let offset_key = OffsetKey::PubSub(self.worker_index);
+        let mut persisted_entries_count = None;
+        for frontier in receiver.try_iter() {
+            if let Some(OffsetValue::PubSubReadMessagesCount(count)) =
+                frontier.get_offset(&offset_key)
+            {
+                persisted_entries_count = Some(*count);
+            }
+        }
+        match persisted_entries_count {
+            Some(count) => self.ack_messages(count),
+            None => Ok(()),
+        }
+    }
+
+    fn ack_messages(&mut self, persisted_entries_count: usize) -> Result<(), ReadError> {
+        let mut ack_ids = Vec::new();
+        while let Some((entry_number, _)) = self.pending_acks.front() {
+            if *entry_number > persisted_entries_count {
+                break;
+            }
+            if let Some((_, ack_id)) = self.pending_acks.pop_front() {
+                ack_ids.push(ack_id);
+            }
+        }
+        self.runtime.block_on(async {
+            for chunk in ack_ids.chunks(PUBSUB_MAX_ACK_IDS_PER_REQUEST) {
+                self.subscription.ack(chunk.to_vec()).await?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Extends the ack deadlines of the messages that are read but not yet
+    /// persisted, so that the server doesn't redeliver them.
+    fn extend_ack_deadlines(&mut self) -> Result<(), ReadError> {
+        if self.last_deadline_extension.elapsed() < PUBSUB_ACK_DEADLINE_EXTENSION_INTERVAL {
+            return Ok(());
+        }
+        self.last_deadline_extension = Instant::now();
+        let ack_ids: Vec<String> = self
+            .pending_acks
+            .iter()
+            .map(|(_, ack_id)| ack_id.clone())
+            .collect();
+        let client = self.subscription.get_client();
+        let subscription_name = self.subscription.fully_qualified_name().to_string();
+        self.runtime.block_on(async {
+            for chunk in ack_ids.chunks(PUBSUB_MAX_ACK_IDS_PER_REQUEST) {
+                let request = PubSubModifyAckDeadlineRequest {
+                    subscription: subscription_name.clone(),
+                    ack_ids: chunk.to_vec(),
+                    ack_deadline_seconds: PUBSUB_ACK_DEADLINE_SECONDS,
+                };
+                client.modify_ack_deadline(request, None).await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Reader for PubSubReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        if let Some(deferred_read_result) = self.deferred_read_result.take() {
+            return Ok(deferred_read_result);
+        }
+
+        loop {
+            self.ack_persisted_messages()?;
+            self.extend_ack_deadlines()?;
+            // The waiting is limited, so that the messages get acknowledged
+            // and their deadlines get extended even if there are no new ones
+            let next_message = self.runtime.block_on(async {
+                tokio::time::timeout(PUBSUB_ACK_CHECK_INTERVAL, self.stream.read()).await
+            });
+            let Ok(next_message) = next_message else {
+                continue;
+            };
+            let Some(message) = next_message else {
+                return Ok(ReadResult::Finished);
+            };
+
+            self.total_entries_read += 1;
+            self.pending_acks
+                .push_back((self.total_entries_read, message.ack_id().to_string()));
+            let delivery_attempt = message.delivery_attempt();
+            let message = message.message;
+            let offset = (
+                OffsetKey::PubSub(self.worker_index),
+                OffsetValue::PubSubReadMessagesCount(self.total_entries_read),
+            );
+            let publish_time_millis = message
+                .publish_time
+                .map(|time| time.seconds * 1000 + i64::from(time.nanos) / 1_000_000);
+            let ordering_key = if message.ordering_key.is_empty() {
+                None
+            } else {
+                Some(message.ordering_key)
+            };
+            let metadata = PubSubMetadata::new(
+                self.subscription.fully_qualified_name().to_string(),
+                message.message_id,
+                publish_time_millis,
+                ordering_key,
+                delivery_attempt,
+                message.attributes.into_iter().collect(),
+            );
+            let payload = ReaderContext::from_raw_bytes(DataEventType::Insert, message.data);
+            self.deferred_read_result = Some(ReadResult::Data(payload, offset));
+            return Ok(ReadResult::NewSource(metadata.into()));
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        let offset_value = frontier.get_offset(&OffsetKey::PubSub(self.worker_index));
+        if let Some(offset) = offset_value {
+            if let OffsetValue::PubSubReadMessagesCount(last_run_entries_read) = offset {
+                self.total_entries_read = *last_run_entries_read;
+            } else {
+                error!("Unexpected offset type for Pub/Sub reader: {offset:?}");
+            }
+        }
+        Ok(())
+    }
+
+    fn set_persisted_frontier_receiver(&mut self, receiver: Receiver<OffsetAntichain>) {
+        self.persisted_frontier_receiver = Some(receiver);
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::PubSub
+    }
+
+    fn max_allowed_consecutive_errors(&self) -> usize {
+        32
+    }
+}
+
+/// Publishes the rows to a Pub/Sub topic. The ordering key of a message is
+/// taken from the key fields, if they are given, and the attributes are taken
+/// from the header fields. `flush` waits until the server confirms all
+/// messages published so far.
+pub struct PubSubWriter {
+    runtime: TokioRuntime,
+    publisher: PubSubPublisher,
+    header_fields: Vec<(String, usize)>,
+    key_fields: Vec<(String, usize)>,
+    pending_awaiters: Vec<PubSubAwaiter>,
+}
+
+impl PubSubWriter {
+    pub fn new(
+        runtime: TokioRuntime,
+        publisher: PubSubPublisher,
+        header_fields: Vec<(String, usize)>,
+        key_fields: Vec<(String, usize)>,
+    ) -> Self {
+        PubSubWriter {
+            runtime,
+            publisher,
+            header_fields,
+            key_fields,
+            pending_awaiters: Vec::new(),
+        }
+    }
+
+    /// Ordering keys are strings, so the keys that aren't valid UTF-8 are
+    /// encoded in base64. Without the key fields, the messages are unordered.
+    fn ordering_key(&self, data: &FormatterContext) -> Result<String, WriteError> {
+        if self.key_fields.is_empty() {
+            return Ok(String::new());
+        }
+        let key = data.construct_message_key(&self.key_fields)?;
+        Ok(String::from_utf8(key).unwrap_or_else(|e| base64encoder.encode(e.as_bytes())))
+    }
+}
+
+impl Writer for PubSubWriter {
+    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
+        let ordering_key = self.ordering_key(&data)?;
+        let attributes = data.construct_pubsub_attributes(&self.header_fields);
+        for payload in data.payloads {
+            let message = PubsubMessage {
+                data: payload.into_raw_bytes()?,
+                attributes: attributes.clone(),
+                ordering_key: ordering_key.clone(),
+                ..Default::default()
+            };
+            let awaiter = self.runtime.block_on(self.publisher.publish(message));
+            self.pending_awaiters.push(awaiter);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
+        self.runtime.block_on(async {
+            for awaiter in take(&mut self.pending_awaiters) {
+                awaiter.get().await?;
+            }
+            Ok(())
+        })
+    }
+
+    fn retriable(&self) -> bool {
+        true
+    }
+
+    fn single_threaded(&self) -> bool {
+        false
+    }
+}
+
+impl Drop for PubSubWriter {
+    fn drop(&mut self) {
+        self.flush(true).expect("failed to send the final messages");
+        self.runtime.block_on(self.publisher.shutdown());
+    }
+}
+
+const GRPC_ACK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reads the batches of rows that the clients stream to the gRPC server. The
+/// rows of a batch are put into the same timestamp, and the offset is the
+/// number of batches read.
+///
+/// A batch is acknowledged to its client only after it gets into the persisted
+/// frontier, so a client that hasn't received the acknowledgement before a
+/// restart may send the batch again. Without persistence, a batch is
+/// acknowledged as soon as the time advancement that closes it is passed to
+/// the engine.
+pub struct GrpcReader {
+    runtime: TokioRuntime,
+    server: Option<GrpcIngestServerHandle>,
+    batch_receiver: TokioMpscReceiver<GrpcIngestedBatch>,
+    persistent_id: Option<PersistentId>,
+    total_batches_read: usize,
+    pending_acks: VecDeque<(usize, TokioOneshotSender<()>)>,
+    persisted_frontier_receiver: Option<Receiver<OffsetAntichain>>,
+    deferred_read_results: VecDeque<ReadResult>,
+}
+
+impl GrpcReader {
+    pub fn new(
+        runtime: TokioRuntime,
+        server: Option<GrpcIngestServerHandle>,
+        batch_receiver: TokioMpscReceiver<GrpcIngestedBatch>,
+        persistent_id: Option<PersistentId>,
+    ) -> GrpcReader {
+        GrpcReader {
+            runtime,
+            server,
+            batch_receiver,
+            persistent_id,
+            total_batches_read: 0,
+            pending_acks: VecDeque::new(),
+            persisted_frontier_receiver: None,
+            deferred_read_results: VecDeque::new(),
+        }
+    }
+
+    fn ack_accepted_batches(&mut self) {
+        let accepted_batches_count = match &self.persisted_frontier_receiver {
+            Some(receiver) => {
+                let mut persisted_batches_count = None;
+                for frontier in receiver.try_iter() {
+                    if let Some(OffsetValue::GrpcReadBatchesCount(count)) =
+                        frontier.get_offset(&OffsetKey::Grpc)
+                    {
+                        persisted_batches_count = Some(*count);
+                    }
+                }
+                let Some(count) = persisted_batches_count else {
+                    return;
+                };
+                count
+            }
+            // All batches read so far have been passed to the engine
+            None => usize::MAX,
+        };
+        while let Some((batch_number, _)) = self.pending_acks.front() {
+            if *batch_number > accepted_batches_count {
+                break;
+            }
+            if let Some((_, accepted)) = self.pending_acks.pop_front() {
+                // The client may have gone already
+                let _ = accepted.send(());
+            }
+        }
+    }
+}
+
+impl Reader for GrpcReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        if let Some(deferred_read_result) = self.deferred_read_results.pop_front() {
+            return Ok(deferred_read_result);
+        }
+
+        loop {
+            self.ack_accepted_batches();
+            // The waiting is limited, so that the batches get acknowledged
+            // even if there are no new ones
+            let next_batch = self.runtime.block_on(async {
+                tokio::time::timeout(GRPC_ACK_CHECK_INTERVAL, self.batch_receiver.recv()).await
+            });
+            let Ok(next_batch) = next_batch else {
+                continue;
+            };
+            let Some(batch) = next_batch else {
+                return Ok(ReadResult::Finished);
+            };
+            if batch.rows.is_empty() {
+                let _ = batch.accepted.send(());
+                continue;
+            }
+
+            self.total_batches_read += 1;
+            self.pending_acks
+                .push_back((self.total_batches_read, batch.accepted));
+            let offset = (
+                OffsetKey::Grpc,
+                OffsetValue::GrpcReadBatchesCount(self.total_batches_read),
+            );
+            for row in batch.rows {
+                let payload = ReaderContext::from_raw_bytes(DataEventType::Insert, row);
+                self.deferred_read_results
+                    .push_back(ReadResult::Data(payload, offset.clone()));
+            }
+            self.deferred_read_results
+                .push_back(ReadResult::FinishedSource {
+                    commit_allowed: true,
+                });
+            let metadata = GrpcMetadata::new(
+                batch.peer_address.map(|address| address.to_string()),
+                batch.batch_id,
+            );
+            return Ok(ReadResult::NewSource(metadata.into()));
+        }
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        let offset_value = frontier.get_offset(&OffsetKey::Grpc);
+        if let Some(offset) = offset_value {
+            if let OffsetValue::GrpcReadBatchesCount(last_run_batches_read) = offset {
+                self.total_batches_read = *last_run_batches_read;
+            } else {
+                error!("Unexpected offset type for gRPC reader: {offset:?}");
             }
-            // Sleep to avoid non-stop pragma requests of a table
-            // that did not change
-            sleep(Self::wait_period());
         }
+        Ok(())
     }
 
-    fn storage_type(&self) -> StorageType {
-        StorageType::Sqlite
+    fn set_persisted_frontier_receiver(&mut self, receiver: Receiver<OffsetAntichain>) {
+        self.persisted_frontier_receiver = Some(receiver);
     }
 
     fn persistent_id(&self) -> Option<PersistentId> {
-        None
+        self.persistent_id
     }
 
     fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
-        if persistent_id.is_some() {
-            unimplemented!("persistence is not supported for Sqlite data source")
-        }
+        self.persistent_id = persistent_id;
     }
-}
 
-pub struct MongoWriter {
-    collection: MongoCollection<BsonDocument>,
-    buffer: Vec<BsonDocument>,
-    max_batch_size: Option<usize>,
+    fn storage_type(&self) -> StorageType {
+        StorageType::Grpc
+    }
 }
 
-impl MongoWriter {
-    pub fn new(collection: MongoCollection<BsonDocument>, max_batch_size: Option<usize>) -> Self {
-        Self {
-            collection,
-            max_batch_size,
-            buffer: Vec::new(),
-        }
+impl Drop for GrpcReader {
+    fn drop(&mut self) {
+        // The server is stopped before the batches that aren't acknowledged
+        // are dropped, so that the clients get the errors for them
+        self.server.take();
     }
 }
 
-impl Writer for MongoWriter {
-    fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
-        for payload in data.payloads {
-            self.buffer.push(payload.into_bson_document()?);
+/// The clients may wait for the processing of their requests, so the
+/// responses are checked for much more often than the acknowledgements of the
+/// other readers.
+const WEBHOOK_RESPONSE_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Reads the rows that the clients send to the webhook server. Each request
+/// is read as a separate source, so its rows get into the same timestamp.
+pub struct WebhookReader {
+    runtime: TokioRuntime,
+    server: Option<WebhookIngestServerHandle>,
+    request_receiver: TokioMpscReceiver<WebhookReceivedRequest>,
+    persistent_id: Option<PersistentId>,
+    wait_for_processing: bool,
+    total_requests_read: usize,
+    pending_responses: VecDeque<(usize, TokioOneshotSender<()>)>,
+    persisted_frontier_receiver: Option<Receiver<OffsetAntichain>>,
+    persisted_requests_count: usize,
+    processed_frontier_receiver: Option<Receiver<OffsetAntichain>>,
+    processed_requests_count: usize,
+    deferred_read_results: VecDeque<ReadResult>,
+}
+
+impl WebhookReader {
+    pub fn new(
+        runtime: TokioRuntime,
+        server: Option<WebhookIngestServerHandle>,
+        request_receiver: TokioMpscReceiver<WebhookReceivedRequest>,
+        persistent_id: Option<PersistentId>,
+        wait_for_processing: bool,
+    ) -> WebhookReader {
+        WebhookReader {
+            runtime,
+            server,
+            request_receiver,
+            persistent_id,
+            wait_for_processing,
+            total_requests_read: 0,
+            pending_responses: VecDeque::new(),
+            persisted_frontier_receiver: None,
+            persisted_requests_count: 0,
+            processed_frontier_receiver: None,
+            processed_requests_count: 0,
+            deferred_read_results: VecDeque::new(),
         }
-        if let Some(max_batch_size) = self.max_batch_size {
-            if self.buffer.len() >= max_batch_size {
-                self.flush(true)?;
+    }
+
+    fn update_requests_count(receiver: Option<&Receiver<OffsetAntichain>>, count: &mut usize) {
+        let Some(receiver) = receiver else {
+            return;
+        };
+        for frontier in receiver.try_iter() {
+            if let Some(OffsetValue::WebhookReadRequestsCount(new_count)) =
+                frontier.get_offset(&OffsetKey::Webhook)
+            {
+                *count = *new_count;
             }
         }
-        Ok(())
     }
 
-    fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
-        if self.buffer.is_empty() {
-            return Ok(());
+    /// Responds to the requests that have been accepted. A request is
+    /// accepted once it's persisted, if the persistence is enabled, and once
+    /// it's processed, if the clients wait for it. Otherwise, it's accepted
+    /// right after reading, as it has been passed to the engine.
+    fn respond_to_accepted_requests(&mut self) {
+        Self::update_requests_count(
+            self.persisted_frontier_receiver.as_ref(),
+            &mut self.persisted_requests_count,
+        );
+        Self::update_requests_count(
+            self.processed_frontier_receiver.as_ref(),
+            &mut self.processed_requests_count,
+        );
+        let mut accepted_requests_count = usize::MAX;
+        if self.persisted_frontier_receiver.is_some() {
+            accepted_requests_count = min(accepted_requests_count, self.persisted_requests_count);
+        }
+        if self.processed_frontier_receiver.is_some() {
+            accepted_requests_count = min(accepted_requests_count, self.processed_requests_count);
+        }
+        while let Some((request_number, _)) = self.pending_responses.front() {
+            if *request_number > accepted_requests_count {
+                break;
+            }
+            if let Some((_, accepted)) = self.pending_responses.pop_front() {
+                // The client may have gone already
+                let _ = accepted.send(());
+            }
         }
-        let command = self.collection.insert_many(take(&mut self.buffer));
-        let _ = command.run()?;
-        Ok(())
     }
 }
 
-pub struct NatsReader {
-    runtime: TokioRuntime,
-    subscriber: NatsSubscriber,
-    worker_index: usize,
-    persistent_id: Option<PersistentId>,
-    total_entries_read: usize,
-}
-
-impl Reader for NatsReader {
+impl Reader for WebhookReader {
     fn read(&mut self) -> Result<ReadResult, ReadError> {
-        if let Some(message) = self.runtime.block_on(async {
-            self.subscriber
-                .next()
+        if let Some(deferred_read_result) = self.deferred_read_results.pop_front() {
+            return Ok(deferred_read_result);
+        }
+
+        loop {
+            self.respond_to_accepted_requests();
+            let next_request = self.runtime.block_on(async {
+                tokio::time::timeout(
+                    WEBHOOK_RESPONSE_CHECK_INTERVAL,
+                    self.request_receiver.recv(),
+                )
                 .await
-                .map(|message| message.payload.to_vec())
-        }) {
-            let payload = ReaderContext::from_raw_bytes(DataEventType::Insert, message);
-            self.total_entries_read += 1;
+            });
+            let Ok(next_request) = next_request else {
+                continue;
+            };
+            let Some(request) = next_request else {
+                return Ok(ReadResult::Finished);
+            };
+
+            self.total_requests_read += 1;
+            self.pending_responses
+                .push_back((self.total_requests_read, request.accepted));
             let offset = (
-                OffsetKey::Nats(self.worker_index),
-                OffsetValue::NatsReadEntriesCount(self.total_entries_read),
+                OffsetKey::Webhook,
+                OffsetValue::WebhookReadRequestsCount(self.total_requests_read),
             );
-            Ok(ReadResult::Data(payload, offset))
-        } else {
-            Ok(ReadResult::Finished)
+            for row in request.rows {
+                let payload = ReaderContext::from_raw_bytes(DataEventType::Insert, row);
+                self.deferred_read_results
+                    .push_back(ReadResult::Data(payload, offset.clone()));
+            }
+            self.deferred_read_results
+                .push_back(ReadResult::FinishedSource {
+                    commit_allowed: true,
+                });
+            let metadata = WebhookMetadata::new(
+                request.route,
+                request.peer_address.map(|address| address.to_string()),
+            );
+            return Ok(ReadResult::NewSource(metadata.into()));
         }
     }
 
     fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
-        let offset_value = frontier.get_offset(&OffsetKey::Nats(self.worker_index));
+        let offset_value = frontier.get_offset(&OffsetKey::Webhook);
         if let Some(offset) = offset_value {
-            if let OffsetValue::NatsReadEntriesCount(last_run_entries_read) = offset {
-                self.total_entries_read = *last_run_entries_read;
+            if let OffsetValue::WebhookReadRequestsCount(last_run_requests_read) = offset {
+                self.total_requests_read = *last_run_requests_read;
             } else {
-                error!("Unexpected offset type for NATS reader: {offset:?}");
+                error!("Unexpected offset type for webhook reader: {offset:?}");
             }
         }
         Ok(())
     }
 
+    fn set_persisted_frontier_receiver(&mut self, receiver: Receiver<OffsetAntichain>) {
+        self.persisted_frontier_receiver = Some(receiver);
+    }
+
+    fn processed_frontier_needed(&self) -> bool {
+        self.wait_for_processing
+    }
+
+    fn set_processed_frontier_receiver(&mut self, receiver: Receiver<OffsetAntichain>) {
+        self.processed_frontier_receiver = Some(receiver);
+    }
+
     fn persistent_id(&self) -> Option<PersistentId> {
         self.persistent_id
     }
@@ -1685,66 +6312,193 @@ impl Reader for NatsReader {
     }
 
     fn storage_type(&self) -> StorageType {
-        StorageType::Nats
+        StorageType::Webhook
     }
+}
 
-    fn max_allowed_consecutive_errors(&self) -> usize {
-        32
+impl Drop for WebhookReader {
+    fn drop(&mut self) {
+        // The server is stopped before the pending responses are dropped, so
+        // that the clients get the errors for them
+        self.server.take();
     }
 }
 
-impl NatsReader {
+/// The retries of a webhook request, after the failures that may be transient.
+pub struct WebhookRetryPolicy {
+    pub max_retries: usize,
+    pub initial_delay: Duration,
+    pub backoff_factor: f64,
+    pub jitter: Duration,
+}
+
+impl WebhookRetryPolicy {
+    /// The longest delay of the backoff, the one before the last retry, not
+    /// counting the jitter.
+    pub fn max_delay(&self) -> Duration {
+        let backoff_steps = i32::try_from(self.max_retries.saturating_sub(1)).unwrap_or(i32::MAX);
+        Duration::try_from_secs_f64(
+            self.initial_delay.as_secs_f64() * self.backoff_factor.powi(backoff_steps),
+        )
+        .unwrap_or(Duration::MAX)
+    }
+}
+
+/// Sends the formatted rows in POST requests to the URLs built from a template.
+/// The rows going to the same URL are sent in a single request, which is
+/// signed, if the secret is given.
+///
+/// A request that fails with a connection error, a server error or the status
+/// 429 is retried with a backoff. For 429 with the `Retry-After` header, the
+/// client waits for the time from the header instead of the backoff, but not
+/// longer than the longest delay of the backoff, so that a server can't block
+/// the output. A request that fails with another client error isn't retried, as
+/// it would fail again.
+pub struct WebhookWriter {
+    client: HttpClient,
+    url_template: WebhookUrlTemplate,
+    batch_format: WebhookBatchFormat,
+    headers: HttpHeaderMap,
+    signature: Option<(HttpHeaderName, Vec<u8>)>,
+    max_batch_size: Option<usize>,
+    retry_policy: WebhookRetryPolicy,
+    batches: BTreeMap<String, Vec<Vec<u8>>>,
+    buffered_rows_count: usize,
+}
+
+/// A failed request of `WebhookWriter`. A retriable failure may carry the delay
+/// from the `Retry-After` header.
+enum WebhookRequestError {
+    Retriable(WriteError, Option<Duration>),
+    Fatal(WriteError),
+}
+
+impl WebhookWriter {
     pub fn new(
-        runtime: TokioRuntime,
-        subscriber: NatsSubscriber,
-        worker_index: usize,
-        persistent_id: Option<PersistentId>,
-    ) -> NatsReader {
-        NatsReader {
-            runtime,
-            subscriber,
-            worker_index,
-            persistent_id,
-            total_entries_read: 0,
+        client: HttpClient,
+        url_template: WebhookUrlTemplate,
+        batch_format: WebhookBatchFormat,
+        headers: HttpHeaderMap,
+        signature: Option<(HttpHeaderName, Vec<u8>)>,
+        max_batch_size: Option<usize>,
+        retry_policy: WebhookRetryPolicy,
+    ) -> WebhookWriter {
+        WebhookWriter {
+            client,
+            url_template,
+            batch_format,
+            headers,
+            signature,
+            max_batch_size,
+            retry_policy,
+            batches: BTreeMap::new(),
+            buffered_rows_count: 0,
         }
     }
-}
 
-pub struct NatsWriter {
-    runtime: TokioRuntime,
-    client: NatsClient,
-    topic: String,
-    header_fields: Vec<(String, usize)>,
+    fn send_batch(&self, url: &str, payloads: &[Vec<u8>]) -> Result<(), WriteError> {
+        let body = self.batch_format.join(payloads);
+        let mut headers = self.headers.clone();
+        headers.insert(
+            HTTP_CONTENT_TYPE,
+            HttpHeaderValue::from_static(self.batch_format.content_type()),
+        );
+        if let Some((header_name, secret)) = &self.signature {
+            let signature = HttpHeaderValue::from_str(&sign_body(secret, &body))
+                .expect("signature must be a valid header value");
+            headers.insert(header_name.clone(), signature);
+        }
+
+        let retry_config = RetryConfig::new(
+            self.retry_policy.initial_delay,
+            self.retry_policy.backoff_factor,
+            self.retry_policy.jitter,
+        );
+        let max_delay = self.retry_policy.max_delay();
+        // The fatal failures are returned as successful attempts, so that they
+        // aren't retried
+        let result = execute_with_retries_and_delays(
+            || match self.send_request(url, &headers, &body) {
+                Ok(()) => Ok(Ok(())),
+                Err(WebhookRequestError::Fatal(error)) => Ok(Err(error)),
+                Err(WebhookRequestError::Retriable(error, retry_after)) => {
+                    warn!("The request to {url} has failed: {error}");
+                    Err((error, retry_after))
+                }
+            },
+            |(_, retry_after)| retry_after.map(|retry_after| retry_after.min(max_delay)),
+            retry_config,
+            self.retry_policy.max_retries,
+        );
+        result.map_err(|(error, _)| error)?
+    }
+
+    fn send_request(
+        &self,
+        url: &str,
+        headers: &HttpHeaderMap,
+        body: &[u8],
+    ) -> Result<(), WebhookRequestError> {
+        let response = self
+            .client
+            .post(url)
+            .headers(headers.clone())
+            .body(body.to_vec())
+            .send()
+            .map_err(|e| WebhookRequestError::Retriable(e.into(), None))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let retry_after = response
+            .headers()
+            .get(HTTP_RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, SystemTime::now()));
+        let error = WriteError::HttpRequestFailed {
+            url: url.to_string(),
+            status,
+            response: response.text().unwrap_or_default(),
+        };
+        if status == HttpStatusCode::TOO_MANY_REQUESTS {
+            Err(WebhookRequestError::Retriable(error, retry_after))
+        } else if status.is_server_error() || status == HttpStatusCode::REQUEST_TIMEOUT {
+            Err(WebhookRequestError::Retriable(error, None))
+        } else {
+            Err(WebhookRequestError::Fatal(error))
+        }
+    }
 }
 
-impl Writer for NatsWriter {
+impl Writer for WebhookWriter {
     fn write(&mut self, data: FormatterContext) -> Result<(), WriteError> {
-        self.runtime.block_on(async {
-            let last_payload_index = data.payloads.len() - 1;
-            let mut common_headers = data.construct_nats_headers(&self.header_fields);
-            for (index, payload) in data.payloads.into_iter().enumerate() {
-                // Avoid copying data on the last iteration, reuse the existing headers
-                let headers = {
-                    if index == last_payload_index {
-                        take(&mut common_headers)
-                    } else {
-                        common_headers.clone()
-                    }
-                };
-                let payload = payload.into_raw_bytes()?;
-                self.client
-                    .publish_with_headers(self.topic.clone(), headers, payload.into())
-                    .await
-                    .map_err(WriteError::NatsPublish)?;
+        // The full batches are sent before the new rows are added, so that if
+        // sending fails, the retried write doesn't add the same rows twice
+        if let Some(max_batch_size) = self.max_batch_size {
+            if self.buffered_rows_count >= max_batch_size {
+                self.flush(true)?;
             }
-            Ok(())
-        })
+        }
+
+        let url = self.url_template.render(&data.values, data.time, data.diff);
+        let batch = self.batches.entry(url).or_default();
+        for payload in data.payloads {
+            batch.push(payload.into_raw_bytes()?);
+            self.buffered_rows_count += 1;
+        }
+        Ok(())
     }
 
     fn flush(&mut self, _forced: bool) -> Result<(), WriteError> {
-        self.runtime
-            .block_on(async { self.client.flush().await })
-            .map_err(WriteError::NatsFlush)
+        while let Some((url, payloads)) = self.batches.pop_first() {
+            if let Err(e) = self.send_batch(&url, &payloads) {
+                // The batch is kept, so that it's sent on the next flush
+                self.batches.insert(url, payloads);
+                return Err(e);
+            }
+            self.buffered_rows_count -= payloads.len();
+        }
+        Ok(())
     }
 
     fn retriable(&self) -> bool {
@@ -1756,24 +6510,83 @@ impl Writer for NatsWriter {
     }
 }
 
-impl Drop for NatsWriter {
+impl Drop for WebhookWriter {
     fn drop(&mut self) {
-        self.flush(true).expect("failed to send the final messages");
+        self.flush(true).expect("failed to send the final batches");
     }
 }
 
-impl NatsWriter {
+/// Reads the messages received by a TCP or a UDP listener. The listener runs
+/// only in one worker, so the readers of the other workers finish right away.
+pub struct SocketReader {
+    protocol: SocketProtocol,
+    // Stops the listener when the reader is dropped
+    _listener: Option<SocketListenerHandle>,
+    message_receiver: Receiver<SocketReceivedMessage>,
+    persistent_id: Option<PersistentId>,
+    total_messages_read: usize,
+    deferred_read_result: Option<ReadResult>,
+}
+
+impl SocketReader {
     pub fn new(
-        runtime: TokioRuntime,
-        client: NatsClient,
-        topic: String,
-        header_fields: Vec<(String, usize)>,
-    ) -> Self {
-        NatsWriter {
-            runtime,
-            client,
-            topic,
-            header_fields,
+        protocol: SocketProtocol,
+        listener: Option<SocketListenerHandle>,
+        message_receiver: Receiver<SocketReceivedMessage>,
+        persistent_id: Option<PersistentId>,
+    ) -> SocketReader {
+        SocketReader {
+            protocol,
+            _listener: listener,
+            message_receiver,
+            persistent_id,
+            total_messages_read: 0,
+            deferred_read_result: None,
+        }
+    }
+}
+
+impl Reader for SocketReader {
+    fn read(&mut self) -> Result<ReadResult, ReadError> {
+        if let Some(deferred_read_result) = self.deferred_read_result.take() {
+            return Ok(deferred_read_result);
+        }
+
+        let Ok(message) = self.message_receiver.recv() else {
+            return Ok(ReadResult::Finished);
+        };
+        self.total_messages_read += 1;
+        let offset = (
+            OffsetKey::Socket,
+            OffsetValue::SocketReadMessagesCount(self.total_messages_read),
+        );
+        let payload = ReaderContext::from_raw_bytes(DataEventType::Insert, message.payload);
+        self.deferred_read_result = Some(ReadResult::Data(payload, offset));
+        let metadata = SocketMetadata::new(self.protocol.name(), message.peer_address);
+        Ok(ReadResult::NewSource(metadata.into()))
+    }
+
+    fn seek(&mut self, frontier: &OffsetAntichain) -> Result<(), ReadError> {
+        let offset_value = frontier.get_offset(&OffsetKey::Socket);
+        if let Some(offset) = offset_value {
+            if let OffsetValue::SocketReadMessagesCount(last_run_messages_read) = offset {
+                self.total_messages_read = *last_run_messages_read;
+            } else {
+                error!("Unexpected offset type for socket reader: {offset:?}");
+            }
         }
+        Ok(())
+    }
+
+    fn persistent_id(&self) -> Option<PersistentId> {
+        self.persistent_id
+    }
+
+    fn update_persistent_id(&mut self, persistent_id: Option<PersistentId>) {
+        self.persistent_id = persistent_id;
+    }
+
+    fn storage_type(&self) -> StorageType {
+        StorageType::Socket
     }
 }
@@ -1,14 +1,22 @@
 // Copyright © 2024 Pathway
 
+use log::error;
+use std::collections::HashMap;
 use std::io::BufReader;
 use std::io::Read;
 use std::mem::take;
 
+use bytes::Bytes;
 use csv::Reader as CsvReader;
 use csv::ReaderBuilder as CsvReaderBuilder;
+use deltalake::parquet::file::reader::{FileReader as ParquetFileReader, SerializedFileReader};
+use deltalake::parquet::file::serialized_reader::ReadOptionsBuilder as ParquetReadOptionsBuilder;
+use deltalake::parquet::record::reader::RowIter as ParquetRowIterator;
 
+use crate::connectors::data_lake::parquet_row_into_values_map;
 use crate::connectors::data_storage::ReadMethod;
 use crate::connectors::{DataEventType, ReadError, ReaderContext};
+use crate::engine::Type;
 
 type TokenizedEntry = (ReaderContext, u64); // The second value is a position of the record within the object read
 
@@ -19,6 +27,28 @@ pub trait Tokenize: Send + 'static {
         data_event_type: DataEventType,
     ) -> Result<(), ReadError>;
     fn next_entry(&mut self) -> Result<Option<TokenizedEntry>, ReadError>;
+
+    /// Whether the reading of an object can be resumed from the position of
+    /// one of its entries.
+    fn supports_resuming(&self) -> bool {
+        false
+    }
+
+    /// Whether the entries returned so far can be committed, so that the
+    /// reading is resumed right after them on restart.
+    fn is_at_resumable_position(&self) -> bool {
+        false
+    }
+
+    /// Sets a new object to be read after the entry with the given position.
+    /// Returns `false` if nothing is left to read in the object.
+    fn set_resumed_reader(
+        &mut self,
+        _source: Box<dyn Read + Send + 'static>,
+        _position: u64,
+    ) -> Result<bool, ReadError> {
+        Ok(false)
+    }
 }
 
 pub struct CsvTokenizer {
@@ -129,3 +159,159 @@ impl Tokenize for BufReaderTokenizer {
         }
     }
 }
+
+/// Reads the rows of Parquet files. The position of a row has the index of its
+/// row group in the upper half and the number of rows read from this row group
+/// in the lower half, so the reading can be resumed from a row group boundary
+/// without decoding the preceding row groups.
+pub struct ParquetTokenizer {
+    column_types: HashMap<String, Type>,
+    current_event_type: DataEventType,
+    rows: Option<ParquetRowIterator<'static>>,
+    row_group_sizes: Vec<u64>,
+    current_row_group: usize,
+    rows_read_within_row_group: u64,
+}
+
+impl ParquetTokenizer {
+    const ROW_GROUP_INDEX_SHIFT: u32 = 32;
+
+    pub fn new(column_types: HashMap<String, Type>) -> Self {
+        Self {
+            column_types,
+            current_event_type: DataEventType::Insert,
+            rows: None,
+            row_group_sizes: Vec::new(),
+            current_row_group: 0,
+            rows_read_within_row_group: 0,
+        }
+    }
+
+    /// Reads the object and its metadata. Returns the object contents and the
+    /// reader of all its row groups, or `None` if the object isn't a valid
+    /// Parquet file.
+    fn reset_contents(
+        &mut self,
+        mut source: Box<dyn Read + Send + 'static>,
+        data_event_type: DataEventType,
+    ) -> Result<Option<(Bytes, SerializedFileReader<Bytes>)>, ReadError> {
+        self.rows = None;
+        self.row_group_sizes.clear();
+        self.current_row_group = 0;
+        self.rows_read_within_row_group = 0;
+        self.current_event_type = data_event_type;
+
+        let mut contents = Vec::new();
+        source.read_to_end(&mut contents)?;
+        let contents = Bytes::from(contents);
+        let reader = match SerializedFileReader::new(contents.clone()) {
+            Ok(reader) => reader,
+            Err(e) => {
+                // The object may be incomplete yet, then it will be reread on the next change.
+                error!("Failed to read Parquet file metadata: {e}");
+                return Ok(None);
+            }
+        };
+        self.row_group_sizes = reader
+            .metadata()
+            .row_groups()
+            .iter()
+            .map(|row_group| u64::try_from(row_group.num_rows()).unwrap_or(0))
+            .collect();
+        Ok(Some((contents, reader)))
+    }
+
+    fn row_position(&self) -> u64 {
+        ((self.current_row_group as u64) << Self::ROW_GROUP_INDEX_SHIFT)
+            | self.rows_read_within_row_group
+    }
+}
+
+impl Tokenize for ParquetTokenizer {
+    fn set_new_reader(
+        &mut self,
+        source: Box<dyn Read + Send + 'static>,
+        data_event_type: DataEventType,
+    ) -> Result<(), ReadError> {
+        if let Some((_, reader)) = self.reset_contents(source, data_event_type)? {
+            self.rows = Some(ParquetRowIterator::from_file_into(Box::new(reader)));
+        }
+        Ok(())
+    }
+
+    fn next_entry(&mut self) -> Result<Option<TokenizedEntry>, ReadError> {
+        let Some(rows) = self.rows.as_mut() else {
+            return Ok(None);
+        };
+        let Some(row) = rows.next().transpose()? else {
+            self.rows = None;
+            return Ok(None);
+        };
+        while self
+            .row_group_sizes
+            .get(self.current_row_group)
+            .is_some_and(|size| self.rows_read_within_row_group >= *size)
+        {
+            self.current_row_group += 1;
+            self.rows_read_within_row_group = 0;
+        }
+        self.rows_read_within_row_group += 1;
+        let values = parquet_row_into_values_map(&row, &self.column_types);
+        Ok(Some((
+            ReaderContext::from_diff(self.current_event_type, None, values),
+            self.row_position(),
+        )))
+    }
+
+    fn supports_resuming(&self) -> bool {
+        true
+    }
+
+    fn is_at_resumable_position(&self) -> bool {
+        self.rows_read_within_row_group > 0
+            && self.row_group_sizes.get(self.current_row_group)
+                == Some(&self.rows_read_within_row_group)
+    }
+
+    fn set_resumed_reader(
+        &mut self,
+        source: Box<dyn Read + Send + 'static>,
+        position: u64,
+    ) -> Result<bool, ReadError> {
+        let Some((contents, _)) = self.reset_contents(source, DataEventType::Insert)? else {
+            return Ok(false);
+        };
+        let row_group = usize::try_from(position >> Self::ROW_GROUP_INDEX_SHIFT)
+            .expect("row group index must fit into usize");
+        let rows_read = position & ((1 << Self::ROW_GROUP_INDEX_SHIFT) - 1);
+        let (first_row_group, rows_to_skip) =
+            if self.row_group_sizes.get(row_group) == Some(&rows_read) {
+                (row_group + 1, 0)
+            } else {
+                (row_group, rows_read)
+            };
+        let rows_left = self
+            .row_group_sizes
+            .get(first_row_group..)
+            .unwrap_or_default()
+            .iter()
+            .sum::<u64>()
+            .saturating_sub(rows_to_skip);
+        if rows_left == 0 {
+            return Ok(false);
+        }
+
+        let options = ParquetReadOptionsBuilder::new()
+            .with_predicate(Box::new(move |_, index| index >= first_row_group))
+            .build();
+        let reader = SerializedFileReader::new_with_options(contents, options)?;
+        let mut rows = ParquetRowIterator::from_file_into(Box::new(reader));
+        for _ in 0..rows_to_skip {
+            rows.next().transpose()?;
+        }
+        self.rows = Some(rows);
+        self.current_row_group = first_row_group;
+        self.rows_read_within_row_group = rows_to_skip;
+        Ok(true)
+    }
+}
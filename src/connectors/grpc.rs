@@ -0,0 +1,340 @@
+// Copyright © 2024 Pathway
+
+//! The gRPC service of the gRPC input connector. The clients stream the
+//! batches of rows to it, and each batch is acknowledged once the reader has
+//! accepted it.
+//!
+//! The service is defined in `python/pathway/io/grpc/ingest.proto`. The
+//! messages and the server below are written by hand in the same way as
+//! `tonic-build` generates them, so that the build doesn't need `protoc`.
+
+use std::convert::Infallible;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::thread::Builder;
+
+use futures::stream;
+use log::error;
+use prost_types::value::Kind as ProtobufValueKind;
+use prost_types::{Struct as ProtobufStruct, Value as ProtobufValue};
+use serde_json::{Map as JsonMap, Number as JsonNumber, Value as JsonValue};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use tonic::body::BoxBody;
+use tonic::codegen::tokio_stream::wrappers::TcpListenerStream;
+use tonic::codegen::{empty_body, http, Body, BoxFuture, BoxStream, Service, StdError};
+use tonic::server::{Grpc, NamedService, StreamingService};
+use tonic::transport::Server;
+use tonic::{Code, Request, Response, Status, Streaming};
+
+/// The maximum number of batches of a single stream that are sent to the
+/// reader, but not yet acknowledged. A client that sends more has to wait.
+const GRPC_MAX_BATCHES_IN_FLIGHT: usize = 64;
+
+/// The number of batches received by the server, but not yet taken by the
+/// reader. When the queue is full, the clients have to wait.
+pub const GRPC_READER_QUEUE_SIZE: usize = 16;
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RowBatch {
+    #[prost(string, tag = "1")]
+    pub table: String,
+    #[prost(uint64, tag = "2")]
+    pub batch_id: u64,
+    #[prost(message, repeated, tag = "3")]
+    pub rows: Vec<Row>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Row {
+    #[prost(oneof = "RowPayload", tags = "1, 2, 3")]
+    pub payload: Option<RowPayload>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum RowPayload {
+    #[prost(bytes, tag = "1")]
+    Json(Vec<u8>),
+    #[prost(message, tag = "2")]
+    Fields(ProtobufStruct),
+    #[prost(bytes, tag = "3")]
+    Raw(Vec<u8>),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BatchAck {
+    #[prost(uint64, tag = "1")]
+    pub batch_id: u64,
+    #[prost(uint64, tag = "2")]
+    pub rows_accepted: u64,
+}
+
+/// A batch of rows received by the server. The reader notifies the client via
+/// `accepted` once the batch is accepted; if it's dropped instead, the client
+/// gets an error.
+#[derive(Debug)]
+pub struct IngestedBatch {
+    pub peer_address: Option<SocketAddr>,
+    pub batch_id: u64,
+    pub rows: Vec<Vec<u8>>,
+    pub accepted: oneshot::Sender<()>,
+}
+
+/// Converts a generic protobuf structure into a JSON object. Protobuf has only
+/// floating point numbers, so the whole numbers are converted into the JSON
+/// integers, which can be parsed into the integer columns.
+pub fn protobuf_struct_to_json(value: ProtobufStruct) -> JsonValue {
+    JsonValue::Object(
+        value
+            .fields
+            .into_iter()
+            .map(|(name, value)| (name, protobuf_value_to_json(value)))
+            .collect::<JsonMap<_, _>>(),
+    )
+}
+
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_precision_loss)]
+fn protobuf_value_to_json(value: ProtobufValue) -> JsonValue {
+    match value.kind {
+        None | Some(ProtobufValueKind::NullValue(_)) => JsonValue::Null,
+        Some(ProtobufValueKind::BoolValue(value)) => JsonValue::Bool(value),
+        Some(ProtobufValueKind::NumberValue(value)) => {
+            if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+                JsonValue::Number((value as i64).into())
+            } else {
+                JsonNumber::from_f64(value).map_or(JsonValue::Null, JsonValue::Number)
+            }
+        }
+        Some(ProtobufValueKind::StringValue(value)) => JsonValue::String(value),
+        Some(ProtobufValueKind::StructValue(value)) => protobuf_struct_to_json(value),
+        Some(ProtobufValueKind::ListValue(value)) => JsonValue::Array(
+            value
+                .values
+                .into_iter()
+                .map(protobuf_value_to_json)
+                .collect(),
+        ),
+    }
+}
+
+/// Returns the payload of a row, as it is passed to the parser.
+pub fn row_payload(row: Row) -> Result<Vec<u8>, Status> {
+    match row.payload {
+        Some(RowPayload::Json(payload) | RowPayload::Raw(payload)) => Ok(payload),
+        Some(RowPayload::Fields(fields)) => {
+            Ok(protobuf_struct_to_json(fields).to_string().into_bytes())
+        }
+        None => Err(Status::invalid_argument("a row must have a payload")),
+    }
+}
+
+type PendingAck = Result<(u64, u64, oneshot::Receiver<()>), Status>;
+
+#[derive(Clone, Debug)]
+pub struct IngestServer {
+    table: Arc<str>,
+    batch_sender: mpsc::Sender<IngestedBatch>,
+}
+
+impl IngestServer {
+    pub fn new(table: String, batch_sender: mpsc::Sender<IngestedBatch>) -> Self {
+        Self {
+            table: table.into(),
+            batch_sender,
+        }
+    }
+
+    async fn send_batch(&self, peer_address: Option<SocketAddr>, batch: RowBatch) -> PendingAck {
+        if batch.table != *self.table {
+            return Err(Status::not_found(format!(
+                "table {:?} is not served by this endpoint",
+                batch.table
+            )));
+        }
+        let rows = batch
+            .rows
+            .into_iter()
+            .map(row_payload)
+            .collect::<Result<Vec<_>, _>>()?;
+        let rows_count = rows.len() as u64;
+        let (accepted, accepted_receiver) = oneshot::channel();
+        self.batch_sender
+            .send(IngestedBatch {
+                peer_address,
+                batch_id: batch.batch_id,
+                rows,
+                accepted,
+            })
+            .await
+            .map_err(|_| Status::unavailable("the reader has stopped"))?;
+        Ok((batch.batch_id, rows_count, accepted_receiver))
+    }
+
+    /// Forwards the batches of a stream to the reader and returns the stream
+    /// of their acknowledgements. The batches are read from the client only
+    /// while the number of the batches in flight is below the limit, and while
+    /// the reader has the room for them, so a fast client is slowed down.
+    fn stream_rows(&self, request: Request<Streaming<RowBatch>>) -> BoxStream<BatchAck> {
+        let peer_address = request.remote_addr();
+        let mut inbound = request.into_inner();
+        let (pending_acks, pending_acks_receiver) = mpsc::channel(GRPC_MAX_BATCHES_IN_FLIGHT);
+        let server = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let pending_ack = match inbound.message().await {
+                    Ok(Some(batch)) => server.send_batch(peer_address, batch).await,
+                    Ok(None) => break,
+                    Err(status) => Err(status),
+                };
+                let failed = pending_ack.is_err();
+                if pending_acks.send(pending_ack).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Box::pin(stream::unfold(
+            pending_acks_receiver,
+            |mut pending_acks_receiver| async move {
+                let (batch_id, rows_accepted, accepted) = match pending_acks_receiver.recv().await?
+                {
+                    Ok(pending_ack) => pending_ack,
+                    Err(status) => return Some((Err(status), pending_acks_receiver)),
+                };
+                let ack = accepted
+                    .await
+                    .map(|()| BatchAck {
+                        batch_id,
+                        rows_accepted,
+                    })
+                    .map_err(|_| Status::unavailable("the batch has not been accepted"));
+                Some((ack, pending_acks_receiver))
+            },
+        ))
+    }
+}
+
+struct StreamRowsService(IngestServer);
+
+impl StreamingService<RowBatch> for StreamRowsService {
+    type Response = BatchAck;
+    type ResponseStream = BoxStream<BatchAck>;
+    type Future = BoxFuture<Response<Self::ResponseStream>, Status>;
+
+    fn call(&mut self, request: Request<Streaming<RowBatch>>) -> Self::Future {
+        let acks = self.0.stream_rows(request);
+        Box::pin(async move { Ok(Response::new(acks)) })
+    }
+}
+
+impl<B> Service<http::Request<B>> for IngestServer
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        if request.uri().path() == "/pathway.ingest.v1.Ingest/StreamRows" {
+            let service = StreamRowsService(self.clone());
+            return Box::pin(async move {
+                let mut grpc = Grpc::new(tonic::codec::ProstCodec::default());
+                Ok(grpc.streaming(service, request).await)
+            });
+        }
+        Box::pin(async move {
+            let mut response = http::Response::new(empty_body());
+            let headers = response.headers_mut();
+            headers.insert(Status::GRPC_STATUS, (Code::Unimplemented as i32).into());
+            headers.insert(
+                http::header::CONTENT_TYPE,
+                tonic::metadata::GRPC_CONTENT_TYPE,
+            );
+            Ok(response)
+        })
+    }
+}
+
+impl NamedService for IngestServer {
+    const NAME: &'static str = "pathway.ingest.v1.Ingest";
+}
+
+/// The server running in its own thread. It is stopped when the handle is
+/// dropped: the streams that are still open get an error, as the reader is
+/// gone, so the thread isn't waited for.
+pub struct IngestServerHandle {
+    shutdown_sender: Option<oneshot::Sender<()>>,
+}
+
+impl IngestServerHandle {
+    /// Binds the address and starts serving the batches for the given table.
+    /// The errors of binding are returned here, the later errors are logged.
+    pub fn start(
+        address: SocketAddr,
+        server: IngestServer,
+    ) -> Result<IngestServerHandle, io::Error> {
+        let (bind_result_sender, bind_result_receiver) = std_mpsc::sync_channel(1);
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+        Builder::new()
+            .name("pathway:grpc_input".to_string())
+            .spawn(move || {
+                let runtime = match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(runtime) => runtime,
+                    Err(e) => {
+                        let _ = bind_result_sender.send(Err(e));
+                        return;
+                    }
+                };
+                runtime.block_on(async move {
+                    let listener = match TcpListener::bind(address).await {
+                        Ok(listener) => {
+                            let _ = bind_result_sender.send(Ok(()));
+                            listener
+                        }
+                        Err(e) => {
+                            let _ = bind_result_sender.send(Err(e));
+                            return;
+                        }
+                    };
+                    let serving = Server::builder()
+                        .add_service(server)
+                        .serve_with_incoming_shutdown(
+                            TcpListenerStream::new(listener),
+                            async move {
+                                let _ = shutdown_receiver.await;
+                            },
+                        )
+                        .await;
+                    if let Err(e) = serving {
+                        error!("gRPC input server at {address} has failed: {e}");
+                    }
+                });
+            })?;
+        bind_result_receiver
+            .recv()
+            .map_err(|_| io::Error::other("gRPC input server thread has stopped"))??;
+        Ok(IngestServerHandle {
+            shutdown_sender: Some(shutdown_sender),
+        })
+    }
+}
+
+impl Drop for IngestServerHandle {
+    fn drop(&mut self) {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+    }
+}
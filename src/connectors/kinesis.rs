@@ -0,0 +1,186 @@
+// Copyright © 2024 Pathway
+
+//! Helpers for the Kinesis connectors: the ordering of the record positions,
+//! which are used as the offsets of the reader, the extraction of the records
+//! aggregated by the Kinesis Producer Library and the tracking of the shards
+//! across the resharding of a stream.
+//!
+//! Resharding description: <https://docs.aws.amazon.com/streams/latest/dev/kinesis-using-sdk-java-after-resharding.html>
+//!
+//! Aggregation format: <https://github.com/awslabs/amazon-kinesis-producer/blob/master/aggregation-format.md>
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use md5::{Digest, Md5};
+use prost::Message;
+use xxhash_rust::xxh3::xxh3_64;
+
+/// The prefix of a record aggregated by the Kinesis Producer Library.
+pub const KPL_AGGREGATED_RECORD_MAGIC: [u8; 4] = [0xf3, 0x89, 0x9a, 0xc2];
+
+/// The length of the MD5 checksum of the protobuf message, which ends an
+/// aggregated record.
+const KPL_CHECKSUM_LENGTH: usize = 16;
+
+/// Compares two sequence numbers. They are decimal strings of up to 129
+/// digits without leading zeros, so they don't fit into the integer types, but
+/// the longer one is always the greater one.
+pub fn compare_sequence_numbers(lhs: &str, rhs: &str) -> Ordering {
+    lhs.len().cmp(&rhs.len()).then_with(|| lhs.cmp(rhs))
+}
+
+/// Compares the positions of two user records, given as the sequence number
+/// and the sub-sequence number of the record. A position without the
+/// sub-sequence number means that the whole Kinesis record has been read, so
+/// it follows all user records aggregated in this record.
+pub fn compare_record_positions(lhs: (&str, Option<u64>), rhs: (&str, Option<u64>)) -> Ordering {
+    compare_sequence_numbers(lhs.0, rhs.0)
+        .then_with(|| lhs.1.unwrap_or(u64::MAX).cmp(&rhs.1.unwrap_or(u64::MAX)))
+}
+
+/// The protobuf message of a record aggregated by the Kinesis Producer
+/// Library. The tags of the user records aren't used, so they're skipped.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AggregatedRecord {
+    #[prost(string, repeated, tag = "1")]
+    pub partition_key_table: Vec<String>,
+    #[prost(string, repeated, tag = "2")]
+    pub explicit_hash_key_table: Vec<String>,
+    #[prost(message, repeated, tag = "3")]
+    pub records: Vec<AggregatedUserRecord>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AggregatedUserRecord {
+    #[prost(uint64, required, tag = "1")]
+    pub partition_key_index: u64,
+    #[prost(uint64, optional, tag = "2")]
+    pub explicit_hash_key_index: Option<u64>,
+    #[prost(bytes = "vec", required, tag = "3")]
+    pub data: Vec<u8>,
+}
+
+/// A record as it was put by a producer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserRecord {
+    pub partition_key: String,
+    pub data: Vec<u8>,
+}
+
+/// Extracts the user records from a record aggregated by the Kinesis Producer
+/// Library. Returns `None` if the data isn't an aggregated record, including
+/// the case when the checksum doesn't match or the message can't be decoded:
+/// then the data is a single user record, like in the Kinesis Client Library.
+pub fn deaggregate_records(data: &[u8]) -> Option<Vec<UserRecord>> {
+    let message = data.strip_prefix(&KPL_AGGREGATED_RECORD_MAGIC)?;
+    let checksum_start = message.len().checked_sub(KPL_CHECKSUM_LENGTH)?;
+    let (message, checksum) = message.split_at(checksum_start);
+    if Md5::digest(message).as_slice() != checksum {
+        return None;
+    }
+    let aggregated_record = AggregatedRecord::decode(message).ok()?;
+    let partition_keys = &aggregated_record.partition_key_table;
+    aggregated_record
+        .records
+        .iter()
+        .map(|record| {
+            let partition_key_index = usize::try_from(record.partition_key_index).ok()?;
+            Some(UserRecord {
+                partition_key: partition_keys.get(partition_key_index)?.clone(),
+                data: record.data.clone(),
+            })
+        })
+        .collect()
+}
+
+/// A shard as listed in the stream. A shard created by a split has a single
+/// parent, while a shard created by a merge has two.
+#[derive(Clone, Debug)]
+pub struct ShardDescription {
+    pub shard_id: String,
+    pub parent_shard_ids: Vec<String>,
+}
+
+/// Decides which shards a reader reads and when it starts reading them.
+///
+/// The shards are distributed among the readers by their lineage: a shard is
+/// assigned to the same reader as its first parent, so after a split both
+/// children are read by the reader of the parent. A shard is started only
+/// after this reader has read its parents to the end, so the records with the
+/// same partition key are read in order. The parent that is assigned to
+/// another reader, which may happen after a merge, or that has already expired
+/// from the stream, doesn't delay the start of the shard.
+#[derive(Debug)]
+pub struct ShardTracker {
+    reader_index: usize,
+    reader_count: usize,
+    first_parents: HashMap<String, String>,
+    listed_shards: HashSet<String>,
+    started_shards: HashSet<String>,
+    finished_shards: HashSet<String>,
+}
+
+impl ShardTracker {
+    pub fn new(reader_index: usize, reader_count: usize) -> Self {
+        assert!(reader_index < reader_count);
+        Self {
+            reader_index,
+            reader_count,
+            first_parents: HashMap::new(),
+            listed_shards: HashSet::new(),
+            started_shards: HashSet::new(),
+            finished_shards: HashSet::new(),
+        }
+    }
+
+    /// Takes the current list of the shards of the stream and returns the
+    /// shards that this reader must start reading now.
+    pub fn ready_shards(&mut self, shards: &[ShardDescription]) -> Vec<String> {
+        self.listed_shards = shards.iter().map(|shard| shard.shard_id.clone()).collect();
+        for shard in shards {
+            if let Some(parent_shard_id) = shard.parent_shard_ids.first() {
+                self.first_parents
+                    .insert(shard.shard_id.clone(), parent_shard_id.clone());
+            }
+        }
+
+        let mut ready_shards = Vec::new();
+        for shard in shards {
+            if self.started_shards.contains(&shard.shard_id) || !self.is_assigned(&shard.shard_id) {
+                continue;
+            }
+            let has_unfinished_parents = shard.parent_shard_ids.iter().any(|parent_shard_id| {
+                self.listed_shards.contains(parent_shard_id)
+                    && self.is_assigned(parent_shard_id)
+                    && !self.finished_shards.contains(parent_shard_id)
+            });
+            if !has_unfinished_parents {
+                self.started_shards.insert(shard.shard_id.clone());
+                ready_shards.push(shard.shard_id.clone());
+            }
+        }
+        ready_shards
+    }
+
+    /// Marks the shard as read to the end, so that its children can be started.
+    pub fn finish(&mut self, shard_id: &str) {
+        self.finished_shards.insert(shard_id.to_string());
+    }
+
+    pub fn is_assigned(&self, shard_id: &str) -> bool {
+        let mut lineage_root = shard_id;
+        let mut visited = HashSet::new();
+        while let Some(parent_shard_id) = self.first_parents.get(lineage_root) {
+            if !visited.insert(lineage_root) {
+                break;
+            }
+            lineage_root = parent_shard_id;
+        }
+        let reader_count =
+            u64::try_from(self.reader_count).expect("reader count must fit into u64");
+        let reader_index = xxh3_64(lineage_root.as_bytes()) % reader_count;
+        usize::try_from(reader_index).expect("reader index must fit into usize")
+            == self.reader_index
+    }
+}
@@ -0,0 +1,21 @@
+// Copyright © 2024 Pathway
+
+use serde::Serialize;
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Serialize)]
+pub struct AmqpMetadata {
+    exchange: String,
+    routing_key: String,
+    redelivered: bool,
+}
+
+impl AmqpMetadata {
+    pub fn new(exchange: String, routing_key: String, redelivered: bool) -> Self {
+        Self {
+            exchange,
+            routing_key,
+            redelivered,
+        }
+    }
+}
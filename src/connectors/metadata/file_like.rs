@@ -80,6 +80,24 @@ impl FileLikeMetadata {
         }
     }
 
+    /// Metadata of a member of an archive. A member has no owner of its own,
+    /// so it's owned by the owner of the archive.
+    pub fn from_archive_member(
+        archive_metadata: &FileLikeMetadata,
+        path: String,
+        size: u64,
+        modified_at: Option<u64>,
+    ) -> Self {
+        Self {
+            created_at: None,
+            modified_at,
+            owner: archive_metadata.owner.clone(),
+            path,
+            size,
+            seen_at: current_unix_timestamp_secs(),
+        }
+    }
+
     /// Checks if file contents could have been changed.
     pub fn is_changed(&self, other: &FileLikeMetadata) -> bool {
         self.modified_at != other.modified_at
@@ -0,0 +1,19 @@
+// Copyright © 2024 Pathway
+
+use serde::Serialize;
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Serialize)]
+pub struct GrpcMetadata {
+    peer_address: Option<String>,
+    batch_id: u64,
+}
+
+impl GrpcMetadata {
+    pub fn new(peer_address: Option<String>, batch_id: u64) -> Self {
+        Self {
+            peer_address,
+            batch_id,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 // Copyright © 2024 Pathway
 
-use rdkafka::message::{BorrowedMessage as KafkaMessage, Message};
+use rdkafka::message::{BorrowedMessage as KafkaMessage, Headers, Message};
 use serde::Serialize;
 
 #[allow(clippy::module_name_repetitions)]
@@ -10,18 +10,67 @@ pub struct KafkaMetadata {
     topic: String,
     partition: i32,
     offset: i64,
+
+    // The headers are only used to fill the fields mapped onto them,
+    // so they don't go to the `_metadata` column
+    #[serde(skip)]
+    headers: Vec<(String, Option<Vec<u8>>)>,
 }
 
 impl KafkaMetadata {
+    pub fn new(
+        timestamp_millis: Option<i64>,
+        topic: String,
+        partition: i32,
+        offset: i64,
+        headers: Vec<(String, Option<Vec<u8>>)>,
+    ) -> Self {
+        Self {
+            timestamp_millis,
+            topic,
+            partition,
+            offset,
+            headers,
+        }
+    }
+
     // TODO: Note that if row deletions take place, one needs to ensure
     // that the deletion uses the same metadata entry as the one used
     // during the row insertion.
     pub fn from_rdkafka_message(message: &KafkaMessage) -> Self {
-        Self {
-            timestamp_millis: message.timestamp().to_millis(),
-            topic: message.topic().to_string(),
-            partition: message.partition(),
-            offset: message.offset(),
-        }
+        let headers = message
+            .headers()
+            .map(|headers| {
+                headers
+                    .iter()
+                    .map(|header| (header.key.to_string(), header.value.map(<[u8]>::to_vec)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self::new(
+            message.timestamp().to_millis(),
+            message.topic().to_string(),
+            message.partition(),
+            message.offset(),
+            headers,
+        )
+    }
+
+    pub fn timestamp_millis(&self) -> Option<i64> {
+        self.timestamp_millis
+    }
+
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Returns the value of the header with the given name. If there are several
+    /// headers with this name, the last one is used, as the Java client does.
+    pub fn header(&self, name: &str) -> Option<&[u8]> {
+        self.headers
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.as_deref())
     }
 }
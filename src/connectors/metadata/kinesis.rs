@@ -0,0 +1,31 @@
+// Copyright © 2024 Pathway
+
+use serde::Serialize;
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Serialize)]
+pub struct KinesisMetadata {
+    stream_name: String,
+    shard_id: String,
+    sequence_number: String,
+    partition_key: String,
+    approximate_arrival_timestamp_millis: Option<i64>,
+}
+
+impl KinesisMetadata {
+    pub fn new(
+        stream_name: String,
+        shard_id: String,
+        sequence_number: String,
+        partition_key: String,
+        approximate_arrival_timestamp_millis: Option<i64>,
+    ) -> Self {
+        Self {
+            stream_name,
+            shard_id,
+            sequence_number,
+            partition_key,
+            approximate_arrival_timestamp_millis,
+        }
+    }
+}
@@ -1,20 +1,52 @@
+pub mod amqp;
 pub mod file_like;
+pub mod grpc;
 pub mod iceberg;
 pub mod kafka;
+pub mod kinesis;
+pub mod mqtt;
+pub mod pubsub;
+pub mod pulsar;
+pub mod socket;
 pub mod sqlite;
+pub mod webhook;
+
+#[allow(clippy::module_name_repetitions)]
+pub use amqp::AmqpMetadata;
 
 #[allow(clippy::module_name_repetitions)]
 pub use file_like::FileLikeMetadata;
 
+#[allow(clippy::module_name_repetitions)]
+pub use grpc::GrpcMetadata;
+
 #[allow(clippy::module_name_repetitions)]
 pub use kafka::KafkaMetadata;
 
 #[allow(clippy::module_name_repetitions)]
 pub use iceberg::IcebergMetadata;
 
+#[allow(clippy::module_name_repetitions)]
+pub use kinesis::KinesisMetadata;
+
+#[allow(clippy::module_name_repetitions)]
+pub use mqtt::MqttMetadata;
+
+#[allow(clippy::module_name_repetitions)]
+pub use pubsub::PubSubMetadata;
+
+#[allow(clippy::module_name_repetitions)]
+pub use pulsar::PulsarMetadata;
+
+#[allow(clippy::module_name_repetitions)]
+pub use socket::SocketMetadata;
+
 #[allow(clippy::module_name_repetitions)]
 pub use sqlite::SQLiteMetadata;
 
+#[allow(clippy::module_name_repetitions)]
+pub use webhook::WebhookMetadata;
+
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug)]
 pub enum SourceMetadata {
@@ -22,6 +54,14 @@ pub enum SourceMetadata {
     Kafka(KafkaMetadata),
     SQLite(SQLiteMetadata),
     Iceberg(IcebergMetadata),
+    Mqtt(MqttMetadata),
+    Amqp(AmqpMetadata),
+    Pulsar(PulsarMetadata),
+    Kinesis(KinesisMetadata),
+    PubSub(PubSubMetadata),
+    Grpc(GrpcMetadata),
+    Webhook(WebhookMetadata),
+    Socket(SocketMetadata),
 }
 
 impl From<FileLikeMetadata> for SourceMetadata {
@@ -48,6 +88,54 @@ impl From<SQLiteMetadata> for SourceMetadata {
     }
 }
 
+impl From<MqttMetadata> for SourceMetadata {
+    fn from(impl_: MqttMetadata) -> Self {
+        Self::Mqtt(impl_)
+    }
+}
+
+impl From<AmqpMetadata> for SourceMetadata {
+    fn from(impl_: AmqpMetadata) -> Self {
+        Self::Amqp(impl_)
+    }
+}
+
+impl From<PulsarMetadata> for SourceMetadata {
+    fn from(impl_: PulsarMetadata) -> Self {
+        Self::Pulsar(impl_)
+    }
+}
+
+impl From<KinesisMetadata> for SourceMetadata {
+    fn from(impl_: KinesisMetadata) -> Self {
+        Self::Kinesis(impl_)
+    }
+}
+
+impl From<PubSubMetadata> for SourceMetadata {
+    fn from(impl_: PubSubMetadata) -> Self {
+        Self::PubSub(impl_)
+    }
+}
+
+impl From<GrpcMetadata> for SourceMetadata {
+    fn from(impl_: GrpcMetadata) -> Self {
+        Self::Grpc(impl_)
+    }
+}
+
+impl From<WebhookMetadata> for SourceMetadata {
+    fn from(impl_: WebhookMetadata) -> Self {
+        Self::Webhook(impl_)
+    }
+}
+
+impl From<SocketMetadata> for SourceMetadata {
+    fn from(impl_: SocketMetadata) -> Self {
+        Self::Socket(impl_)
+    }
+}
+
 impl SourceMetadata {
     pub fn serialize(&self) -> serde_json::Value {
         match self {
@@ -55,14 +143,34 @@ impl SourceMetadata {
             Self::Kafka(meta) => serde_json::to_value(meta),
             Self::SQLite(meta) => serde_json::to_value(meta),
             Self::Iceberg(meta) => serde_json::to_value(meta),
+            Self::Mqtt(meta) => serde_json::to_value(meta),
+            Self::Amqp(meta) => serde_json::to_value(meta),
+            Self::Pulsar(meta) => serde_json::to_value(meta),
+            Self::Kinesis(meta) => serde_json::to_value(meta),
+            Self::PubSub(meta) => serde_json::to_value(meta),
+            Self::Grpc(meta) => serde_json::to_value(meta),
+            Self::Webhook(meta) => serde_json::to_value(meta),
+            Self::Socket(meta) => serde_json::to_value(meta),
         }
         .expect("Internal JSON serialization error")
     }
 
     pub fn commits_allowed_in_between(&self) -> bool {
         match self {
-            Self::FileLike(_) | Self::SQLite(_) | Self::Iceberg(_) => false,
-            Self::Kafka(_) => true,
+            // The rows of a gRPC batch or of a webhook request must get into
+            // the same timestamp
+            Self::FileLike(_)
+            | Self::SQLite(_)
+            | Self::Iceberg(_)
+            | Self::Grpc(_)
+            | Self::Webhook(_) => false,
+            Self::Kafka(_)
+            | Self::Mqtt(_)
+            | Self::Amqp(_)
+            | Self::Pulsar(_)
+            | Self::Kinesis(_)
+            | Self::PubSub(_)
+            | Self::Socket(_) => true,
         }
     }
 }
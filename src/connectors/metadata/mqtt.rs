@@ -0,0 +1,17 @@
+// Copyright © 2024 Pathway
+
+use serde::Serialize;
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Serialize)]
+pub struct MqttMetadata {
+    topic: String,
+    qos: u8,
+    retain: bool,
+}
+
+impl MqttMetadata {
+    pub fn new(topic: String, qos: u8, retain: bool) -> Self {
+        Self { topic, qos, retain }
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright © 2024 Pathway
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Serialize)]
+pub struct PubSubMetadata {
+    subscription: String,
+    message_id: String,
+    publish_time_millis: Option<i64>,
+    ordering_key: Option<String>,
+    delivery_attempt: Option<usize>,
+    attributes: BTreeMap<String, String>,
+}
+
+impl PubSubMetadata {
+    pub fn new(
+        subscription: String,
+        message_id: String,
+        publish_time_millis: Option<i64>,
+        ordering_key: Option<String>,
+        delivery_attempt: Option<usize>,
+        attributes: BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            subscription,
+            message_id,
+            publish_time_millis,
+            ordering_key,
+            delivery_attempt,
+            attributes,
+        }
+    }
+
+    pub fn publish_time_millis(&self) -> Option<i64> {
+        self.publish_time_millis
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright © 2024 Pathway
+
+use serde::Serialize;
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Serialize)]
+pub struct PulsarMetadata {
+    topic: String,
+    key: Option<String>,
+    publish_time_millis: u64,
+    event_time_millis: Option<u64>,
+    ledger_id: u64,
+    entry_id: u64,
+    batch_index: i32,
+}
+
+impl PulsarMetadata {
+    pub fn new(
+        topic: String,
+        key: Option<String>,
+        publish_time_millis: u64,
+        event_time_millis: Option<u64>,
+        ledger_id: u64,
+        entry_id: u64,
+        batch_index: i32,
+    ) -> Self {
+        Self {
+            topic,
+            key,
+            publish_time_millis,
+            event_time_millis,
+            ledger_id,
+            entry_id,
+            batch_index,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright © 2024 Pathway
+
+use std::net::SocketAddr;
+
+use serde::Serialize;
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Serialize)]
+pub struct SocketMetadata {
+    protocol: &'static str,
+    source_address: String,
+    source_port: u16,
+}
+
+impl SocketMetadata {
+    pub fn new(protocol: &'static str, source: SocketAddr) -> Self {
+        Self {
+            protocol,
+            source_address: source.ip().to_string(),
+            source_port: source.port(),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright © 2024 Pathway
+
+use serde::Serialize;
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Serialize)]
+pub struct WebhookMetadata {
+    route: String,
+    peer_address: Option<String>,
+}
+
+impl WebhookMetadata {
+    pub fn new(route: String, peer_address: Option<String>) -> Self {
+        Self {
+            route,
+            peer_address,
+        }
+    }
+}
@@ -4,6 +4,7 @@ use differential_dataflow::input::InputSession;
 use itertools::Itertools;
 use log::{error, info, warn};
 use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::env;
 use std::ops::ControlFlow;
 use std::rc::Rc;
@@ -17,15 +18,26 @@ use scopeguard::guard;
 use timely::dataflow::operators::probe::Handle;
 
 pub mod adaptors;
+pub mod compression;
 pub mod data_format;
 pub mod data_lake;
 pub mod data_storage;
 pub mod data_tokenize;
+pub mod grpc;
+pub mod kinesis;
 pub mod metadata;
 pub mod monitoring;
+pub mod mqtt;
+pub mod mysql_binlog;
 pub mod offset;
+pub mod pgoutput;
 pub mod posix_like;
+pub mod pulsar;
+pub mod redis_stream;
 pub mod scanner;
+pub mod socket;
+pub mod syslog;
+pub mod webhook;
 
 use crate::connectors::monitoring::ConnectorMonitor;
 use crate::engine::error::{DynError, Trace};
@@ -89,6 +101,58 @@ impl StartedConnectorState {
     }
 }
 
+/// Tracks the offsets of the data that has been processed by all outputs, for
+/// the readers that need to know it. The frontier of the data read is saved at
+/// each time advancement, and it is reported to the reader once the output
+/// probe passes the new timestamp, as all data before it has been processed.
+struct ProcessedFrontierTracker {
+    sender: Sender<OffsetAntichain>,
+    read_frontier: OffsetAntichain,
+    read_frontier_updated: bool,
+    pending_frontiers: VecDeque<(Timestamp, OffsetAntichain)>,
+}
+
+impl ProcessedFrontierTracker {
+    fn new(sender: Sender<OffsetAntichain>) -> Self {
+        Self {
+            sender,
+            read_frontier: OffsetAntichain::new(),
+            read_frontier_updated: false,
+            pending_frontiers: VecDeque::new(),
+        }
+    }
+
+    fn advance_offset(&mut self, offset_key: OffsetKey, offset_value: OffsetValue) {
+        self.read_frontier.advance_offset(offset_key, offset_value);
+        self.read_frontier_updated = true;
+    }
+
+    fn on_time_advanced(&mut self, new_timestamp: Timestamp) {
+        if self.read_frontier_updated {
+            self.read_frontier_updated = false;
+            self.pending_frontiers
+                .push_back((new_timestamp, self.read_frontier.clone()));
+        }
+    }
+
+    fn report_processed(&mut self, probe: &Handle<Timestamp>) {
+        let mut processed_frontier = None;
+        while let Some((timestamp, _)) = self.pending_frontiers.front() {
+            if probe.less_than(timestamp) {
+                break;
+            }
+            processed_frontier = self
+                .pending_frontiers
+                .pop_front()
+                .map(|(_, frontier)| frontier);
+        }
+        if let Some(processed_frontier) = processed_frontier {
+            // The reader may have finished already
+            let _ = self.sender.send(processed_frontier);
+        }
+    }
+}
+
 pub struct Connector {
     commit_duration: Option<Duration>,
     current_timestamp: Timestamp,
@@ -96,6 +160,7 @@ pub struct Connector {
     current_frontier: OffsetAntichain,
     skip_all_errors: bool,
     error_logger: Rc<dyn LogError>,
+    processed_frontier_tracker: Option<ProcessedFrontierTracker>,
 }
 
 #[derive(Debug)]
@@ -202,6 +267,7 @@ impl Connector {
             current_frontier: OffsetAntichain::new(),
             skip_all_errors,
             error_logger,
+            processed_frontier_tracker: None,
         }
     }
 
@@ -458,6 +524,19 @@ impl Connector {
             snapshot_access,
         )
         .map_err(EngineError::SnapshotWriterError)?;
+        let persisted_frontier_receiver = snapshot_writer.as_ref().map(|snapshot_writer| {
+            let (sender, receiver) = mpsc::channel();
+            snapshot_writer
+                .lock()
+                .unwrap()
+                .set_persisted_frontier_sender(sender);
+            receiver
+        });
+        let processed_frontier_receiver = reader.processed_frontier_needed().then(|| {
+            let (sender, receiver) = mpsc::channel();
+            self.processed_frontier_tracker = Some(ProcessedFrontierTracker::new(sender));
+            receiver
+        });
 
         let input_thread_handle = thread::Builder::new()
             .name(thread_name)
@@ -470,6 +549,12 @@ impl Connector {
                 });
 
                 let mut reader = reader.build()?;
+                if let Some(receiver) = persisted_frontier_receiver {
+                    reader.set_persisted_frontier_receiver(receiver);
+                }
+                if let Some(receiver) = processed_frontier_receiver {
+                    reader.set_processed_frontier_receiver(receiver);
+                }
                 Self::read_snapshot(
                     &mut *reader,
                     persistent_storage.as_ref(),
@@ -502,6 +587,10 @@ impl Connector {
                 return ControlFlow::Continue(Some(iteration_start));
             }
 
+            if let Some(tracker) = &mut self.processed_frontier_tracker {
+                tracker.report_processed(&probe);
+            }
+
             if let Some(next_commit_at_timestamp) = next_commit_at {
                 if next_commit_at_timestamp <= iteration_start {
                     if backfilling_finished && commit_allowed {
@@ -642,6 +731,9 @@ impl Connector {
                     );
 
                     let (offset_key, offset_value) = offset;
+                    if let Some(tracker) = &mut self.processed_frontier_tracker {
+                        tracker.advance_offset(offset_key.clone(), offset_value.clone());
+                    }
                     if has_persistent_storage {
                         assert!(*backfilling_finished);
                         self.current_frontier
@@ -830,6 +922,9 @@ impl Connector {
                 }
                 ParsedEvent::AdvanceTime => {
                     let time_advanced = self.advance_time(input_session);
+                    if let Some(tracker) = &mut self.processed_frontier_tracker {
+                        tracker.on_time_advanced(time_advanced);
+                    }
                     if let Some(ref mut connector_monitor) = connector_monitor {
                         connector_monitor.commit();
                     }
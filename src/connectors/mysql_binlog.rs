@@ -0,0 +1,258 @@
+// Copyright © 2024 Pathway
+
+//! Helpers for reading the row-based binary log of `MySQL`: the conversion of
+//! the decoded row values and the bookkeeping of the GTID sets, which are used
+//! to resume the replication after a restart.
+//!
+//! GTID format description: <https://dev.mysql.com/doc/refman/8.0/en/replication-gtids-concepts.html>
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::{from_utf8, FromStr};
+
+use chrono::NaiveDate;
+use mysql::binlog::value::BinlogValue;
+use mysql::Value as MysqlValue;
+use mysql_common::packets::Sid;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use crate::engine::{DateTimeNaive, DateTimeUtc, Duration, Type, Value};
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("invalid GTID set {0:?}")]
+    InvalidGtidSet(String),
+}
+
+/// A set of the global transaction identifiers, formatted in the same way as
+/// `@@GLOBAL.gtid_executed`, for example `3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:7`.
+///
+/// The transaction numbers are stored as sorted non-overlapping inclusive intervals.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GtidSet {
+    intervals: BTreeMap<String, Vec<(u64, u64)>>,
+}
+
+impl GtidSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, source_id: &str, transaction_id: u64) -> bool {
+        self.intervals.get(source_id).is_some_and(|intervals| {
+            intervals
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&transaction_id))
+        })
+    }
+
+    pub fn add(&mut self, source_id: &str, transaction_id: u64) {
+        self.add_interval(source_id, transaction_id, transaction_id);
+    }
+
+    fn add_interval(&mut self, source_id: &str, start: u64, end: u64) {
+        let intervals = self.intervals.entry(source_id.to_lowercase()).or_default();
+        intervals.push((start, end));
+        intervals.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(intervals.len());
+        for (start, end) in intervals.drain(..) {
+            match merged.last_mut() {
+                Some((_, last_end)) if start <= last_end.saturating_add(1) => {
+                    *last_end = (*last_end).max(end);
+                }
+                _ => merged.push((start, end)),
+            }
+        }
+        *intervals = merged;
+    }
+
+    /// The total number of transactions in the set. Since the set of the executed
+    /// transactions only grows as the binlog is read, it can be used to compare
+    /// the positions within a single replication stream.
+    pub fn transactions_count(&self) -> u64 {
+        self.intervals
+            .values()
+            .flatten()
+            .map(|(start, end)| end - start + 1)
+            .sum()
+    }
+
+    /// Converts the set into the blocks that `COM_BINLOG_DUMP_GTID` command accepts.
+    pub fn to_sids(&self) -> Vec<Sid<'static>> {
+        self.to_string()
+            .split(',')
+            .filter(|block| !block.is_empty())
+            .map(|block| {
+                block
+                    .parse()
+                    .expect("formatted GTID set block must be parseable")
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for GtidSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (block_idx, (source_id, intervals)) in self.intervals.iter().enumerate() {
+            if block_idx > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{source_id}")?;
+            for (start, end) in intervals {
+                if start == end {
+                    write!(f, ":{start}")?;
+                } else {
+                    write!(f, ":{start}-{end}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GtidSet {
+    type Err = Error;
+
+    fn from_str(gtid_set: &str) -> Result<Self, Self::Err> {
+        let invalid_gtid_set = || Error::InvalidGtidSet(gtid_set.to_string());
+        let mut result = Self::new();
+        // The server splits long sets into several lines
+        for block in gtid_set.split(',').map(str::trim) {
+            if block.is_empty() {
+                continue;
+            }
+            let mut parts = block.split(':');
+            let source_id = parts.next().ok_or_else(invalid_gtid_set)?;
+            if Uuid::parse_str(source_id).is_err() {
+                return Err(invalid_gtid_set());
+            }
+            for interval in parts {
+                let (start, end) = interval.split_once('-').unwrap_or((interval, interval));
+                let start: u64 = start.parse().map_err(|_| invalid_gtid_set())?;
+                let end: u64 = end.parse().map_err(|_| invalid_gtid_set())?;
+                if start == 0 || start > end {
+                    return Err(invalid_gtid_set());
+                }
+                result.add_interval(source_id, start, end);
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Formats the source identifier of the `GTID_EVENT` in the same way as the
+/// server does.
+pub fn format_source_id(sid: [u8; 16]) -> String {
+    Uuid::from_bytes(sid).to_string()
+}
+
+/// Returns the sequence number of a binlog file, for instance, 12 for
+/// `binlog.000012`. The files of a single server are ordered by these numbers.
+pub fn binlog_file_index(file_name: &str) -> Option<u64> {
+    file_name
+        .rsplit_once('.')
+        .and_then(|(_, index)| index.parse().ok())
+}
+
+/// Converts a value from a row image of the binary log into the value of the
+/// requested type. Returns `None` if it's not possible.
+pub fn parse_binlog_value(value: &BinlogValue, type_: &Type) -> Option<Value> {
+    match (type_, value) {
+        (Type::Optional(_) | Type::Any, BinlogValue::Value(MysqlValue::NULL)) => Some(Value::None),
+        (Type::Optional(arg), value) => parse_binlog_value(value, arg),
+        (_, BinlogValue::Value(value)) => parse_mysql_value(value, type_),
+        (Type::Json | Type::Any, BinlogValue::Jsonb(value)) => {
+            JsonValue::try_from(value.clone()).ok().map(Value::from)
+        }
+        (Type::String, BinlogValue::Jsonb(value)) => JsonValue::try_from(value.clone())
+            .ok()
+            .map(|value| Value::from(value.to_string().as_str())),
+        // Partial JSON updates are only sent if `binlog_row_value_options` is
+        // set to `PARTIAL_JSON` and can't be applied without the previous value
+        _ => None,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn parse_mysql_value(value: &MysqlValue, type_: &Type) -> Option<Value> {
+    match (type_, value) {
+        (Type::Int | Type::Any, MysqlValue::Int(i)) => Some(Value::Int(*i)),
+        (Type::Int | Type::Any, MysqlValue::UInt(u)) => i64::try_from(*u).ok().map(Value::Int),
+        (Type::Bool, MysqlValue::Int(i)) => Some(Value::Bool(*i != 0)),
+        (Type::Float, MysqlValue::Int(i)) => Some(Value::from(*i as f64)),
+        (Type::Float | Type::Any, MysqlValue::Float(f)) => Some(Value::from(f64::from(*f))),
+        (Type::Float | Type::Any, MysqlValue::Double(f)) => Some(Value::from(*f)),
+        // Pre-5.6 `TIMESTAMP` columns are sent as the number of seconds since the epoch
+        (Type::DateTimeUtc, MysqlValue::Int(seconds)) => DateTimeUtc::from_timestamp(*seconds, "s")
+            .ok()
+            .map(Value::from),
+        (
+            Type::DateTimeNaive | Type::DateTimeUtc | Type::Any,
+            MysqlValue::Date(year, month, day, hour, minute, second, microsecond),
+        ) => {
+            let parsed = NaiveDate::from_ymd_opt((*year).into(), (*month).into(), (*day).into())?
+                .and_hms_micro_opt(
+                (*hour).into(),
+                (*minute).into(),
+                (*second).into(),
+                *microsecond,
+            )?;
+            if matches!(type_, Type::DateTimeUtc) {
+                Some(Value::from(DateTimeUtc::from(parsed.and_utc())))
+            } else {
+                Some(Value::from(DateTimeNaive::from(parsed)))
+            }
+        }
+        (
+            Type::Duration | Type::Any,
+            MysqlValue::Time(is_negative, days, hours, minutes, seconds, microseconds),
+        ) => {
+            let total_seconds =
+                ((i64::from(*days) * 24 + i64::from(*hours)) * 60 + i64::from(*minutes)) * 60
+                    + i64::from(*seconds);
+            let total_microseconds = total_seconds * 1_000_000 + i64::from(*microseconds);
+            let signed_microseconds = if *is_negative {
+                -total_microseconds
+            } else {
+                total_microseconds
+            };
+            Duration::new_with_unit(signed_microseconds, "us")
+                .ok()
+                .map(Value::from)
+        }
+        (_, MysqlValue::Bytes(bytes)) => parse_bytes_value(bytes, type_),
+        _ => None,
+    }
+}
+
+/// Strings, blobs, decimals, `BIT` and `TIMESTAMP` columns come as bytes.
+fn parse_bytes_value(bytes: &[u8], type_: &Type) -> Option<Value> {
+    match type_ {
+        Type::Bytes => Some(Value::Bytes(bytes.into())),
+        Type::Any | Type::String => from_utf8(bytes).ok().map(Value::from),
+        Type::Bool => Some(Value::Bool(bytes.iter().any(|byte| *byte != 0))),
+        Type::Int => from_utf8(bytes).ok()?.parse::<i64>().ok().map(Value::from),
+        Type::Float => from_utf8(bytes).ok()?.parse::<f64>().ok().map(Value::from),
+        Type::Json => serde_json::from_slice::<JsonValue>(bytes)
+            .ok()
+            .map(Value::from),
+        Type::DateTimeUtc => {
+            // The number of seconds since the epoch, possibly with the fractional part
+            let timestamp = from_utf8(bytes).ok()?;
+            let (seconds, microseconds) = timestamp.split_once('.').unwrap_or((timestamp, "0"));
+            let seconds: i64 = seconds.parse().ok()?;
+            let microseconds: i64 = format!("{microseconds:0<6}").get(..6)?.parse().ok()?;
+            DateTimeUtc::from_timestamp(seconds * 1_000_000 + microseconds, "us")
+                .ok()
+                .map(Value::from)
+        }
+        _ => None,
+    }
+}
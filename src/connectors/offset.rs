@@ -18,6 +18,16 @@ pub enum OffsetKey {
     Kafka(ArcStr, i32),
     Nats(usize),
     Empty,
+    NatsSubject(ArcStr),
+    Mqtt,
+    RedisStream(ArcStr),
+    Amqp(usize),
+    Pulsar(ArcStr),
+    Kinesis(ArcStr),
+    PubSub(usize),
+    Grpc,
+    Webhook,
+    Socket,
 }
 
 impl HashInto for OffsetKey {
@@ -27,8 +37,18 @@ impl HashInto for OffsetKey {
                 hasher.update(topic_name.as_bytes());
                 partition.hash_into(hasher);
             }
-            OffsetKey::Nats(worker_index) => worker_index.hash_into(hasher),
-            OffsetKey::Empty => {}
+            OffsetKey::Nats(worker_index)
+            | OffsetKey::Amqp(worker_index)
+            | OffsetKey::PubSub(worker_index) => worker_index.hash_into(hasher),
+            OffsetKey::Empty
+            | OffsetKey::Mqtt
+            | OffsetKey::Grpc
+            | OffsetKey::Webhook
+            | OffsetKey::Socket => {}
+            OffsetKey::NatsSubject(subject) => hasher.update(subject.as_bytes()),
+            OffsetKey::RedisStream(stream_key) => hasher.update(stream_key.as_bytes()),
+            OffsetKey::Pulsar(topic) => hasher.update(topic.as_bytes()),
+            OffsetKey::Kinesis(shard_id) => hasher.update(shard_id.as_bytes()),
         };
     }
 }
@@ -67,6 +87,37 @@ pub enum OffsetValue {
     },
     NatsReadEntriesCount(usize),
     Empty,
+    PsqlReplicationPosition {
+        transaction_lsn: u64,
+        entries_read_within_transaction: u64,
+    },
+    MongoDbResumeToken(Arc<[u8]>),
+    MysqlBinlogPosition {
+        file_name: ArcStr,
+        transaction_position: u64,
+        gtid_set: Option<ArcStr>,
+        entries_read_within_transaction: u64,
+    },
+    NatsStreamSequence(u64),
+    MqttReadEntriesCount(usize),
+    RedisStreamEntryId {
+        milliseconds: u64,
+        sequence: u64,
+    },
+    AmqpReadEntriesCount(usize),
+    PulsarMessageId {
+        ledger_id: u64,
+        entry_id: u64,
+        batch_index: i32,
+    },
+    KinesisRecordPosition {
+        sequence_number: ArcStr,
+        sub_sequence_number: Option<u64>,
+    },
+    PubSubReadMessagesCount(usize),
+    GrpcReadBatchesCount(usize),
+    WebhookReadRequestsCount(usize),
+    SocketReadMessagesCount(usize),
 }
 
 impl OffsetValue {
@@ -140,10 +191,61 @@ impl HashInto for OffsetValue {
                     .unwrap_or_default()
                     .hash_into(hasher);
             }
-            OffsetValue::NatsReadEntriesCount(count) => count.hash_into(hasher),
+            OffsetValue::NatsReadEntriesCount(count)
+            | OffsetValue::MqttReadEntriesCount(count)
+            | OffsetValue::AmqpReadEntriesCount(count)
+            | OffsetValue::PubSubReadMessagesCount(count)
+            | OffsetValue::GrpcReadBatchesCount(count)
+            | OffsetValue::WebhookReadRequestsCount(count)
+            | OffsetValue::SocketReadMessagesCount(count) => count.hash_into(hasher),
             OffsetValue::IcebergSnapshot { snapshot_id } => {
                 snapshot_id.hash_into(hasher);
             }
+            OffsetValue::PsqlReplicationPosition {
+                transaction_lsn,
+                entries_read_within_transaction,
+            } => {
+                transaction_lsn.hash_into(hasher);
+                entries_read_within_transaction.hash_into(hasher);
+            }
+            OffsetValue::MongoDbResumeToken(serialized_token) => {
+                hasher.update(serialized_token);
+            }
+            OffsetValue::MysqlBinlogPosition {
+                file_name,
+                transaction_position,
+                entries_read_within_transaction,
+                ..
+            } => {
+                hasher.update(file_name.as_bytes());
+                transaction_position.hash_into(hasher);
+                entries_read_within_transaction.hash_into(hasher);
+            }
+            OffsetValue::NatsStreamSequence(sequence) => sequence.hash_into(hasher),
+            OffsetValue::RedisStreamEntryId {
+                milliseconds,
+                sequence,
+            } => {
+                milliseconds.hash_into(hasher);
+                sequence.hash_into(hasher);
+            }
+            OffsetValue::PulsarMessageId {
+                ledger_id,
+                entry_id,
+                batch_index,
+            } => {
+                ledger_id.hash_into(hasher);
+                entry_id.hash_into(hasher);
+                batch_index.hash_into(hasher);
+            }
+            OffsetValue::KinesisRecordPosition {
+                sequence_number,
+                sub_sequence_number,
+            } => {
+                hasher.update(sequence_number.as_bytes());
+                sub_sequence_number.is_some().hash_into(hasher);
+                sub_sequence_number.unwrap_or_default().hash_into(hasher);
+            }
             OffsetValue::Empty => {}
         };
     }
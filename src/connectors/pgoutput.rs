@@ -0,0 +1,336 @@
+// Copyright © 2024 Pathway
+
+//! Decoder for the messages of `pgoutput`, the standard logical decoding plugin of
+//! `PostgreSQL`. Only the first version of the protocol is supported, since it is
+//! what `pg_logical_slot_peek_binary_changes` produces by default.
+//!
+//! Protocol description: <https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html>
+
+use std::str::{from_utf8, Utf8Error};
+
+use chrono::{DateTime, NaiveDateTime};
+use serde_json::Value as JsonValue;
+
+use crate::engine::{Type, Value};
+
+pub type Lsn = u64;
+pub type RelationId = u32;
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("unexpected end of pgoutput message")]
+    UnexpectedEnd,
+
+    #[error("unknown pgoutput message type {0:?}")]
+    UnknownMessageType(char),
+
+    #[error("unknown tuple column kind {0:?}")]
+    UnknownColumnKind(char),
+
+    #[error("unexpected tuple marker {0:?}")]
+    UnexpectedTupleMarker(char),
+
+    #[error("unknown replica identity setting {0:?}")]
+    UnknownReplicaIdentity(char),
+
+    #[error("string in pgoutput message is not null-terminated")]
+    UnterminatedString,
+
+    #[error(transparent)]
+    Utf8(#[from] Utf8Error),
+
+    #[error("invalid LSN {0:?}")]
+    InvalidLsn(String),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelationColumn {
+    pub name: String,
+    pub is_key: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReplicaIdentity {
+    Default,
+    Nothing,
+    Full,
+    Index,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Relation {
+    pub id: RelationId,
+    pub namespace: String,
+    pub name: String,
+    pub replica_identity: ReplicaIdentity,
+    pub columns: Vec<RelationColumn>,
+}
+
+impl Relation {
+    /// Checks if the relation corresponds to the table name, given
+    /// either as `table` or as `schema.table`.
+    pub fn matches(&self, table_name: &str) -> bool {
+        match table_name.split_once('.') {
+            Some((namespace, name)) => self.namespace == namespace && self.name == name,
+            None => self.name == table_name,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TupleColumn {
+    Null,
+    UnchangedToast,
+    Text(String),
+}
+
+pub type Tuple = Vec<TupleColumn>;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Message {
+    Begin {
+        final_lsn: Lsn,
+    },
+    Commit {
+        commit_lsn: Lsn,
+        // The position right after the commit record, from which the next
+        // transactions are decoded
+        end_lsn: Lsn,
+    },
+    Relation(Relation),
+    Insert {
+        relation_id: RelationId,
+        new_tuple: Tuple,
+    },
+    Update {
+        relation_id: RelationId,
+        // Either the full old row or only its replica identity, depending on the table settings
+        old_tuple: Option<Tuple>,
+        new_tuple: Tuple,
+    },
+    Delete {
+        relation_id: RelationId,
+        old_tuple: Tuple,
+    },
+    Truncate {
+        relation_ids: Vec<RelationId>,
+    },
+
+    // Origin, Type and logical decoding messages don't affect the table contents
+    Other,
+}
+
+struct MessageCursor<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> MessageCursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn take(&mut self, n_bytes: usize) -> Result<&'a [u8], Error> {
+        let end = self.position + n_bytes;
+        let result = self
+            .data
+            .get(self.position..end)
+            .ok_or(Error::UnexpectedEnd)?;
+        self.position = end;
+        Ok(result)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_i16(&mut self) -> Result<i16, Error> {
+        Ok(i16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_string(&mut self) -> Result<String, Error> {
+        let remaining = &self.data[self.position..];
+        let length = remaining
+            .iter()
+            .position(|c| *c == 0)
+            .ok_or(Error::UnterminatedString)?;
+        let result = from_utf8(&remaining[..length])?.to_string();
+        self.position += length + 1;
+        Ok(result)
+    }
+
+    fn read_tuple(&mut self) -> Result<Tuple, Error> {
+        let n_columns = self.read_i16()?;
+        let mut tuple = Vec::with_capacity(n_columns.try_into().unwrap_or_default());
+        for _ in 0..n_columns {
+            let column = match self.read_u8()? {
+                b'n' => TupleColumn::Null,
+                b'u' => TupleColumn::UnchangedToast,
+                b't' => {
+                    let length = self.read_u32()?;
+                    let value = self.take(length as usize)?;
+                    TupleColumn::Text(from_utf8(value)?.to_string())
+                }
+                other => return Err(Error::UnknownColumnKind(other.into())),
+            };
+            tuple.push(column);
+        }
+        Ok(tuple)
+    }
+
+    fn read_marked_tuple(&mut self, expected_marker: u8) -> Result<Tuple, Error> {
+        let marker = self.read_u8()?;
+        if marker != expected_marker {
+            return Err(Error::UnexpectedTupleMarker(marker.into()));
+        }
+        self.read_tuple()
+    }
+}
+
+impl Message {
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        let mut cursor = MessageCursor::new(data);
+        let message = match cursor.read_u8()? {
+            b'B' => Self::Begin {
+                final_lsn: cursor.read_u64()?,
+            },
+            b'C' => {
+                let _flags = cursor.read_u8()?;
+                Self::Commit {
+                    commit_lsn: cursor.read_u64()?,
+                    end_lsn: cursor.read_u64()?,
+                }
+            }
+            b'R' => {
+                let id = cursor.read_u32()?;
+                let namespace = cursor.read_string()?;
+                let name = cursor.read_string()?;
+                let replica_identity = match cursor.read_u8()? {
+                    b'd' => ReplicaIdentity::Default,
+                    b'n' => ReplicaIdentity::Nothing,
+                    b'f' => ReplicaIdentity::Full,
+                    b'i' => ReplicaIdentity::Index,
+                    other => return Err(Error::UnknownReplicaIdentity(other.into())),
+                };
+                let n_columns = cursor.read_i16()?;
+                let mut columns = Vec::with_capacity(n_columns.try_into().unwrap_or_default());
+                for _ in 0..n_columns {
+                    let flags = cursor.read_u8()?;
+                    let name = cursor.read_string()?;
+                    let _type_id = cursor.read_u32()?;
+                    let _type_modifier = cursor.read_u32()?;
+                    columns.push(RelationColumn {
+                        name,
+                        is_key: flags & 1 == 1,
+                    });
+                }
+                Self::Relation(Relation {
+                    id,
+                    namespace,
+                    name,
+                    replica_identity,
+                    columns,
+                })
+            }
+            b'I' => {
+                let relation_id = cursor.read_u32()?;
+                Self::Insert {
+                    relation_id,
+                    new_tuple: cursor.read_marked_tuple(b'N')?,
+                }
+            }
+            b'U' => {
+                let relation_id = cursor.read_u32()?;
+                let (old_tuple, new_tuple) = match cursor.read_u8()? {
+                    b'K' | b'O' => {
+                        let old_tuple = cursor.read_tuple()?;
+                        (Some(old_tuple), cursor.read_marked_tuple(b'N')?)
+                    }
+                    b'N' => (None, cursor.read_tuple()?),
+                    other => return Err(Error::UnexpectedTupleMarker(other.into())),
+                };
+                Self::Update {
+                    relation_id,
+                    old_tuple,
+                    new_tuple,
+                }
+            }
+            b'D' => {
+                let relation_id = cursor.read_u32()?;
+                let old_tuple = match cursor.read_u8()? {
+                    b'K' | b'O' => cursor.read_tuple()?,
+                    other => return Err(Error::UnexpectedTupleMarker(other.into())),
+                };
+                Self::Delete {
+                    relation_id,
+                    old_tuple,
+                }
+            }
+            b'T' => {
+                let n_relations = cursor.read_u32()?;
+                let _options = cursor.read_u8()?;
+                let mut relation_ids = Vec::with_capacity(n_relations as usize);
+                for _ in 0..n_relations {
+                    relation_ids.push(cursor.read_u32()?);
+                }
+                Self::Truncate { relation_ids }
+            }
+            b'O' | b'Y' | b'M' => Self::Other,
+            other => return Err(Error::UnknownMessageType(other.into())),
+        };
+        Ok(message)
+    }
+}
+
+/// Formats LSN in the same way as `PostgreSQL` does, for example `16/B374D848`.
+pub fn format_lsn(lsn: Lsn) -> String {
+    format!("{:X}/{:X}", lsn >> 32, lsn & 0xFFFF_FFFF)
+}
+
+pub fn parse_lsn(lsn: &str) -> Result<Lsn, Error> {
+    let parsed = lsn.split_once('/').and_then(|(high, low)| {
+        let high = u32::from_str_radix(high, 16).ok()?;
+        let low = u32::from_str_radix(low, 16).ok()?;
+        Some((u64::from(high) << 32) | u64::from(low))
+    });
+    parsed.ok_or_else(|| Error::InvalidLsn(lsn.to_string()))
+}
+
+/// Converts a value in the `PostgreSQL` text representation into the value
+/// of the requested type. Returns `None` if it's not possible.
+pub fn parse_text_value(raw_value: &str, type_: &Type) -> Option<Value> {
+    match type_ {
+        Type::Optional(arg) => parse_text_value(raw_value, arg),
+        Type::Any | Type::String => Some(Value::from(raw_value)),
+        Type::Bool => match raw_value {
+            "t" => Some(Value::Bool(true)),
+            "f" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        Type::Int => raw_value.parse::<i64>().ok().map(Value::from),
+        Type::Float => raw_value.parse::<f64>().ok().map(Value::from),
+        Type::Json => serde_json::from_str::<JsonValue>(raw_value)
+            .ok()
+            .map(Value::from),
+        Type::Bytes => raw_value
+            .strip_prefix("\\x")
+            .and_then(|encoded| hex::decode(encoded).ok())
+            .map(|decoded| Value::Bytes(decoded.into())),
+        Type::DateTimeNaive => NaiveDateTime::parse_from_str(raw_value, "%Y-%m-%d %H:%M:%S%.f")
+            .ok()
+            .map(|parsed| Value::DateTimeNaive(parsed.into())),
+        Type::DateTimeUtc => DateTime::parse_from_str(raw_value, "%Y-%m-%d %H:%M:%S%.f%#z")
+            .ok()
+            .map(|parsed| Value::DateTimeUtc(parsed.into())),
+        _ => None,
+    }
+}
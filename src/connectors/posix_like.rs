@@ -2,15 +2,15 @@
 
 use log::{error, info, warn};
 use std::collections::VecDeque;
-use std::io::Cursor;
 use std::mem::take;
 use std::sync::Arc;
-use std::thread::sleep;
 use std::time::Duration;
 
+use crate::connectors::compression::{decompressing_reader, Decompression};
 use crate::connectors::data_storage::ConnectorMode;
 use crate::connectors::data_tokenize::Tokenize;
-use crate::connectors::scanner::{PosixLikeScanner, QueuedAction};
+use crate::connectors::metadata::FileLikeMetadata;
+use crate::connectors::scanner::{ArchiveScanner, PosixLikeScanner, QueuedAction};
 use crate::connectors::{
     DataEventType, OffsetKey, OffsetValue, ReadError, ReadResult, Reader, StorageType,
 };
@@ -23,6 +23,11 @@ use crate::persistence::PersistentId;
 struct CurrentAction {
     action: QueuedAction,
     offset_path: Arc<[u8]>,
+
+    // Whether the object can be committed in parts, if the tokenizer allows.
+    // It's not the case when the object replaces its older version, since the
+    // replacement must be atomic.
+    partial_commits_allowed: bool,
 }
 
 impl From<QueuedAction> for CurrentAction {
@@ -30,6 +35,7 @@ impl From<QueuedAction> for CurrentAction {
         Self {
             offset_path: action.path().into(),
             action,
+            partial_commits_allowed: false,
         }
     }
 }
@@ -40,12 +46,16 @@ pub struct PosixLikeReader {
     tokenizer: Box<dyn Tokenize>,
     persistent_id: Option<PersistentId>,
     streaming_mode: ConnectorMode,
+    decompression: Decompression,
 
     total_entries_read: u64,
     had_queue_refresh: bool,
     cached_object_storage: CachedObjectStorage,
     current_action: Option<CurrentAction>,
     scanner_actions_queue: VecDeque<QueuedAction>,
+    is_replacement_pending: bool,
+    is_at_resumable_position: bool,
+    deferred_read_results: VecDeque<ReadResult>,
 }
 
 impl PosixLikeReader {
@@ -60,14 +70,45 @@ impl PosixLikeReader {
             tokenizer,
             streaming_mode,
             persistent_id,
+            decompression: Decompression::None,
 
             total_entries_read: 0,
             had_queue_refresh: false,
             current_action: None,
             scanner_actions_queue: VecDeque::new(),
+            is_replacement_pending: false,
+            is_at_resumable_position: false,
+            deferred_read_results: VecDeque::new(),
             cached_object_storage: CachedObjectStorage::new(Box::new(MemoryKVStorage::new()))?,
         })
     }
+
+    /// Makes the reader expand the tar and zip archives, so that each of
+    /// their members is read as a separate object.
+    pub fn with_expanded_archives(self) -> Result<Self, ReadError> {
+        Ok(Self {
+            scanner: Box::new(ArchiveScanner::new(self.scanner)?),
+            ..self
+        })
+    }
+
+    /// Makes the reader decompress the objects, which are read as they are
+    /// otherwise.
+    #[must_use]
+    pub fn with_decompression(self, decompression: Decompression) -> Self {
+        Self {
+            decompression,
+            ..self
+        }
+    }
+
+    /// Makes the reader wait for the inotify events instead of rescanning
+    /// all objects on every poll. Only the filesystem sources on Linux
+    /// support it.
+    pub fn with_inotify(mut self) -> Result<Self, ReadError> {
+        self.scanner.enable_inotify()?;
+        Ok(self)
+    }
 }
 
 impl Reader for PosixLikeReader {
@@ -80,7 +121,7 @@ impl Reader for PosixLikeReader {
         let Some(OffsetValue::PosixLikeOffset {
             total_entries_read,
             path: object_path_arc,
-            bytes_offset: _,
+            bytes_offset,
             cached_object_version,
         }) = offset_value.as_posix_like_offset()
         else {
@@ -93,6 +134,9 @@ impl Reader for PosixLikeReader {
 
         self.current_action = None;
         self.scanner_actions_queue.clear();
+        self.is_replacement_pending = false;
+        self.is_at_resumable_position = false;
+        self.deferred_read_results.clear();
         let are_deletions_enabled = self.are_deletions_enabled();
         let stored_metadata = self
             .cached_object_storage
@@ -107,6 +151,8 @@ impl Reader for PosixLikeReader {
                     );
                     self.scanner_actions_queue
                         .push_back(QueuedAction::Update(object_path_arc.to_vec(), metadata));
+                } else if !reread_needed {
+                    self.resume_object_reading(&object_path_arc, bytes_offset, metadata)?;
                 }
             } else if are_deletions_enabled {
                 info!("The last read object is no longer present in the source. It will be removed from the engine.");
@@ -115,9 +161,9 @@ impl Reader for PosixLikeReader {
             }
         }
 
-        // No need to set up a tokenizer here: `current_action` is set only
-        // in case the object had already been read in full and requires no
-        // further processing.
+        // No need to set up a tokenizer here, unless the object reading is
+        // resumed: otherwise the object had already been read in full and
+        // requires no further processing.
         self.total_entries_read = total_entries_read;
         Ok(())
     }
@@ -133,9 +179,15 @@ impl Reader for PosixLikeReader {
     }
 
     fn read(&mut self) -> Result<ReadResult, ReadError> {
+        if let Some(deferred_read_result) = self.deferred_read_results.pop_front() {
+            return Ok(deferred_read_result);
+        }
+
         // Try to continue to read the current object.
         let maybe_entry = self.tokenizer.next_entry()?;
         if let Some((entry, bytes_offset)) = maybe_entry {
+            let partial_commit_metadata = self.partial_commit_metadata();
+            self.is_at_resumable_position = self.tokenizer.is_at_resumable_position();
             self.total_entries_read += 1;
             let offset = (
                 OffsetKey::Empty,
@@ -146,7 +198,18 @@ impl Reader for PosixLikeReader {
                     cached_object_version: Some(self.cached_object_storage.actual_version()),
                 },
             );
-            return Ok(ReadResult::Data(entry, offset));
+            let data = ReadResult::Data(entry, offset);
+            if let Some(metadata) = partial_commit_metadata {
+                // The previous entries end a part of the object that can be
+                // committed, so the object is split into several sources.
+                self.deferred_read_results
+                    .push_back(ReadResult::NewSource(metadata.into()));
+                self.deferred_read_results.push_back(data);
+                return Ok(ReadResult::FinishedSource {
+                    commit_allowed: true,
+                });
+            }
+            return Ok(data);
         }
 
         // We've failed to read the current object because it's over.
@@ -173,7 +236,52 @@ impl Reader for PosixLikeReader {
 }
 
 impl PosixLikeReader {
+    /// Returns the metadata of the object being read if the entries read
+    /// before can be committed separately from the rest of the object.
+    fn partial_commit_metadata(&self) -> Option<FileLikeMetadata> {
+        if !self.is_at_resumable_position {
+            return None;
+        }
+        match self.current_action.as_ref()? {
+            CurrentAction {
+                action: QueuedAction::Read(_, metadata),
+                partial_commits_allowed: true,
+                ..
+            } => Some(metadata.clone()),
+            _ => None,
+        }
+    }
+
+    fn resume_object_reading(
+        &mut self,
+        path: &Arc<[u8]>,
+        position: u64,
+        metadata: FileLikeMetadata,
+    ) -> Result<(), ReadError> {
+        if !self.tokenizer.supports_resuming() {
+            return Ok(());
+        }
+        let Ok(cached_object_contents) = self.cached_object_storage.get_object(path.as_ref())
+        else {
+            return Ok(());
+        };
+        let reader =
+            decompressing_reader(path.as_ref(), cached_object_contents, self.decompression)?;
+        if self.tokenizer.set_resumed_reader(reader, position)? {
+            info!("The last read object was read partially. Its reading will be resumed.");
+            self.deferred_read_results
+                .push_back(ReadResult::NewSource(metadata.clone().into()));
+            let mut current_action =
+                CurrentAction::from(QueuedAction::Read(path.to_vec(), metadata));
+            current_action.partial_commits_allowed = true;
+            self.current_action = Some(current_action);
+        }
+        Ok(())
+    }
+
     fn next_scanner_action(&mut self) -> Result<Option<ReadResult>, ReadError> {
+        self.is_at_resumable_position = false;
+
         // If there is an ongoing action, we must finalize it
         // and emit the corresponding event.
         if let Some(current_action) = take(&mut self.current_action) {
@@ -182,6 +290,7 @@ impl PosixLikeReader {
                 QueuedAction::Update(path, metadata) => {
                     self.scanner_actions_queue
                         .push_front(QueuedAction::Read(path, metadata));
+                    self.is_replacement_pending = true;
                     false
                 }
                 QueuedAction::Read(path, _) => {
@@ -206,6 +315,7 @@ impl PosixLikeReader {
             let action = self.scanner_actions_queue.pop_front();
             match &action {
                 Some(QueuedAction::Read(path, metadata)) => {
+                    let is_replacement = take(&mut self.is_replacement_pending);
                     let Ok(cached_object_contents) = self.scanner.read_object(path.as_ref()) else {
                         error!("Failed to get contents of a queued object {metadata:?}");
                         continue;
@@ -220,11 +330,17 @@ impl PosixLikeReader {
                         contents_for_caching,
                         metadata.clone(),
                     )?;
-                    let reader = Box::new(Cursor::new(cached_object_contents));
+                    let reader = decompressing_reader(
+                        path.as_ref(),
+                        cached_object_contents,
+                        self.decompression,
+                    )?;
                     self.tokenizer
                         .set_new_reader(reader, DataEventType::Insert)?;
                     let result = ReadResult::NewSource(metadata.clone().into());
-                    self.current_action = Some(action.unwrap().into());
+                    let mut current_action = CurrentAction::from(action.unwrap());
+                    current_action.partial_commits_allowed = !is_replacement;
+                    self.current_action = Some(current_action);
                     return Ok(Some(result));
                 }
                 Some(QueuedAction::Delete(path) | QueuedAction::Update(path, _)) => {
@@ -240,7 +356,11 @@ impl PosixLikeReader {
                     self.cached_object_storage
                         .remove_object(path.as_ref())
                         .expect("Cached object storage doesn't contain an indexed object");
-                    let reader = Box::new(Cursor::new(cached_object_contents));
+                    let reader = decompressing_reader(
+                        path.as_ref(),
+                        cached_object_contents,
+                        self.decompression,
+                    )?;
                     self.tokenizer
                         .set_new_reader(reader, DataEventType::Delete)?;
                     let result = ReadResult::NewSource(old_metadata.clone().into());
@@ -259,7 +379,7 @@ impl PosixLikeReader {
                         }
                         if self.scanner_actions_queue.is_empty() {
                             // Don't poll the backend too often.
-                            sleep(Self::sleep_duration());
+                            self.scanner.wait_for_changes(Self::sleep_duration());
                         }
                     } else {
                         return Ok(None);
@@ -0,0 +1,54 @@
+// Copyright © 2024 Pathway
+
+//! Helpers for the Pulsar connectors: the ordering of the message IDs within
+//! a topic partition, which are used as the offsets of the reader, and the
+//! routing of the keyed messages to the partitions of a topic.
+//!
+//! The routing is the same as in the default round-robin router of the Java
+//! client with its default `JavaStringHash` hashing scheme for the messages
+//! with a key: <https://pulsar.apache.org/docs/next/concepts-messaging/#routing-modes>
+
+use base64::engine::general_purpose::STANDARD as base64encoder;
+use base64::Engine;
+use pulsar::proto::MessageIdData;
+
+/// The position of a message within a topic partition. The messages of a
+/// batch share the ledger and the entry and differ by the index in the batch.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct MessageId {
+    pub ledger_id: u64,
+    pub entry_id: u64,
+    pub batch_index: i32,
+}
+
+impl From<&MessageIdData> for MessageId {
+    fn from(id: &MessageIdData) -> Self {
+        Self {
+            ledger_id: id.ledger_id,
+            entry_id: id.entry_id,
+            batch_index: id.batch_index.unwrap_or(-1),
+        }
+    }
+}
+
+/// The hash of a message key used by the Java client by default, the
+/// `JavaStringHash` hashing scheme. It is the `String.hashCode` of the key,
+/// computed over its UTF-16 code units, with the sign bit cleared.
+pub fn java_string_hash(key: &str) -> u32 {
+    let hash = key.encode_utf16().fold(0_u32, |hash, code_unit| {
+        hash.wrapping_mul(31).wrapping_add(u32::from(code_unit))
+    });
+    hash & 0x7fff_ffff
+}
+
+/// Returns the index of the partition for a message with the given key.
+pub fn key_partition(key: &str, partition_count: usize) -> usize {
+    let hash = java_string_hash(key);
+    usize::try_from(hash).expect("hash must fit into usize") % partition_count
+}
+
+/// Pulsar message keys are strings, so the keys that aren't valid UTF-8 are
+/// encoded in base64.
+pub fn message_key(key: Vec<u8>) -> String {
+    String::from_utf8(key).unwrap_or_else(|e| base64encoder.encode(e.as_bytes()))
+}
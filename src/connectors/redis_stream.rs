@@ -0,0 +1,97 @@
+// Copyright © 2024 Pathway
+
+//! Helpers for the Redis connectors: the stream entry identifiers, which are
+//! used as the offsets of the Streams reader, and the conversion between the
+//! Redis strings and the engine values.
+//!
+//! Entry ID format description: <https://redis.io/docs/latest/develop/data-types/streams/#entry-ids>
+
+use std::fmt;
+use std::str::{from_utf8, FromStr};
+
+use chrono::{DateTime, NaiveDateTime};
+use serde_json::Value as JsonValue;
+
+use crate::connectors::data_format::{serialize_value_to_json, FormatterError};
+use crate::engine::{Type, Value};
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("invalid stream entry ID {0:?}")]
+    InvalidEntryId(String),
+}
+
+/// The identifier of a stream entry, `<milliseconds>-<sequence>`. The entries
+/// of a stream are ordered by their identifiers.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct EntryId {
+    pub milliseconds: u64,
+    pub sequence: u64,
+}
+
+impl FromStr for EntryId {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid_entry_id = || Error::InvalidEntryId(value.to_string());
+        let (milliseconds, sequence) = value.split_once('-').ok_or_else(invalid_entry_id)?;
+        Ok(Self {
+            milliseconds: milliseconds.parse().map_err(|_| invalid_entry_id())?,
+            sequence: sequence.parse().map_err(|_| invalid_entry_id())?,
+        })
+    }
+}
+
+impl fmt::Display for EntryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.milliseconds, self.sequence)
+    }
+}
+
+/// Parses the value of a stream entry field. The booleans are accepted as
+/// `true`/`false` or `1`/`0`, the dates in the ISO 8601 format.
+pub fn parse_field_value(raw_value: &[u8], type_: &Type) -> Option<Value> {
+    match type_ {
+        Type::Optional(arg) => parse_field_value(raw_value, arg),
+        Type::Bytes => Some(Value::Bytes(raw_value.into())),
+        _ => {
+            let raw_value = from_utf8(raw_value).ok()?;
+            match type_ {
+                Type::Any | Type::String => Some(Value::from(raw_value)),
+                Type::Bool => match raw_value.to_lowercase().as_str() {
+                    "true" | "1" => Some(Value::Bool(true)),
+                    "false" | "0" => Some(Value::Bool(false)),
+                    _ => None,
+                },
+                Type::Int => raw_value.parse::<i64>().ok().map(Value::from),
+                Type::Float => raw_value.parse::<f64>().ok().map(Value::from),
+                Type::Json => serde_json::from_str::<JsonValue>(raw_value)
+                    .ok()
+                    .map(Value::from),
+                Type::DateTimeNaive => NaiveDateTime::from_str(raw_value)
+                    .ok()
+                    .map(|parsed| Value::DateTimeNaive(parsed.into())),
+                Type::DateTimeUtc => DateTime::parse_from_rfc3339(raw_value)
+                    .ok()
+                    .map(|parsed| Value::DateTimeUtc(parsed.into())),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Formats a value to be stored in a Redis string or a hash field. The strings
+/// and the bytes are stored as they are, the other values in their JSON
+/// representation. `None` means that the value is absent.
+pub fn format_field_value(value: &Value) -> Result<Option<Vec<u8>>, FormatterError> {
+    match value {
+        Value::None => Ok(None),
+        Value::String(string) => Ok(Some(string.as_bytes().to_vec())),
+        Value::Bytes(bytes) => Ok(Some(bytes.to_vec())),
+        value => match serialize_value_to_json(value)? {
+            JsonValue::String(string) => Ok(Some(string.into_bytes())),
+            json => Ok(Some(json.to_string().into_bytes())),
+        },
+    }
+}
@@ -0,0 +1,464 @@
+// Copyright © 2024 Pathway
+
+//! Expansion of tar and zip archives into their members. Each member is an
+//! object of its own, addressed by the path of the archive and its path within
+//! the archive, joined with `!/`: for example, `bundle.zip!/a.csv`.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read};
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use log::warn;
+use tar::Archive as TarArchive;
+use zip::DateTime as ZipDateTime;
+use zip::ZipArchive;
+
+use crate::connectors::compression::{decompressing_reader, Decompression};
+use crate::connectors::metadata::FileLikeMetadata;
+use crate::connectors::scanner::{PosixLikeScanner, QueuedAction};
+use crate::connectors::ReadError;
+use crate::persistence::backends::MemoryKVStorage;
+use crate::persistence::cached_object_storage::CachedObjectStorage;
+
+pub const MEMBER_PATH_SEPARATOR: &[u8] = b"!/";
+
+const ZIP_EXTENSIONS: [&str; 1] = [".zip"];
+const TAR_EXTENSIONS: [&str; 9] = [
+    ".tar",
+    ".tar.gz",
+    ".tgz",
+    ".tar.zst",
+    ".tar.zstd",
+    ".tar.bz2",
+    ".tbz2",
+    ".tar.xz",
+    ".txz",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum ArchiveFormat {
+    Tar,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Detects the format of an archive by its extension. A tar archive may
+    /// also be compressed with any of the supported codecs.
+    pub fn from_path(path: &[u8]) -> Option<Self> {
+        let path = String::from_utf8_lossy(path).to_ascii_lowercase();
+        if ZIP_EXTENSIONS
+            .iter()
+            .any(|extension| path.ends_with(extension))
+        {
+            Some(Self::Zip)
+        } else if TAR_EXTENSIONS
+            .iter()
+            .any(|extension| path.ends_with(extension))
+        {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn member_path(archive_path: &[u8], member_name: &str) -> Vec<u8> {
+    let mut path = archive_path.to_vec();
+    path.extend_from_slice(MEMBER_PATH_SEPARATOR);
+    path.extend_from_slice(member_name.as_bytes());
+    path
+}
+
+/// Splits the path of an archive member into the path of the archive and the
+/// name of the member. Returns `None` if the path doesn't address a member.
+pub fn split_member_path(path: &[u8]) -> Option<(&[u8], &str)> {
+    let mut start = 0;
+    while let Some(position) = path[start..]
+        .windows(MEMBER_PATH_SEPARATOR.len())
+        .position(|window| window == MEMBER_PATH_SEPARATOR)
+    {
+        let separator_start = start + position;
+        let archive_path = &path[..separator_start];
+        if ArchiveFormat::from_path(archive_path).is_some() {
+            let member_name =
+                std::str::from_utf8(&path[separator_start + MEMBER_PATH_SEPARATOR.len()..]).ok()?;
+            return Some((archive_path, member_name));
+        }
+        start = separator_start + 1;
+    }
+    None
+}
+
+struct ArchiveMember {
+    name: String,
+    modified_at: Option<u64>,
+    contents: Vec<u8>,
+}
+
+impl ArchiveMember {
+    fn metadata(&self, archive_metadata: &FileLikeMetadata, path: &[u8]) -> FileLikeMetadata {
+        FileLikeMetadata::from_archive_member(
+            archive_metadata,
+            String::from_utf8_lossy(path).to_string(),
+            self.contents.len() as u64,
+            self.modified_at,
+        )
+    }
+}
+
+/// Reads the regular files of an archive. If a name occurs several times,
+/// the last member with it is taken, as `tar` does when extracting.
+fn read_members(
+    archive_path: &[u8],
+    format: ArchiveFormat,
+    contents: Vec<u8>,
+) -> Result<Vec<ArchiveMember>, std::io::Error> {
+    let mut members: Vec<ArchiveMember> = Vec::new();
+    let mut member_index_by_name = HashMap::new();
+    let mut add_member = |member: ArchiveMember| {
+        if let Some(index) = member_index_by_name.get(&member.name) {
+            members[*index] = member;
+        } else {
+            member_index_by_name.insert(member.name.clone(), members.len());
+            members.push(member);
+        }
+    };
+
+    match format {
+        ArchiveFormat::Tar => {
+            // The compressed tarballs, such as `.tar.gz`, are expanded regardless
+            // of whether the decompression of the objects is enabled
+            let reader = decompressing_reader(archive_path, contents, Decompression::Auto)?;
+            let mut archive = TarArchive::new(reader);
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                if !entry.header().entry_type().is_file() {
+                    continue;
+                }
+                let name = entry.path()?.to_string_lossy().to_string();
+                let modified_at = entry.header().mtime().ok();
+                let mut contents = Vec::new();
+                entry.read_to_end(&mut contents)?;
+                add_member(ArchiveMember {
+                    name,
+                    modified_at,
+                    contents,
+                });
+            }
+        }
+        ArchiveFormat::Zip => {
+            let mut archive = ZipArchive::new(Cursor::new(contents))?;
+            for index in 0..archive.len() {
+                let mut file = archive.by_index(index)?;
+                if !file.is_file() {
+                    continue;
+                }
+                let name = file.name().to_string();
+                let modified_at = file.last_modified().and_then(zip_time_to_unix_timestamp);
+                let mut contents = Vec::new();
+                file.read_to_end(&mut contents)?;
+                add_member(ArchiveMember {
+                    name,
+                    modified_at,
+                    contents,
+                });
+            }
+        }
+    }
+
+    Ok(members)
+}
+
+/// The time of a zip member has no time zone, so it's taken in UTC. It's only
+/// compared with the earlier times of the same member, so it doesn't matter.
+fn zip_time_to_unix_timestamp(time: ZipDateTime) -> Option<u64> {
+    let time = NaiveDate::from_ymd_opt(time.year().into(), time.month().into(), time.day().into())?
+        .and_hms_opt(
+            time.hour().into(),
+            time.minute().into(),
+            time.second().into(),
+        )?;
+    time.and_utc().timestamp().try_into().ok()
+}
+
+/// Wraps a scanner, so that the tar and zip archives it finds are expanded
+/// into their members. The other objects are passed as they are.
+///
+/// The reader tracks the members, while the wrapped scanner needs to track the
+/// archives themselves, so it's given a separate storage with the objects it
+/// has reported. When an archive is new or modified, its members are compared
+/// with the ones stored by the reader, and only the changed members are
+/// reread. A member is considered changed if its size or modification time
+/// is different.
+///
+/// An archive is extracted in full at once, and its members are kept until
+/// they are read or until the archive changes.
+#[allow(clippy::module_name_repetitions)]
+pub struct ArchiveScanner {
+    scanner: Box<dyn PosixLikeScanner>,
+    scanned_objects: CachedObjectStorage,
+    pending_members: HashMap<Vec<u8>, ArchiveMember>,
+    reported_corrupt_archives: HashSet<(Vec<u8>, Option<u64>)>,
+    is_initialized: bool,
+}
+
+impl PosixLikeScanner for ArchiveScanner {
+    fn object_metadata(
+        &mut self,
+        object_path: &[u8],
+    ) -> Result<Option<FileLikeMetadata>, ReadError> {
+        let Some((archive_path, _)) = split_member_path(object_path) else {
+            return self.scanner.object_metadata(object_path);
+        };
+        let Some(archive_metadata) = self.scanner.object_metadata(archive_path)? else {
+            return Ok(None);
+        };
+        if !self.pending_members.contains_key(object_path) {
+            self.extract_members(archive_path)?;
+        }
+        let metadata = self
+            .pending_members
+            .get(object_path)
+            .map(|member| member.metadata(&archive_metadata, object_path));
+        Ok(metadata)
+    }
+
+    fn read_object(&mut self, object_path: &[u8]) -> Result<Vec<u8>, ReadError> {
+        if let Some(member) = self.pending_members.remove(object_path) {
+            return Ok(member.contents);
+        }
+        let Some((archive_path, _)) = split_member_path(object_path) else {
+            return self.scanner.read_object(object_path);
+        };
+        self.extract_members(archive_path)?;
+        match self.pending_members.remove(object_path) {
+            Some(member) => Ok(member.contents),
+            None => Err(ReadError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!(
+                    "archive member {} not found",
+                    String::from_utf8_lossy(object_path)
+                ),
+            ))),
+        }
+    }
+
+    fn next_scanner_actions(
+        &mut self,
+        are_deletions_enabled: bool,
+        cached_object_storage: &CachedObjectStorage,
+    ) -> Result<Vec<QueuedAction>, ReadError> {
+        let mut result = Vec::new();
+        if !self.is_initialized {
+            result.append(&mut self.initialize(are_deletions_enabled, cached_object_storage)?);
+            self.is_initialized = true;
+        }
+
+        let scanner_actions = self
+            .scanner
+            .next_scanner_actions(are_deletions_enabled, &self.scanned_objects)?;
+        for action in scanner_actions {
+            let is_archive = ArchiveFormat::from_path(action.path()).is_some();
+            if is_archive {
+                // The members extracted from the previous version aren't valid
+                self.forget_members(action.path());
+            }
+            match action {
+                QueuedAction::Read(path, metadata) | QueuedAction::Update(path, metadata)
+                    if is_archive =>
+                {
+                    match self.member_actions(
+                        &path,
+                        &metadata,
+                        are_deletions_enabled,
+                        cached_object_storage,
+                    ) {
+                        Ok(mut member_actions) => {
+                            result.append(&mut member_actions);
+                            self.reported_corrupt_archives
+                                .retain(|(archive_path, _)| *archive_path != path);
+                            self.scanned_objects
+                                .place_object(&path, Vec::new(), metadata)?;
+                        }
+                        Err(e) => {
+                            // The archive is retried on every scan, but the
+                            // warning is shown once for each of its versions
+                            let version = (path.clone(), metadata.modified_at);
+                            if self.reported_corrupt_archives.insert(version) {
+                                warn!(
+                                    "Failed to expand the archive {}: {e}. It will be retried with the next bulk of updates.",
+                                    String::from_utf8_lossy(&path)
+                                );
+                            }
+                        }
+                    }
+                }
+                QueuedAction::Delete(path) if is_archive => {
+                    self.reported_corrupt_archives
+                        .retain(|(archive_path, _)| *archive_path != path);
+                    result.append(&mut Self::member_deletions(
+                        &path,
+                        &HashSet::new(),
+                        cached_object_storage,
+                    ));
+                    self.scanned_objects.remove_object(&path)?;
+                }
+                QueuedAction::Read(ref path, ref metadata)
+                | QueuedAction::Update(ref path, ref metadata) => {
+                    self.scanned_objects
+                        .place_object(path, Vec::new(), metadata.clone())?;
+                    result.push(action);
+                }
+                QueuedAction::Delete(ref path) => {
+                    self.scanned_objects.remove_object(path)?;
+                    result.push(action);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    fn enable_inotify(&mut self) -> Result<(), ReadError> {
+        self.scanner.enable_inotify()
+    }
+
+    fn wait_for_changes(&mut self, timeout: Duration) {
+        self.scanner.wait_for_changes(timeout);
+    }
+}
+
+impl ArchiveScanner {
+    pub fn new(scanner: Box<dyn PosixLikeScanner>) -> Result<Self, ReadError> {
+        Ok(Self {
+            scanner,
+            scanned_objects: CachedObjectStorage::new(Box::new(MemoryKVStorage::new()))?,
+            pending_members: HashMap::new(),
+            reported_corrupt_archives: HashSet::new(),
+            is_initialized: false,
+        })
+    }
+
+    /// Restores the objects known to the wrapped scanner from the objects
+    /// stored by the reader. The metadata of the archives isn't stored, so
+    /// they are reported again and their members are compared with the stored
+    /// ones. The archives that no longer exist are removed right away.
+    fn initialize(
+        &mut self,
+        are_deletions_enabled: bool,
+        cached_object_storage: &CachedObjectStorage,
+    ) -> Result<Vec<QueuedAction>, ReadError> {
+        let mut stored_archive_paths = HashSet::new();
+        for (path, metadata) in cached_object_storage.get_iter() {
+            if let Some((archive_path, _)) = split_member_path(path) {
+                stored_archive_paths.insert(archive_path.to_vec());
+            } else {
+                self.scanned_objects
+                    .place_object(path, Vec::new(), metadata.clone())?;
+            }
+        }
+
+        let mut result = Vec::new();
+        if !are_deletions_enabled {
+            return Ok(result);
+        }
+        let mut stored_archive_paths: Vec<_> = stored_archive_paths.into_iter().collect();
+        stored_archive_paths.sort();
+        for archive_path in stored_archive_paths {
+            if self.scanner.object_metadata(&archive_path)?.is_none() {
+                result.append(&mut Self::member_deletions(
+                    &archive_path,
+                    &HashSet::new(),
+                    cached_object_storage,
+                ));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Extracts all members of an archive in one pass and keeps them until
+    /// they are read, so that an archive with many members is read once and
+    /// not once for each of them.
+    fn extract_members(&mut self, archive_path: &[u8]) -> Result<(), ReadError> {
+        let format = ArchiveFormat::from_path(archive_path)
+            .expect("member paths must start with an archive path");
+        let contents = self.scanner.read_object(archive_path)?;
+        for member in read_members(archive_path, format, contents)? {
+            self.pending_members
+                .insert(member_path(archive_path, &member.name), member);
+        }
+        Ok(())
+    }
+
+    fn forget_members(&mut self, archive_path: &[u8]) {
+        let members_prefix = member_path(archive_path, "");
+        self.pending_members
+            .retain(|path, _| !path.starts_with(&members_prefix));
+    }
+
+    fn member_actions(
+        &mut self,
+        archive_path: &[u8],
+        archive_metadata: &FileLikeMetadata,
+        are_deletions_enabled: bool,
+        cached_object_storage: &CachedObjectStorage,
+    ) -> Result<Vec<QueuedAction>, ReadError> {
+        let format = ArchiveFormat::from_path(archive_path)
+            .expect("archive actions must have archive paths");
+        let contents = self.scanner.read_object(archive_path)?;
+        let members = read_members(archive_path, format, contents)?;
+
+        let mut result = Vec::new();
+        let mut present_member_paths = HashSet::new();
+        for member in members {
+            let path = member_path(archive_path, &member.name);
+            let metadata = member.metadata(archive_metadata, &path);
+            let action = match cached_object_storage.stored_metadata(&path) {
+                None => Some(QueuedAction::Read(path.clone(), metadata)),
+                Some(stored_metadata)
+                    if are_deletions_enabled && stored_metadata.is_changed(&metadata) =>
+                {
+                    Some(QueuedAction::Update(path.clone(), metadata))
+                }
+                Some(_) => None,
+            };
+            if let Some(action) = action {
+                self.pending_members.insert(path.clone(), member);
+                result.push(action);
+            }
+            present_member_paths.insert(path);
+        }
+        if are_deletions_enabled {
+            result.append(&mut Self::member_deletions(
+                archive_path,
+                &present_member_paths,
+                cached_object_storage,
+            ));
+        }
+        Ok(result)
+    }
+
+    /// Returns the deletions of the stored members of an archive, except for
+    /// the ones that are still present in it.
+    fn member_deletions(
+        archive_path: &[u8],
+        present_member_paths: &HashSet<Vec<u8>>,
+        cached_object_storage: &CachedObjectStorage,
+    ) -> Vec<QueuedAction> {
+        let members_prefix = member_path(archive_path, "");
+        let mut deleted_member_paths: Vec<_> = cached_object_storage
+            .get_iter()
+            .map(|(path, _)| path)
+            .filter(|path| {
+                path.starts_with(&members_prefix) && !present_member_paths.contains(*path)
+            })
+            .cloned()
+            .collect();
+        deleted_member_paths.sort();
+        deleted_member_paths
+            .into_iter()
+            .map(QueuedAction::Delete)
+            .collect()
+    }
+}
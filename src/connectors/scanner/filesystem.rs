@@ -2,14 +2,24 @@ use std::ffi::OsStr;
 use std::fmt::Debug;
 use std::os::unix::ffi::OsStrExt;
 use std::path::PathBuf;
+#[cfg(target_os = "linux")]
+use std::path::{Component, Path};
+#[cfg(target_os = "linux")]
+use std::time::Duration;
 
 use log::error;
+#[cfg(target_os = "linux")]
+use log::{info, warn};
 
 use crate::connectors::metadata::FileLikeMetadata;
+#[cfg(target_os = "linux")]
+use crate::connectors::scanner::inotify::InotifyWatcher;
 use crate::connectors::scanner::{PosixLikeScanner, QueuedAction};
 use crate::connectors::ReadError;
 use crate::persistence::cached_object_storage::CachedObjectStorage;
 
+#[cfg(target_os = "linux")]
+use glob::MatchOptions as GlobMatchOptions;
 use glob::Pattern as GlobPattern;
 
 #[derive(Debug)]
@@ -17,6 +27,22 @@ use glob::Pattern as GlobPattern;
 pub struct FilesystemScanner {
     path: GlobPattern,
     object_pattern: String,
+
+    #[cfg(target_os = "linux")]
+    notifications: Option<Notifications>,
+}
+
+/// The state of the scanner, which detects the changes by inotify events.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+struct Notifications {
+    watcher: InotifyWatcher,
+
+    // The patterns to check if a changed file is among the scanned ones. The
+    // first one is matched by the scanned files and directories, the second
+    // one by the names of the files within the directories.
+    entry_pattern: GlobPattern,
+    object_pattern: GlobPattern,
 }
 
 impl PosixLikeScanner for FilesystemScanner {
@@ -47,14 +73,37 @@ impl PosixLikeScanner for FilesystemScanner {
         are_deletions_enabled: bool,
         cached_object_storage: &CachedObjectStorage,
     ) -> Result<Vec<QueuedAction>, ReadError> {
-        let mut result = Vec::new();
-        if are_deletions_enabled {
-            result.append(&mut Self::new_deletion_and_replacement_actions(
-                cached_object_storage,
-            ));
+        #[cfg(target_os = "linux")]
+        if self.notifications.is_some() {
+            return self.next_notified_actions(are_deletions_enabled, cached_object_storage);
+        }
+        self.next_full_scan_actions(are_deletions_enabled, cached_object_storage)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn enable_inotify(&mut self) -> Result<(), ReadError> {
+        let (root, is_recursive) = self.watched_root();
+        let entry_path = self.path.as_str().trim_end_matches('/');
+        self.notifications = Some(Notifications {
+            watcher: InotifyWatcher::new(root, is_recursive)?,
+            entry_pattern: GlobPattern::new(if entry_path.is_empty() {
+                "/"
+            } else {
+                entry_path
+            })?,
+            object_pattern: GlobPattern::new(&self.object_pattern)?,
+        });
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    fn wait_for_changes(&mut self, timeout: Duration) {
+        match &self.notifications {
+            Some(notifications) if notifications.watcher.is_watching() => {
+                notifications.watcher.wait_for_events(timeout);
+            }
+            _ => std::thread::sleep(timeout),
         }
-        result.append(&mut self.new_insertion_actions(cached_object_storage)?);
-        Ok(result)
     }
 }
 
@@ -64,9 +113,179 @@ impl FilesystemScanner {
         Ok(Self {
             path: path_glob,
             object_pattern: object_pattern.to_string(),
+
+            #[cfg(target_os = "linux")]
+            notifications: None,
+        })
+    }
+
+    fn next_full_scan_actions(
+        &mut self,
+        are_deletions_enabled: bool,
+        cached_object_storage: &CachedObjectStorage,
+    ) -> Result<Vec<QueuedAction>, ReadError> {
+        let mut result = Vec::new();
+        if are_deletions_enabled {
+            result.append(&mut Self::new_deletion_and_replacement_actions(
+                cached_object_storage,
+            ));
+        }
+        result.append(&mut self.new_insertion_actions(cached_object_storage)?);
+        Ok(result)
+    }
+
+    /// Returns the actions for the paths reported by inotify. The whole tree
+    /// is rescanned instead if the events could have been lost or if the tree
+    /// isn't watched yet.
+    #[cfg(target_os = "linux")]
+    fn next_notified_actions(
+        &mut self,
+        are_deletions_enabled: bool,
+        cached_object_storage: &CachedObjectStorage,
+    ) -> Result<Vec<QueuedAction>, ReadError> {
+        let notifications = self
+            .notifications
+            .as_mut()
+            .expect("inotify must be enabled");
+        let changes = notifications.watcher.read_changes()?;
+        if !notifications.watcher.is_watching() {
+            if changes.needs_full_rescan {
+                info!(
+                    "Some inotify events could have been missed. The directory will be rescanned."
+                );
+            }
+            if let Err(e) = notifications.watcher.watch() {
+                warn!(
+                    "Failed to watch the changes of {}: {e}. It will be rescanned on every poll until watching succeeds.",
+                    self.path.as_str()
+                );
+            }
+            return self.next_full_scan_actions(are_deletions_enabled, cached_object_storage);
+        }
+
+        let mut changed_paths: Vec<PathBuf> = changes.paths.into_iter().collect();
+        for (encoded_path, _) in cached_object_storage.get_iter() {
+            let path = Path::new(OsStr::from_bytes(encoded_path));
+            if changes
+                .removed_directories
+                .iter()
+                .any(|directory| path.starts_with(directory))
+            {
+                changed_paths.push(path.to_path_buf());
+            }
+        }
+        changed_paths.sort();
+        changed_paths.dedup();
+
+        let is_recursive = notifications.watcher.is_recursive();
+        let mut result = Vec::new();
+        for path in changed_paths {
+            if !is_recursive && path.is_dir() && self.is_scanned_entry(&path) {
+                // The scanned directory has appeared after the watch was set
+                // up, so its parent was watched instead. Now it can be watched
+                // itself.
+                self.enable_inotify()?;
+                return self.next_notified_actions(are_deletions_enabled, cached_object_storage);
+            }
+            let object_key = path.as_os_str().as_bytes();
+            if let Some(stored_metadata) = cached_object_storage.stored_metadata(object_key) {
+                // The same checks as in the full rescan
+                if !are_deletions_enabled {
+                    continue;
+                }
+                match std::fs::metadata(&path) {
+                    Err(e) => {
+                        if e.kind() == std::io::ErrorKind::NotFound {
+                            result.push(QueuedAction::Delete(object_key.into()));
+                        }
+                    }
+                    Ok(metadata) => {
+                        let actual_metadata = FileLikeMetadata::from_fs_meta(&path, &metadata);
+                        if stored_metadata.is_changed(&actual_metadata) {
+                            result.push(QueuedAction::Update(object_key.into(), actual_metadata));
+                        }
+                    }
+                }
+            } else if self.is_scanned_file(&path) {
+                let Ok(metadata) = std::fs::metadata(&path) else {
+                    continue;
+                };
+                let metadata = FileLikeMetadata::from_fs_meta(&path, &metadata);
+                result.push(QueuedAction::Read(object_key.into(), metadata));
+            }
+        }
+        Ok(result)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn is_scanned_entry(&self, path: &Path) -> bool {
+        self.notifications.as_ref().is_some_and(|notifications| {
+            notifications
+                .entry_pattern
+                .matches_path_with(path, Self::match_options())
         })
     }
 
+    /// Checks if the full rescan would return the file: either the file is
+    /// matched by the path pattern itself, or it lies within a matched
+    /// directory and its name is matched by the object pattern.
+    #[cfg(target_os = "linux")]
+    fn is_scanned_file(&self, path: &Path) -> bool {
+        let Some(notifications) = &self.notifications else {
+            return false;
+        };
+        if !path.is_file() {
+            return false;
+        }
+        if self.is_scanned_entry(path) {
+            return true;
+        }
+        let Some(file_name) = path.file_name() else {
+            return false;
+        };
+        notifications
+            .object_pattern
+            .matches_path_with(Path::new(file_name), Self::match_options())
+            && path
+                .ancestors()
+                .skip(1)
+                .any(|ancestor| self.is_scanned_entry(ancestor))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn match_options() -> GlobMatchOptions {
+        GlobMatchOptions {
+            require_literal_separator: true,
+            ..GlobMatchOptions::new()
+        }
+    }
+
+    /// Returns the directory to watch: the longest prefix of the path pattern
+    /// without the special characters. If it's a directory, it's watched with
+    /// all its subdirectories, otherwise only its parent is watched.
+    #[cfg(target_os = "linux")]
+    fn watched_root(&self) -> (PathBuf, bool) {
+        let mut root = PathBuf::new();
+        let mut has_special_characters = false;
+        for component in Path::new(self.path.as_str()).components() {
+            let is_pattern = matches!(component, Component::Normal(name)
+                if name.as_bytes().iter().any(|byte| matches!(byte, b'*' | b'?' | b'[')));
+            if is_pattern {
+                has_special_characters = true;
+                break;
+            }
+            root.push(component);
+        }
+        if has_special_characters || root.is_dir() {
+            return (root, true);
+        }
+        let parent = match root.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        (parent, false)
+    }
+
     fn new_deletion_and_replacement_actions(
         cached_object_storage: &CachedObjectStorage,
     ) -> Vec<QueuedAction> {
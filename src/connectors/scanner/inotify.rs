@@ -0,0 +1,241 @@
+// Copyright © 2024 Pathway
+
+//! Watching of a directory tree with inotify. The watcher collects the paths
+//! changed since the last check, so that only they need to be examined instead
+//! of the whole tree. If some events are lost, which happens when the kernel
+//! queue overflows, the tree must be rescanned in full.
+
+use log::warn;
+use std::collections::{HashMap, HashSet};
+use std::os::fd::AsFd;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use nix::errno::Errno;
+use nix::poll::{poll, PollFd, PollFlags, PollTimeout};
+use nix::sys::inotify::{AddWatchFlags, InitFlags, Inotify, WatchDescriptor};
+
+use crate::connectors::ReadError;
+
+#[derive(Debug, Default)]
+pub struct Changes {
+    /// The paths of the created, modified or removed entries of the watched
+    /// directories. It also has the files of the newly appeared directories.
+    pub paths: HashSet<PathBuf>,
+
+    /// The removed or moved away directories, all files under which are gone.
+    pub removed_directories: HashSet<PathBuf>,
+
+    /// Whether some changes could have been missed, so the tree must be
+    /// rescanned in full.
+    pub needs_full_rescan: bool,
+}
+
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct InotifyWatcher {
+    inotify: Inotify,
+    root: PathBuf,
+    is_recursive: bool,
+    is_watching: bool,
+    directory_by_watch: HashMap<WatchDescriptor, PathBuf>,
+    watch_by_directory: HashMap<PathBuf, WatchDescriptor>,
+}
+
+impl InotifyWatcher {
+    /// Creates a watcher of the `root` directory. If it's recursive, all the
+    /// subdirectories are watched as well.
+    pub fn new(root: PathBuf, is_recursive: bool) -> Result<Self, ReadError> {
+        let inotify = Inotify::init(InitFlags::IN_NONBLOCK | InitFlags::IN_CLOEXEC)
+            .map_err(std::io::Error::from)?;
+        Ok(Self {
+            inotify,
+            root,
+            is_recursive,
+            is_watching: false,
+            directory_by_watch: HashMap::new(),
+            watch_by_directory: HashMap::new(),
+        })
+    }
+
+    pub fn is_recursive(&self) -> bool {
+        self.is_recursive
+    }
+
+    /// Whether all changes since the last successful call of `watch` are
+    /// reported by the watcher.
+    pub fn is_watching(&self) -> bool {
+        self.is_watching
+    }
+
+    /// Sets up the watches of the whole tree. It must be done before the full
+    /// rescan, so that the changes made during the rescan aren't lost.
+    pub fn watch(&mut self) -> Result<(), Errno> {
+        self.is_watching = false;
+        let root = self.root.clone();
+        self.watch_directory(&root)?;
+        if self.is_recursive {
+            self.watch_recursively(&root)?;
+        }
+        self.is_watching = true;
+        Ok(())
+    }
+
+    /// Reads the events that have arrived since the last call.
+    pub fn read_changes(&mut self) -> Result<Changes, ReadError> {
+        let mut changes = Changes::default();
+        loop {
+            let events = match self.inotify.read_events() {
+                Ok(events) => events,
+                Err(Errno::EAGAIN) => break,
+                Err(e) => return Err(std::io::Error::from(e).into()),
+            };
+            for event in events {
+                if event.mask.contains(AddWatchFlags::IN_Q_OVERFLOW) {
+                    changes.needs_full_rescan = true;
+                    continue;
+                }
+                if event.mask.contains(AddWatchFlags::IN_IGNORED) {
+                    // The watched directory is removed, so is its watch
+                    self.forget_watch(event.wd);
+                    continue;
+                }
+                let Some(directory) = self.directory_by_watch.get(&event.wd).cloned() else {
+                    continue;
+                };
+                let Some(name) = event.name else {
+                    // The event is about the watched directory itself. Its
+                    // removal is reported by the parent, unless it's the root.
+                    if directory == self.root
+                        && event
+                            .mask
+                            .intersects(AddWatchFlags::IN_DELETE_SELF | AddWatchFlags::IN_MOVE_SELF)
+                    {
+                        changes.needs_full_rescan = true;
+                    }
+                    continue;
+                };
+                let path = directory.join(name);
+                if !event.mask.contains(AddWatchFlags::IN_ISDIR) {
+                    changes.paths.insert(path);
+                } else if event
+                    .mask
+                    .intersects(AddWatchFlags::IN_CREATE | AddWatchFlags::IN_MOVED_TO)
+                {
+                    if self.is_recursive {
+                        match self.watch_recursively(&path) {
+                            Ok(files) => changes.paths.extend(files),
+                            Err(e) => {
+                                warn!("Failed to watch the directory {path:?}: {e}");
+                                changes.needs_full_rescan = true;
+                            }
+                        }
+                    } else {
+                        changes.paths.insert(path);
+                    }
+                } else if event
+                    .mask
+                    .intersects(AddWatchFlags::IN_DELETE | AddWatchFlags::IN_MOVED_FROM)
+                {
+                    self.unwatch_recursively(&path);
+                    changes.removed_directories.insert(path);
+                }
+            }
+        }
+        if changes.needs_full_rescan {
+            self.is_watching = false;
+        }
+        Ok(changes)
+    }
+
+    /// Waits until there are new events, but no longer than `timeout`.
+    pub fn wait_for_events(&self, timeout: Duration) {
+        let poll_timeout = PollTimeout::try_from(timeout).unwrap_or(PollTimeout::MAX);
+        let mut poll_fds = [PollFd::new(self.inotify.as_fd(), PollFlags::POLLIN)];
+        match poll(&mut poll_fds, poll_timeout) {
+            Ok(_) | Err(Errno::EINTR) => {}
+            Err(e) => {
+                warn!("Failed to wait for inotify events: {e}");
+                std::thread::sleep(timeout);
+            }
+        }
+    }
+
+    fn watched_events() -> AddWatchFlags {
+        AddWatchFlags::IN_CREATE
+            | AddWatchFlags::IN_MODIFY
+            | AddWatchFlags::IN_CLOSE_WRITE
+            | AddWatchFlags::IN_ATTRIB
+            | AddWatchFlags::IN_DELETE
+            | AddWatchFlags::IN_MOVED_FROM
+            | AddWatchFlags::IN_MOVED_TO
+            | AddWatchFlags::IN_DELETE_SELF
+            | AddWatchFlags::IN_MOVE_SELF
+            | AddWatchFlags::IN_ONLYDIR
+    }
+
+    fn watch_directory(&mut self, directory: &Path) -> Result<(), Errno> {
+        let watch = self.inotify.add_watch(directory, Self::watched_events())?;
+        if let Some(previous_directory) = self
+            .directory_by_watch
+            .insert(watch, directory.to_path_buf())
+        {
+            if previous_directory != directory {
+                self.watch_by_directory.remove(&previous_directory);
+            }
+        }
+        self.watch_by_directory
+            .insert(directory.to_path_buf(), watch);
+        Ok(())
+    }
+
+    /// Watches a directory and all its subdirectories and returns the files
+    /// found in them. The directories that vanish meanwhile are skipped.
+    fn watch_recursively(&mut self, directory: &Path) -> Result<Vec<PathBuf>, Errno> {
+        let mut files = Vec::new();
+        let mut pending_directories = vec![directory.to_path_buf()];
+        while let Some(directory) = pending_directories.pop() {
+            match self.watch_directory(&directory) {
+                Ok(()) => {}
+                Err(Errno::ENOENT | Errno::ENOTDIR) => continue,
+                Err(e) => return Err(e),
+            }
+            let Ok(entries) = std::fs::read_dir(&directory) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                    pending_directories.push(entry.path());
+                } else {
+                    files.push(entry.path());
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    fn unwatch_recursively(&mut self, directory: &Path) {
+        let removed_directories: Vec<PathBuf> = self
+            .watch_by_directory
+            .keys()
+            .filter(|watched_directory| watched_directory.starts_with(directory))
+            .cloned()
+            .collect();
+        for removed_directory in removed_directories {
+            if let Some(watch) = self.watch_by_directory.remove(&removed_directory) {
+                self.directory_by_watch.remove(&watch);
+                // The watch is already gone if the directory is deleted
+                let _ = self.inotify.rm_watch(watch);
+            }
+        }
+    }
+
+    fn forget_watch(&mut self, watch: WatchDescriptor) {
+        if let Some(directory) = self.directory_by_watch.remove(&watch) {
+            self.watch_by_directory.remove(&directory);
+            if directory == self.root {
+                self.is_watching = false;
+            }
+        }
+    }
+}
@@ -1,10 +1,19 @@
+use std::thread::sleep;
+use std::time::Duration;
+
 use crate::connectors::metadata::FileLikeMetadata;
 use crate::connectors::ReadError;
 use crate::persistence::cached_object_storage::CachedObjectStorage;
 
+pub mod archive;
 pub mod filesystem;
+#[cfg(target_os = "linux")]
+pub mod inotify;
 pub mod s3;
 
+#[allow(clippy::module_name_repetitions)]
+pub use archive::ArchiveScanner;
+
 #[allow(clippy::module_name_repetitions)]
 pub use filesystem::FilesystemScanner;
 
@@ -38,4 +47,15 @@ pub trait PosixLikeScanner: Send {
         are_deletions_enabled: bool,
         cached_object_storage: &CachedObjectStorage,
     ) -> Result<Vec<QueuedAction>, ReadError>;
+
+    /// Makes the scanner detect the changes with inotify events instead of
+    /// scanning all objects on every poll.
+    fn enable_inotify(&mut self) -> Result<(), ReadError> {
+        Err(ReadError::InotifyUnsupported)
+    }
+
+    /// Blocks until the objects may have changed, but no longer than `timeout`.
+    fn wait_for_changes(&mut self, timeout: Duration) {
+        sleep(timeout);
+    }
 }
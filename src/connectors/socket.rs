@@ -0,0 +1,393 @@
+// Copyright © 2024 Pathway
+
+//! The listeners of the socket input connectors. A TCP listener splits the
+//! streams of its connections into messages according to the framing, while a
+//! UDP listener reads each datagram as a message.
+//!
+//! The listeners are run in their own threads and pass the messages to the
+//! reader through a bounded queue, so when the reader falls behind, the TCP
+//! clients are slowed down, and the UDP datagrams are dropped by the system.
+
+use std::io::{self, ErrorKind, Read};
+use std::net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::SyncSender;
+use std::sync::Arc;
+use std::thread::{sleep, Builder};
+use std::time::Duration;
+
+use log::{error, warn};
+
+/// The number of messages received by the listener, but not yet taken by the
+/// reader.
+pub const SOCKET_READER_QUEUE_SIZE: usize = 1024;
+
+pub const SOCKET_DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024;
+
+/// How often the listener threads check whether they must stop.
+const SOCKET_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+const TCP_READ_BUFFER_SIZE: usize = 8192;
+
+/// The largest possible UDP payload fits into this buffer.
+const UDP_RECEIVE_BUFFER_SIZE: usize = 65536;
+
+/// The number of decimal digits in the largest octet count that can be parsed.
+const MAX_OCTET_COUNT_LENGTH: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+impl Protocol {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Tcp => "tcp",
+            Self::Udp => "udp",
+        }
+    }
+}
+
+/// The way the messages are delimited in a TCP stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Framing {
+    /// Each message is a line, terminated with `\n` or `\r\n`. Empty lines
+    /// are skipped.
+    Lines,
+
+    /// Each message is preceded by its length as a 4-byte big-endian integer.
+    LengthPrefixed,
+
+    /// Each message is preceded by its length as a decimal number and a
+    /// space, as in the octet-counting framing of syslog (RFC 6587).
+    OctetCounting,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum FramingError {
+    #[error("message of {length} bytes exceeds the limit of {limit} bytes")]
+    TooLong { length: usize, limit: usize },
+
+    #[error("invalid octet count {0:?}")]
+    InvalidOctetCount(String),
+}
+
+/// Splits a stream of bytes into messages. The data is added as it comes
+/// from the socket, and the complete messages are taken one by one.
+#[derive(Debug)]
+pub struct FrameDecoder {
+    framing: Framing,
+    max_message_size: usize,
+    buffer: Vec<u8>,
+}
+
+impl FrameDecoder {
+    pub fn new(framing: Framing, max_message_size: usize) -> Self {
+        Self {
+            framing,
+            max_message_size,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn extend(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Returns the next complete message, or `None` if more data is needed.
+    /// After an error the stream can't be split further.
+    pub fn next_message(&mut self) -> Result<Option<Vec<u8>>, FramingError> {
+        match self.framing {
+            Framing::Lines => loop {
+                let Some(line_end) = self.buffer.iter().position(|byte| *byte == b'\n') else {
+                    self.check_length(self.buffer.len())?;
+                    return Ok(None);
+                };
+                let mut line: Vec<u8> = self.buffer.drain(..=line_end).collect();
+                line.pop();
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                self.check_length(line.len())?;
+                if !line.is_empty() {
+                    return Ok(Some(line));
+                }
+            },
+            Framing::LengthPrefixed => {
+                let Some(prefix) = self.buffer.get(..4) else {
+                    return Ok(None);
+                };
+                let length = u32::from_be_bytes(prefix.try_into().expect("prefix has 4 bytes"))
+                    .try_into()
+                    .expect("message length must fit into usize");
+                self.check_length(length)?;
+                Ok(self.take_message(4, length))
+            }
+            Framing::OctetCounting => {
+                let Some(separator) = self.buffer.iter().position(|byte| *byte == b' ') else {
+                    if self.buffer.len() > MAX_OCTET_COUNT_LENGTH
+                        || !self.buffer.iter().all(u8::is_ascii_digit)
+                    {
+                        return Err(self.invalid_octet_count(self.buffer.len()));
+                    }
+                    return Ok(None);
+                };
+                let octet_count = &self.buffer[..separator];
+                let is_valid = !octet_count.is_empty()
+                    && octet_count.len() <= MAX_OCTET_COUNT_LENGTH
+                    && octet_count[0] != b'0'
+                    && octet_count.iter().all(u8::is_ascii_digit);
+                if !is_valid {
+                    return Err(self.invalid_octet_count(separator));
+                }
+                let length = String::from_utf8_lossy(octet_count)
+                    .parse()
+                    .map_err(|_| self.invalid_octet_count(separator))?;
+                self.check_length(length)?;
+                Ok(self.take_message(separator + 1, length))
+            }
+        }
+    }
+
+    /// Returns the data left after the end of the stream. Only an unterminated
+    /// last line is a message, the incomplete frames of other framings are
+    /// dropped.
+    pub fn finish(self) -> Option<Vec<u8>> {
+        match self.framing {
+            Framing::Lines => {
+                let mut line = self.buffer;
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                (!line.is_empty()).then_some(line)
+            }
+            Framing::LengthPrefixed | Framing::OctetCounting => {
+                if !self.buffer.is_empty() {
+                    warn!(
+                        "The stream has ended with an incomplete message of {} bytes",
+                        self.buffer.len()
+                    );
+                }
+                None
+            }
+        }
+    }
+
+    fn check_length(&self, length: usize) -> Result<(), FramingError> {
+        if length > self.max_message_size {
+            Err(FramingError::TooLong {
+                length,
+                limit: self.max_message_size,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn take_message(&mut self, header_length: usize, length: usize) -> Option<Vec<u8>> {
+        if self.buffer.len() < header_length + length {
+            return None;
+        }
+        let message = self.buffer[header_length..header_length + length].to_vec();
+        self.buffer.drain(..header_length + length);
+        Some(message)
+    }
+
+    fn invalid_octet_count(&self, length: usize) -> FramingError {
+        let octet_count = &self.buffer[..length.min(MAX_OCTET_COUNT_LENGTH + 1)];
+        FramingError::InvalidOctetCount(String::from_utf8_lossy(octet_count).to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct ReceivedMessage {
+    pub peer_address: SocketAddr,
+    pub payload: Vec<u8>,
+}
+
+/// The listener running in its own thread. It is stopped when the handle is
+/// dropped: the threads notice it within the poll interval, so they aren't
+/// waited for.
+pub struct ListenerHandle {
+    local_address: SocketAddr,
+    stop: Arc<AtomicBool>,
+}
+
+impl ListenerHandle {
+    /// Binds the address and starts accepting the TCP connections. Each
+    /// connection is read in a separate thread.
+    pub fn start_tcp(
+        address: SocketAddr,
+        framing: Framing,
+        max_message_size: usize,
+        message_sender: SyncSender<ReceivedMessage>,
+    ) -> Result<ListenerHandle, io::Error> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        let local_address = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_listener = stop.clone();
+        Builder::new()
+            .name("pathway:tcp_input".to_string())
+            .spawn(move || {
+                while !stop_listener.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, peer_address)) => {
+                            let stop = stop_listener.clone();
+                            let message_sender = message_sender.clone();
+                            let decoder = FrameDecoder::new(framing, max_message_size);
+                            let spawned = Builder::new()
+                                .name("pathway:tcp_input_connection".to_string())
+                                .spawn(move || {
+                                    read_tcp_connection(
+                                        stream,
+                                        peer_address,
+                                        decoder,
+                                        &message_sender,
+                                        &stop,
+                                    );
+                                });
+                            if let Err(e) = spawned {
+                                error!("Failed to start reading the connection from {peer_address}: {e}");
+                            }
+                        }
+                        Err(e) if e.kind() == ErrorKind::WouldBlock => sleep(SOCKET_POLL_INTERVAL),
+                        Err(e) => {
+                            error!("Failed to accept a TCP connection at {local_address}: {e}");
+                            sleep(SOCKET_POLL_INTERVAL);
+                        }
+                    }
+                }
+            })?;
+        Ok(ListenerHandle {
+            local_address,
+            stop,
+        })
+    }
+
+    /// Binds the address and starts receiving the UDP datagrams. The datagrams
+    /// larger than `max_message_size` are dropped.
+    pub fn start_udp(
+        address: SocketAddr,
+        max_message_size: usize,
+        message_sender: SyncSender<ReceivedMessage>,
+    ) -> Result<ListenerHandle, io::Error> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_read_timeout(Some(SOCKET_POLL_INTERVAL))?;
+        let local_address = socket.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_listener = stop.clone();
+        Builder::new()
+            .name("pathway:udp_input".to_string())
+            .spawn(move || {
+                let mut buffer = vec![0; UDP_RECEIVE_BUFFER_SIZE];
+                while !stop_listener.load(Ordering::Relaxed) {
+                    match socket.recv_from(&mut buffer) {
+                        Ok((length, peer_address)) => {
+                            if length > max_message_size {
+                                warn!("Dropping a datagram of {length} bytes from {peer_address}, as it exceeds the limit of {max_message_size} bytes");
+                                continue;
+                            }
+                            let message = ReceivedMessage {
+                                peer_address,
+                                payload: buffer[..length].to_vec(),
+                            };
+                            if message_sender.send(message).is_err() {
+                                break;
+                            }
+                        }
+                        Err(e) if is_timeout(&e) => {}
+                        Err(e) => {
+                            error!("Failed to receive a UDP datagram at {local_address}: {e}");
+                            sleep(SOCKET_POLL_INTERVAL);
+                        }
+                    }
+                }
+            })?;
+        Ok(ListenerHandle {
+            local_address,
+            stop,
+        })
+    }
+
+    /// The address the listener is bound to. It differs from the requested
+    /// one if the port 0 was requested.
+    pub fn local_address(&self) -> SocketAddr {
+        self.local_address
+    }
+}
+
+impl Drop for ListenerHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn is_timeout(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted
+    )
+}
+
+/// Reads the messages of a TCP connection until it's closed. A connection
+/// that violates the framing is closed, as the rest of it can't be split.
+fn read_tcp_connection(
+    mut stream: TcpStream,
+    peer_address: SocketAddr,
+    mut decoder: FrameDecoder,
+    message_sender: &SyncSender<ReceivedMessage>,
+    stop: &AtomicBool,
+) {
+    // The accepted socket may inherit the non-blocking mode of the listener
+    let configured = stream
+        .set_nonblocking(false)
+        .and_then(|()| stream.set_read_timeout(Some(SOCKET_POLL_INTERVAL)));
+    if let Err(e) = configured {
+        error!("Failed to configure the connection from {peer_address}: {e}");
+        return;
+    }
+    let send = |payload| {
+        message_sender
+            .send(ReceivedMessage {
+                peer_address,
+                payload,
+            })
+            .is_ok()
+    };
+    let mut buffer = [0; TCP_READ_BUFFER_SIZE];
+    while !stop.load(Ordering::Relaxed) {
+        match stream.read(&mut buffer) {
+            Ok(0) => {
+                if let Some(payload) = decoder.finish() {
+                    send(payload);
+                }
+                return;
+            }
+            Ok(length) => {
+                decoder.extend(&buffer[..length]);
+                loop {
+                    match decoder.next_message() {
+                        Ok(Some(payload)) => {
+                            if !send(payload) {
+                                return;
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            error!("Closing the connection from {peer_address}: {e}");
+                            return;
+                        }
+                    }
+                }
+            }
+            Err(e) if is_timeout(&e) => {}
+            Err(e) => {
+                error!("Failed to read the connection from {peer_address}: {e}");
+                return;
+            }
+        }
+    }
+}
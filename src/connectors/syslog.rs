@@ -0,0 +1,319 @@
+// Copyright © 2024 Pathway
+
+//! Parsing of the syslog messages, both in the format of RFC 5424 and in the
+//! older BSD format of RFC 3164. The format is detected by the version and the
+//! timestamp, which follow the priority only in RFC 5424 messages.
+//!
+//! RFC 5424: <https://datatracker.ietf.org/doc/html/rfc5424>
+//! RFC 3164: <https://datatracker.ietf.org/doc/html/rfc3164>
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+
+const NIL_VALUE: &str = "-";
+const MAX_PRIORITY: u8 = 191;
+const MAX_TAG_LENGTH: usize = 32;
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("message doesn't start with a priority")]
+    MissingPriority,
+
+    #[error("invalid priority {0:?}")]
+    InvalidPriority(String),
+
+    #[error("header field {0} is missing")]
+    MissingHeaderField(&'static str),
+
+    #[error("invalid structured data: {0}")]
+    InvalidStructuredData(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub facility: u8,
+    pub severity: u8,
+
+    /// The version of the format, present only in RFC 5424 messages.
+    pub version: Option<u32>,
+    pub timestamp: Option<DateTime<FixedOffset>>,
+    pub hostname: Option<String>,
+    pub app_name: Option<String>,
+    pub procid: Option<String>,
+    pub msgid: Option<String>,
+
+    /// The structured data elements by their IDs, each being an object of
+    /// the parameters. If an element is repeated, its parameters are merged.
+    pub structured_data: JsonMap<String, JsonValue>,
+    pub message: String,
+}
+
+impl Message {
+    /// Parses a message. The timestamps of RFC 3164 don't have the year and
+    /// the time zone, so they are taken in UTC in the year, for which the
+    /// timestamp is the closest to `now`.
+    pub fn parse(line: &str, now: DateTime<Utc>) -> Result<Self, Error> {
+        let line = line.trim_end_matches(['\r', '\n', '\0']);
+        let rest = line.strip_prefix('<').ok_or(Error::MissingPriority)?;
+        let priority_end = rest.find('>').ok_or(Error::MissingPriority)?;
+        let priority_str = &rest[..priority_end];
+        let priority: u8 = priority_str
+            .parse()
+            .ok()
+            .filter(|priority| {
+                *priority <= MAX_PRIORITY
+                    && priority_str.len() <= 3
+                    && priority_str.bytes().all(|byte| byte.is_ascii_digit())
+            })
+            .ok_or_else(|| Error::InvalidPriority(priority_str.to_string()))?;
+        let rest = &rest[priority_end + 1..];
+
+        let mut message = Self {
+            facility: priority / 8,
+            severity: priority % 8,
+            version: None,
+            timestamp: None,
+            hostname: None,
+            app_name: None,
+            procid: None,
+            msgid: None,
+            structured_data: JsonMap::new(),
+            message: String::new(),
+        };
+
+        if let Some((version, timestamp, rest)) = split_rfc5424_version(rest) {
+            message.version = Some(version);
+            message.timestamp = timestamp;
+            message.parse_rfc5424(rest)?;
+        } else {
+            message.parse_rfc3164(rest, now);
+        }
+        Ok(message)
+    }
+
+    /// Parses the header of an RFC 5424 message after the timestamp, and the
+    /// rest of the message.
+    fn parse_rfc5424(&mut self, rest: &str) -> Result<(), Error> {
+        let (hostname, rest) = next_header_field(rest, "HOSTNAME")?;
+        let (app_name, rest) = next_header_field(rest, "APP-NAME")?;
+        let (procid, rest) = next_header_field(rest, "PROCID")?;
+        let (msgid, rest) = next_header_field(rest, "MSGID")?;
+        self.hostname = hostname.map(str::to_string);
+        self.app_name = app_name.map(str::to_string);
+        self.procid = procid.map(str::to_string);
+        self.msgid = msgid.map(str::to_string);
+
+        let rest = if let Some(rest) = rest.strip_prefix(NIL_VALUE) {
+            rest
+        } else if rest.starts_with('[') {
+            self.parse_structured_data(rest)?
+        } else {
+            return Err(Error::MissingHeaderField("STRUCTURED-DATA"));
+        };
+        let message = match rest.strip_prefix(' ') {
+            Some(message) => message,
+            None if rest.is_empty() => rest,
+            None => {
+                return Err(Error::InvalidStructuredData(
+                    "no space after the structured data".to_string(),
+                ))
+            }
+        };
+        self.message = message
+            .strip_prefix(BYTE_ORDER_MARK)
+            .unwrap_or(message)
+            .to_string();
+        Ok(())
+    }
+
+    /// Parses the structured data elements and returns the rest of the line.
+    fn parse_structured_data<'a>(&mut self, mut rest: &'a str) -> Result<&'a str, Error> {
+        let invalid = |reason: &str| Error::InvalidStructuredData(reason.to_string());
+        while let Some(element) = rest.strip_prefix('[') {
+            let id_end = element
+                .find([' ', ']'])
+                .ok_or_else(|| invalid("unterminated element"))?;
+            let id = &element[..id_end];
+            if id.is_empty() {
+                return Err(invalid("element without an ID"));
+            }
+            let mut parameters = JsonMap::new();
+            rest = &element[id_end..];
+            loop {
+                if let Some(after_element) = rest.strip_prefix(']') {
+                    rest = after_element;
+                    break;
+                }
+                let parameter = rest
+                    .strip_prefix(' ')
+                    .ok_or_else(|| invalid("unterminated element"))?;
+                let name_end = parameter
+                    .find('=')
+                    .ok_or_else(|| invalid("parameter without a value"))?;
+                let name = &parameter[..name_end];
+                let quoted_value = parameter[name_end + 1..]
+                    .strip_prefix('"')
+                    .ok_or_else(|| invalid("parameter value isn't quoted"))?;
+                let (value, after_value) = parse_parameter_value(quoted_value)
+                    .ok_or_else(|| invalid("unterminated parameter value"))?;
+                parameters.insert(name.to_string(), JsonValue::String(value));
+                rest = after_value;
+            }
+            match self.structured_data.get_mut(id) {
+                Some(JsonValue::Object(existing_parameters)) => {
+                    existing_parameters.extend(parameters);
+                }
+                _ => {
+                    self.structured_data
+                        .insert(id.to_string(), JsonValue::Object(parameters));
+                }
+            }
+        }
+        Ok(rest)
+    }
+
+    fn parse_rfc3164(&mut self, rest: &str, now: DateTime<Utc>) {
+        let rest = match parse_rfc3164_timestamp(rest, now) {
+            Some((timestamp, rest)) => {
+                self.timestamp = Some(timestamp);
+                let rest = rest.trim_start_matches(' ');
+                // The hostname may be omitted, then the tag follows the timestamp
+                match rest.split_once(' ') {
+                    Some((hostname, rest))
+                        if !hostname.is_empty()
+                            && !hostname.ends_with(':')
+                            && !hostname.contains('[') =>
+                    {
+                        self.hostname = Some(hostname.to_string());
+                        rest
+                    }
+                    _ => rest,
+                }
+            }
+            None => rest,
+        };
+        self.message = self.parse_rfc3164_tag(rest).to_string();
+    }
+
+    /// Takes the tag in the form of `app_name[procid]:` or `app_name:` from
+    /// the beginning of the content and returns the rest. If the content
+    /// doesn't start with a tag, it's all the message.
+    fn parse_rfc3164_tag<'a>(&mut self, content: &'a str) -> &'a str {
+        let tag_end = content.find([':', '[', ' ']).unwrap_or(content.len());
+        let app_name = &content[..tag_end];
+        if app_name.is_empty() || app_name.len() > MAX_TAG_LENGTH {
+            return content;
+        }
+        let mut rest = &content[tag_end..];
+        let mut procid = None;
+        if let Some(after_bracket) = rest.strip_prefix('[') {
+            let Some(procid_end) = after_bracket.find(']') else {
+                return content;
+            };
+            procid = Some(&after_bracket[..procid_end]);
+            rest = &after_bracket[procid_end + 1..];
+        }
+        if let Some(message) = rest.strip_prefix(':') {
+            rest = message;
+        } else if procid.is_none() {
+            return content;
+        }
+        self.app_name = Some(app_name.to_string());
+        self.procid = procid.map(str::to_string);
+        rest.strip_prefix(' ').unwrap_or(rest)
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        json!({
+            "facility": self.facility,
+            "severity": self.severity,
+            "version": self.version,
+            "timestamp": self.timestamp.map(|timestamp| timestamp.to_rfc3339()),
+            "hostname": self.hostname,
+            "app_name": self.app_name,
+            "procid": self.procid,
+            "msgid": self.msgid,
+            "structured_data": self.structured_data,
+            "message": self.message,
+        })
+    }
+}
+
+/// Splits the version and the timestamp of RFC 5424 from the rest of the
+/// header. Only the version 1 is defined, and it must be followed by an RFC 3339
+/// timestamp or the nil value, as the content of an RFC 3164 message without
+/// a timestamp can start with a number too, e.g. `<13>1 disk is full`.
+fn split_rfc5424_version(rest: &str) -> Option<(u32, Option<DateTime<FixedOffset>>, &str)> {
+    let rest = rest.strip_prefix("1 ")?;
+    let (timestamp, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+    let timestamp = if timestamp == NIL_VALUE {
+        None
+    } else {
+        Some(DateTime::parse_from_rfc3339(timestamp).ok()?)
+    };
+    Some((1, timestamp, rest))
+}
+
+/// Returns the next space-delimited field of an RFC 5424 header, or `None` in
+/// place of the nil value.
+fn next_header_field<'a>(
+    rest: &'a str,
+    name: &'static str,
+) -> Result<(Option<&'a str>, &'a str), Error> {
+    let (field, rest) = rest
+        .split_once(' ')
+        .ok_or(Error::MissingHeaderField(name))?;
+    if field.is_empty() {
+        return Err(Error::MissingHeaderField(name));
+    }
+    let field = (field != NIL_VALUE).then_some(field);
+    Ok((field, rest))
+}
+
+/// Parses a parameter value after the opening quote, resolving the escaped
+/// characters, and returns it with the rest after the closing quote.
+fn parse_parameter_value(quoted_value: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = quoted_value.char_indices();
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '"' => return Some((value, &quoted_value[index + 1..])),
+            '\\' => {
+                let (_, escaped) = chars.next()?;
+                // Only these characters are escaped, otherwise the backslash
+                // is a part of the value
+                if !matches!(escaped, '"' | '\\' | ']') {
+                    value.push('\\');
+                }
+                value.push(escaped);
+            }
+            _ => value.push(char),
+        }
+    }
+    None
+}
+
+/// Parses the `Mmm dd hh:mm:ss` timestamp and returns it with the rest of the
+/// line.
+fn parse_rfc3164_timestamp(
+    rest: &str,
+    now: DateTime<Utc>,
+) -> Option<(DateTime<FixedOffset>, &str)> {
+    let timestamp = rest.get(..15)?;
+    let rest = &rest[15..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    let closest_timestamp = [now.year() - 1, now.year(), now.year() + 1]
+        .into_iter()
+        .filter_map(|year| {
+            // The day is padded with a space, which the parser skips
+            let time =
+                NaiveDateTime::parse_from_str(&format!("{year} {timestamp}"), "%Y %b %e %H:%M:%S")
+                    .ok()?;
+            Some(Utc.from_utc_datetime(&time))
+        })
+        .min_by_key(|timestamp| (*timestamp - now).abs())?;
+    Some((closest_timestamp.fixed_offset(), rest))
+}
@@ -0,0 +1,441 @@
+// Copyright © 2024 Pathway
+
+//! The webhook connectors.
+//!
+//! The HTTP server of the input connector accepts the rows in the bodies of
+//! the POST requests to the configured routes and passes them to the reader.
+//! The response is sent once the reader has accepted the request, or, if the
+//! reader is configured so, once the rows have been processed.
+//!
+//! The output connector sends the formatted rows in batches to the URLs built
+//! from a template, and the helpers for building these requests are here too.
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use std::thread::Builder;
+use std::time::{Duration, SystemTime};
+
+use base64::engine::general_purpose::STANDARD as base64encoder;
+use base64::Engine;
+use chrono::DateTime;
+use hmac::{Hmac, Mac};
+use hyper::body::HttpBody;
+use hyper::server::conn::AddrStream;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{header, Body, Method, Request, Response, Server, StatusCode};
+use log::error;
+use serde_json::{json, Value as JsonValue};
+use sha2::Sha256;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::engine::{Timestamp, Value};
+
+/// The number of requests received by the server, but not yet taken by the
+/// reader. When the queue is full, the clients have to wait.
+pub const WEBHOOK_READER_QUEUE_SIZE: usize = 64;
+
+/// The default limit of the size of a request body, in bytes.
+pub const WEBHOOK_DEFAULT_MAX_REQUEST_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum RequestError {
+    #[error("request body exceeds the limit of {0} bytes")]
+    TooLarge(usize),
+
+    #[error("failed to read request body: {0}")]
+    Body(#[from] hyper::Error),
+
+    #[error("invalid JSON in request body: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    #[error("the reader has stopped")]
+    ReaderStopped,
+
+    #[error("the request has not been accepted")]
+    NotAccepted,
+}
+
+impl RequestError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::TooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Body(_) | Self::InvalidJson(_) => StatusCode::BAD_REQUEST,
+            Self::ReaderStopped | Self::NotAccepted => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+/// A request received by the server. The reader notifies the client via
+/// `accepted` once the rows are accepted; if it's dropped instead, the client
+/// gets an error.
+#[derive(Debug)]
+pub struct ReceivedRequest {
+    pub route: String,
+    pub peer_address: Option<SocketAddr>,
+    pub rows: Vec<Vec<u8>>,
+    pub accepted: oneshot::Sender<()>,
+}
+
+/// Splits a request body into the rows. A body sent as `application/json` is a
+/// single JSON value: an array gives a row for each of its elements, any other
+/// value is a single row. Otherwise, the body is read as JSON Lines and each
+/// non-empty line is a row, which is validated later by the parser.
+pub fn split_body_into_rows(
+    body: &[u8],
+    is_json_document: bool,
+) -> Result<Vec<Vec<u8>>, RequestError> {
+    if !is_json_document {
+        return Ok(body
+            .split(|byte| *byte == b'\n')
+            .map(<[u8]>::trim_ascii)
+            .filter(|line| !line.is_empty())
+            .map(<[u8]>::to_vec)
+            .collect());
+    }
+    match serde_json::from_slice(body)? {
+        JsonValue::Array(values) => Ok(values
+            .into_iter()
+            .map(|value| value.to_string().into_bytes())
+            .collect()),
+        value => Ok(vec![value.to_string().into_bytes()]),
+    }
+}
+
+fn is_json_document(request: &Request<Body>) -> bool {
+    request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(|content_type| content_type.split(';').next())
+        .is_some_and(|mime_type| mime_type.trim().eq_ignore_ascii_case("application/json"))
+}
+
+async fn read_body(mut body: Body, max_size: usize) -> Result<Vec<u8>, RequestError> {
+    if body.size_hint().lower() > u64::try_from(max_size).unwrap_or(u64::MAX) {
+        return Err(RequestError::TooLarge(max_size));
+    }
+    let mut result = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if result.len() + chunk.len() > max_size {
+            return Err(RequestError::TooLarge(max_size));
+        }
+        result.extend_from_slice(&chunk);
+    }
+    Ok(result)
+}
+
+fn json_response(status: StatusCode, body: &JsonValue) -> Response<Body> {
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+#[derive(Clone, Debug)]
+pub struct IngestServer {
+    routes: Arc<HashSet<String>>,
+    max_request_size: usize,
+    request_sender: mpsc::Sender<ReceivedRequest>,
+}
+
+impl IngestServer {
+    pub fn new(
+        routes: impl IntoIterator<Item = String>,
+        max_request_size: usize,
+        request_sender: mpsc::Sender<ReceivedRequest>,
+    ) -> Self {
+        Self {
+            routes: Arc::new(routes.into_iter().collect()),
+            max_request_size,
+            request_sender,
+        }
+    }
+
+    async fn handle(&self, request: Request<Body>, peer_address: SocketAddr) -> Response<Body> {
+        let route = request.uri().path().to_string();
+        if !self.routes.contains(&route) {
+            return json_response(
+                StatusCode::NOT_FOUND,
+                &json!({"error": format!("route {route:?} is not served")}),
+            );
+        }
+        if request.method() != Method::POST {
+            let mut response = json_response(
+                StatusCode::METHOD_NOT_ALLOWED,
+                &json!({"error": "only POST requests are accepted"}),
+            );
+            response
+                .headers_mut()
+                .insert(header::ALLOW, header::HeaderValue::from_static("POST"));
+            return response;
+        }
+        match self.send_rows(route, peer_address, request).await {
+            Ok(rows_accepted) => {
+                json_response(StatusCode::OK, &json!({"rows_accepted": rows_accepted}))
+            }
+            Err(e) => json_response(e.status_code(), &json!({"error": e.to_string()})),
+        }
+    }
+
+    async fn send_rows(
+        &self,
+        route: String,
+        peer_address: SocketAddr,
+        request: Request<Body>,
+    ) -> Result<usize, RequestError> {
+        let is_json_document = is_json_document(&request);
+        let body = read_body(request.into_body(), self.max_request_size).await?;
+        let rows = split_body_into_rows(&body, is_json_document)?;
+        let rows_count = rows.len();
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        let (accepted, accepted_receiver) = oneshot::channel();
+        self.request_sender
+            .send(ReceivedRequest {
+                route,
+                peer_address: Some(peer_address),
+                rows,
+                accepted,
+            })
+            .await
+            .map_err(|_| RequestError::ReaderStopped)?;
+        accepted_receiver
+            .await
+            .map_err(|_| RequestError::NotAccepted)?;
+        Ok(rows_count)
+    }
+}
+
+/// The server running in its own thread. It is stopped when the handle is
+/// dropped: the requests waiting for the reader get an error, as the reader
+/// is gone, so the thread isn't waited for.
+pub struct IngestServerHandle {
+    shutdown_sender: Option<oneshot::Sender<()>>,
+}
+
+impl IngestServerHandle {
+    /// Binds the address and starts serving the configured routes. The errors
+    /// of binding are returned here, the later errors are logged.
+    pub fn start(address: SocketAddr, server: IngestServer) -> Result<Self, io::Error> {
+        let (bind_result_sender, bind_result_receiver) = std_mpsc::sync_channel(1);
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+        Builder::new()
+            .name("pathway:webhook_input".to_string())
+            .spawn(move || {
+                let runtime = match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(runtime) => runtime,
+                    Err(e) => {
+                        let _ = bind_result_sender.send(Err(e));
+                        return;
+                    }
+                };
+                runtime.block_on(async move {
+                    let builder = match Server::try_bind(&address) {
+                        Ok(builder) => {
+                            let _ = bind_result_sender.send(Ok(()));
+                            builder
+                        }
+                        Err(e) => {
+                            let _ = bind_result_sender.send(Err(io::Error::other(e)));
+                            return;
+                        }
+                    };
+                    let make_service = make_service_fn(move |connection: &AddrStream| {
+                        let peer_address = connection.remote_addr();
+                        let server = server.clone();
+                        async move {
+                            Ok::<_, Infallible>(service_fn(move |request| {
+                                let server = server.clone();
+                                async move {
+                                    Ok::<_, Infallible>(server.handle(request, peer_address).await)
+                                }
+                            }))
+                        }
+                    });
+                    let serving = builder
+                        .serve(make_service)
+                        .with_graceful_shutdown(async move {
+                            let _ = shutdown_receiver.await;
+                        })
+                        .await;
+                    if let Err(e) = serving {
+                        error!("Webhook input server at {address} has failed: {e}");
+                    }
+                });
+            })?;
+        bind_result_receiver
+            .recv()
+            .map_err(|_| io::Error::other("webhook input server thread has stopped"))??;
+        Ok(IngestServerHandle {
+            shutdown_sender: Some(shutdown_sender),
+        })
+    }
+}
+
+impl Drop for IngestServerHandle {
+    fn drop(&mut self) {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            let _ = shutdown_sender.send(());
+        }
+    }
+}
+
+/// The way the formatted rows of a batch are put into a request body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchFormat {
+    JsonLines,
+    JsonArray,
+}
+
+impl BatchFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::JsonLines => "application/x-ndjson",
+            Self::JsonArray => "application/json",
+        }
+    }
+
+    pub fn join(self, payloads: &[Vec<u8>]) -> Vec<u8> {
+        let (opening, separator, closing): (&[u8], &[u8], &[u8]) = match self {
+            Self::JsonLines => (b"", b"\n", b"\n"),
+            Self::JsonArray => (b"[", b",", b"]"),
+        };
+        let payloads_size: usize = payloads.iter().map(Vec::len).sum();
+        let mut body = Vec::with_capacity(payloads_size + payloads.len() + 2);
+        body.extend_from_slice(opening);
+        for (index, payload) in payloads.iter().enumerate() {
+            if index > 0 {
+                body.extend_from_slice(separator);
+            }
+            body.extend_from_slice(payload);
+        }
+        if !payloads.is_empty() || self == Self::JsonArray {
+            body.extend_from_slice(closing);
+        }
+        body
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum UrlTemplateError {
+    #[error("column {0:?} used in the URL template doesn't exist")]
+    UnknownColumn(String),
+
+    #[error("a wildcard in the URL template isn't closed")]
+    UnclosedWildcard,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum UrlTemplatePart {
+    Literal(String),
+    Column(usize),
+    Time,
+    Diff,
+}
+
+/// A URL with the wildcards `{table.<column>}`, which are replaced with the
+/// values of the columns, the same as in the Python HTTP output connector.
+/// The wildcards `{table.time}` and `{table.diff}` are replaced with the time
+/// and the diff of the change. The substituted values are percent-encoded.
+#[derive(Clone, Debug)]
+pub struct UrlTemplate {
+    parts: Vec<UrlTemplatePart>,
+}
+
+impl UrlTemplate {
+    const WILDCARD_PREFIX: &'static str = "{table.";
+
+    pub fn parse(template: &str, column_names: &[String]) -> Result<Self, UrlTemplateError> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+        while let Some(wildcard_start) = rest.find(Self::WILDCARD_PREFIX) {
+            if wildcard_start > 0 {
+                parts.push(UrlTemplatePart::Literal(rest[..wildcard_start].to_string()));
+            }
+            rest = &rest[wildcard_start + Self::WILDCARD_PREFIX.len()..];
+            let wildcard_end = rest.find('}').ok_or(UrlTemplateError::UnclosedWildcard)?;
+            let name = &rest[..wildcard_end];
+            let part = match name {
+                "time" => UrlTemplatePart::Time,
+                "diff" => UrlTemplatePart::Diff,
+                name => UrlTemplatePart::Column(
+                    column_names
+                        .iter()
+                        .position(|column_name| column_name == name)
+                        .ok_or_else(|| UrlTemplateError::UnknownColumn(name.to_string()))?,
+                ),
+            };
+            parts.push(part);
+            rest = &rest[wildcard_end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(UrlTemplatePart::Literal(rest.to_string()));
+        }
+        Ok(Self { parts })
+    }
+
+    pub fn render(&self, values: &[Value], time: Timestamp, diff: isize) -> String {
+        let mut url = String::new();
+        for part in &self.parts {
+            match part {
+                UrlTemplatePart::Literal(literal) => url.push_str(literal),
+                UrlTemplatePart::Column(index) => {
+                    let value = match &values[*index] {
+                        Value::String(string) => string.to_string(),
+                        Value::Bytes(bytes) => base64encoder.encode(bytes),
+                        other => other.to_string(),
+                    };
+                    url.push_str(&percent_encode(&value));
+                }
+                UrlTemplatePart::Time => url.push_str(&time.to_string()),
+                UrlTemplatePart::Diff => url.push_str(&diff.to_string()),
+            }
+        }
+        url
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            result.push(char::from(byte));
+        } else {
+            result.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    result
+}
+
+/// Computes the signature of a request body: the HMAC-SHA256 of the body with
+/// the given secret, in the form `sha256=<hex digest>`.
+pub fn sign_body(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Parses the value of the `Retry-After` header, which is either a number of
+/// seconds or an HTTP date, into the duration to wait from `now`.
+pub fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let retry_at: SystemTime = DateTime::parse_from_rfc2822(value).ok()?.into();
+    Some(retry_at.duration_since(now).unwrap_or(Duration::ZERO))
+}
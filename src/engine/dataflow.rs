@@ -115,7 +115,7 @@ use super::{
     BatchWrapper, ColumnHandle, ColumnPath, ColumnProperties, ComplexColumn, Error, ErrorLogHandle,
     Expression, ExpressionData, Graph, IterationLogic, IxKeyPolicy, JoinData, JoinType, Key,
     LegacyTable, OperatorStats, ProberStats, Reducer, ReducerData, Result, ShardPolicy,
-    TableHandle, TableProperties, Timestamp, UniverseHandle, Value,
+    TableHandle, TableProperties, Timestamp, TotalFrontier, UniverseHandle, Value,
 };
 use crate::external_integration::{
     make_accessor, make_option_accessor, ExternalIndex, IndexDerivedImpl,
@@ -3700,18 +3700,29 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = Timestamp>> DataflowGraphInner<S>
         stats: &mut OutputConnectorStats,
         t: Option<Timestamp>,
         sink_id: Option<usize>,
+        data_sink: &mut Box<dyn Writer>,
         worker_persistent_storage: Option<&SharedWorkerPersistentStorage>,
-    ) {
+    ) -> Result<(), DynError> {
+        data_sink.flush(t.is_none()).map_err(DynError::from)?;
         if let Some(worker_persistent_storage) = worker_persistent_storage {
-            worker_persistent_storage
-                .lock()
-                .unwrap()
-                .update_sink_finalized_time(
-                    sink_id.expect("undefined sink_id while using persistent storage"),
-                    t,
-                );
+            let sink_id = sink_id.expect("undefined sink_id while using persistent storage");
+            // The storage stays locked until the time is saved, so that the sink
+            // finalizes its output up to exactly the saved time
+            let mut worker_persistent_storage = worker_persistent_storage.lock().unwrap();
+            let finalized_time =
+                worker_persistent_storage.finalized_time_after_sink_update(sink_id, t);
+            data_sink
+                .on_time_finalized(finalized_time)
+                .map_err(DynError::from)?;
+            worker_persistent_storage.update_sink_finalized_time(sink_id, t);
+        } else {
+            // Without persistence, nothing is produced again, so the time is always final
+            data_sink
+                .on_time_finalized(t.map_or(TotalFrontier::Done, TotalFrontier::At))
+                .map_err(DynError::from)?;
         }
         stats.on_time_committed(t.map(|t| t.0));
+        Ok(())
     }
 
     fn output_table(
@@ -3777,9 +3788,9 @@ impl<S: MaybeTotalScope<MaybeTotalTimestamp = Timestamp>> DataflowGraphInner<S>
                                     &mut stats,
                                     t,
                                     sink_id,
+                                    &mut data_sink,
                                     worker_persistent_storage.as_ref(),
-                                );
-                                data_sink.flush(t.is_none()).map_err(DynError::from)?;
+                                )?;
                                 if t.is_none() {
                                     break Ok(());
                                 }
@@ -1,6 +1,8 @@
 use log::{error, info};
+use std::collections::VecDeque;
 use std::io::{BufReader, Cursor, ErrorKind as IoErrorKind, Read, Seek, SeekFrom};
 use std::mem::take;
+use std::sync::mpsc::Sender;
 
 use bincode::{deserialize_from, serialize, ErrorKind as BincodeError};
 use serde::{Deserialize, Serialize};
@@ -222,6 +224,11 @@ pub struct InputSnapshotWriter {
     current_chunk_entries: usize,
     chunk_save_futures: Vec<BackendPutFuture>,
     next_chunk_id: ChunkId,
+
+    // Frontiers that have been written but not yet covered by the committed time.
+    // They are tracked only if there is a party interested in persisted frontiers.
+    persisted_frontier_sender: Option<Sender<OffsetAntichain>>,
+    uncommitted_frontiers: VecDeque<(Timestamp, OffsetAntichain)>,
 }
 
 impl InputSnapshotWriter {
@@ -234,15 +241,52 @@ impl InputSnapshotWriter {
             current_chunk_entries: 0,
             chunk_save_futures: Vec::new(),
             next_chunk_id: chunk_keys.iter().max().copied().unwrap_or_default() + 1,
+            persisted_frontier_sender: None,
+            uncommitted_frontiers: VecDeque::new(),
         })
     }
 
+    /// Subscribes to the frontiers which are guaranteed to be restored after a restart.
+    /// The frontier is sent each time the committed logical time passes it.
+    pub fn set_persisted_frontier_sender(&mut self, sender: Sender<OffsetAntichain>) {
+        self.persisted_frontier_sender = Some(sender);
+    }
+
+    /// This method is called after the time `timestamp` is committed to the metadata
+    /// storage. It reports the latest frontier, whose data is fully covered by the
+    /// committed time, if there is a subscriber for it.
+    pub fn on_timestamp_committed(&mut self, timestamp: TotalFrontier<Timestamp>) {
+        let Some(sender) = &self.persisted_frontier_sender else {
+            return;
+        };
+        let mut last_persisted_frontier = None;
+        while let Some((time, _)) = self.uncommitted_frontiers.front() {
+            if TotalFrontier::At(*time) > timestamp {
+                break;
+            }
+            last_persisted_frontier = self.uncommitted_frontiers.pop_front().map(|(_, f)| f);
+        }
+        if let Some(frontier) = last_persisted_frontier {
+            if sender.send(frontier).is_err() {
+                // The receiving side isn't interested in the persisted frontiers anymore.
+                self.persisted_frontier_sender = None;
+                self.uncommitted_frontiers.clear();
+            }
+        }
+    }
+
     /// A non-blocking call, pushing an entry in the buffer.
     /// The buffer should not be flushed in the same thread.
     pub fn write(&mut self, event: &Event) {
         if !self.mode.is_event_included(event) {
             return;
         }
+        if let (Some(_), Event::AdvanceTime(time, frontier)) =
+            (&self.persisted_frontier_sender, event)
+        {
+            self.uncommitted_frontiers
+                .push_back((*time, frontier.clone()));
+        }
 
         let mut entry_serialized = serialize(&event).expect("unable to serialize an entry");
         self.current_chunk.append(&mut entry_serialized);
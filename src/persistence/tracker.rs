@@ -161,26 +161,50 @@ impl WorkerPersistentStorage {
         self.sink_threshold_times.len() - 1
     }
 
-    pub fn update_sink_finalized_time(
-        &mut self,
+    /// Returns the finalized time of the worker, which is saved if the sink reports
+    /// `reported_timestamp`. The sink can make its output before this time durable
+    /// and only then report it with `update_sink_finalized_time`.
+    pub fn finalized_time_after_sink_update(
+        &self,
         sink_id: usize,
         reported_timestamp: Option<Timestamp>,
-    ) {
-        self.sink_threshold_times[sink_id] =
-            reported_timestamp.map_or(TotalFrontier::Done, TotalFrontier::At);
-        let worker_finalized_timestamp = *self
+    ) -> TotalFrontier<Timestamp> {
+        let reported_timestamp = reported_timestamp.map_or(TotalFrontier::Done, TotalFrontier::At);
+        let worker_finalized_timestamp = self
             .sink_threshold_times
             .iter()
+            .enumerate()
+            .map(|(id, threshold_time)| {
+                if id == sink_id {
+                    reported_timestamp
+                } else {
+                    *threshold_time
+                }
+            })
             .min()
             .expect("no known sinks");
 
-        let normalized_finalized_timestamp = match worker_finalized_timestamp {
+        match worker_finalized_timestamp {
             TotalFrontier::At(worker_finalized_timestamp) => TotalFrontier::At(
                 worker_finalized_timestamp
                     .most_recent_possible_snapshot_time(self.config.snapshot_interval),
             ),
             TotalFrontier::Done => TotalFrontier::Done,
-        };
+        }
+    }
+
+    /// Updates the time, up to which the sink has produced its output. If the finalized
+    /// time of the worker advances, it is saved, so that the output before it won't be
+    /// produced again after a restart.
+    pub fn update_sink_finalized_time(
+        &mut self,
+        sink_id: usize,
+        reported_timestamp: Option<Timestamp>,
+    ) {
+        let normalized_finalized_timestamp =
+            self.finalized_time_after_sink_update(sink_id, reported_timestamp);
+        self.sink_threshold_times[sink_id] =
+            reported_timestamp.map_or(TotalFrontier::Done, TotalFrontier::At);
         let timestamp_updated = normalized_finalized_timestamp != self.last_finalized_timestamp();
         if timestamp_updated {
             let mut commit_data = self.accept_finalized_timestamp(normalized_finalized_timestamp);
@@ -231,6 +255,14 @@ impl WorkerPersistentStorage {
 
         if let Err(e) = self.metadata_storage.save_current_state() {
             error!("Failed to save the current state, the data may duplicate in the re-run: {e}");
+            return;
+        }
+
+        for snapshot_writer in self.snapshot_writers.values() {
+            snapshot_writer
+                .lock()
+                .unwrap()
+                .on_timestamp_committed(commit_data.timestamp);
         }
     }
 
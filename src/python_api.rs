@@ -15,8 +15,17 @@ use crate::engine::{
 use crate::persistence::frontier::OffsetAntichain;
 
 use async_nats::connect as nats_connect;
+use async_nats::jetstream::consumer::pull::Config as NatsPullConsumerConfig;
+use async_nats::jetstream::consumer::{
+    AckPolicy as NatsAckPolicy, PullConsumer as NatsPullConsumer,
+};
 use async_nats::Client as NatsClient;
 use async_nats::Subscriber as NatsSubscriber;
+use aws_sdk_kinesis::config::{
+    BehaviorVersion as AwsBehaviorVersion, Credentials as KinesisCredentials,
+    Region as KinesisRegion,
+};
+use aws_sdk_kinesis::Client as KinesisClient;
 use csv::ReaderBuilder as CsvReaderBuilder;
 use elasticsearch::{
     auth::Credentials as ESCredentials,
@@ -26,13 +35,29 @@ use elasticsearch::{
     },
     Elasticsearch,
 };
+use google_cloud_pubsub::client::google_cloud_auth::credentials::CredentialsFile as PubSubCredentialsFile;
+use google_cloud_pubsub::client::{Client as PubSubClient, ClientConfig as PubSubClientConfig};
+use google_cloud_pubsub::subscriber::SubscriberConfig as PubSubSubscriberConfig;
+use google_cloud_pubsub::subscription::SubscribeConfig as PubSubSubscribeConfig;
 use itertools::Itertools;
+use lapin::options::{BasicConsumeOptions, BasicQosOptions, ConfirmSelectOptions};
+use lapin::types::FieldTable as AmqpFieldTable;
+use lapin::{
+    Channel as AmqpChannel, Connection as AmqpConnection,
+    ConnectionProperties as AmqpConnectionProperties,
+};
 use log::{info, warn};
 use mongodb::sync::Client as MongoClient;
+use mysql::Opts as MysqlOpts;
 use ndarray;
 use numpy::{PyArray, PyReadonlyArrayDyn};
 use once_cell::sync::Lazy;
 use postgres::{Client, NoTls};
+use pulsar::consumer::InitialPosition as PulsarInitialPosition;
+use pulsar::{
+    ConsumerOptions as PulsarConsumerOptions, Pulsar, SubType as PulsarSubscriptionType,
+    TokioExecutor,
+};
 use pyo3::exceptions::{
     PyBaseException, PyException, PyIOError, PyIndexError, PyKeyError, PyRuntimeError, PyTypeError,
     PyValueError, PyZeroDivisionError,
@@ -44,8 +69,14 @@ use pyo3::types::{PyBool, PyBytes, PyDict, PyFloat, PyInt, PyString, PyTuple, Py
 use pyo3::{intern, AsPyPointer, PyTypeInfo};
 use pyo3_log::ResetHandle;
 use rdkafka::consumer::{BaseConsumer, Consumer};
-use rdkafka::producer::{DefaultProducerContext, ThreadedProducer};
+use rdkafka::metadata::MetadataTopic;
+use rdkafka::producer::{DefaultProducerContext, Producer, ThreadedProducer};
 use rdkafka::{ClientConfig, Offset as KafkaOffset, TopicPartitionList};
+use redis::{Client as RedisClient, Connection as RedisConnection};
+use reqwest::blocking::Client as HttpClient;
+use reqwest::header::{
+    HeaderMap as HttpHeaderMap, HeaderName as HttpHeaderName, HeaderValue as HttpHeaderValue,
+};
 use rusqlite::Connection as SqliteConnection;
 use rusqlite::OpenFlags as SqliteOpenFlags;
 use s3::bucket::Bucket as S3Bucket;
@@ -59,34 +90,61 @@ use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufWriter, Read};
 use std::mem::take;
+use std::net::ToSocketAddrs;
 use std::os::unix::prelude::*;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time;
+use tokio::runtime::Runtime as TokioRuntime;
 
 use self::external_index_wrappers::{
     PyBruteForceKnnMetricKind, PyExternalIndexData, PyExternalIndexQuery, PyUSearchMetricKind,
 };
 use self::threads::PythonThreadState;
 
+use crate::connectors::compression::{Codec, Decompression};
 use crate::connectors::data_format::{
     BsonFormatter, DebeziumDBType, DebeziumMessageParser, DsvSettings, Formatter,
     IdentityFormatter, IdentityParser, InnerSchemaField, JsonLinesFormatter, JsonLinesParser,
-    KeyGenerationPolicy, NullFormatter, Parser, PsqlSnapshotFormatter, PsqlUpdatesFormatter,
-    SingleColumnFormatter, TransparentParser,
+    KeyGenerationPolicy, MessageMetadataField, NullFormatter, Parser, PsqlSnapshotFormatter,
+    PsqlUpdatesFormatter, SingleColumnFormatter, SyslogParser, TransparentParser,
 };
 use crate::connectors::data_lake::iceberg::{
     IcebergBatchWriter, IcebergDBParams, IcebergTableParams,
 };
 use crate::connectors::data_lake::DeltaBatchWriter;
 use crate::connectors::data_storage::{
-    new_csv_filesystem_reader, new_filesystem_reader, new_s3_csv_reader, new_s3_generic_reader,
-    ConnectorMode, DeltaTableReader, ElasticSearchWriter, FileWriter, IcebergReader, KafkaReader,
-    KafkaWriter, LakeWriter, MongoWriter, NatsReader, NatsWriter, NullWriter, ObjectDownloader,
-    PsqlWriter, PythonConnectorEventType, PythonReaderBuilder, ReadError, ReadMethod,
-    ReaderBuilder, SqliteReader, Writer,
+    new_csv_filesystem_reader, new_filesystem_reader, new_parquet_filesystem_reader,
+    new_s3_csv_reader, new_s3_generic_reader, new_s3_parquet_reader, AmqpReader, AmqpWriter,
+    ConnectorMode, DeltaTableReader, ElasticSearchWriter, FileWriter, GrpcReader, IcebergReader,
+    KafkaBoundedRead, KafkaPartitioner, KafkaReader, KafkaTopicSubscription, KafkaWriter,
+    KinesisReader, KinesisWriter, LakeWriter, MongoReader, MongoWriter, MqttReader, MqttWriter,
+    MysqlBinlogReader, NatsJetStreamReader, NatsReader, NatsWriter, NullWriter, ObjectDownloader,
+    PsqlReplicationReader, PsqlWriter, PubSubReader, PubSubWriter, PulsarReader, PulsarWriter,
+    PythonConnectorEventType, PythonReaderBuilder, ReadError, ReadMethod, ReaderBuilder,
+    RedisSnapshotWriter, RedisStreamsReader, RedisValueType, SocketReader, SqliteReader,
+    WebhookReader, WebhookRetryPolicy, WebhookWriter, Writer, AMQP_PREFETCH_COUNT,
+    KAFKA_TRANSACTION_TIMEOUT, MQTT_FLUSH_TIMEOUT, NATS_JETSTREAM_DEFAULT_MAX_ACK_PENDING,
+    PUBSUB_ACK_DEADLINE_SECONDS, PUBSUB_MAX_OUTSTANDING_MESSAGES,
+};
+use crate::connectors::grpc::{
+    IngestServer as GrpcIngestServer, IngestServerHandle as GrpcIngestServerHandle,
+    GRPC_READER_QUEUE_SIZE,
 };
+use crate::connectors::mqtt::{
+    connect as mqtt_connect, ProtocolVersion as MqttProtocolVersion, QualityOfService as MqttQoS,
+};
+use crate::connectors::posix_like::PosixLikeReader;
 use crate::connectors::scanner::S3Scanner;
+use crate::connectors::socket::{
+    Framing as SocketFraming, ListenerHandle as SocketListenerHandle, Protocol as SocketProtocol,
+    SOCKET_DEFAULT_MAX_MESSAGE_SIZE, SOCKET_READER_QUEUE_SIZE,
+};
+use crate::connectors::webhook::{
+    BatchFormat as WebhookBatchFormat, IngestServer as WebhookIngestServer,
+    IngestServerHandle as WebhookIngestServerHandle, UrlTemplate as WebhookUrlTemplate,
+    WEBHOOK_DEFAULT_MAX_REQUEST_SIZE, WEBHOOK_READER_QUEUE_SIZE,
+};
 use crate::connectors::{PersistenceMode, SessionType, SnapshotAccess};
 use crate::engine::dataflow::Config;
 use crate::engine::error::{DataError, DynError, DynResult, Trace as EngineTrace};
@@ -1733,6 +1791,10 @@ impl PyDebeziumDBType {
     pub const POSTGRES: DebeziumDBType = DebeziumDBType::Postgres;
     #[classattr]
     pub const MONGO_DB: DebeziumDBType = DebeziumDBType::MongoDB;
+    #[classattr]
+    pub const MY_SQL: DebeziumDBType = DebeziumDBType::MySQL;
+    #[classattr]
+    pub const SQL_SERVER: DebeziumDBType = DebeziumDBType::SqlServer;
 }
 
 #[pyclass(module = "pathway.engine", frozen, name = "KeyGenerationPolicy")]
@@ -2384,6 +2446,7 @@ impl Scope {
             &data_format.borrow(),
             connector_index,
             self_.borrow().worker_index(),
+            self_.borrow().worker_count(),
             self_.borrow().license.as_ref(),
         )?;
 
@@ -3096,9 +3159,11 @@ impl Scope {
     ) -> PyResult<()> {
         let py = self_.py();
 
-        let sink_impl = data_sink
-            .borrow()
-            .construct_writer(py, &data_format.borrow())?;
+        let worker_index = self_.borrow().worker_index();
+        let sink_impl =
+            data_sink
+                .borrow()
+                .construct_writer(py, &data_format.borrow(), worker_index)?;
         let format_impl = data_format.borrow().construct_formatter(py)?;
 
         self_
@@ -3681,8 +3746,122 @@ impl ElasticSearchParams {
     }
 }
 
+#[pyclass(module = "pathway.engine", frozen)]
+pub struct WebhookSettings {
+    url: String,
+    batch_format: String,
+    headers: HashMap<String, String>,
+    signing_secret: Option<String>,
+    signature_header: String,
+    max_retries: usize,
+    first_retry_delay_ms: u64,
+    retry_backoff_factor: f64,
+    retry_jitter_ms: u64,
+    request_timeout_ms: Option<u64>,
+}
+
+#[pymethods]
+impl WebhookSettings {
+    #[new]
+    #[pyo3(signature = (
+        url,
+        batch_format,
+        headers,
+        signing_secret,
+        signature_header,
+        max_retries,
+        first_retry_delay_ms,
+        retry_backoff_factor,
+        retry_jitter_ms,
+        request_timeout_ms,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        url: String,
+        batch_format: String,
+        headers: HashMap<String, String>,
+        signing_secret: Option<String>,
+        signature_header: String,
+        max_retries: usize,
+        first_retry_delay_ms: u64,
+        retry_backoff_factor: f64,
+        retry_jitter_ms: u64,
+        request_timeout_ms: Option<u64>,
+    ) -> Self {
+        WebhookSettings {
+            url,
+            batch_format,
+            headers,
+            signing_secret,
+            signature_header,
+            max_retries,
+            first_retry_delay_ms,
+            retry_backoff_factor,
+            retry_jitter_ms,
+            request_timeout_ms,
+        }
+    }
+}
+
+impl WebhookSettings {
+    fn header_name(name: &str) -> PyResult<HttpHeaderName> {
+        HttpHeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| PyValueError::new_err(format!("Invalid header name {name:?}: {e}")))
+    }
+
+    fn headers(&self) -> PyResult<HttpHeaderMap> {
+        let mut headers = HttpHeaderMap::with_capacity(self.headers.len());
+        for (name, value) in &self.headers {
+            let value = HttpHeaderValue::from_str(value).map_err(|e| {
+                PyValueError::new_err(format!("Invalid value of header {name:?}: {e}"))
+            })?;
+            headers.insert(Self::header_name(name)?, value);
+        }
+        Ok(headers)
+    }
+
+    fn signature(&self) -> PyResult<Option<(HttpHeaderName, Vec<u8>)>> {
+        let Some(secret) = &self.signing_secret else {
+            return Ok(None);
+        };
+        let header_name = Self::header_name(&self.signature_header)?;
+        Ok(Some((header_name, secret.as_bytes().to_vec())))
+    }
+
+    fn batch_format(&self) -> PyResult<WebhookBatchFormat> {
+        match self.batch_format.as_str() {
+            "jsonlines" => Ok(WebhookBatchFormat::JsonLines),
+            "json" => Ok(WebhookBatchFormat::JsonArray),
+            other => Err(PyValueError::new_err(format!(
+                "Unknown webhook batch format {other:?}"
+            ))),
+        }
+    }
+
+    fn client(&self) -> PyResult<HttpClient> {
+        let mut builder = HttpClient::builder();
+        if let Some(request_timeout_ms) = self.request_timeout_ms {
+            builder = builder.timeout(time::Duration::from_millis(request_timeout_ms));
+        }
+        builder
+            .build()
+            .map_err(|e| PyIOError::new_err(format!("Failed to create HTTP client: {e}")))
+    }
+
+    fn retry_policy(&self) -> WebhookRetryPolicy {
+        WebhookRetryPolicy {
+            max_retries: self.max_retries,
+            initial_delay: time::Duration::from_millis(self.first_retry_delay_ms),
+            backoff_factor: self.retry_backoff_factor,
+            // The jitter is sampled from a non-empty range
+            jitter: time::Duration::from_millis(self.retry_jitter_ms.max(1)),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 #[pyclass(module = "pathway.engine", frozen, get_all)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct DataStorage {
     storage_type: String,
     path: Option<String>,
@@ -3703,12 +3882,48 @@ pub struct DataStorage {
     mock_events: Option<HashMap<(ExternalPersistentId, usize), Vec<SnapshotEvent>>>,
     table_name: Option<String>,
     header_fields: Vec<(String, usize)>,
-    key_field_index: Option<usize>,
+    key_fields: Vec<(String, usize)>,
     min_commit_frequency: Option<u64>,
     downloader_threads_count: Option<usize>,
     database: Option<String>,
     start_from_timestamp_ms: Option<i64>,
     namespace: Option<Vec<String>>,
+    slot_name: Option<String>,
+    publication_name: Option<String>,
+    server_id: Option<u32>,
+    partition_field_index: Option<usize>,
+    partition_by_key_hash: bool,
+    produce_tombstones: bool,
+    transactional_id: Option<String>,
+    topic_names: Option<Vec<String>>,
+    topic_pattern: Option<String>,
+    end_at_timestamp_ms: Option<i64>,
+    jetstream_stream: Option<String>,
+    durable_name: Option<String>,
+    ack_wait_ms: Option<u64>,
+    max_ack_pending: Option<usize>,
+    mqtt_protocol_version: Option<String>,
+    qos: Option<u8>,
+    retain: bool,
+    topic_field_index: Option<usize>,
+    consumer_group: Option<String>,
+    consumer_name: Option<String>,
+    key_prefix: Option<String>,
+    redis_value_type: Option<String>,
+    exchange: Option<String>,
+    subscription: Option<String>,
+    project_id: Option<String>,
+    credentials_file: Option<String>,
+    routes: Option<Vec<String>>,
+    max_request_size: Option<usize>,
+    wait_for_processing: bool,
+    webhook_settings: Option<Py<WebhookSettings>>,
+    protocol: Option<String>,
+    framing: Option<String>,
+    max_message_size: Option<usize>,
+    expand_archives: bool,
+    use_inotify: bool,
+    decompression: Option<String>,
 }
 
 #[pyclass(module = "pathway.engine", frozen, name = "PersistenceMode")]
@@ -3993,6 +4208,10 @@ pub struct DataFormat {
     session_type: SessionType,
     value_field_index: Option<usize>,
     key_generation_policy: KeyGenerationPolicy,
+    header_fields: Option<HashMap<String, String>>,
+    timestamp_field: Option<String>,
+    attribute_fields: Option<HashMap<String, String>>,
+    publish_time_field: Option<String>,
 }
 
 #[pymethods]
@@ -4018,14 +4237,51 @@ impl DataStorage {
         mock_events = None,
         table_name = None,
         header_fields = Vec::new(),
-        key_field_index = None,
+        key_fields = Vec::new(),
         min_commit_frequency = None,
         downloader_threads_count = None,
         database = None,
         start_from_timestamp_ms = None,
         namespace = None,
+        slot_name = None,
+        publication_name = None,
+        server_id = None,
+        partition_field_index = None,
+        partition_by_key_hash = false,
+        produce_tombstones = false,
+        transactional_id = None,
+        topic_names = None,
+        topic_pattern = None,
+        end_at_timestamp_ms = None,
+        jetstream_stream = None,
+        durable_name = None,
+        ack_wait_ms = None,
+        max_ack_pending = None,
+        mqtt_protocol_version = None,
+        qos = None,
+        retain = false,
+        topic_field_index = None,
+        consumer_group = None,
+        consumer_name = None,
+        key_prefix = None,
+        redis_value_type = None,
+        exchange = None,
+        subscription = None,
+        project_id = None,
+        credentials_file = None,
+        routes = None,
+        max_request_size = None,
+        wait_for_processing = false,
+        webhook_settings = None,
+        protocol = None,
+        framing = None,
+        max_message_size = None,
+        expand_archives = false,
+        use_inotify = false,
+        decompression = None,
     ))]
     #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::fn_params_excessive_bools)]
     fn new(
         storage_type: String,
         path: Option<String>,
@@ -4046,12 +4302,48 @@ impl DataStorage {
         mock_events: Option<HashMap<(ExternalPersistentId, usize), Vec<SnapshotEvent>>>,
         table_name: Option<String>,
         header_fields: Vec<(String, usize)>,
-        key_field_index: Option<usize>,
+        key_fields: Vec<(String, usize)>,
         min_commit_frequency: Option<u64>,
         downloader_threads_count: Option<usize>,
         database: Option<String>,
         start_from_timestamp_ms: Option<i64>,
         namespace: Option<Vec<String>>,
+        slot_name: Option<String>,
+        publication_name: Option<String>,
+        server_id: Option<u32>,
+        partition_field_index: Option<usize>,
+        partition_by_key_hash: bool,
+        produce_tombstones: bool,
+        transactional_id: Option<String>,
+        topic_names: Option<Vec<String>>,
+        topic_pattern: Option<String>,
+        end_at_timestamp_ms: Option<i64>,
+        jetstream_stream: Option<String>,
+        durable_name: Option<String>,
+        ack_wait_ms: Option<u64>,
+        max_ack_pending: Option<usize>,
+        mqtt_protocol_version: Option<String>,
+        qos: Option<u8>,
+        retain: bool,
+        topic_field_index: Option<usize>,
+        consumer_group: Option<String>,
+        consumer_name: Option<String>,
+        key_prefix: Option<String>,
+        redis_value_type: Option<String>,
+        exchange: Option<String>,
+        subscription: Option<String>,
+        project_id: Option<String>,
+        credentials_file: Option<String>,
+        routes: Option<Vec<String>>,
+        max_request_size: Option<usize>,
+        wait_for_processing: bool,
+        webhook_settings: Option<Py<WebhookSettings>>,
+        protocol: Option<String>,
+        framing: Option<String>,
+        max_message_size: Option<usize>,
+        expand_archives: bool,
+        use_inotify: bool,
+        decompression: Option<String>,
     ) -> Self {
         DataStorage {
             storage_type,
@@ -4073,12 +4365,48 @@ impl DataStorage {
             mock_events,
             table_name,
             header_fields,
-            key_field_index,
+            key_fields,
             min_commit_frequency,
             downloader_threads_count,
             database,
             start_from_timestamp_ms,
             namespace,
+            slot_name,
+            publication_name,
+            server_id,
+            partition_field_index,
+            partition_by_key_hash,
+            produce_tombstones,
+            transactional_id,
+            topic_names,
+            topic_pattern,
+            end_at_timestamp_ms,
+            jetstream_stream,
+            durable_name,
+            ack_wait_ms,
+            max_ack_pending,
+            mqtt_protocol_version,
+            qos,
+            retain,
+            topic_field_index,
+            consumer_group,
+            consumer_name,
+            key_prefix,
+            redis_value_type,
+            exchange,
+            subscription,
+            project_id,
+            credentials_file,
+            routes,
+            max_request_size,
+            wait_for_processing,
+            webhook_settings,
+            protocol,
+            framing,
+            max_message_size,
+            expand_archives,
+            use_inotify,
+            decompression,
         }
     }
 }
@@ -4100,6 +4428,10 @@ impl DataFormat {
         session_type = SessionType::Native,
         value_field_index = None,
         key_generation_policy = KeyGenerationPolicy::PreferMessageKey,
+        header_fields = None,
+        timestamp_field = None,
+        attribute_fields = None,
+        publish_time_field = None,
     ))]
     #[allow(clippy::too_many_arguments)]
     fn new(
@@ -4115,6 +4447,10 @@ impl DataFormat {
         session_type: SessionType,
         value_field_index: Option<usize>,
         key_generation_policy: KeyGenerationPolicy,
+        header_fields: Option<HashMap<String, String>>,
+        timestamp_field: Option<String>,
+        attribute_fields: Option<HashMap<String, String>>,
+        publish_time_field: Option<String>,
     ) -> Self {
         DataFormat {
             format_type,
@@ -4129,6 +4465,10 @@ impl DataFormat {
             session_type,
             value_field_index,
             key_generation_policy,
+            header_fields,
+            timestamp_field,
+            attribute_fields,
+            publish_time_field,
         }
     }
 }
@@ -4240,6 +4580,26 @@ impl DataStorage {
         )
     }
 
+    fn slot_name(&self) -> PyResult<&str> {
+        Self::extract_string_field(
+            &self.slot_name,
+            "For Postgres replication, the 'slot_name' field must be specified",
+        )
+    }
+
+    fn publication_name(&self) -> PyResult<&str> {
+        Self::extract_string_field(
+            &self.publication_name,
+            "For Postgres replication, the 'publication_name' field must be specified",
+        )
+    }
+
+    fn server_id(&self) -> PyResult<u32> {
+        self.server_id.ok_or_else(|| {
+            PyValueError::new_err("For MySQL, the 'server_id' field must be specified")
+        })
+    }
+
     fn s3_bucket(&self, py: pyo3::Python) -> PyResult<S3Bucket> {
         let (bucket_name, _) = S3Scanner::deduce_bucket_and_path(self.path()?);
         let bucket = self
@@ -4379,6 +4739,37 @@ impl DataStorage {
             .map(IntoPersistentId::into_persistent_id)
     }
 
+    fn decompression(&self) -> PyResult<Decompression> {
+        match self.decompression.as_deref() {
+            None | Some("none") => Ok(Decompression::None),
+            Some("auto") => Ok(Decompression::Auto),
+            Some(name) => Codec::from_name(name)
+                .map(Decompression::Codec)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown decompression {name:?}"))),
+        }
+    }
+
+    fn posix_like_reader_builder(
+        &self,
+        reader: PosixLikeReader,
+    ) -> PyResult<Box<dyn ReaderBuilder>> {
+        let reader = reader.with_decompression(self.decompression()?);
+        let reader = if self.use_inotify {
+            reader.with_inotify().map_err(|e| {
+                PyIOError::new_err(format!("Failed to set up the inotify watcher: {e}"))
+            })?
+        } else {
+            reader
+        };
+        if !self.expand_archives {
+            return Ok(Box::new(reader));
+        }
+        let reader = reader.with_expanded_archives().map_err(|e| {
+            PyIOError::new_err(format!("Failed to set up the expansion of archives: {e}"))
+        })?;
+        Ok(Box::new(reader))
+    }
+
     fn construct_fs_reader(&self) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
         let storage = new_filesystem_reader(
             self.path()?,
@@ -4388,7 +4779,7 @@ impl DataStorage {
             &self.object_pattern,
         )
         .map_err(|e| PyIOError::new_err(format!("Failed to initialize Filesystem reader: {e}")))?;
-        Ok((Box::new(storage), 1))
+        Ok((self.posix_like_reader_builder(storage)?, 1))
     }
 
     fn construct_s3_reader(&self, py: pyo3::Python) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
@@ -4402,7 +4793,7 @@ impl DataStorage {
             self.downloader_threads_count()?,
         )
         .map_err(|e| PyRuntimeError::new_err(format!("Creating S3 reader failed: {e}")))?;
-        Ok((Box::new(storage), 1))
+        Ok((self.posix_like_reader_builder(storage)?, 1))
     }
 
     fn construct_s3_csv_reader(
@@ -4419,7 +4810,7 @@ impl DataStorage {
             self.downloader_threads_count()?,
         )
         .map_err(|e| PyRuntimeError::new_err(format!("Creating S3 reader failed: {e}")))?;
-        Ok((Box::new(storage), 1))
+        Ok((self.posix_like_reader_builder(storage)?, 1))
     }
 
     fn construct_csv_reader(&self, py: pyo3::Python) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
@@ -4433,18 +4824,161 @@ impl DataStorage {
         .map_err(|e| {
             PyIOError::new_err(format!("Failed to initialize CsvFilesystem reader: {e}"))
         })?;
-        Ok((Box::new(reader), 1))
+        Ok((self.posix_like_reader_builder(reader)?, 1))
+    }
+
+    fn construct_parquet_reader(
+        &self,
+        py: pyo3::Python,
+        data_format: &DataFormat,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let reader = new_parquet_filesystem_reader(
+            self.path()?,
+            data_format.value_fields_type_map(py),
+            self.mode,
+            self.internal_persistent_id(),
+            &self.object_pattern,
+        )
+        .map_err(|e| {
+            PyIOError::new_err(format!(
+                "Failed to initialize Parquet filesystem reader: {e}"
+            ))
+        })?;
+        Ok((self.posix_like_reader_builder(reader)?, 1))
+    }
+
+    fn construct_s3_parquet_reader(
+        &self,
+        py: pyo3::Python,
+        data_format: &DataFormat,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let (_, deduced_path) = S3Scanner::deduce_bucket_and_path(self.path()?);
+        let storage = new_s3_parquet_reader(
+            self.s3_bucket(py)?,
+            deduced_path,
+            data_format.value_fields_type_map(py),
+            self.mode,
+            self.internal_persistent_id(),
+            self.downloader_threads_count()?,
+        )
+        .map_err(|e| PyRuntimeError::new_err(format!("Creating S3 reader failed: {e}")))?;
+        Ok((self.posix_like_reader_builder(storage)?, 1))
+    }
+
+    /// Returns the topics to be read by a Kafka reader.
+    fn kafka_reader_subscription(&self) -> PyResult<KafkaTopicSubscription> {
+        let topic_names: Vec<String> = self
+            .topic
+            .iter()
+            .chain(self.topic_names.iter().flatten())
+            .cloned()
+            .collect();
+        let subscription = KafkaTopicSubscription::new(topic_names, self.topic_pattern.as_deref())
+            .map_err(|e| {
+                PyValueError::new_err(format!(
+                    "Invalid topic pattern {:?}: {e}",
+                    self.topic_pattern.as_deref().unwrap_or_default()
+                ))
+            })?;
+        if subscription.is_empty() {
+            return Err(PyValueError::new_err(
+                "For Kafka input, either topic or topic pattern must be specified",
+            ));
+        }
+        Ok(subscription)
     }
 
-    fn total_partitions_for_topic(consumer: &BaseConsumer, topic: &str) -> PyResult<usize> {
+    /// Returns the names and the numbers of partitions of the existing topics
+    /// that match the subscription.
+    fn kafka_subscribed_topics(
+        consumer: &BaseConsumer,
+        subscription: &KafkaTopicSubscription,
+    ) -> PyResult<Vec<(String, usize)>> {
+        let requested_topic = match (subscription.topic_names(), subscription.has_pattern()) {
+            ([topic], false) => Some(topic.as_str()),
+            _ => None,
+        };
         let metadata = consumer
-            .fetch_metadata(Some(topic), std::time::Duration::from_secs(30))
+            .fetch_metadata(requested_topic, std::time::Duration::from_secs(30))
             .map_err(|e| PyIOError::new_err(format!("Failed to fetch topic metadata: {e}")))?;
-        if let Some(topic) = metadata.topics().iter().find(|t| t.name() == topic) {
-            Ok(topic.partitions().len())
-        } else {
-            Err(PyIOError::new_err(format!("Topic '{topic}' not found")))
+
+        let mut subscribed_topics = Vec::new();
+        for topic_name in subscription.resolve(metadata.topics().iter().map(MetadataTopic::name)) {
+            if let Some(topic) = metadata.topics().iter().find(|t| t.name() == topic_name) {
+                subscribed_topics.push((topic_name, topic.partitions().len()));
+            } else {
+                return Err(PyIOError::new_err(format!(
+                    "Topic '{topic_name}' not found"
+                )));
+            }
         }
+        Ok(subscribed_topics)
+    }
+
+    /// Returns the low and the high watermarks of all partitions of the given topics.
+    fn kafka_watermarks(
+        consumer: &BaseConsumer,
+        topics: &[(String, usize)],
+    ) -> PyResult<HashMap<(String, i32), (i64, i64)>> {
+        let mut watermarks = HashMap::new();
+        for (topic, total_partitions) in topics {
+            for partition_idx in 0..*total_partitions {
+                let partition_idx: i32 = partition_idx.try_into().unwrap();
+                let partition_watermarks = consumer
+                    .fetch_watermarks(topic, partition_idx, std::time::Duration::from_secs(30))
+                    .map_err(|e| {
+                        PyIOError::new_err(format!(
+                            "Failed to fetch watermarks for ({topic}, {partition_idx}): {e}"
+                        ))
+                    })?;
+                watermarks.insert((topic.clone(), partition_idx), partition_watermarks);
+            }
+        }
+        Ok(watermarks)
+    }
+
+    /// Returns the offset of the first message with the timestamp not less than the
+    /// given one for each partition. If there are no such messages in a partition,
+    /// the high watermark is used, which is the offset of the next message.
+    fn kafka_offsets_for_timestamp(
+        consumer: &BaseConsumer,
+        watermarks: &HashMap<(String, i32), (i64, i64)>,
+        timestamp_ms: i64,
+    ) -> PyResult<HashMap<(String, i32), i64>> {
+        let mut tpl = TopicPartitionList::new();
+        for (topic, partition) in watermarks.keys() {
+            tpl.add_partition_offset(topic, *partition, KafkaOffset::Offset(timestamp_ms))
+                .expect("Failed to add partition offset");
+        }
+
+        let offsets = consumer
+            .offsets_for_times(tpl, std::time::Duration::from_secs(30))
+            .map_err(|e| {
+                PyIOError::new_err(format!("Failed to fetch offsets for the timestamp: {e}"))
+            })?;
+
+        let mut result = HashMap::new();
+        for element in offsets.elements() {
+            let topic_partition = (element.topic().to_string(), element.partition());
+            let offset = match element.offset() {
+                KafkaOffset::Offset(offset) => offset,
+                KafkaOffset::End => {
+                    info!(
+                        "Partition {} of topic {} doesn't have messages with timestamp greater than {timestamp_ms}.",
+                        element.partition(),
+                        element.topic(),
+                    );
+                    watermarks[&topic_partition].1
+                }
+                _ => {
+                    return Err(PyRuntimeError::new_err(format!(
+                        "rdkafka returned invalid offset, details: {offsets:?}"
+                    )))
+                }
+            };
+            result.insert(topic_partition, offset);
+        }
+        Ok(result)
     }
 
     fn construct_kafka_reader(&self) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
@@ -4454,86 +4988,88 @@ impl DataStorage {
             .create()
             .map_err(|e| PyValueError::new_err(format!("Creating Kafka consumer failed: {e}")))?;
 
-        let topic = self.kafka_or_nats_topic()?;
-        consumer
-            .subscribe(&[topic])
-            .map_err(|e| PyIOError::new_err(format!("Subscription to Kafka topic failed: {e}")))?;
+        let subscription = self.kafka_reader_subscription()?;
+        let is_bounded = !self.mode.is_polling_enabled();
+        let watermarks = if is_bounded || self.start_from_timestamp_ms.is_some() {
+            // Note that the topics matching the pattern, which are created later, are read
+            // from the position defined by the `auto.offset.reset` setting
+            let subscribed_topics = Self::kafka_subscribed_topics(&consumer, &subscription)?;
+            Self::kafka_watermarks(&consumer, &subscribed_topics)?
+        } else {
+            HashMap::new()
+        };
 
-        let mut seek_positions = HashMap::new();
+        let mut start_offsets = HashMap::new();
         if let Some(start_from_timestamp_ms) = self.start_from_timestamp_ms {
             let current_timestamp = current_unix_timestamp_ms();
             if start_from_timestamp_ms > current_timestamp.try_into().unwrap() {
                 warn!("The timestamp {start_from_timestamp_ms} is greater than the current timestamp {current_timestamp}. All new entries will be read.");
             }
+            start_offsets =
+                Self::kafka_offsets_for_timestamp(&consumer, &watermarks, start_from_timestamp_ms)?;
+        }
 
-            let mut tpl = TopicPartitionList::new();
-            let total_partitions = Self::total_partitions_for_topic(&consumer, topic)?;
-            for partition_idx in 0..total_partitions {
-                tpl.add_partition_offset(
-                    topic,
-                    partition_idx.try_into().unwrap(),
-                    KafkaOffset::Offset(start_from_timestamp_ms),
-                )
-                .expect("Failed to add partition offset");
-            }
+        let mut seek_positions = HashMap::new();
+        let mut stop_offsets = None;
+        if is_bounded {
+            // The partitions are assigned to the single reader explicitly, so that it
+            // knows when every one of them is read up to the end
+            let end_offsets = if let Some(end_at_timestamp_ms) = self.end_at_timestamp_ms {
+                Self::kafka_offsets_for_timestamp(&consumer, &watermarks, end_at_timestamp_ms)?
+            } else {
+                watermarks
+                    .iter()
+                    .map(|(topic_partition, (_, high))| (topic_partition.clone(), *high))
+                    .collect()
+            };
 
-            // First we find the next offset within partition to gracefully handle the case
-            // when the later call of `offsets_for_times` returns `KafkaOffset::End` for some
-            // of the partitions
-            let mut next_used_offset_per_partition = Vec::with_capacity(total_partitions);
-            for partition_idx in 0..total_partitions {
-                let (_, next_offset) = consumer
-                    .fetch_watermarks(
-                        topic,
-                        partition_idx.try_into().unwrap(),
-                        std::time::Duration::from_secs(30),
-                    )
-                    .map_err(|e| {
-                        PyIOError::new_err(format!(
-                            "Failed to fetch watermarks for ({topic}, {partition_idx}): {e}"
-                        ))
-                    })?;
-                next_used_offset_per_partition.push(KafkaOffset::Offset(next_offset));
+            let bounded_read = KafkaBoundedRead::new(&watermarks, &start_offsets, end_offsets);
+            let mut tpl = TopicPartitionList::new();
+            for (topic, partition, start_offset) in &bounded_read.assignment {
+                let offset = start_offset.map_or(KafkaOffset::Stored, KafkaOffset::Offset);
+                tpl.add_partition_offset(topic, *partition, offset)
+                    .expect("Failed to add partition offset");
             }
-
-            let offsets = consumer
-                .offsets_for_times(tpl, std::time::Duration::from_secs(30))
-                .map_err(|e| {
-                    PyIOError::new_err(format!("Failed to fetch offsets for the timestamp: {e}"))
+            consumer.assign(&tpl).map_err(|e| {
+                PyIOError::new_err(format!("Assignment of Kafka partitions failed: {e}"))
+            })?;
+            stop_offsets = Some(bounded_read.stop_offsets);
+        } else {
+            // With a topic pattern, the reader subscribes to the matching topics itself
+            if !subscription.has_pattern() {
+                let topic_names: Vec<&str> = subscription
+                    .topic_names()
+                    .iter()
+                    .map(String::as_str)
+                    .collect();
+                consumer.subscribe(&topic_names).map_err(|e| {
+                    PyIOError::new_err(format!("Subscription to Kafka topic failed: {e}"))
                 })?;
+            }
 
             // We could have done a simple `consumer.assign` here, but it would damage the automatic consumer rebalance
             // So we act differently: we pass the seek positions to consumer, and it seeks lazily
-            for element in offsets.elements() {
-                assert_eq!(element.topic(), topic);
-                let offset = match element.offset() {
-                    KafkaOffset::Invalid => {
-                        return Err(PyRuntimeError::new_err(format!(
-                            "rdkafka returned invalid offset, details: {offsets:?}"
-                        )))
-                    }
-                    KafkaOffset::End => {
-                        let partition_idx: usize = element.partition().try_into().unwrap();
-                        info!("Partition {partition_idx} doesn't have messages with timestamp greater than {start_from_timestamp_ms}. All new messages will be read.");
-                        next_used_offset_per_partition[partition_idx]
-                    }
-                    offset => offset,
-                };
-                info!(
-                    "Adding a lazy seek position for ({topic}, {}) to ({:?})",
-                    element.partition(),
-                    offset
-                );
-                seek_positions.insert(element.partition(), offset);
+            for ((topic, partition), offset) in start_offsets {
+                info!("Adding a lazy seek position for ({topic}, {partition}) to ({offset})");
+                seek_positions.insert((topic.into(), partition), KafkaOffset::Offset(offset));
             }
         }
+
+        let pattern_subscription =
+            (!is_bounded && subscription.has_pattern()).then_some(subscription);
         let reader = KafkaReader::new(
             consumer,
-            topic.to_string(),
             self.internal_persistent_id(),
             seek_positions,
+            stop_offsets,
+            pattern_subscription,
         );
-        Ok((Box::new(reader), self.parallel_readers.unwrap_or(256)))
+        let parallel_readers = if is_bounded {
+            1
+        } else {
+            self.parallel_readers.unwrap_or(256)
+        };
+        Ok((Box::new(reader), parallel_readers))
     }
 
     fn construct_python_reader(
@@ -4581,6 +5117,76 @@ impl DataStorage {
         Ok((Box::new(reader), 1))
     }
 
+    fn construct_postgres_replication_reader(
+        &self,
+        py: pyo3::Python,
+        data_format: &DataFormat,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let client = Client::connect(self.connection_string()?, NoTls).map_err(|e| {
+            PyIOError::new_err(format!("Failed to establish PostgreSQL connection: {e:?}"))
+        })?;
+        let table_name = self.table_name.clone().ok_or_else(|| {
+            PyValueError::new_err("For Postgres replication, table_name should be specified")
+        })?;
+
+        let reader = PsqlReplicationReader::new(
+            client,
+            self.slot_name()?.to_string(),
+            self.publication_name()?.to_string(),
+            table_name,
+            data_format.value_fields_type_map(py),
+            data_format.session_type,
+            self.internal_persistent_id(),
+        )
+        .map_err(|e| PyIOError::new_err(format!("Failed to start PostgreSQL replication: {e}")))?;
+        Ok((Box::new(reader), 1))
+    }
+
+    fn construct_mongodb_reader(
+        &self,
+        py: pyo3::Python,
+        data_format: &DataFormat,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let uri = self.connection_string()?;
+        let client = MongoClient::with_uri_str(uri)
+            .map_err(|e| PyIOError::new_err(format!("Failed to connect to MongoDB: {e}")))?;
+        let database = client.database(self.database()?);
+        let collection = database.collection(self.table_name()?);
+        let reader = MongoReader::new(
+            collection,
+            data_format.value_fields_type_map(py),
+            self.mode,
+            self.internal_persistent_id(),
+        );
+        Ok((Box::new(reader), 1))
+    }
+
+    fn construct_mysql_reader(
+        &self,
+        py: pyo3::Python,
+        data_format: &DataFormat,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let connection_options = MysqlOpts::from_url(self.connection_string()?)
+            .map_err(|e| PyValueError::new_err(format!("Invalid MySQL connection string: {e}")))?;
+        let database_name = connection_options
+            .get_db_name()
+            .ok_or_else(|| {
+                PyValueError::new_err("For MySQL, the connection string must contain the database")
+            })?
+            .to_string();
+        let reader = MysqlBinlogReader::new(
+            connection_options,
+            self.server_id()?,
+            database_name,
+            self.table_name()?.to_string(),
+            data_format.value_fields_type_map(py),
+            data_format.session_type,
+            self.internal_persistent_id(),
+        )
+        .map_err(|e| PyIOError::new_err(format!("Failed to start MySQL binlog reading: {e}")))?;
+        Ok((Box::new(reader), 1))
+    }
+
     fn object_downloader(&self, py: pyo3::Python) -> PyResult<ObjectDownloader> {
         if self.aws_s3_settings.is_some() {
             Ok(ObjectDownloader::S3(Box::new(self.s3_bucket(py)?)))
@@ -4633,6 +5239,66 @@ impl DataStorage {
         let uri = self.path()?;
         let topic: String = self.kafka_or_nats_topic()?.to_string();
         let runtime = create_async_tokio_runtime()?;
+        if let Some(stream_name) = &self.jetstream_stream {
+            let durable_name = self.durable_name.clone().ok_or_else(|| {
+                PyValueError::new_err("For NATS JetStream input, durable_name must be specified")
+            })?;
+            let max_ack_pending = self
+                .max_ack_pending
+                .unwrap_or(NATS_JETSTREAM_DEFAULT_MAX_ACK_PENDING);
+            if max_ack_pending == 0 {
+                return Err(PyValueError::new_err(
+                    "For NATS JetStream input, max_ack_pending must be positive",
+                ));
+            }
+            let messages = runtime.block_on(async {
+                let client = nats_connect(uri)
+                    .await
+                    .map_err(|e| PyIOError::new_err(format!("Failed to connect to NATS: {e}")))?;
+                let stream = async_nats::jetstream::new(client)
+                    .get_stream(stream_name)
+                    .await
+                    .map_err(|e| {
+                        PyIOError::new_err(format!(
+                            "Failed to get NATS JetStream stream {stream_name:?}: {e}"
+                        ))
+                    })?;
+                let consumer: NatsPullConsumer = stream
+                    .get_or_create_consumer(
+                        &durable_name,
+                        NatsPullConsumerConfig {
+                            durable_name: Some(durable_name.clone()),
+                            filter_subject: topic,
+                            ack_policy: NatsAckPolicy::Explicit,
+                            ack_wait: self
+                                .ack_wait_ms
+                                .map(time::Duration::from_millis)
+                                .unwrap_or_default(),
+                            max_ack_pending: max_ack_pending
+                                .try_into()
+                                .expect("max_ack_pending must fit into i64"),
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .map_err(|e| {
+                        PyIOError::new_err(format!(
+                            "Failed to create NATS JetStream consumer {durable_name:?}: {e}"
+                        ))
+                    })?;
+                consumer.messages().await.map_err(|e| {
+                    PyIOError::new_err(format!("Failed to read NATS JetStream messages: {e}"))
+                })
+            })?;
+            let reader = NatsJetStreamReader::new(
+                runtime,
+                messages,
+                self.internal_persistent_id(),
+                max_ack_pending,
+            );
+            // The offsets are tracked per subject, so the messages must be read by a single reader
+            return Ok((Box::new(reader), 1));
+        }
         let subscriber = runtime.block_on(async {
             let consumer_queue = format!("pathway-reader-{connector_index}");
             let client = nats_connect(uri)
@@ -4655,6 +5321,457 @@ impl DataStorage {
         Ok((Box::new(reader), 32))
     }
 
+    fn mqtt_protocol_version(&self) -> PyResult<MqttProtocolVersion> {
+        let protocol_version = self.mqtt_protocol_version.as_deref().unwrap_or("5");
+        MqttProtocolVersion::try_from(protocol_version)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn mqtt_qos(&self) -> PyResult<MqttQoS> {
+        MqttQoS::try_from(self.qos.unwrap_or(1)).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn construct_mqtt_reader(&self) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let uri = self.path()?;
+        let topic = self
+            .topic
+            .clone()
+            .ok_or_else(|| PyValueError::new_err("For MQTT input, topic must be specified"))?;
+        let (client, connection) = mqtt_connect(uri, self.mqtt_protocol_version()?)
+            .map_err(|e| PyValueError::new_err(format!("Failed to create MQTT client: {e}")))?;
+        let reader = MqttReader::new(
+            client,
+            connection,
+            topic,
+            self.mqtt_qos()?,
+            self.internal_persistent_id(),
+        );
+        // The client identifier is unique within the broker, so there can be only one reader
+        Ok((Box::new(reader), 1))
+    }
+
+    fn redis_connection(&self) -> PyResult<RedisConnection> {
+        let uri = self.connection_string()?;
+        RedisClient::open(uri)
+            .and_then(|client| client.get_connection())
+            .map_err(|e| PyIOError::new_err(format!("Failed to connect to Redis: {e}")))
+    }
+
+    fn construct_redis_reader(
+        &self,
+        py: pyo3::Python,
+        data_format: &DataFormat,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let stream_keys = self
+            .topic_names
+            .clone()
+            .or_else(|| self.topic.clone().map(|stream_key| vec![stream_key]))
+            .ok_or_else(|| {
+                PyValueError::new_err("For Redis Streams input, the stream key must be specified")
+            })?;
+        let group_name = self.consumer_group.clone().ok_or_else(|| {
+            PyValueError::new_err("For Redis Streams input, consumer group must be specified")
+        })?;
+        let consumer_name = self.consumer_name.clone().ok_or_else(|| {
+            PyValueError::new_err("For Redis Streams input, consumer name must be specified")
+        })?;
+        let reader = RedisStreamsReader::new(
+            self.redis_connection()?,
+            stream_keys,
+            group_name,
+            consumer_name,
+            data_format.value_fields_type_map(py),
+            self.internal_persistent_id(),
+        )
+        .map_err(|e| PyIOError::new_err(format!("Failed to start Redis Streams reader: {e}")))?;
+        // The offsets are tracked per stream, so the entries must be read by a single reader
+        Ok((Box::new(reader), 1))
+    }
+
+    fn amqp_channel(&self, runtime: &TokioRuntime) -> PyResult<AmqpChannel> {
+        let uri = self.connection_string()?;
+        runtime.block_on(async {
+            let connection = AmqpConnection::connect(uri, AmqpConnectionProperties::default())
+                .await
+                .map_err(|e| {
+                    PyIOError::new_err(format!("Failed to connect to AMQP broker: {e}"))
+                })?;
+            connection
+                .create_channel()
+                .await
+                .map_err(|e| PyIOError::new_err(format!("Failed to open AMQP channel: {e}")))
+        })
+    }
+
+    fn construct_amqp_reader(
+        &self,
+        connector_index: usize,
+        worker_index: usize,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let queue = self
+            .topic
+            .clone()
+            .ok_or_else(|| PyValueError::new_err("For AMQP input, queue must be specified"))?;
+        let runtime = create_async_tokio_runtime()?;
+        let channel = self.amqp_channel(&runtime)?;
+        let consumer = runtime.block_on(async {
+            // The messages stay unacknowledged until they are persisted, so the
+            // limit must cover the messages read between two snapshots
+            channel
+                .basic_qos(AMQP_PREFETCH_COUNT, BasicQosOptions::default())
+                .await
+                .map_err(|e| PyIOError::new_err(format!("Failed to set AMQP prefetch: {e}")))?;
+            channel
+                .basic_consume(
+                    &queue,
+                    &format!("pathway-reader-{connector_index}-{worker_index}"),
+                    BasicConsumeOptions::default(),
+                    AmqpFieldTable::default(),
+                )
+                .await
+                .map_err(|e| {
+                    PyIOError::new_err(format!("Failed to consume AMQP queue {queue:?}: {e}"))
+                })
+        })?;
+        let reader = AmqpReader::new(
+            runtime,
+            consumer,
+            worker_index,
+            self.internal_persistent_id(),
+        );
+        // The workers are competing consumers of the same queue
+        Ok((Box::new(reader), 32))
+    }
+
+    fn pulsar_client(&self, runtime: &TokioRuntime) -> PyResult<Pulsar<TokioExecutor>> {
+        let uri = self.connection_string()?;
+        runtime
+            .block_on(Pulsar::builder(uri, TokioExecutor).build())
+            .map_err(|e| PyIOError::new_err(format!("Failed to connect to Pulsar: {e}")))
+    }
+
+    fn construct_pulsar_reader(&self) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let topics = self
+            .topic_names
+            .clone()
+            .or_else(|| self.topic.clone().map(|topic| vec![topic]))
+            .ok_or_else(|| PyValueError::new_err("For Pulsar input, topic must be specified"))?;
+        let subscription = self.subscription.clone().ok_or_else(|| {
+            PyValueError::new_err("For Pulsar input, subscription must be specified")
+        })?;
+        let runtime = create_async_tokio_runtime()?;
+        let client = self.pulsar_client(&runtime)?;
+        let consumer = runtime
+            .block_on(
+                client
+                    .consumer()
+                    .with_topics(&topics)
+                    .with_subscription(subscription)
+                    // Like in a Kafka consumer group, each partition is read by a single reader
+                    .with_subscription_type(PulsarSubscriptionType::Failover)
+                    .with_options(
+                        PulsarConsumerOptions::default()
+                            .with_initial_position(PulsarInitialPosition::Earliest),
+                    )
+                    .build::<Vec<u8>>(),
+            )
+            .map_err(|e| {
+                PyIOError::new_err(format!("Failed to subscribe to Pulsar topics: {e}"))
+            })?;
+        let reader = PulsarReader::new(runtime, client, consumer, self.internal_persistent_id());
+        Ok((Box::new(reader), self.parallel_readers.unwrap_or(256)))
+    }
+
+    fn kinesis_client(&self, py: pyo3::Python, runtime: &TokioRuntime) -> KinesisClient {
+        let mut config_loader = aws_config::defaults(AwsBehaviorVersion::latest());
+        if let Some(aws_settings) = &self.aws_s3_settings {
+            let aws_settings = aws_settings.borrow(py);
+            if let s3::Region::Custom { endpoint, region } = &aws_settings.region {
+                if endpoint.starts_with("https://") || endpoint.starts_with("http://") {
+                    config_loader = config_loader.endpoint_url(endpoint);
+                } else {
+                    config_loader = config_loader.endpoint_url(format!("https://{endpoint}"));
+                }
+                if region != endpoint {
+                    config_loader = config_loader.region(KinesisRegion::new(region.clone()));
+                }
+            } else {
+                config_loader =
+                    config_loader.region(KinesisRegion::new(aws_settings.region.to_string()));
+            }
+            if let (Some(access_key), Some(secret_access_key)) =
+                (&aws_settings.access_key, &aws_settings.secret_access_key)
+            {
+                config_loader = config_loader.credentials_provider(KinesisCredentials::new(
+                    access_key,
+                    secret_access_key,
+                    aws_settings.session_token.clone(),
+                    None,
+                    "pathway",
+                ));
+            }
+            if let Some(profile) = &aws_settings.profile {
+                config_loader = config_loader.profile_name(profile);
+            }
+        }
+        // Otherwise, the settings are taken from the environment and the AWS profile
+        let config = runtime.block_on(config_loader.load());
+        KinesisClient::new(&config)
+    }
+
+    fn construct_kinesis_reader(
+        &self,
+        py: pyo3::Python,
+        worker_index: usize,
+        worker_count: usize,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let stream_name = self.topic.clone().ok_or_else(|| {
+            PyValueError::new_err("For Kinesis input, stream name must be specified")
+        })?;
+        let reader_count = self
+            .parallel_readers
+            .unwrap_or(worker_count)
+            .clamp(1, worker_count);
+        let runtime = create_async_tokio_runtime()?;
+        let client = self.kinesis_client(py, &runtime);
+        // The workers with the greater indices don't run the reader
+        let reader = KinesisReader::new(
+            runtime,
+            client,
+            stream_name,
+            worker_index % reader_count,
+            reader_count,
+            self.internal_persistent_id(),
+        );
+        Ok((Box::new(reader), reader_count))
+    }
+
+    fn pubsub_client(&self, runtime: &TokioRuntime) -> PyResult<PubSubClient> {
+        runtime.block_on(async {
+            // With the PUBSUB_EMULATOR_HOST environment variable set, the
+            // client connects to the emulator and doesn't authenticate
+            let config = PubSubClientConfig {
+                project_id: self.project_id.clone(),
+                ..Default::default()
+            };
+            let config = match &self.credentials_file {
+                Some(path) => {
+                    let credentials = PubSubCredentialsFile::new_from_file(path.clone())
+                        .await
+                        .map_err(|e| {
+                            PyIOError::new_err(format!(
+                                "Failed to read Google Cloud credentials: {e}"
+                            ))
+                        })?;
+                    config.with_credentials(credentials).await
+                }
+                None => config.with_auth().await,
+            }
+            .map_err(|e| {
+                PyIOError::new_err(format!("Failed to authenticate in Google Cloud: {e}"))
+            })?;
+            PubSubClient::new(config)
+                .await
+                .map_err(|e| PyIOError::new_err(format!("Failed to create Pub/Sub client: {e}")))
+        })
+    }
+
+    fn construct_pubsub_reader(
+        &self,
+        worker_index: usize,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let subscription_id = self.subscription.clone().ok_or_else(|| {
+            PyValueError::new_err("For Pub/Sub input, subscription must be specified")
+        })?;
+        let runtime = create_async_tokio_runtime()?;
+        let client = self.pubsub_client(&runtime)?;
+        let subscription = client.subscription(&subscription_id);
+        // The messages stay unacknowledged until they are persisted, so the
+        // limit must cover the messages read between two snapshots
+        let subscriber_config = PubSubSubscriberConfig {
+            stream_ack_deadline_seconds: PUBSUB_ACK_DEADLINE_SECONDS,
+            max_outstanding_messages: PUBSUB_MAX_OUTSTANDING_MESSAGES,
+            ..Default::default()
+        };
+        let stream = runtime
+            .block_on(subscription.subscribe(Some(
+                PubSubSubscribeConfig::default().with_subscriber_config(subscriber_config),
+            )))
+            .map_err(|e| {
+                PyIOError::new_err(format!(
+                    "Failed to subscribe to Pub/Sub subscription {subscription_id:?}: {e}"
+                ))
+            })?;
+        let reader = PubSubReader::new(
+            runtime,
+            subscription,
+            stream,
+            worker_index,
+            self.internal_persistent_id(),
+        );
+        // The workers are competing subscribers of the same subscription
+        Ok((Box::new(reader), 32))
+    }
+
+    fn construct_grpc_reader(
+        &self,
+        worker_index: usize,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let address = self
+            .connection_string()?
+            .to_socket_addrs()
+            .map_err(|e| PyValueError::new_err(format!("Invalid address of the gRPC server: {e}")))?
+            .next()
+            .ok_or_else(|| PyValueError::new_err("Address of the gRPC server is not resolved"))?;
+        let table_name = self.table_name()?;
+        let runtime = create_async_tokio_runtime()?;
+        let (batch_sender, batch_receiver) = tokio::sync::mpsc::channel(GRPC_READER_QUEUE_SIZE);
+        // The address can be bound only once, so the server is run by the
+        // first worker. The other workers don't read in real time.
+        let server = if worker_index == 0 {
+            let server = GrpcIngestServerHandle::start(
+                address,
+                GrpcIngestServer::new(table_name.to_string(), batch_sender),
+            )
+            .map_err(|e| {
+                PyIOError::new_err(format!("Failed to start gRPC server at {address}: {e}"))
+            })?;
+            Some(server)
+        } else {
+            None
+        };
+        let reader = GrpcReader::new(
+            runtime,
+            server,
+            batch_receiver,
+            self.internal_persistent_id(),
+        );
+        Ok((Box::new(reader), 1))
+    }
+
+    fn construct_webhook_reader(
+        &self,
+        worker_index: usize,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let address = self
+            .connection_string()?
+            .to_socket_addrs()
+            .map_err(|e| {
+                PyValueError::new_err(format!("Invalid address of the webhook server: {e}"))
+            })?
+            .next()
+            .ok_or_else(|| {
+                PyValueError::new_err("Address of the webhook server is not resolved")
+            })?;
+        let routes = self
+            .routes
+            .clone()
+            .filter(|routes| !routes.is_empty())
+            .ok_or_else(|| PyValueError::new_err("At least one route must be specified"))?;
+        if let Some(route) = routes.iter().find(|route| !route.starts_with('/')) {
+            return Err(PyValueError::new_err(format!(
+                "Route {route:?} must start with '/'"
+            )));
+        }
+        let max_request_size = self
+            .max_request_size
+            .unwrap_or(WEBHOOK_DEFAULT_MAX_REQUEST_SIZE);
+        let runtime = create_async_tokio_runtime()?;
+        let (request_sender, request_receiver) =
+            tokio::sync::mpsc::channel(WEBHOOK_READER_QUEUE_SIZE);
+        // The address can be bound only once, so the server is run by the
+        // first worker. The other workers don't read in real time.
+        let server = if worker_index == 0 {
+            let server = WebhookIngestServerHandle::start(
+                address,
+                WebhookIngestServer::new(routes, max_request_size, request_sender),
+            )
+            .map_err(|e| {
+                PyIOError::new_err(format!("Failed to start webhook server at {address}: {e}"))
+            })?;
+            Some(server)
+        } else {
+            None
+        };
+        let reader = WebhookReader::new(
+            runtime,
+            server,
+            request_receiver,
+            self.internal_persistent_id(),
+            self.wait_for_processing,
+        );
+        Ok((Box::new(reader), 1))
+    }
+
+    fn socket_framing(&self) -> PyResult<SocketFraming> {
+        match self.framing.as_deref() {
+            None | Some("lines") => Ok(SocketFraming::Lines),
+            Some("length_prefixed") => Ok(SocketFraming::LengthPrefixed),
+            Some("octet_counting") => Ok(SocketFraming::OctetCounting),
+            Some(other) => Err(PyValueError::new_err(format!(
+                "Unknown socket framing {other:?}"
+            ))),
+        }
+    }
+
+    fn construct_socket_reader(
+        &self,
+        worker_index: usize,
+    ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
+        let address = self
+            .connection_string()?
+            .to_socket_addrs()
+            .map_err(|e| PyValueError::new_err(format!("Invalid address of the socket: {e}")))?
+            .next()
+            .ok_or_else(|| PyValueError::new_err("Address of the socket is not resolved"))?;
+        let protocol = match self.protocol.as_deref() {
+            None | Some("tcp") => SocketProtocol::Tcp,
+            Some("udp") => SocketProtocol::Udp,
+            Some(other) => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown socket protocol {other:?}"
+                )))
+            }
+        };
+        let framing = self.socket_framing()?;
+        let max_message_size = self
+            .max_message_size
+            .unwrap_or(SOCKET_DEFAULT_MAX_MESSAGE_SIZE);
+        let (message_sender, message_receiver) =
+            std::sync::mpsc::sync_channel(SOCKET_READER_QUEUE_SIZE);
+        // The address can be bound only once, so the socket is listened to by
+        // the first worker
+        let listener = if worker_index == 0 {
+            let listener = match protocol {
+                SocketProtocol::Tcp => SocketListenerHandle::start_tcp(
+                    address,
+                    framing,
+                    max_message_size,
+                    message_sender,
+                ),
+                SocketProtocol::Udp => {
+                    SocketListenerHandle::start_udp(address, max_message_size, message_sender)
+                }
+            }
+            .map_err(|e| {
+                PyIOError::new_err(format!(
+                    "Failed to listen on {} socket at {address}: {e}",
+                    protocol.name()
+                ))
+            })?;
+            Some(listener)
+        } else {
+            None
+        };
+        let reader = SocketReader::new(
+            protocol,
+            listener,
+            message_receiver,
+            self.internal_persistent_id(),
+        );
+        Ok((Box::new(reader), 1))
+    }
+
     fn construct_iceberg_reader(
         &self,
         py: pyo3::Python,
@@ -4706,6 +5823,7 @@ impl DataStorage {
         data_format: &DataFormat,
         connector_index: usize,
         worker_index: usize,
+        worker_count: usize,
         license: Option<&License>,
     ) -> PyResult<(Box<dyn ReaderBuilder>, usize)> {
         match self.storage_type.as_ref() {
@@ -4713,11 +5831,25 @@ impl DataStorage {
             "s3" => self.construct_s3_reader(py),
             "s3_csv" => self.construct_s3_csv_reader(py),
             "csv" => self.construct_csv_reader(py),
+            "parquet" => self.construct_parquet_reader(py, data_format),
+            "s3_parquet" => self.construct_s3_parquet_reader(py, data_format),
             "kafka" => self.construct_kafka_reader(),
             "python" => self.construct_python_reader(py, data_format),
             "sqlite" => self.construct_sqlite_reader(py, data_format),
+            "postgres_replication" => self.construct_postgres_replication_reader(py, data_format),
+            "mongodb" => self.construct_mongodb_reader(py, data_format),
+            "mysql" => self.construct_mysql_reader(py, data_format),
             "deltalake" => self.construct_deltalake_reader(py, data_format, license),
             "nats" => self.construct_nats_reader(connector_index, worker_index),
+            "mqtt" => self.construct_mqtt_reader(),
+            "redis" => self.construct_redis_reader(py, data_format),
+            "amqp" => self.construct_amqp_reader(connector_index, worker_index),
+            "pulsar" => self.construct_pulsar_reader(),
+            "kinesis" => self.construct_kinesis_reader(py, worker_index, worker_count),
+            "pubsub" => self.construct_pubsub_reader(worker_index),
+            "grpc" => self.construct_grpc_reader(worker_index),
+            "webhook" => self.construct_webhook_reader(worker_index),
+            "socket" => self.construct_socket_reader(worker_index),
             "iceberg" => self.construct_iceberg_reader(py, data_format, license),
             other => Err(PyValueError::new_err(format!(
                 "Unknown data source {other:?}"
@@ -4785,20 +5917,48 @@ impl DataStorage {
         Ok(Box::new(storage))
     }
 
-    fn construct_kafka_writer(&self) -> PyResult<Box<dyn Writer>> {
-        let client_config = self.kafka_client_config()?;
+    fn construct_kafka_writer(&self, worker_index: usize) -> PyResult<Box<dyn Writer>> {
+        let mut client_config = self.kafka_client_config()?;
+        if let Some(transactional_id) = &self.transactional_id {
+            // Each worker has its own producer, which fences its previous instances
+            client_config.set(
+                "transactional.id",
+                format!("{transactional_id}-{worker_index}"),
+            );
+        }
 
         let producer: ThreadedProducer<DefaultProducerContext> = match client_config.create() {
             Ok(producer) => producer,
             Err(_) => return Err(PyIOError::new_err("Producer creation failed")),
         };
+        if self.transactional_id.is_some() {
+            producer
+                .init_transactions(KAFKA_TRANSACTION_TIMEOUT)
+                .map_err(|e| {
+                    PyIOError::new_err(format!("Failed to initialize Kafka transactions: {e}"))
+                })?;
+        }
+
+        let partitioner = match (self.partition_field_index, self.partition_by_key_hash) {
+            (Some(_), true) => {
+                return Err(PyValueError::new_err(
+                    "Partition column and key hash partitioning can't be used together",
+                ))
+            }
+            (Some(index), false) => KafkaPartitioner::Column(index),
+            (None, true) => KafkaPartitioner::KeyHash,
+            (None, false) => KafkaPartitioner::Default,
+        };
 
         let topic = self.kafka_or_nats_topic()?;
         let writer = KafkaWriter::new(
             producer,
             topic.to_string(),
             self.header_fields.clone(),
-            self.key_field_index,
+            self.key_fields.clone(),
+            partitioner,
+            self.produce_tombstones,
+            self.transactional_id.is_some(),
         );
 
         Ok(Box::new(writer))
@@ -4831,6 +5991,33 @@ impl DataStorage {
         Ok(Box::new(writer))
     }
 
+    fn construct_webhook_writer(
+        &self,
+        py: pyo3::Python,
+        data_format: &DataFormat,
+    ) -> PyResult<Box<dyn Writer>> {
+        let settings = self
+            .webhook_settings
+            .as_ref()
+            .ok_or_else(|| {
+                PyValueError::new_err("For webhook output, webhook_settings must be specified")
+            })?
+            .borrow(py);
+        let url_template =
+            WebhookUrlTemplate::parse(&settings.url, &data_format.value_field_names(py))
+                .map_err(|e| PyValueError::new_err(format!("Invalid URL template: {e}")))?;
+        let writer = WebhookWriter::new(
+            settings.client()?,
+            url_template,
+            settings.batch_format()?,
+            settings.headers()?,
+            settings.signature()?,
+            self.max_batch_size,
+            settings.retry_policy(),
+        );
+        Ok(Box::new(writer))
+    }
+
     fn construct_deltalake_writer(
         &self,
         py: pyo3::Python,
@@ -4904,6 +6091,122 @@ impl DataStorage {
         Ok(Box::new(writer))
     }
 
+    fn construct_mqtt_writer(&self, worker_index: usize) -> PyResult<Box<dyn Writer>> {
+        let uri = self.path()?;
+        let topic = self.topic.clone().unwrap_or_default();
+        if self.topic.is_none() && self.topic_field_index.is_none() {
+            return Err(PyValueError::new_err(
+                "For MQTT output, either topic or topic column must be specified",
+            ));
+        }
+        // Each worker publishes with its own connection, and the broker doesn't
+        // allow two connections with the same client identifier
+        let uri = if worker_index == 0 {
+            uri.to_string()
+        } else {
+            let mut uri = Url::parse(uri)
+                .map_err(|e| PyValueError::new_err(format!("Failed to parse MQTT URI: {e}")))?;
+            let client_id = uri
+                .query_pairs()
+                .find_map(|(key, value)| (key == "client_id").then(|| value.into_owned()))
+                .unwrap_or_default();
+            let other_pairs: Vec<(String, String)> = uri
+                .query_pairs()
+                .filter(|(key, _)| key != "client_id")
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect();
+            uri.query_pairs_mut()
+                .clear()
+                .extend_pairs(other_pairs)
+                .append_pair("client_id", &format!("{client_id}-{worker_index}"));
+            uri.to_string()
+        };
+        let (client, connection) = mqtt_connect(&uri, self.mqtt_protocol_version()?)
+            .map_err(|e| PyValueError::new_err(format!("Failed to create MQTT client: {e}")))?;
+        let writer = MqttWriter::new(
+            client,
+            connection,
+            topic,
+            self.topic_field_index,
+            self.mqtt_qos()?,
+            self.retain,
+            MQTT_FLUSH_TIMEOUT,
+        )
+        .map_err(|e| PyIOError::new_err(format!("Failed to start MQTT writer: {e}")))?;
+        Ok(Box::new(writer))
+    }
+
+    fn construct_redis_writer(
+        &self,
+        py: pyo3::Python,
+        data_format: &DataFormat,
+    ) -> PyResult<Box<dyn Writer>> {
+        let value_type = match self.redis_value_type.as_deref() {
+            None | Some("hash") => RedisValueType::Hash,
+            Some("json") => RedisValueType::Json,
+            Some(other) => {
+                return Err(PyValueError::new_err(format!(
+                "Unknown Redis value type {other:?}, the supported types are \"hash\" and \"json\""
+            )))
+            }
+        };
+        let key_field_positions = self.key_fields.iter().map(|(_, index)| *index).collect();
+        let writer = RedisSnapshotWriter::new(
+            self.redis_connection()?,
+            self.key_prefix.clone().unwrap_or_default(),
+            key_field_positions,
+            data_format.value_field_names(py),
+            value_type,
+        );
+        Ok(Box::new(writer))
+    }
+
+    fn construct_amqp_writer(&self) -> PyResult<Box<dyn Writer>> {
+        let exchange = self.exchange.clone().unwrap_or_default();
+        let routing_key = self.topic.clone().unwrap_or_default();
+        let runtime = create_async_tokio_runtime()?;
+        let channel = self.amqp_channel(&runtime)?;
+        runtime
+            .block_on(channel.confirm_select(ConfirmSelectOptions::default()))
+            .map_err(|e| {
+                PyIOError::new_err(format!("Failed to enable AMQP publisher confirms: {e}"))
+            })?;
+        let writer = AmqpWriter::new(
+            runtime,
+            channel,
+            exchange,
+            routing_key,
+            self.topic_field_index,
+            self.header_fields.clone(),
+        );
+        Ok(Box::new(writer))
+    }
+
+    fn construct_pulsar_writer(&self) -> PyResult<Box<dyn Writer>> {
+        let topic = self
+            .topic
+            .clone()
+            .ok_or_else(|| PyValueError::new_err("For Pulsar output, topic must be specified"))?;
+        let runtime = create_async_tokio_runtime()?;
+        let client = self.pulsar_client(&runtime)?;
+        let partition_topics = runtime
+            .block_on(client.lookup_partitioned_topic(topic))
+            .map_err(|e| PyIOError::new_err(format!("Failed to look up Pulsar topic: {e}")))?
+            .into_iter()
+            .map(|(partition_topic, _)| partition_topic)
+            .collect();
+        let producer = client.producer().build_multi_topic();
+        let writer = PulsarWriter::new(
+            runtime,
+            producer,
+            partition_topics,
+            self.header_fields.clone(),
+            self.key_fields.clone(),
+            self.produce_tombstones,
+        );
+        Ok(Box::new(writer))
+    }
+
     fn construct_mongodb_writer(&self) -> PyResult<Box<dyn Writer>> {
         let uri = self.connection_string()?;
         let client = MongoClient::with_uri_str(uri)
@@ -4914,20 +6217,56 @@ impl DataStorage {
         Ok(Box::new(writer))
     }
 
+    fn construct_kinesis_writer(&self, py: pyo3::Python) -> PyResult<Box<dyn Writer>> {
+        let stream_name = self.topic.clone().ok_or_else(|| {
+            PyValueError::new_err("For Kinesis output, stream name must be specified")
+        })?;
+        let runtime = create_async_tokio_runtime()?;
+        let client = self.kinesis_client(py, &runtime);
+        let writer = KinesisWriter::new(runtime, client, stream_name, self.key_fields.clone());
+        Ok(Box::new(writer))
+    }
+
+    fn construct_pubsub_writer(&self) -> PyResult<Box<dyn Writer>> {
+        let topic_id = self
+            .topic
+            .clone()
+            .ok_or_else(|| PyValueError::new_err("For Pub/Sub output, topic must be specified"))?;
+        let runtime = create_async_tokio_runtime()?;
+        let client = self.pubsub_client(&runtime)?;
+        // The publisher spawns its tasks, so it must be created within the runtime
+        let publisher = runtime.block_on(async { client.topic(&topic_id).new_publisher(None) });
+        let writer = PubSubWriter::new(
+            runtime,
+            publisher,
+            self.header_fields.clone(),
+            self.key_fields.clone(),
+        );
+        Ok(Box::new(writer))
+    }
+
     fn construct_writer(
         &self,
         py: pyo3::Python,
         data_format: &DataFormat,
+        worker_index: usize,
     ) -> PyResult<Box<dyn Writer>> {
         match self.storage_type.as_ref() {
             "fs" => self.construct_fs_writer(),
-            "kafka" => self.construct_kafka_writer(),
+            "kafka" => self.construct_kafka_writer(worker_index),
             "postgres" => self.construct_postgres_writer(),
             "elasticsearch" => self.construct_elasticsearch_writer(py),
             "deltalake" => self.construct_deltalake_writer(py, data_format),
             "mongodb" => self.construct_mongodb_writer(),
             "null" => Ok(Box::new(NullWriter::new())),
             "nats" => self.construct_nats_writer(),
+            "mqtt" => self.construct_mqtt_writer(worker_index),
+            "redis" => self.construct_redis_writer(py, data_format),
+            "amqp" => self.construct_amqp_writer(),
+            "pulsar" => self.construct_pulsar_writer(),
+            "kinesis" => self.construct_kinesis_writer(py),
+            "pubsub" => self.construct_pubsub_writer(),
+            "webhook" => self.construct_webhook_writer(py, data_format),
             "iceberg" => self.construct_iceberg_writer(py, data_format),
             other => Err(PyValueError::new_err(format!(
                 "Unknown data sink {other:?}"
@@ -4998,6 +6337,38 @@ impl DataFormat {
         Ok(types)
     }
 
+    fn message_metadata_fields(&self) -> Vec<(String, MessageMetadataField)> {
+        let header_fields = self.header_fields.iter().flatten().map(|(name, header)| {
+            (
+                name.clone(),
+                MessageMetadataField::KafkaHeader(header.clone()),
+            )
+        });
+        let timestamp_field = self
+            .timestamp_field
+            .iter()
+            .map(|name| (name.clone(), MessageMetadataField::KafkaTimestamp));
+        let attribute_fields = self
+            .attribute_fields
+            .iter()
+            .flatten()
+            .map(|(name, attribute)| {
+                (
+                    name.clone(),
+                    MessageMetadataField::PubSubAttribute(attribute.clone()),
+                )
+            });
+        let publish_time_field = self
+            .publish_time_field
+            .iter()
+            .map(|name| (name.clone(), MessageMetadataField::PubSubPublishTime));
+        header_fields
+            .chain(timestamp_field)
+            .chain(attribute_fields)
+            .chain(publish_time_field)
+            .collect()
+    }
+
     fn construct_parser(&self, py: pyo3::Python) -> PyResult<Box<dyn Parser>> {
         match self.format_type.as_ref() {
             "dsv" => {
@@ -5010,6 +6381,7 @@ impl DataFormat {
                     self.value_field_names(py),
                     DebeziumMessageParser::standard_separator(),
                     self.debezium_db_type,
+                    self.schema(py)?,
                 );
                 Ok(Box::new(parser))
             }
@@ -5021,7 +6393,8 @@ impl DataFormat {
                     self.field_absence_is_error,
                     self.schema(py)?,
                     self.session_type,
-                )?;
+                )?
+                .with_message_metadata_fields(self.message_metadata_fields())?;
                 Ok(Box::new(parser))
             }
             "identity" => Ok(Box::new(IdentityParser::new(
@@ -5030,6 +6403,10 @@ impl DataFormat {
                 self.key_generation_policy,
                 self.session_type,
             ))),
+            "syslog" => Ok(Box::new(SyslogParser::new(
+                self.value_field_names(py),
+                self.schema(py)?,
+            )?)),
             "transparent" => Ok(Box::new(TransparentParser::new(
                 self.key_field_names.clone(),
                 self.value_field_names(py),
@@ -5510,6 +6887,7 @@ fn engine(_py: Python<'_>, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<AwsS3Settings>()?;
     m.add_class::<ElasticSearchParams>()?;
     m.add_class::<ElasticSearchAuth>()?;
+    m.add_class::<WebhookSettings>()?;
     m.add_class::<CsvParserSettings>()?;
     m.add_class::<ValueField>()?;
     m.add_class::<DataStorage>()?;
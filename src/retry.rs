@@ -41,17 +41,31 @@ impl Default for RetryConfig {
 }
 
 pub fn execute_with_retries<T, E>(
+    func: impl FnMut() -> Result<T, E>,
+    retry_config: RetryConfig,
+    max_retries: usize,
+) -> Result<T, E> {
+    execute_with_retries_and_delays(func, |_| None, retry_config, max_retries)
+}
+
+/// Same as `execute_with_retries`, but an error may set the delay before the next
+/// attempt, which is then used instead of the backoff.
+pub fn execute_with_retries_and_delays<T, E>(
     mut func: impl FnMut() -> Result<T, E>,
+    error_delay: impl Fn(&E) -> Option<Duration>,
     mut retry_config: RetryConfig,
     max_retries: usize,
 ) -> Result<T, E> {
     let mut exec_result = func();
     for attempt_idx in 0..max_retries {
-        if exec_result.is_ok() {
+        let Err(error) = &exec_result else {
             return exec_result;
-        }
+        };
         warn!("Attempt {attempt_idx}: retrying operation after an error...");
-        retry_config.sleep_after_error();
+        match error_delay(error) {
+            Some(delay) => std::thread::sleep(delay),
+            None => retry_config.sleep_after_error(),
+        }
         exec_result = func();
     }
 
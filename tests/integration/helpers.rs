@@ -6,21 +6,29 @@ use std::sync::{mpsc, mpsc::Receiver, Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+use mongodb::bson::doc;
+
 use pathway_engine::engine::error::DynError;
 use pathway_engine::engine::{report_error::ReportError, Error};
 use pathway_engine::persistence::config::{PersistenceManagerOuterConfig, PersistentStorageConfig};
 use pathway_engine::persistence::tracker::WorkerPersistentStorage;
 
 use pathway_engine::connectors::data_format::{
-    ErrorRemovalLogic, FormattedDocument, ParseResult, ParsedEvent, ParsedEventWithErrors, Parser,
+    DsvParser, DsvSettings, ErrorRemovalLogic, FormattedDocument, InnerSchemaField, ParseResult,
+    ParsedEvent, ParsedEventWithErrors, Parser,
 };
 use pathway_engine::connectors::data_storage::{
-    DataEventType, ReadResult, Reader, ReaderBuilder, ReaderContext,
+    new_csv_filesystem_reader, ConnectorMode, DataEventType, ReadResult, Reader, ReaderBuilder,
+    ReaderContext,
+};
+use pathway_engine::connectors::posix_like::PosixLikeReader;
+use pathway_engine::connectors::{
+    Connector, Entry, OffsetKey, OffsetValue, PersistenceMode, SnapshotAccess, StorageType,
 };
-use pathway_engine::connectors::{Connector, Entry, PersistenceMode, SnapshotAccess};
-use pathway_engine::engine::{Key, Timestamp, TotalFrontier, Value};
+use pathway_engine::engine::{Key, Timestamp, TotalFrontier, Type, Value};
 use pathway_engine::persistence::frontier::OffsetAntichain;
 use pathway_engine::persistence::input_snapshot::Event as SnapshotEvent;
+use pathway_engine::persistence::PersistentId;
 
 #[derive(Debug)]
 pub struct FullReadResult {
@@ -231,6 +239,53 @@ pub fn create_persistence_manager(
     ))
 }
 
+/// Creates a static reader of the CSV files with the `key` and the `value`
+/// columns and a parser for them. The reader can be set up further with
+/// `setup_reader`, for example, to expand the archives.
+pub fn read_csv_kv(
+    input_path: &Path,
+    persistent_id: Option<PersistentId>,
+    setup_reader: impl FnOnce(PosixLikeReader) -> eyre::Result<PosixLikeReader>,
+) -> eyre::Result<(Box<dyn ReaderBuilder>, DsvParser)> {
+    let mut builder = csv::ReaderBuilder::new();
+    builder.has_headers(false);
+    let reader = new_csv_filesystem_reader(
+        input_path.to_str().unwrap(),
+        builder,
+        ConnectorMode::Static,
+        persistent_id,
+        "*",
+    )?;
+    let schema = [
+        ("key".to_string(), InnerSchemaField::new(Type::String, None)),
+        (
+            "value".to_string(),
+            InnerSchemaField::new(Type::String, None),
+        ),
+    ];
+    let parser = DsvParser::new(
+        DsvSettings::new(
+            Some(vec!["key".to_string()]),
+            vec!["value".to_string()],
+            ',',
+        ),
+        schema.into(),
+    )?;
+    Ok((Box::new(setup_reader(reader)?), parser))
+}
+
+pub fn kv_event(insert: bool, key: &str, value: &str) -> ParsedEvent {
+    let key_value = (
+        Some(vec![Value::String(key.into())]),
+        vec![Value::String(value.into())],
+    );
+    if insert {
+        ParsedEvent::Insert(key_value)
+    } else {
+        ParsedEvent::Delete(key_value)
+    }
+}
+
 pub fn get_entries_in_receiver<T>(receiver: Receiver<T>) -> Vec<T> {
     let mut result = Vec::new();
     while let Ok(entry) = receiver.recv_timeout(Duration::from_secs(1)) {
@@ -363,3 +418,171 @@ pub fn assert_document_raw_byte_contents(document: &FormattedDocument, target: &
         unreachable!("Unexpected comparison with raw bytes document: {document:?}");
     }
 }
+
+/// A reader, for which the merging of the frontiers is checked. The offset
+/// function gives the offset for a key index and a position in the source,
+/// the offsets of a key grow with the position.
+pub struct FrontierMergeCase {
+    pub storage_type: StorageType,
+    pub has_multiple_keys: bool,
+    offset: fn(usize, usize) -> (OffsetKey, OffsetValue),
+}
+
+pub const FRONTIER_MERGE_CASES: &[FrontierMergeCase] = &[
+    FrontierMergeCase {
+        storage_type: StorageType::PsqlReplication,
+        has_multiple_keys: false,
+        offset: |_, position| {
+            (
+                OffsetKey::Empty,
+                OffsetValue::PsqlReplicationPosition {
+                    transaction_lsn: 0x16_B374_D848 + (position / 2) as u64 * 0x100,
+                    entries_read_within_transaction: (position % 2 + 1) as u64,
+                },
+            )
+        },
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::MongoDB,
+        has_multiple_keys: false,
+        offset: |_, position| {
+            let resume_token = mongodb::bson::to_vec(&doc! {
+                "_data": format!("8266F0A3C1{position:08X}2B0229296E04"),
+            })
+            .expect("resume token must be serialized");
+            (
+                OffsetKey::Empty,
+                OffsetValue::MongoDbResumeToken(resume_token.into()),
+            )
+        },
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::MysqlBinlog,
+        has_multiple_keys: false,
+        offset: |_, position| {
+            // The binary log files are rotated, so the position in a later file
+            // may be smaller
+            (
+                OffsetKey::Empty,
+                OffsetValue::MysqlBinlogPosition {
+                    file_name: format!("binlog.{:06}", 1 + position / 4).into(),
+                    transaction_position: 4 + (position % 4) as u64 * 100,
+                    gtid_set: None,
+                    entries_read_within_transaction: 1,
+                },
+            )
+        },
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::Mqtt,
+        has_multiple_keys: false,
+        offset: |_, position| (OffsetKey::Mqtt, OffsetValue::MqttReadEntriesCount(position)),
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::NatsJetStream,
+        has_multiple_keys: true,
+        offset: |key, position| {
+            (
+                OffsetKey::NatsSubject(format!("events.{key}").into()),
+                OffsetValue::NatsStreamSequence(position as u64),
+            )
+        },
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::RedisStreams,
+        has_multiple_keys: true,
+        offset: |key, position| {
+            (
+                OffsetKey::RedisStream(format!("stream-{key}").into()),
+                OffsetValue::RedisStreamEntryId {
+                    milliseconds: 100 + position as u64 / 4,
+                    sequence: position as u64 % 4,
+                },
+            )
+        },
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::Amqp,
+        has_multiple_keys: true,
+        offset: |worker_index, position| {
+            (
+                OffsetKey::Amqp(worker_index),
+                OffsetValue::AmqpReadEntriesCount(position),
+            )
+        },
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::Pulsar,
+        has_multiple_keys: true,
+        offset: |partition, position| {
+            (
+                OffsetKey::Pulsar(format!("orders-partition-{partition}").into()),
+                OffsetValue::PulsarMessageId {
+                    ledger_id: 3,
+                    entry_id: position as u64 / 2,
+                    batch_index: (position % 2) as i32,
+                },
+            )
+        },
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::Kinesis,
+        has_multiple_keys: true,
+        offset: |shard, position| {
+            // A fully read record follows the user records aggregated in it
+            (
+                OffsetKey::Kinesis(format!("shardId-00000000000{shard}").into()),
+                OffsetValue::KinesisRecordPosition {
+                    sequence_number: (99 + position / 2).to_string().into(),
+                    sub_sequence_number: (position % 2 == 0).then_some(0),
+                },
+            )
+        },
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::PubSub,
+        has_multiple_keys: true,
+        offset: |worker_index, position| {
+            (
+                OffsetKey::PubSub(worker_index),
+                OffsetValue::PubSubReadMessagesCount(position),
+            )
+        },
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::Grpc,
+        has_multiple_keys: false,
+        offset: |_, position| (OffsetKey::Grpc, OffsetValue::GrpcReadBatchesCount(position)),
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::Webhook,
+        has_multiple_keys: false,
+        offset: |_, position| {
+            (
+                OffsetKey::Webhook,
+                OffsetValue::WebhookReadRequestsCount(position),
+            )
+        },
+    },
+    FrontierMergeCase {
+        storage_type: StorageType::Socket,
+        has_multiple_keys: false,
+        offset: |_, position| {
+            (
+                OffsetKey::Socket,
+                OffsetValue::SocketReadMessagesCount(position),
+            )
+        },
+    },
+];
+
+impl FrontierMergeCase {
+    pub fn frontier(&self, positions: &[(usize, usize)]) -> OffsetAntichain {
+        let mut frontier = OffsetAntichain::new();
+        for (key, position) in positions {
+            let (key, value) = (self.offset)(*key, *position);
+            frontier.advance_offset(key, value);
+        }
+        frontier
+    }
+}
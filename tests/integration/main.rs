@@ -3,10 +3,13 @@
 mod helpers;
 mod operator_test_utils;
 
+mod test_amqp;
+mod test_archive;
 mod test_arrow;
 mod test_bson;
 mod test_bytes;
 mod test_cached_object_storage;
+mod test_compression;
 mod test_connector_field_defaults;
 mod test_dd_distinct_total;
 mod test_debezium;
@@ -15,18 +18,36 @@ mod test_dsv;
 mod test_dsv_dir;
 mod test_dsv_output;
 mod test_file_kv;
+mod test_frontier_merge;
+mod test_grpc;
+#[cfg(target_os = "linux")]
+mod test_inotify;
 mod test_json_output;
 mod test_jsonlines;
+mod test_kafka_headers;
+mod test_kafka_offsets;
+mod test_kafka_subscription;
+mod test_kafka_upsert;
+mod test_kafka_writer;
+mod test_kinesis;
 mod test_metadata;
+mod test_mqtt;
+mod test_mysql_binlog;
 mod test_null_writer;
 mod test_offsets_storage;
 mod test_operator_persistence;
+mod test_parquet;
 mod test_parser;
 mod test_parser_errors;
+mod test_pgoutput;
 mod test_prev_next;
 mod test_psql_output;
 mod test_psql_snapshot;
+mod test_pubsub;
+mod test_pulsar;
+mod test_redis;
 mod test_seek;
+mod test_socket;
 mod test_sqlite;
 mod test_stream_snapshot;
 mod test_time;
@@ -34,3 +55,4 @@ mod test_time_column;
 mod test_types;
 mod test_upsert_session;
 mod test_value_to_sql;
+mod test_webhook;
@@ -0,0 +1,18 @@
+// Copyright © 2024 Pathway
+
+use pathway_engine::connectors::metadata::{AmqpMetadata, SourceMetadata};
+
+#[test]
+fn test_amqp_metadata_serialization() {
+    let metadata: SourceMetadata =
+        AmqpMetadata::new("alerts".to_string(), "critical".to_string(), true).into();
+    assert_eq!(
+        metadata.serialize(),
+        serde_json::json!({
+            "exchange": "alerts",
+            "routing_key": "critical",
+            "redelivered": true,
+        })
+    );
+    assert!(metadata.commits_allowed_in_between());
+}
@@ -0,0 +1,337 @@
+// Copyright © 2024 Pathway
+
+use super::helpers::{create_persistence_manager, full_cycle_read, kv_event, read_csv_kv};
+
+use std::io::{Cursor, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use tempfile::tempdir;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use pathway_engine::connectors::data_storage::ReadError;
+use pathway_engine::connectors::metadata::FileLikeMetadata;
+use pathway_engine::connectors::posix_like::PosixLikeReader;
+use pathway_engine::connectors::scanner::archive::{member_path, split_member_path, ArchiveFormat};
+use pathway_engine::connectors::scanner::{
+    ArchiveScanner, FilesystemScanner, PosixLikeScanner, QueuedAction,
+};
+use pathway_engine::persistence::backends::MemoryKVStorage;
+use pathway_engine::persistence::cached_object_storage::CachedObjectStorage;
+
+fn write_zip(path: &Path, members: &[(&str, &str)]) -> eyre::Result<()> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in members {
+        writer.start_file(*name, SimpleFileOptions::default())?;
+        writer.write_all(contents.as_bytes())?;
+    }
+    std::fs::write(path, writer.finish()?.into_inner())?;
+    Ok(())
+}
+
+fn write_tar_gz(path: &Path, members: &[(&str, &str)]) -> eyre::Result<()> {
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    for (name, contents) in members {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(1_700_000_000);
+        header.set_cksum();
+        builder.append_data(&mut header, name, contents.as_bytes())?;
+    }
+    std::fs::write(path, builder.into_inner()?.finish()?)?;
+    Ok(())
+}
+
+fn expand_archives(reader: PosixLikeReader) -> eyre::Result<PosixLikeReader> {
+    Ok(reader.with_expanded_archives()?)
+}
+
+/// Passes everything to the filesystem scanner and counts the objects read.
+struct CountingScanner {
+    scanner: FilesystemScanner,
+    objects_read: Arc<AtomicUsize>,
+}
+
+impl PosixLikeScanner for CountingScanner {
+    fn object_metadata(
+        &mut self,
+        object_path: &[u8],
+    ) -> Result<Option<FileLikeMetadata>, ReadError> {
+        self.scanner.object_metadata(object_path)
+    }
+
+    fn read_object(&mut self, object_path: &[u8]) -> Result<Vec<u8>, ReadError> {
+        self.objects_read.fetch_add(1, Ordering::SeqCst);
+        self.scanner.read_object(object_path)
+    }
+
+    fn next_scanner_actions(
+        &mut self,
+        are_deletions_enabled: bool,
+        cached_object_storage: &CachedObjectStorage,
+    ) -> Result<Vec<QueuedAction>, ReadError> {
+        self.scanner
+            .next_scanner_actions(are_deletions_enabled, cached_object_storage)
+    }
+}
+
+fn counting_archive_scanner(
+    inputs_path: &Path,
+) -> eyre::Result<(ArchiveScanner, Arc<AtomicUsize>)> {
+    let objects_read = Arc::new(AtomicUsize::new(0));
+    let scanner = CountingScanner {
+        scanner: FilesystemScanner::new(inputs_path.to_str().unwrap(), "*")?,
+        objects_read: objects_read.clone(),
+    };
+    Ok((ArchiveScanner::new(Box::new(scanner))?, objects_read))
+}
+
+#[test]
+fn test_archive_member_paths() {
+    assert_eq!(
+        ArchiveFormat::from_path(b"vendor/bundle.ZIP"),
+        Some(ArchiveFormat::Zip)
+    );
+    assert_eq!(
+        ArchiveFormat::from_path(b"vendor/bundle.tar"),
+        Some(ArchiveFormat::Tar)
+    );
+    assert_eq!(
+        ArchiveFormat::from_path(b"vendor/bundle.tar.zst"),
+        Some(ArchiveFormat::Tar)
+    );
+    assert_eq!(
+        ArchiveFormat::from_path(b"vendor/bundle.tgz"),
+        Some(ArchiveFormat::Tar)
+    );
+    assert_eq!(ArchiveFormat::from_path(b"vendor/data.csv.gz"), None);
+
+    let path = member_path(b"vendor/bundle.zip", "daily/a.csv");
+    assert_eq!(path, b"vendor/bundle.zip!/daily/a.csv");
+    assert_eq!(
+        split_member_path(&path),
+        Some((&b"vendor/bundle.zip"[..], "daily/a.csv"))
+    );
+    // The separator may occur in the path before the archive
+    assert_eq!(
+        split_member_path(b"vendor!/bundle.tar!/a.csv"),
+        Some((&b"vendor!/bundle.tar"[..], "a.csv"))
+    );
+    assert_eq!(split_member_path(b"vendor/a.csv"), None);
+}
+
+#[test]
+fn test_read_archive_members() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let inputs_path = test_storage.path();
+    write_zip(
+        &inputs_path.join("bundle.zip"),
+        &[
+            ("a.csv", "key,value\n1,a"),
+            ("nested/b.csv", "key,value\n2,b"),
+        ],
+    )?;
+    write_tar_gz(
+        &inputs_path.join("bundle.tar.gz"),
+        &[("c.csv", "key,value\n3,c")],
+    )?;
+    std::fs::write(inputs_path.join("d.csv"), "key,value\n4,d")?;
+
+    let (reader, mut parser) = read_csv_kv(inputs_path, None, expand_archives)?;
+    let mut entries = full_cycle_read(reader, &mut parser, None).new_parsed_entries;
+    entries.sort_by_key(|entry| format!("{entry:?}"));
+    assert_eq!(
+        entries,
+        vec![
+            kv_event(true, "1", "a"),
+            kv_event(true, "2", "b"),
+            kv_event(true, "3", "c"),
+            kv_event(true, "4", "d"),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rewritten_archive_recovery() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+    let pstorage_root_path = test_storage_path.join("pstorage");
+    let inputs_path = test_storage_path.join("inputs");
+    std::fs::create_dir(&inputs_path)?;
+    let archive_path = inputs_path.join("bundle.zip");
+
+    write_zip(
+        &archive_path,
+        &[
+            ("a.csv", "key,value\n1,a"),
+            ("b.csv", "key,value\n2,b"),
+            ("c.csv", "key,value\n3,c"),
+        ],
+    )?;
+    {
+        let tracker = create_persistence_manager(&pstorage_root_path, true);
+        let (reader, mut parser) = read_csv_kv(&inputs_path, Some(1), expand_archives)?;
+        let data_stream = full_cycle_read(reader, &mut parser, Some(&tracker));
+        assert_eq!(
+            data_stream.new_parsed_entries,
+            vec![
+                kv_event(true, "1", "a"),
+                kv_event(true, "2", "b"),
+                kv_event(true, "3", "c"),
+            ]
+        );
+    }
+
+    // The archive is rewritten with the same members, so nothing is reread
+    write_zip(
+        &archive_path,
+        &[
+            ("a.csv", "key,value\n1,a"),
+            ("b.csv", "key,value\n2,b"),
+            ("c.csv", "key,value\n3,c"),
+        ],
+    )?;
+    {
+        let tracker = create_persistence_manager(&pstorage_root_path, false);
+        let (reader, mut parser) = read_csv_kv(&inputs_path, Some(1), expand_archives)?;
+        let data_stream = full_cycle_read(reader, &mut parser, Some(&tracker));
+        assert_eq!(data_stream.new_parsed_entries, vec![]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_modified_archive_recovery() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+    let pstorage_root_path = test_storage_path.join("pstorage");
+    let inputs_path = test_storage_path.join("inputs");
+    std::fs::create_dir(&inputs_path)?;
+    let archive_path = inputs_path.join("bundle.zip");
+
+    write_zip(
+        &archive_path,
+        &[
+            ("a.csv", "key,value\n1,a"),
+            ("b.csv", "key,value\n2,b"),
+            ("c.csv", "key,value\n3,c"),
+        ],
+    )?;
+    {
+        let tracker = create_persistence_manager(&pstorage_root_path, true);
+        let (reader, mut parser) = read_csv_kv(&inputs_path, Some(1), expand_archives)?;
+        let data_stream = full_cycle_read(reader, &mut parser, Some(&tracker));
+        assert_eq!(
+            data_stream.new_parsed_entries,
+            vec![
+                kv_event(true, "1", "a"),
+                kv_event(true, "2", "b"),
+                kv_event(true, "3", "c"),
+            ]
+        );
+    }
+
+    // Only the changed members produce updates
+    write_zip(
+        &archive_path,
+        &[
+            ("b.csv", "key,value\n2,bb"),
+            ("c.csv", "key,value\n3,c"),
+            ("d.csv", "key,value\n4,d"),
+        ],
+    )?;
+    {
+        let tracker = create_persistence_manager(&pstorage_root_path, false);
+        let (reader, mut parser) = read_csv_kv(&inputs_path, Some(1), expand_archives)?;
+        let data_stream = full_cycle_read(reader, &mut parser, Some(&tracker));
+        assert_eq!(
+            data_stream.new_parsed_entries,
+            vec![
+                kv_event(false, "2", "b"),
+                kv_event(true, "2", "bb"),
+                kv_event(true, "4", "d"),
+                kv_event(false, "1", "a"),
+            ]
+        );
+    }
+
+    // All members are removed together with the archive
+    std::fs::remove_file(&archive_path)?;
+    {
+        let tracker = create_persistence_manager(&pstorage_root_path, false);
+        let (reader, mut parser) = read_csv_kv(&inputs_path, Some(1), expand_archives)?;
+        let data_stream = full_cycle_read(reader, &mut parser, Some(&tracker));
+        assert_eq!(
+            data_stream.new_parsed_entries,
+            vec![
+                kv_event(false, "2", "bb"),
+                kv_event(false, "3", "c"),
+                kv_event(false, "4", "d"),
+            ]
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_archive_is_extracted_once() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let inputs_path = test_storage.path();
+    let archive_path = inputs_path.join("bundle.zip");
+    let members: Vec<_> = (0..10)
+        .map(|index| (format!("{index}.csv"), format!("key,value\n{index},x")))
+        .collect();
+    let members: Vec<_> = members
+        .iter()
+        .map(|(name, contents)| (name.as_str(), contents.as_str()))
+        .collect();
+    write_zip(&archive_path, &members)?;
+
+    // The members are looked up one by one, as after a restart, but the
+    // archive is read only for the first of them
+    let (mut scanner, objects_read) = counting_archive_scanner(inputs_path)?;
+    for (name, contents) in &members {
+        let path = member_path(archive_path.to_str().unwrap().as_bytes(), name);
+        let metadata = scanner
+            .object_metadata(&path)?
+            .expect("member must be found");
+        assert_eq!(metadata.size, contents.len() as u64);
+        assert_eq!(scanner.read_object(&path)?, contents.as_bytes());
+    }
+    assert_eq!(objects_read.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[test]
+fn test_changed_archive_members_are_not_kept() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let inputs_path = test_storage.path();
+    let archive_path = inputs_path.join("bundle.zip");
+    let member = |name| member_path(archive_path.to_str().unwrap().as_bytes(), name);
+    let cached_object_storage = CachedObjectStorage::new(Box::new(MemoryKVStorage::new()))?;
+    let (mut scanner, _) = counting_archive_scanner(inputs_path)?;
+
+    write_zip(&archive_path, &[("a.csv", "1"), ("b.csv", "2")])?;
+    let actions = scanner.next_scanner_actions(true, &cached_object_storage)?;
+    assert_eq!(actions.len(), 2);
+
+    // The members extracted from the previous version of the archive aren't
+    // returned, even if they haven't been read yet
+    write_zip(&archive_path, &[("b.csv", "22")])?;
+    let actions = scanner.next_scanner_actions(true, &cached_object_storage)?;
+    assert_eq!(actions.len(), 1);
+    assert!(scanner.read_object(&member("a.csv")).is_err());
+    assert_eq!(scanner.read_object(&member("b.csv"))?, b"22");
+
+    std::fs::remove_file(&archive_path)?;
+    scanner.next_scanner_actions(true, &cached_object_storage)?;
+    assert!(scanner.read_object(&member("b.csv")).is_err());
+    Ok(())
+}
@@ -6,9 +6,14 @@ use ndarray::{arr1, ArrayD};
 use ordered_float::OrderedFloat;
 use serde_json::json;
 
-use pathway_engine::connectors::data_format::{BsonFormatter, Formatter};
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{bson, doc, Bson as BsonValue};
+
+use pathway_engine::connectors::data_format::{parse_value_from_bson, BsonFormatter, Formatter};
+use pathway_engine::connectors::{OffsetKey, OffsetValue, StorageType};
 use pathway_engine::engine::time::DateTime;
 use pathway_engine::engine::{DateTimeNaive, DateTimeUtc, Duration, Key, Timestamp, Type, Value};
+use pathway_engine::persistence::frontier::OffsetAntichain;
 
 const TEST_FIELD: &str = "field";
 
@@ -220,3 +225,113 @@ fn test_unsupported_type() -> eyre::Result<()> {
 
     Ok(())
 }
+
+fn test_type_parsing(type_: Type, values: &[Value]) -> eyre::Result<()> {
+    let value_fields = vec![TEST_FIELD.to_string()];
+    let mut formatter = BsonFormatter::new(value_fields);
+
+    for value in values {
+        let context = formatter
+            .format(&Key::random(), &[value.clone()], Timestamp(0), 1)
+            .expect("formatter failed");
+        let document = context.payloads[0].clone().into_bson_document().unwrap();
+        let parsed = parse_value_from_bson(document.get(TEST_FIELD).unwrap(), &type_);
+        assert_eq!(parsed.as_ref(), Some(value));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_formatted_values() -> eyre::Result<()> {
+    test_type_parsing(Type::Bool, &[Value::Bool(true), Value::Bool(false)])?;
+    test_type_parsing(Type::Int, &[Value::Int(-1), Value::Int(0), Value::Int(1)])?;
+    test_type_parsing(
+        Type::Float,
+        &[
+            Value::Float(OrderedFloat(-1.0)),
+            Value::Float(OrderedFloat(1e50)),
+        ],
+    )?;
+    test_type_parsing(
+        Type::String,
+        &[Value::String("abc".into()), Value::String("".into())],
+    )?;
+    test_type_parsing(Type::Bytes, &[Value::Bytes([1, 10, 5].into())])?;
+    test_type_parsing(
+        Type::DateTimeNaive,
+        &[Value::DateTimeNaive(DateTimeNaive::from_timestamp(
+            10000, "s",
+        )?)],
+    )?;
+    test_type_parsing(
+        Type::DateTimeUtc,
+        &[Value::DateTimeUtc(DateTimeUtc::new(-10_000_000_000_000))],
+    )?;
+    test_type_parsing(
+        Type::Duration,
+        &[Value::Duration(Duration::new(10_000_000_000_000))],
+    )?;
+    test_type_parsing(
+        Type::Tuple(Arc::new([Type::Bool, Type::Int])),
+        &[Value::Tuple(vec![Value::Bool(true), Value::Int(2)].into())],
+    )?;
+    test_type_parsing(
+        Type::Optional(Type::Int.into()),
+        &[Value::None, Value::Int(5)],
+    )
+}
+
+#[test]
+fn test_parse_native_bson_values() {
+    let object_id = ObjectId::parse_str("65a1b2c3d4e5f6a7b8c9d0e1").unwrap();
+    assert_eq!(
+        parse_value_from_bson(&BsonValue::ObjectId(object_id), &Type::String),
+        Some(Value::from("65a1b2c3d4e5f6a7b8c9d0e1"))
+    );
+    assert_eq!(
+        parse_value_from_bson(&BsonValue::Int32(7), &Type::Float),
+        Some(Value::Float(OrderedFloat(7.0)))
+    );
+    assert_eq!(
+        parse_value_from_bson(&BsonValue::Int32(7), &Type::Any),
+        Some(Value::Int(7))
+    );
+    assert_eq!(
+        parse_value_from_bson(&bson!({"a": [1, "b"]}), &Type::Json),
+        Some(Value::from(json!({"a": [1, "b"]})))
+    );
+    assert_eq!(
+        parse_value_from_bson(&bson!([1, 2, 3]), &Type::List(Type::Int.into())),
+        Some(Value::Tuple(
+            vec![Value::Int(1), Value::Int(2), Value::Int(3)].into()
+        ))
+    );
+    assert_eq!(parse_value_from_bson(&BsonValue::Null, &Type::Int), None);
+    assert_eq!(
+        parse_value_from_bson(&BsonValue::String("1".into()), &Type::Int),
+        None
+    );
+}
+
+fn resume_token_frontier(data: &str) -> eyre::Result<OffsetAntichain> {
+    let token = mongodb::bson::to_vec(&doc! { "_data": data })?;
+    let mut frontier = OffsetAntichain::new();
+    frontier.advance_offset(
+        OffsetKey::Empty,
+        OffsetValue::MongoDbResumeToken(token.into()),
+    );
+    Ok(frontier)
+}
+
+#[test]
+fn test_merge_resume_token_frontiers() -> eyre::Result<()> {
+    let storage_type = StorageType::MongoDB;
+
+    let earlier = resume_token_frontier("8266F0A3C1000000012B0229296E04")?;
+    let later = resume_token_frontier("8266F0A3C5000000022B0229296E04")?;
+    assert_eq!(storage_type.merge_two_frontiers(&earlier, &later), later);
+    assert_eq!(storage_type.merge_two_frontiers(&later, &earlier), later);
+    assert_eq!(storage_type.merge_two_frontiers(&later, &later), later);
+    Ok(())
+}
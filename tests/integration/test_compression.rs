@@ -0,0 +1,278 @@
+// Copyright © 2024 Pathway
+
+use super::helpers::{
+    create_persistence_manager, full_cycle_read, kv_event, read_csv_kv, read_data_from_reader,
+};
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use tempfile::tempdir;
+
+use pathway_engine::connectors::compression::{decompressing_reader, Codec, Decompression};
+use pathway_engine::connectors::data_format::{
+    IdentityParser, InnerSchemaField, JsonLinesParser, KeyGenerationPolicy, ParsedEvent,
+};
+use pathway_engine::connectors::data_storage::{new_filesystem_reader, ConnectorMode, ReadMethod};
+use pathway_engine::connectors::posix_like::PosixLikeReader;
+use pathway_engine::connectors::SessionType;
+use pathway_engine::engine::{Type, Value};
+
+fn compress(codec: Codec, data: &[u8]) -> eyre::Result<Vec<u8>> {
+    let compressed = match codec {
+        Codec::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        Codec::Zstd => zstd::stream::encode_all(data, 0)?,
+        Codec::Bzip2 => {
+            let mut encoder =
+                bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        Codec::Xz => {
+            let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+    };
+    Ok(compressed)
+}
+
+fn decompress(
+    path: &str,
+    contents: Vec<u8>,
+    decompression: Decompression,
+) -> eyre::Result<Vec<u8>> {
+    let mut result = Vec::new();
+    decompressing_reader(path.as_bytes(), contents, decompression)?.read_to_end(&mut result)?;
+    Ok(result)
+}
+
+fn detect_compression(reader: PosixLikeReader) -> eyre::Result<PosixLikeReader> {
+    Ok(reader.with_decompression(Decompression::Auto))
+}
+
+#[test]
+fn test_codec_detection() -> eyre::Result<()> {
+    for codec in [Codec::Gzip, Codec::Zstd, Codec::Bzip2, Codec::Xz] {
+        let compressed = compress(codec, b"hello")?;
+        assert_eq!(Codec::from_magic_bytes(&compressed), Some(codec));
+        assert_eq!(Codec::detect(b"data.txt", &compressed), Some(codec));
+    }
+    assert_eq!(Codec::from_path(b"logs/data.jsonl.gz"), Some(Codec::Gzip));
+    assert_eq!(Codec::from_path(b"logs/data.csv.ZST"), Some(Codec::Zstd));
+    assert_eq!(Codec::from_path(b"logs/data.bz2"), Some(Codec::Bzip2));
+    assert_eq!(Codec::from_path(b"logs/data.xz"), Some(Codec::Xz));
+    assert_eq!(Codec::from_path(b"logs/data.csv"), None);
+    assert_eq!(Codec::from_path(b"logs/gz"), None);
+
+    // The magic bytes of bzip2 are checked together with the first block header
+    assert_eq!(Codec::from_magic_bytes(b"BZh9 isn't compressed"), None);
+    // A mislabeled object is read as it is
+    assert_eq!(Codec::detect(b"data.gz", b"plain text"), None);
+    assert_eq!(
+        decompress("data.gz", b"plain text".to_vec(), Decompression::Auto)?,
+        b"plain text"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_multiple_members_decompression() -> eyre::Result<()> {
+    let mut contents = compress(Codec::Gzip, b"first\n")?;
+    contents.extend(compress(Codec::Gzip, b"second\n")?);
+    assert_eq!(
+        decompress("data.gz", contents, Decompression::Auto)?,
+        b"first\nsecond\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decompression_modes() -> eyre::Result<()> {
+    let compressed = compress(Codec::Zstd, b"hello")?;
+
+    // The objects are read as they are, unless the decompression is enabled
+    assert_eq!(
+        decompress("data.zst", compressed.clone(), Decompression::None)?,
+        compressed
+    );
+    assert_eq!(
+        decompress("data.zst", compressed.clone(), Decompression::Auto)?,
+        b"hello"
+    );
+
+    // The given codec is used regardless of the extension
+    assert_eq!(
+        decompress(
+            "data.gz",
+            compressed.clone(),
+            Decompression::Codec(Codec::Zstd)
+        )?,
+        b"hello"
+    );
+    assert!(decompress("data.txt", compressed, Decompression::Codec(Codec::Gzip)).is_err());
+
+    assert_eq!(Codec::from_name("bzip2"), Some(Codec::Bzip2));
+    assert_eq!(Codec::from_name("bz2"), None);
+    Ok(())
+}
+
+#[test]
+fn test_read_without_decompression() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let input_path = test_storage.path().join("data.bin.gz");
+    let compressed = compress(Codec::Gzip, b"hello")?;
+    std::fs::write(&input_path, &compressed)?;
+
+    let reader = new_filesystem_reader(
+        input_path.to_str().unwrap(),
+        ConnectorMode::Static,
+        None,
+        ReadMethod::Full,
+        "*",
+    )?;
+    let parser = IdentityParser::new(
+        vec!["data".to_string()],
+        false,
+        KeyGenerationPolicy::PreferMessageKey,
+        SessionType::Native,
+    );
+    let events = read_data_from_reader(Box::new(reader), Box::new(parser))?;
+    assert_eq!(
+        events,
+        vec![ParsedEvent::Insert((
+            None,
+            vec![Value::Bytes(compressed.into())]
+        ))]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_compressed_jsonlines() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let inputs_path = test_storage.path();
+    let objects = [
+        ("1.jsonl.gz", Codec::Gzip),
+        ("2.jsonl.zst", Codec::Zstd),
+        ("3.jsonl.bz2", Codec::Bzip2),
+        ("4.jsonl.xz", Codec::Xz),
+        ("5.jsonl", Codec::Gzip),
+    ];
+    for (index, (name, codec)) in objects.iter().enumerate() {
+        let contents = format!("{{\"key\": {index}}}\n{{\"key\": {}}}\n", index + 10);
+        std::fs::write(
+            inputs_path.join(name),
+            compress(*codec, contents.as_bytes())?,
+        )?;
+    }
+
+    let reader = new_filesystem_reader(
+        inputs_path.to_str().unwrap(),
+        ConnectorMode::Static,
+        None,
+        ReadMethod::ByLine,
+        "*",
+    )?
+    .with_decompression(Decompression::Auto);
+    let schema = [("key".to_string(), InnerSchemaField::new(Type::Int, None))];
+    let parser = JsonLinesParser::new(
+        None,
+        vec!["key".to_string()],
+        HashMap::new(),
+        true,
+        schema.into(),
+        SessionType::Native,
+    )?;
+    let mut keys: Vec<i64> = read_data_from_reader(Box::new(reader), Box::new(parser))?
+        .into_iter()
+        .map(|event| match event {
+            ParsedEvent::Insert((_, values)) => match values.as_slice() {
+                [Value::Int(key)] => *key,
+                other => panic!("unexpected values: {other:?}"),
+            },
+            other => panic!("unexpected event: {other:?}"),
+        })
+        .collect();
+    keys.sort_unstable();
+    assert_eq!(keys, vec![0, 1, 2, 3, 4, 10, 11, 12, 13, 14]);
+    Ok(())
+}
+
+#[test]
+fn test_unchanged_compressed_csv_recovery() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let pstorage_root_path = test_storage_path.join("pstorage");
+    let input_path = test_storage_path.join("input.csv.zst");
+
+    std::fs::write(&input_path, compress(Codec::Zstd, b"key,value\n1,2\na,b")?)?;
+    {
+        let tracker = create_persistence_manager(&pstorage_root_path, true);
+        let (reader, mut parser) = read_csv_kv(&input_path, Some(1), detect_compression)?;
+        let data_stream = full_cycle_read(reader, &mut parser, Some(&tracker));
+        assert_eq!(
+            data_stream.new_parsed_entries,
+            vec![kv_event(true, "1", "2"), kv_event(true, "a", "b")]
+        );
+    }
+
+    // Nothing is reread if the object hasn't changed
+    {
+        let tracker = create_persistence_manager(&pstorage_root_path, false);
+        let (reader, mut parser) = read_csv_kv(&input_path, Some(1), detect_compression)?;
+        let data_stream = full_cycle_read(reader, &mut parser, Some(&tracker));
+        assert_eq!(data_stream.new_parsed_entries, vec![]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_compressed_csv_recovery() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+
+    let pstorage_root_path = test_storage_path.join("pstorage");
+    let input_path = test_storage_path.join("input.csv.zst");
+
+    std::fs::write(&input_path, compress(Codec::Zstd, b"key,value\n1,2\na,b")?)?;
+    {
+        let tracker = create_persistence_manager(&pstorage_root_path, true);
+        let (reader, mut parser) = read_csv_kv(&input_path, Some(1), detect_compression)?;
+        let data_stream = full_cycle_read(reader, &mut parser, Some(&tracker));
+        assert_eq!(
+            data_stream.new_parsed_entries,
+            vec![kv_event(true, "1", "2"), kv_event(true, "a", "b")]
+        );
+    }
+
+    std::fs::write(
+        &input_path,
+        compress(Codec::Zstd, b"key,value\n1,2\na,b\nc,d\n55,66")?,
+    )?;
+    {
+        let tracker = create_persistence_manager(&pstorage_root_path, false);
+        let (reader, mut parser) = read_csv_kv(&input_path, Some(1), detect_compression)?;
+        let data_stream = full_cycle_read(reader, &mut parser, Some(&tracker));
+        assert_eq!(
+            data_stream.new_parsed_entries,
+            vec![
+                kv_event(false, "1", "2"),
+                kv_event(false, "a", "b"),
+                kv_event(true, "1", "2"),
+                kv_event(true, "a", "b"),
+                kv_event(true, "c", "d"),
+                kv_event(true, "55", "66"),
+            ]
+        );
+    }
+
+    Ok(())
+}
@@ -4,19 +4,20 @@ use crate::helpers::{ErrorPlacement, ReplaceErrors};
 
 use super::helpers::{assert_error_shown_for_raw_data, read_data_from_reader};
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
 use assert_matches::assert_matches;
 
 use pathway_engine::connectors::data_format::{
-    DebeziumDBType, DebeziumMessageParser, ParseError, ParsedEvent, Parser,
+    DebeziumDBType, DebeziumMessageParser, InnerSchemaField, ParseError, ParsedEvent, Parser,
 };
 use pathway_engine::connectors::data_storage::{
     new_filesystem_reader, ConnectorMode, ReadMethod, ReaderContext,
 };
 use pathway_engine::connectors::SessionType;
-use pathway_engine::engine::Value;
+use pathway_engine::engine::{DateTimeNaive, DateTimeUtc, Duration, Type, Value};
 
 #[test]
 fn test_debezium_reads_ok() -> eyre::Result<()> {
@@ -32,6 +33,7 @@ fn test_debezium_reads_ok() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
 
     assert_matches!(parser.session_type(), SessionType::Native);
@@ -61,6 +63,7 @@ fn test_debezium_unparsable_json() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
 
     assert_error_shown_for_raw_data(
@@ -81,6 +84,7 @@ fn test_debezium_json_format_incorrect() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
     assert_error_shown_for_raw_data(incorrect_json_pair, Box::new(parser), "received message doesn't comply with debezium format: there is no payload at the top level of value json", ErrorPlacement::Message);
     Ok(())
@@ -94,6 +98,7 @@ fn test_debezium_json_no_operation_specified() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
     assert_error_shown_for_raw_data(incorrect_json_pair, Box::new(parser), "received message doesn't comply with debezium format: incorrect type of payload.op field or it is missing", ErrorPlacement::Message);
     Ok(())
@@ -107,6 +112,7 @@ fn test_debezium_json_unsupported_operation() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
     assert_error_shown_for_raw_data(
         incorrect_json_pair,
@@ -125,6 +131,7 @@ fn test_debezium_json_incomplete_data() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
     assert_error_shown_for_raw_data(
         incorrect_json_pair,
@@ -143,6 +150,7 @@ fn test_debezium_tokens_amt_mismatch() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
     assert_error_shown_for_raw_data(
         incorrect_json_pair,
@@ -157,6 +165,7 @@ fn test_debezium_tokens_amt_mismatch() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
     assert_error_shown_for_raw_data(
         incorrect_json_pair,
@@ -182,6 +191,7 @@ fn test_debezium_mongodb_format() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::MongoDB,
+        HashMap::new(),
     );
 
     assert_matches!(parser.session_type(), SessionType::Upsert);
@@ -227,6 +237,7 @@ fn test_postgres_with_empty_pkey() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
 
     let reader = BufReader::new(File::open("tests/data/sample_debezium.txt")?);
@@ -251,3 +262,177 @@ fn test_postgres_with_empty_pkey() -> eyre::Result<()> {
 
     Ok(())
 }
+
+const LOGICAL_TYPES_KEY: &str = r#"{
+    "schema": {"type": "struct", "fields": [{"type": "int32", "optional": false, "field": "id"}]},
+    "payload": {"id": 1}
+}"#;
+
+fn logical_types_message(op: &str, before: &str, after: &str) -> String {
+    let row_schema = r#"[
+        {"type": "int32", "optional": false, "field": "id"},
+        {"type": "int32", "optional": true, "name": "io.debezium.time.Date", "field": "birthday"},
+        {"type": "int64", "optional": true, "name": "io.debezium.time.MicroTimestamp", "field": "created_at"},
+        {"type": "string", "optional": true, "name": "io.debezium.time.ZonedTimestamp", "field": "updated_at"},
+        {"type": "int64", "optional": true, "name": "io.debezium.time.MicroTime", "field": "wake_up"},
+        {
+            "type": "bytes",
+            "optional": true,
+            "name": "org.apache.kafka.connect.data.Decimal",
+            "parameters": {"scale": "2", "connect.decimal.precision": "10"},
+            "field": "balance"
+        },
+        {"type": "string", "optional": true, "name": "io.debezium.data.Json", "field": "tags"}
+    ]"#;
+    format!(
+        r#"{{
+            "schema": {{
+                "type": "struct",
+                "fields": [
+                    {{"type": "struct", "optional": true, "field": "before", "fields": {row_schema}}},
+                    {{"type": "struct", "optional": true, "field": "after", "fields": {row_schema}}},
+                    {{"type": "string", "optional": false, "field": "op"}}
+                ]
+            }},
+            "payload": {{"before": {before}, "after": {after}, "op": "{op}"}}
+        }}"#
+    )
+}
+
+fn logical_types_parser(db_type: DebeziumDBType, balance_type: Type) -> DebeziumMessageParser {
+    let schema = [
+        ("id", Type::Int),
+        ("birthday", Type::DateTimeNaive),
+        ("created_at", Type::DateTimeNaive),
+        ("updated_at", Type::DateTimeUtc),
+        ("wake_up", Type::Optional(Type::Duration.into())),
+        ("balance", balance_type),
+        ("tags", Type::Json),
+    ]
+    .into_iter()
+    .map(|(name, type_)| (name.to_string(), InnerSchemaField::new(type_, None)))
+    .collect();
+    DebeziumMessageParser::new(
+        Some(vec!["id".to_string()]),
+        vec![
+            "birthday".to_string(),
+            "created_at".to_string(),
+            "updated_at".to_string(),
+            "wake_up".to_string(),
+            "balance".to_string(),
+            "tags".to_string(),
+        ],
+        "        ".to_string(),
+        db_type,
+        schema,
+    )
+}
+
+fn parse_key_value(
+    parser: &mut DebeziumMessageParser,
+    value: &str,
+) -> eyre::Result<Vec<ParsedEvent>> {
+    let context = ReaderContext::KeyValue((
+        Some(LOGICAL_TYPES_KEY.as_bytes().to_vec()),
+        Some(value.as_bytes().to_vec()),
+    ));
+    Ok(parser
+        .parse(&context)
+        .map_err(ParseError::from)?
+        .into_iter()
+        .map(|entry| entry.replace_errors())
+        .collect())
+}
+
+#[test]
+fn test_debezium_mysql_logical_types() -> eyre::Result<()> {
+    let mut parser = logical_types_parser(DebeziumDBType::MySQL, Type::Float);
+    assert_matches!(parser.session_type(), SessionType::Native);
+
+    let after = r#"{
+        "id": 1,
+        "birthday": 19724,
+        "created_at": 1704164645123456,
+        "updated_at": "2024-01-02T03:04:05.5Z",
+        "wake_up": 27000000000,
+        "balance": "MDk=",
+        "tags": "{\"a\": [1, 2]}"
+    }"#;
+    let changelog = parse_key_value(&mut parser, &logical_types_message("c", "null", after))?;
+
+    let expected_values = vec![
+        Value::from(DateTimeNaive::from_timestamp(1_704_153_600, "s")?),
+        Value::from(DateTimeNaive::from_timestamp(1_704_164_645_123_456, "us")?),
+        Value::from(DateTimeUtc::from_timestamp(1_704_164_645_500, "ms")?),
+        Value::from(Duration::new_with_unit(27_000, "s")?),
+        Value::from(123.45),
+        Value::from(serde_json::json!({"a": [1, 2]})),
+    ];
+    assert_eq!(
+        changelog,
+        vec![ParsedEvent::Insert((
+            Some(vec![Value::Int(1)]),
+            expected_values
+        ))]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_debezium_sql_server_update_with_logical_types() -> eyre::Result<()> {
+    let mut parser = logical_types_parser(DebeziumDBType::SqlServer, Type::String);
+    assert_matches!(parser.session_type(), SessionType::Native);
+
+    let before = r#"{
+        "id": 1,
+        "birthday": 0,
+        "created_at": 0,
+        "updated_at": "1970-01-01T02:00:00+02:00",
+        "wake_up": null,
+        "balance": "z8c=",
+        "tags": "[]"
+    }"#;
+    let after = r#"{
+        "id": 1,
+        "birthday": 0,
+        "created_at": 0,
+        "updated_at": "1970-01-01T00:00:00Z",
+        "wake_up": 1,
+        "balance": "AQ==",
+        "tags": "null"
+    }"#;
+    let changelog = parse_key_value(&mut parser, &logical_types_message("u", before, after))?;
+
+    let epoch_naive = Value::from(DateTimeNaive::from_timestamp(0, "s")?);
+    let epoch_utc = Value::from(DateTimeUtc::from_timestamp(0, "s")?);
+    assert_eq!(
+        changelog,
+        vec![
+            ParsedEvent::Delete((
+                Some(vec![Value::Int(1)]),
+                vec![
+                    epoch_naive.clone(),
+                    epoch_naive.clone(),
+                    epoch_utc.clone(),
+                    Value::None,
+                    Value::from("-123.45"),
+                    Value::from(serde_json::json!([])),
+                ]
+            )),
+            ParsedEvent::Insert((
+                Some(vec![Value::Int(1)]),
+                vec![
+                    epoch_naive.clone(),
+                    epoch_naive,
+                    epoch_utc,
+                    Value::from(Duration::new_with_unit(1, "us")?),
+                    Value::from("0.01"),
+                    Value::from(serde_json::json!(null)),
+                ]
+            )),
+        ]
+    );
+
+    Ok(())
+}
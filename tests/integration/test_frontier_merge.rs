@@ -0,0 +1,35 @@
+// Copyright © 2024 Pathway
+
+use super::helpers::FRONTIER_MERGE_CASES;
+
+/// The merge of two frontiers keeps the greatest offset of each key and
+/// doesn't depend on the order of the arguments.
+#[test]
+fn test_merge_reader_frontiers() {
+    for case in FRONTIER_MERGE_CASES {
+        let (lhs, rhs, expected) = if case.has_multiple_keys {
+            (
+                case.frontier(&[(0, 10), (1, 3)]),
+                case.frontier(&[(0, 7), (1, 12), (2, 1)]),
+                case.frontier(&[(0, 10), (1, 12), (2, 1)]),
+            )
+        } else {
+            (
+                case.frontier(&[(0, 3)]),
+                case.frontier(&[(0, 5)]),
+                case.frontier(&[(0, 5)]),
+            )
+        };
+        let storage_type = case.storage_type;
+        assert_eq!(
+            storage_type.merge_two_frontiers(&lhs, &rhs),
+            expected,
+            "{storage_type:?}"
+        );
+        assert_eq!(
+            storage_type.merge_two_frontiers(&rhs, &lhs),
+            expected,
+            "{storage_type:?}"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright © 2024 Pathway
+
+use std::collections::BTreeMap;
+
+use prost_types::value::Kind;
+use prost_types::{ListValue, Struct, Value as ProtobufValue};
+use tokio::sync::{mpsc, oneshot};
+
+use pathway_engine::async_runtime::create_async_tokio_runtime;
+use pathway_engine::connectors::data_storage::{
+    DataEventType, GrpcReader, ReadResult, Reader, ReaderContext,
+};
+use pathway_engine::connectors::grpc::{
+    protobuf_struct_to_json, row_payload, IngestedBatch, Row, RowPayload, GRPC_READER_QUEUE_SIZE,
+};
+use pathway_engine::connectors::metadata::{GrpcMetadata, SourceMetadata};
+use pathway_engine::connectors::{OffsetKey, OffsetValue};
+
+fn protobuf_value(kind: Kind) -> ProtobufValue {
+    ProtobufValue { kind: Some(kind) }
+}
+
+fn batch(batch_id: u64, rows: &[&str]) -> (IngestedBatch, oneshot::Receiver<()>) {
+    let (accepted, accepted_receiver) = oneshot::channel();
+    let batch = IngestedBatch {
+        peer_address: None,
+        batch_id,
+        rows: rows.iter().map(|row| row.as_bytes().to_vec()).collect(),
+        accepted,
+    };
+    (batch, accepted_receiver)
+}
+
+fn assert_data(read_result: ReadResult, expected_row: &str, expected_batches_count: usize) {
+    let ReadResult::Data(ReaderContext::RawBytes(DataEventType::Insert, row), offset) = read_result
+    else {
+        panic!("unexpected read result: {read_result:?}");
+    };
+    assert_eq!(row, expected_row.as_bytes());
+    assert_eq!(
+        offset,
+        (
+            OffsetKey::Grpc,
+            OffsetValue::GrpcReadBatchesCount(expected_batches_count)
+        )
+    );
+}
+
+#[test]
+fn test_protobuf_struct_to_json() {
+    let fields = BTreeMap::from([
+        (
+            "user".to_string(),
+            protobuf_value(Kind::StringValue("alice".to_string())),
+        ),
+        ("count".to_string(), protobuf_value(Kind::NumberValue(3.0))),
+        (
+            "amount".to_string(),
+            protobuf_value(Kind::NumberValue(10.5)),
+        ),
+        ("active".to_string(), protobuf_value(Kind::BoolValue(true))),
+        ("comment".to_string(), protobuf_value(Kind::NullValue(0))),
+        (
+            "tags".to_string(),
+            protobuf_value(Kind::ListValue(ListValue {
+                values: vec![
+                    protobuf_value(Kind::StringValue("new".to_string())),
+                    protobuf_value(Kind::NumberValue(-1.0)),
+                ],
+            })),
+        ),
+        (
+            "address".to_string(),
+            protobuf_value(Kind::StructValue(Struct {
+                fields: BTreeMap::from([(
+                    "city".to_string(),
+                    protobuf_value(Kind::StringValue("Paris".to_string())),
+                )]),
+            })),
+        ),
+    ]);
+    assert_eq!(
+        protobuf_struct_to_json(Struct { fields }),
+        serde_json::json!({
+            "user": "alice",
+            "count": 3,
+            "amount": 10.5,
+            "active": true,
+            "comment": null,
+            "tags": ["new", -1],
+            "address": {"city": "Paris"},
+        })
+    );
+}
+
+#[test]
+fn test_row_payload() {
+    let json_row = Row {
+        payload: Some(RowPayload::Json(br#"{"a": 1}"#.to_vec())),
+    };
+    assert_eq!(row_payload(json_row).unwrap(), br#"{"a": 1}"#);
+
+    let raw_row = Row {
+        payload: Some(RowPayload::Raw(vec![0, 255])),
+    };
+    assert_eq!(row_payload(raw_row).unwrap(), vec![0, 255]);
+
+    let fields_row = Row {
+        payload: Some(RowPayload::Fields(Struct {
+            fields: BTreeMap::from([("a".to_string(), protobuf_value(Kind::NumberValue(1.0)))]),
+        })),
+    };
+    assert_eq!(row_payload(fields_row).unwrap(), br#"{"a":1}"#);
+
+    let empty_row = Row { payload: None };
+    assert_eq!(
+        row_payload(empty_row).unwrap_err().code(),
+        tonic::Code::InvalidArgument
+    );
+}
+
+#[test]
+fn test_grpc_metadata_serialization() {
+    let metadata: SourceMetadata = GrpcMetadata::new(Some("127.0.0.1:53412".to_string()), 7).into();
+    assert_eq!(
+        metadata.serialize(),
+        serde_json::json!({
+            "peer_address": "127.0.0.1:53412",
+            "batch_id": 7,
+        })
+    );
+    assert!(!metadata.commits_allowed_in_between());
+}
+
+#[test]
+fn test_grpc_reader_acknowledges_accepted_batches() -> eyre::Result<()> {
+    let (batch_sender, batch_receiver) = mpsc::channel(GRPC_READER_QUEUE_SIZE);
+    let mut reader = GrpcReader::new(create_async_tokio_runtime()?, None, batch_receiver, None);
+
+    let (first_batch, mut first_accepted) = batch(1, &[r#"{"a": 1}"#, r#"{"a": 2}"#]);
+    batch_sender.try_send(first_batch)?;
+    assert!(matches!(reader.read()?, ReadResult::NewSource(_)));
+    assert_data(reader.read()?, r#"{"a": 1}"#, 1);
+    assert_data(reader.read()?, r#"{"a": 2}"#, 1);
+    assert!(matches!(
+        reader.read()?,
+        ReadResult::FinishedSource {
+            commit_allowed: true
+        }
+    ));
+    // The time advancement closing the batch hasn't been passed to the engine yet
+    assert!(first_accepted.try_recv().is_err());
+
+    let (empty_batch, mut empty_accepted) = batch(2, &[]);
+    let (second_batch, mut second_accepted) = batch(3, &[r#"{"a": 3}"#]);
+    batch_sender.try_send(empty_batch)?;
+    batch_sender.try_send(second_batch)?;
+    assert!(matches!(reader.read()?, ReadResult::NewSource(_)));
+    assert!(first_accepted.try_recv().is_ok());
+    assert!(empty_accepted.try_recv().is_ok());
+    assert!(second_accepted.try_recv().is_err());
+    assert_data(reader.read()?, r#"{"a": 3}"#, 2);
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+// Copyright © 2024 Pathway
+
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tempfile::tempdir;
+
+use pathway_engine::connectors::scanner::{FilesystemScanner, PosixLikeScanner, QueuedAction};
+use pathway_engine::persistence::backends::MemoryKVStorage;
+use pathway_engine::persistence::cached_object_storage::CachedObjectStorage;
+
+fn create_scanner(path: &Path, object_pattern: &str) -> eyre::Result<FilesystemScanner> {
+    let mut scanner = FilesystemScanner::new(path.to_str().unwrap(), object_pattern)?;
+    scanner.enable_inotify()?;
+    Ok(scanner)
+}
+
+/// Returns the next actions of the scanner and applies them to the storage in
+/// the same way as the reader does. The actions are returned as the action
+/// kinds and the paths relative to `root`, sorted.
+fn next_actions(
+    scanner: &mut FilesystemScanner,
+    storage: &mut CachedObjectStorage,
+    root: &Path,
+) -> eyre::Result<Vec<(&'static str, String)>> {
+    let mut result = Vec::new();
+    for action in scanner.next_scanner_actions(true, storage)? {
+        let kind = match &action {
+            QueuedAction::Read(path, metadata) => {
+                storage.place_object(path, Vec::new(), metadata.clone())?;
+                "read"
+            }
+            QueuedAction::Update(path, metadata) => {
+                storage.place_object(path, Vec::new(), metadata.clone())?;
+                "update"
+            }
+            QueuedAction::Delete(path) => {
+                storage.remove_object(path)?;
+                "delete"
+            }
+        };
+        let path = Path::new(std::ffi::OsStr::from_bytes(action.path()));
+        let relative_path = path.strip_prefix(root)?.to_string_lossy().to_string();
+        result.push((kind, relative_path));
+    }
+    result.sort();
+    Ok(result)
+}
+
+fn actions(expected: &[(&'static str, &str)]) -> Vec<(&'static str, String)> {
+    expected
+        .iter()
+        .map(|(kind, path)| (*kind, (*path).to_string()))
+        .collect()
+}
+
+#[test]
+fn test_inotify_file_changes() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let root = test_storage.path();
+    std::fs::write(root.join("a.csv"), "a")?;
+
+    let mut storage = CachedObjectStorage::new(Box::new(MemoryKVStorage::new()))?;
+    let mut scanner = create_scanner(root, "*")?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[("read", "a.csv")])
+    );
+    assert_eq!(next_actions(&mut scanner, &mut storage, root)?, vec![]);
+
+    std::fs::write(root.join("a.csv"), "aa")?;
+    std::fs::write(root.join("b.csv"), "b")?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[("read", "b.csv"), ("update", "a.csv")])
+    );
+
+    std::fs::rename(root.join("b.csv"), root.join("c.csv"))?;
+    std::fs::remove_file(root.join("a.csv"))?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[("delete", "a.csv"), ("delete", "b.csv"), ("read", "c.csv")])
+    );
+    assert_eq!(next_actions(&mut scanner, &mut storage, root)?, vec![]);
+
+    Ok(())
+}
+
+#[test]
+fn test_inotify_subdirectories() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let root = test_storage.path();
+
+    let mut storage = CachedObjectStorage::new(Box::new(MemoryKVStorage::new()))?;
+    let mut scanner = create_scanner(root, "*.csv")?;
+    assert_eq!(next_actions(&mut scanner, &mut storage, root)?, vec![]);
+
+    // The files of a new directory are found, even if they are created before
+    // the directory is watched
+    std::fs::create_dir_all(root.join("sub/nested"))?;
+    std::fs::write(root.join("sub/a.csv"), "a")?;
+    std::fs::write(root.join("sub/nested/b.csv"), "b")?;
+    std::fs::write(root.join("sub/c.txt"), "c")?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[("read", "sub/a.csv"), ("read", "sub/nested/b.csv")])
+    );
+
+    std::fs::write(root.join("sub/nested/d.csv"), "d")?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[("read", "sub/nested/d.csv")])
+    );
+
+    std::fs::rename(root.join("sub"), root.join("moved"))?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[
+            ("delete", "sub/a.csv"),
+            ("delete", "sub/nested/b.csv"),
+            ("delete", "sub/nested/d.csv"),
+            ("read", "moved/a.csv"),
+            ("read", "moved/nested/b.csv"),
+            ("read", "moved/nested/d.csv"),
+        ])
+    );
+
+    std::fs::remove_dir_all(root.join("moved"))?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[
+            ("delete", "moved/a.csv"),
+            ("delete", "moved/nested/b.csv"),
+            ("delete", "moved/nested/d.csv"),
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_inotify_single_file_and_late_directory() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let root = test_storage.path();
+
+    // Only the given file is read, not its neighbours
+    let mut storage = CachedObjectStorage::new(Box::new(MemoryKVStorage::new()))?;
+    let mut scanner = create_scanner(&root.join("a.csv"), "*")?;
+    assert_eq!(next_actions(&mut scanner, &mut storage, root)?, vec![]);
+    std::fs::write(root.join("a.csv"), "a")?;
+    std::fs::write(root.join("b.csv"), "b")?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[("read", "a.csv")])
+    );
+
+    // The directory to be read doesn't exist when the reading starts
+    let mut storage = CachedObjectStorage::new(Box::new(MemoryKVStorage::new()))?;
+    let mut scanner = create_scanner(&root.join("later"), "*")?;
+    assert_eq!(next_actions(&mut scanner, &mut storage, root)?, vec![]);
+    std::fs::create_dir(root.join("later"))?;
+    std::fs::write(root.join("later/c.csv"), "c")?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[("read", "later/c.csv")])
+    );
+    std::fs::write(root.join("later/d.csv"), "d")?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[("read", "later/d.csv")])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_inotify_wait_for_changes() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let root = test_storage.path().to_path_buf();
+
+    let mut storage = CachedObjectStorage::new(Box::new(MemoryKVStorage::new()))?;
+    let mut scanner = create_scanner(&root, "*")?;
+    assert_eq!(next_actions(&mut scanner, &mut storage, &root)?, vec![]);
+
+    let started_at = Instant::now();
+    scanner.wait_for_changes(Duration::from_millis(100));
+    assert!(started_at.elapsed() >= Duration::from_millis(100));
+
+    let writer = {
+        let root = root.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            std::fs::write(root.join("a.csv"), "a").unwrap();
+        })
+    };
+    let started_at = Instant::now();
+    scanner.wait_for_changes(Duration::from_secs(60));
+    assert!(started_at.elapsed() < Duration::from_secs(30));
+    writer.join().unwrap();
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, &root)?,
+        actions(&[("read", "a.csv")])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_inotify_queue_overflow() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let root = test_storage.path();
+
+    let mut storage = CachedObjectStorage::new(Box::new(MemoryKVStorage::new()))?;
+    let mut scanner = create_scanner(root, "*")?;
+    assert_eq!(next_actions(&mut scanner, &mut storage, root)?, vec![]);
+
+    // Each file produces several events, so the queue overflows and the
+    // directory is rescanned
+    let max_queued_events: usize =
+        std::fs::read_to_string("/proc/sys/fs/inotify/max_queued_events")?
+            .trim()
+            .parse()?;
+    for index in 0..=max_queued_events {
+        std::fs::write(root.join(format!("{index}.csv")), "a")?;
+    }
+    let new_actions = next_actions(&mut scanner, &mut storage, root)?;
+    assert_eq!(new_actions.len(), max_queued_events + 1);
+    assert!(new_actions.iter().all(|(kind, _)| *kind == "read"));
+
+    // Watching is restored after the rescan
+    std::fs::write(root.join("last.csv"), "a")?;
+    assert_eq!(
+        next_actions(&mut scanner, &mut storage, root)?,
+        actions(&[("read", "last.csv")])
+    );
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+// Copyright © 2024 Pathway
+
+use crate::helpers::ReplaceErrors;
+
+use std::collections::HashMap;
+
+use pathway_engine::connectors::data_format::{
+    InnerSchemaField, JsonLinesParser, MessageMetadataField, ParseError, ParsedEvent, Parser,
+};
+use pathway_engine::connectors::data_storage::ReaderContext;
+use pathway_engine::connectors::metadata::{KafkaMetadata, SourceMetadata};
+use pathway_engine::connectors::SessionType;
+use pathway_engine::engine::{DateTimeUtc, Type, Value};
+
+fn kafka_metadata(
+    timestamp_millis: Option<i64>,
+    headers: &[(&str, Option<&[u8]>)],
+) -> SourceMetadata {
+    let headers = headers
+        .iter()
+        .map(|(key, value)| ((*key).to_string(), value.map(<[u8]>::to_vec)))
+        .collect();
+    KafkaMetadata::new(timestamp_millis, "events".to_string(), 0, 42, headers).into()
+}
+
+fn headers_parser() -> eyre::Result<JsonLinesParser> {
+    let schema = [
+        ("a".to_string(), InnerSchemaField::new(Type::Int, None)),
+        (
+            "trace_id".to_string(),
+            InnerSchemaField::new(Type::String, Some(Value::from("unknown"))),
+        ),
+        (
+            "attempt".to_string(),
+            InnerSchemaField::new(Type::Optional(Type::Int.into()), None),
+        ),
+        ("raw".to_string(), InnerSchemaField::new(Type::Bytes, None)),
+        (
+            "time".to_string(),
+            InnerSchemaField::new(Type::DateTimeUtc, None),
+        ),
+    ];
+    let parser = JsonLinesParser::new(
+        None,
+        vec![
+            "a".to_string(),
+            "trace_id".to_string(),
+            "attempt".to_string(),
+            "raw".to_string(),
+            "time".to_string(),
+        ],
+        HashMap::new(),
+        true,
+        schema.into(),
+        SessionType::Native,
+    )?
+    .with_message_metadata_fields(vec![
+        (
+            "trace_id".to_string(),
+            MessageMetadataField::KafkaHeader("trace-id".to_string()),
+        ),
+        (
+            "attempt".to_string(),
+            MessageMetadataField::KafkaHeader("attempt".to_string()),
+        ),
+        (
+            "raw".to_string(),
+            MessageMetadataField::KafkaHeader("raw".to_string()),
+        ),
+        ("time".to_string(), MessageMetadataField::KafkaTimestamp),
+    ])?;
+    Ok(parser)
+}
+
+fn parse_message(
+    parser: &mut dyn Parser,
+    metadata: &SourceMetadata,
+    payload: &str,
+) -> eyre::Result<Vec<ParsedEvent>> {
+    parser.on_new_source_started(metadata);
+    let context = ReaderContext::KeyValue((None, Some(payload.as_bytes().to_vec())));
+    let parsed = parser.parse(&context).map_err(ParseError::from)?;
+    Ok(parsed
+        .into_iter()
+        .map(|event| event.replace_errors())
+        .collect())
+}
+
+#[test]
+fn test_headers_and_timestamp_as_fields() -> eyre::Result<()> {
+    let mut parser = headers_parser()?;
+    let metadata = kafka_metadata(
+        Some(1_704_164_645_123),
+        &[
+            ("trace-id", Some(b"first")),
+            ("trace-id", Some(b"abc-123")),
+            ("attempt", Some(b"3")),
+            ("raw", Some(&[0, 255])),
+        ],
+    );
+
+    let events = parse_message(&mut parser, &metadata, r#"{"a": 1}"#)?;
+    assert_eq!(
+        events,
+        vec![ParsedEvent::Insert((
+            None,
+            vec![
+                Value::Int(1),
+                Value::from("abc-123"),
+                Value::Int(3),
+                Value::Bytes(vec![0, 255].into()),
+                Value::from(DateTimeUtc::from_timestamp(1_704_164_645_123, "ms")?),
+            ]
+        ))]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_absent_headers_use_defaults() -> eyre::Result<()> {
+    let mut parser = headers_parser()?;
+    let metadata = kafka_metadata(
+        Some(1_704_164_645_000),
+        &[("raw", Some(b"x")), ("attempt", None)],
+    );
+
+    let events = parse_message(&mut parser, &metadata, r#"{"a": 2, "trace_id": "ignored"}"#)?;
+    assert_eq!(
+        events,
+        vec![ParsedEvent::Insert((
+            None,
+            vec![
+                Value::Int(2),
+                Value::from("unknown"),
+                Value::None,
+                Value::Bytes(b"x".to_vec().into()),
+                Value::from(DateTimeUtc::from_timestamp(1_704_164_645_000, "ms")?),
+            ]
+        ))]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_headers_with_incorrect_values() -> eyre::Result<()> {
+    let mut parser = headers_parser()?;
+    let metadata = kafka_metadata(None, &[("attempt", Some(b"third"))]);
+
+    let events = parse_message(&mut parser, &metadata, r#"{"a": 3}"#)?;
+    assert_eq!(
+        events,
+        vec![ParsedEvent::Insert((
+            None,
+            vec![
+                Value::Int(3),
+                Value::from("unknown"),
+                Value::Error,
+                Value::Error,
+                Value::Error,
+            ]
+        ))]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_message_metadata_field_not_in_schema() -> eyre::Result<()> {
+    let schema = [("a".to_string(), InnerSchemaField::new(Type::Int, None))];
+    let parser = JsonLinesParser::new(
+        None,
+        vec!["a".to_string()],
+        HashMap::new(),
+        true,
+        schema.into(),
+        SessionType::Native,
+    )?
+    .with_message_metadata_fields(vec![(
+        "b".to_string(),
+        MessageMetadataField::KafkaTimestamp,
+    )]);
+    assert!(parser.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+// Copyright © 2024 Pathway
+
+use std::collections::HashMap;
+
+use arcstr::ArcStr;
+
+use pathway_engine::connectors::data_storage::{KafkaBoundedRead, KafkaStopOffsets};
+
+fn partition_map<T: Copy>(entries: &[(&str, i32, T)]) -> HashMap<(String, i32), T> {
+    entries
+        .iter()
+        .map(|(topic, partition, value)| (((*topic).to_string(), *partition), *value))
+        .collect()
+}
+
+fn topic_partition(topic: &str, partition: i32) -> (ArcStr, i32) {
+    (ArcStr::from(topic), partition)
+}
+
+fn stop_offsets(high_watermarks: &[(&str, i32, i64)]) -> KafkaStopOffsets {
+    let watermarks = partition_map(
+        &high_watermarks
+            .iter()
+            .map(|(topic, partition, high)| (*topic, *partition, (0, *high)))
+            .collect::<Vec<_>>(),
+    );
+    KafkaBoundedRead::new(&watermarks, &HashMap::new(), partition_map(high_watermarks)).stop_offsets
+}
+
+#[test]
+fn test_bounded_read_up_to_high_watermarks() {
+    let watermarks = partition_map(&[("a", 0, (0, 10)), ("a", 1, (5, 5)), ("b", 0, (3, 7))]);
+    let end_offsets = partition_map(&[("a", 0, 10), ("a", 1, 5), ("b", 0, 7)]);
+    let bounded_read = KafkaBoundedRead::new(&watermarks, &HashMap::new(), end_offsets);
+
+    // The empty partition isn't assigned and the others are read from the committed offsets
+    assert_eq!(
+        bounded_read.assignment,
+        vec![("a".to_string(), 0, None), ("b".to_string(), 0, None)]
+    );
+    assert!(!bounded_read.stop_offsets.is_finished());
+    assert!(!bounded_read
+        .stop_offsets
+        .is_partition_finished(&topic_partition("a", 0)));
+    assert!(bounded_read
+        .stop_offsets
+        .is_partition_finished(&topic_partition("a", 1)));
+}
+
+#[test]
+fn test_bounded_read_from_timestamp_until_timestamp() {
+    let watermarks = partition_map(&[("a", 0, (0, 10)), ("a", 1, (0, 10)), ("a", 2, (0, 10))]);
+    // Partition 1 has no messages between the timestamps, partition 2 has no messages
+    // after the start timestamp
+    let start_offsets = partition_map(&[("a", 0, 4), ("a", 1, 6), ("a", 2, 10)]);
+    let end_offsets = partition_map(&[("a", 0, 8), ("a", 1, 6), ("a", 2, 10)]);
+    let bounded_read = KafkaBoundedRead::new(&watermarks, &start_offsets, end_offsets);
+    assert_eq!(bounded_read.assignment, vec![("a".to_string(), 0, Some(4))]);
+
+    let mut stop_offsets = bounded_read.stop_offsets;
+    let partition = topic_partition("a", 0);
+    assert!(stop_offsets.accept_message(&partition, 4));
+    assert!(stop_offsets.accept_message(&partition, 6));
+    assert!(!stop_offsets.is_finished());
+    assert!(stop_offsets.accept_message(&partition, 7));
+    assert!(stop_offsets.is_finished());
+    assert!(!stop_offsets.accept_message(&partition, 8));
+}
+
+#[test]
+fn test_bounded_read_end_before_low_watermark() {
+    // The messages before the end have already been removed by the retention
+    let watermarks = partition_map(&[("a", 0, (20, 30))]);
+    let end_offsets = partition_map(&[("a", 0, 15)]);
+    let bounded_read = KafkaBoundedRead::new(&watermarks, &HashMap::new(), end_offsets);
+    assert_eq!(bounded_read.assignment, vec![]);
+    assert!(bounded_read.stop_offsets.is_finished());
+}
+
+#[test]
+fn test_stop_offsets_message_at_or_after_stop() {
+    // A gap in the offsets, for example because of the transaction markers or
+    // the compaction, finishes the partition on the first message after the end
+    let mut stop_offsets = stop_offsets(&[("a", 0, 10), ("b", 0, 3)]);
+    let partition = topic_partition("a", 0);
+    assert!(stop_offsets.accept_message(&partition, 5));
+    assert!(!stop_offsets.accept_message(&partition, 12));
+    assert!(stop_offsets.is_partition_finished(&partition));
+    assert!(!stop_offsets.is_finished());
+
+    // The messages of the finished partition are skipped
+    assert!(!stop_offsets.accept_message(&partition, 6));
+
+    let partition = topic_partition("b", 0);
+    assert!(stop_offsets.accept_message(&partition, 2));
+    assert!(stop_offsets.is_finished());
+}
+
+#[test]
+fn test_stop_offsets_consumer_position() {
+    // The partition ends with transaction markers, so there is no message to
+    // finish it, but the position of the consumer reaches the end
+    let mut stop_offsets = stop_offsets(&[("a", 0, 10), ("a", 1, 10)]);
+    stop_offsets.on_consumer_position(&topic_partition("a", 0), 9);
+    assert!(!stop_offsets.is_partition_finished(&topic_partition("a", 0)));
+    stop_offsets.on_consumer_position(&topic_partition("a", 0), 10);
+    assert!(stop_offsets.is_partition_finished(&topic_partition("a", 0)));
+    assert!(!stop_offsets.is_finished());
+
+    // The committed offset of the consumer group is already beyond the end
+    stop_offsets.on_consumer_position(&topic_partition("a", 1), 25);
+    assert!(stop_offsets.is_finished());
+
+    // The positions of the unknown partitions are ignored
+    stop_offsets.on_consumer_position(&topic_partition("c", 0), 100);
+    assert!(stop_offsets.is_finished());
+}
@@ -0,0 +1,95 @@
+// Copyright © 2024 Pathway
+
+use pathway_engine::connectors::data_storage::KafkaTopicSubscription;
+
+fn subscription(topic_names: &[&str], topic_pattern: Option<&str>) -> KafkaTopicSubscription {
+    let topic_names = topic_names.iter().map(ToString::to_string).collect();
+    KafkaTopicSubscription::new(topic_names, topic_pattern).unwrap()
+}
+
+#[test]
+fn test_topic_names_only() {
+    let subscription = subscription(&["b", "a"], None);
+    assert!(!subscription.has_pattern());
+    assert_eq!(subscription.resolve(["a", "b", "c"]), vec!["b", "a"]);
+    assert_eq!(subscription.resolve([]), vec!["b", "a"]);
+}
+
+#[test]
+fn test_topic_pattern_matches_whole_name() {
+    let subscription = subscription(&[], Some("events-.*"));
+    assert!(subscription.matches_pattern("events-1"));
+    assert!(subscription.matches_pattern("events-"));
+    assert!(!subscription.matches_pattern("old-events-1"));
+    assert!(!subscription.matches_pattern("events"));
+
+    // The pattern with the explicit anchors behaves the same way
+    let subscription = self::subscription(&[], Some("^events-.*$"));
+    assert!(subscription.matches_pattern("events-1"));
+    assert!(!subscription.matches_pattern("old-events-1"));
+}
+
+#[test]
+fn test_topic_pattern_doesnt_match_name_prefix() {
+    let subscription = subscription(&[], Some("events"));
+    assert!(subscription.matches_pattern("events"));
+    assert!(!subscription.matches_pattern("events_archive"));
+    assert!(!subscription.matches_pattern("events-dlq"));
+    assert_eq!(
+        subscription.resolve(["events-dlq", "events", "events_archive"]),
+        vec!["events"]
+    );
+}
+
+#[test]
+fn test_topic_pattern_alternation_is_anchored() {
+    // Each alternative is matched against the whole name, not only the first one
+    let subscription = subscription(&[], Some("orders|payments"));
+    assert!(subscription.matches_pattern("orders"));
+    assert!(subscription.matches_pattern("payments"));
+    assert!(!subscription.matches_pattern("orders.eu"));
+    assert!(!subscription.matches_pattern("eu.payments"));
+}
+
+#[test]
+fn test_topic_pattern_uses_regex_crate_syntax() {
+    // Perl-style classes, which POSIX regular expressions of librdkafka don't support
+    let subscription = subscription(&[], Some(r"events-\d+"));
+    assert!(subscription.matches_pattern("events-12"));
+    assert!(!subscription.matches_pattern("events-d"));
+    assert!(!subscription.matches_pattern("events-12a"));
+
+    let subscription = self::subscription(&[], Some(r"(?i)events\.\w+"));
+    assert!(subscription.matches_pattern("EVENTS.clicks"));
+    assert!(!subscription.matches_pattern("events-clicks"));
+}
+
+#[test]
+fn test_resolve_names_and_pattern() {
+    let subscription = subscription(&["orders"], Some(r"events\.\d+"));
+    assert!(subscription.has_pattern());
+    assert_eq!(
+        subscription.resolve(["events.2", "orders", "events.x", "events.1", "events.2"]),
+        vec!["orders", "events.1", "events.2"]
+    );
+
+    // An explicitly given topic, which also matches the pattern, is subscribed once
+    let subscription = self::subscription(&["events.3"], Some(r"events\.\d+"));
+    assert_eq!(
+        subscription.resolve(["events.1", "events.3"]),
+        vec!["events.3", "events.1"]
+    );
+}
+
+#[test]
+fn test_invalid_topic_pattern() {
+    assert!(KafkaTopicSubscription::new(Vec::new(), Some("events-(")).is_err());
+    assert!(KafkaTopicSubscription::new(Vec::new(), Some(r"events-\p{Unknown}")).is_err());
+}
+
+#[test]
+fn test_empty_subscription() {
+    assert!(subscription(&[], None).is_empty());
+    assert!(!subscription(&["a"], None).is_empty());
+    assert!(!subscription(&[], Some("a")).is_empty());
+}
@@ -0,0 +1,189 @@
+// Copyright © 2024 Pathway
+
+use crate::helpers::{ErrorPlacement, ReplaceErrors};
+
+use super::helpers::assert_error_shown_for_reader_context;
+
+use std::collections::HashMap;
+
+use pathway_engine::connectors::data_format::{
+    IdentityParser, InnerSchemaField, JsonLinesParser, KeyGenerationPolicy, ParseError,
+    ParsedEvent, Parser,
+};
+use pathway_engine::connectors::data_storage::ReaderContext;
+use pathway_engine::connectors::SessionType;
+use pathway_engine::engine::{Type, Value};
+
+fn kafka_message(key: Option<&str>, value: Option<&str>) -> ReaderContext {
+    ReaderContext::KeyValue((
+        key.map(|key| key.as_bytes().to_vec()),
+        value.map(|value| value.as_bytes().to_vec()),
+    ))
+}
+
+fn parse_messages(
+    parser: &mut dyn Parser,
+    messages: &[ReaderContext],
+) -> eyre::Result<Vec<ParsedEvent>> {
+    let mut events = Vec::new();
+    for message in messages {
+        let parsed = parser.parse(message).map_err(ParseError::from)?;
+        events.extend(parsed.into_iter().map(|event| event.replace_errors()));
+    }
+    Ok(events)
+}
+
+#[test]
+fn test_jsonlines_upsert_message_key() -> eyre::Result<()> {
+    let schema = [("b".to_string(), InnerSchemaField::new(Type::Int, None))];
+    let mut parser = JsonLinesParser::new(
+        None,
+        vec!["b".to_string()],
+        HashMap::new(),
+        true,
+        schema.into(),
+        SessionType::Upsert,
+    )?;
+
+    let events = parse_messages(
+        &mut parser,
+        &[
+            kafka_message(Some("abc"), Some(r#"{"b": 1}"#)),
+            kafka_message(Some("abc"), Some(r#"{"b": 2}"#)),
+            kafka_message(Some("abc"), None),
+        ],
+    )?;
+    assert_eq!(
+        events,
+        vec![
+            ParsedEvent::Upsert((Some(vec![Value::from("abc")]), Some(vec![Value::Int(1)]))),
+            ParsedEvent::Upsert((Some(vec![Value::from("abc")]), Some(vec![Value::Int(2)]))),
+            ParsedEvent::Upsert((Some(vec![Value::from("abc")]), None)),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_jsonlines_upsert_key_fields() -> eyre::Result<()> {
+    let schema = [
+        ("a".to_string(), InnerSchemaField::new(Type::Int, None)),
+        ("b".to_string(), InnerSchemaField::new(Type::String, None)),
+    ];
+    let mut parser = JsonLinesParser::new(
+        Some(vec!["a".to_string()]),
+        vec!["a".to_string(), "b".to_string()],
+        HashMap::new(),
+        true,
+        schema.into(),
+        SessionType::Upsert,
+    )?;
+
+    let events = parse_messages(
+        &mut parser,
+        &[
+            kafka_message(Some(r#"{"a": 7}"#), Some(r#"{"a": 7, "b": "x"}"#)),
+            kafka_message(Some(r#"{"a": 7}"#), None),
+        ],
+    )?;
+    assert_eq!(
+        events,
+        vec![
+            ParsedEvent::Upsert((
+                Some(vec![Value::Int(7)]),
+                Some(vec![Value::Int(7), Value::from("x")])
+            )),
+            ParsedEvent::Upsert((Some(vec![Value::Int(7)]), None)),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_jsonlines_upsert_requires_key() -> eyre::Result<()> {
+    let schema = [("b".to_string(), InnerSchemaField::new(Type::Int, None))];
+    let parser = JsonLinesParser::new(
+        None,
+        vec!["b".to_string()],
+        HashMap::new(),
+        true,
+        schema.into(),
+        SessionType::Upsert,
+    )?;
+
+    assert_error_shown_for_reader_context(
+        &kafka_message(None, None),
+        Box::new(parser),
+        "received message doesn't have key, which is required in the upsert mode",
+        ErrorPlacement::Message,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_jsonlines_tombstone_in_native_session() -> eyre::Result<()> {
+    let schema = [("b".to_string(), InnerSchemaField::new(Type::Int, None))];
+    let parser = JsonLinesParser::new(
+        None,
+        vec!["b".to_string()],
+        HashMap::new(),
+        true,
+        schema.into(),
+        SessionType::Native,
+    )?;
+
+    assert_error_shown_for_reader_context(
+        &kafka_message(Some("abc"), None),
+        Box::new(parser),
+        "received message doesn't have payload",
+        ErrorPlacement::Message,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_identity_upsert() -> eyre::Result<()> {
+    let mut parser = IdentityParser::new(
+        vec!["data".to_string()],
+        true,
+        KeyGenerationPolicy::PreferMessageKey,
+        SessionType::Upsert,
+    );
+
+    let events = parse_messages(
+        &mut parser,
+        &[
+            kafka_message(Some("k"), Some("first")),
+            kafka_message(Some("k"), None),
+        ],
+    )?;
+    assert_eq!(
+        events,
+        vec![
+            ParsedEvent::Upsert((
+                Some(vec![Value::from("k")]),
+                Some(vec![Value::from("first")])
+            )),
+            ParsedEvent::Upsert((Some(vec![Value::from("k")]), None)),
+        ]
+    );
+
+    let parser = IdentityParser::new(
+        vec!["data".to_string()],
+        true,
+        KeyGenerationPolicy::AlwaysAutogenerate,
+        SessionType::Upsert,
+    );
+    assert_error_shown_for_reader_context(
+        &kafka_message(Some("k"), Some("first")),
+        Box::new(parser),
+        "received message doesn't have key, which is required in the upsert mode",
+        ErrorPlacement::Message,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// Copyright © 2024 Pathway
+
+use pathway_engine::connectors::data_format::{FormatterContext, FormatterError};
+use pathway_engine::connectors::data_storage::{kafka_key_hash_partition, kafka_murmur2};
+use pathway_engine::engine::{Key, Timestamp, Value};
+
+fn formatter_context(values: Vec<Value>) -> FormatterContext {
+    FormatterContext::new_single_payload(
+        b"payload".to_vec(),
+        Key::for_value(&Value::from("1")),
+        values,
+        Timestamp(0),
+        1,
+    )
+}
+
+#[test]
+fn test_murmur2_matches_java_client() {
+    // The reference values are taken from the tests of the Java client
+    assert_eq!(kafka_murmur2(b"21"), -973_932_308);
+    assert_eq!(kafka_murmur2(b"foobar"), -790_332_482);
+    assert_eq!(kafka_murmur2(b"a-little-bit-long-string"), -985_981_536);
+    assert_eq!(kafka_murmur2(b"a-little-bit-longer-string"), -1_486_304_829);
+    assert_eq!(
+        kafka_murmur2(b"lkjh234lh9fiuh90y23oiuhsafujhadof229phr9h19h89h8"),
+        -58_897_971
+    );
+    assert_eq!(kafka_murmur2(b"abc"), 479_470_107);
+}
+
+#[test]
+fn test_key_hash_partition() {
+    for key in [b"21".as_slice(), b"foobar", b"abc", b""] {
+        let partition = kafka_key_hash_partition(key, 7);
+        assert!((0..7).contains(&partition));
+        assert_eq!(partition, kafka_key_hash_partition(key, 7));
+    }
+    assert_eq!(kafka_key_hash_partition(b"21", 1), 0);
+    // -973932308 & 0x7fffffff = 1173551340
+    assert_eq!(kafka_key_hash_partition(b"21", 10), 0);
+    assert_eq!(kafka_key_hash_partition(b"21", 7), 1_173_551_340 % 7);
+}
+
+#[test]
+fn test_message_key_from_fields() -> eyre::Result<()> {
+    let context = formatter_context(vec![
+        Value::from("user-1"),
+        Value::Int(42),
+        Value::Bytes(vec![1, 2].into()),
+    ]);
+
+    assert_eq!(
+        context.construct_message_key(&[("name".to_string(), 0)])?,
+        b"user-1".to_vec()
+    );
+    assert!(matches!(
+        context.construct_message_key(&[("id".to_string(), 1)]),
+        Err(FormatterError::IncorrectKeyFieldType(Value::Int(42)))
+    ));
+    assert_eq!(
+        context.construct_message_key(&[("payload".to_string(), 2)])?,
+        vec![1, 2]
+    );
+    let composite_key =
+        context.construct_message_key(&[("name".to_string(), 0), ("id".to_string(), 1)])?;
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&composite_key)?,
+        serde_json::json!({"name": "user-1", "id": 42})
+    );
+    assert_eq!(
+        context.construct_message_key(&[])?,
+        context.key.0.to_le_bytes().to_vec()
+    );
+
+    Ok(())
+}
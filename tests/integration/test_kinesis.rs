@@ -0,0 +1,222 @@
+// Copyright © 2024 Pathway
+
+use std::cmp::Ordering;
+
+use md5::{Digest, Md5};
+use prost::Message;
+
+use pathway_engine::connectors::kinesis::{
+    compare_record_positions, compare_sequence_numbers, deaggregate_records, AggregatedRecord,
+    AggregatedUserRecord, ShardDescription, ShardTracker, UserRecord, KPL_AGGREGATED_RECORD_MAGIC,
+};
+use pathway_engine::connectors::metadata::{KinesisMetadata, SourceMetadata};
+
+/// Builds a record in the same way as the Kinesis Producer Library does.
+fn aggregated_record(partition_keys: &[&str], records: &[(u64, &str)]) -> Vec<u8> {
+    let message = AggregatedRecord {
+        partition_key_table: partition_keys.iter().map(ToString::to_string).collect(),
+        explicit_hash_key_table: Vec::new(),
+        records: records
+            .iter()
+            .map(|(partition_key_index, data)| AggregatedUserRecord {
+                partition_key_index: *partition_key_index,
+                explicit_hash_key_index: None,
+                data: data.as_bytes().to_vec(),
+            })
+            .collect(),
+    }
+    .encode_to_vec();
+    let mut result = KPL_AGGREGATED_RECORD_MAGIC.to_vec();
+    result.extend_from_slice(&message);
+    result.extend_from_slice(&Md5::digest(&message));
+    result
+}
+
+fn user_record(partition_key: &str, data: &str) -> UserRecord {
+    UserRecord {
+        partition_key: partition_key.to_string(),
+        data: data.as_bytes().to_vec(),
+    }
+}
+
+fn shard(shard_id: &str, parent_shard_ids: &[&str]) -> ShardDescription {
+    ShardDescription {
+        shard_id: shard_id.to_string(),
+        parent_shard_ids: parent_shard_ids.iter().map(ToString::to_string).collect(),
+    }
+}
+
+#[test]
+fn test_compare_sequence_numbers() {
+    assert_eq!(
+        compare_sequence_numbers(
+            "49590338271490256608559692538361571095921575989136588898",
+            "49590338271490256608559692538361571095921575989136588898"
+        ),
+        Ordering::Equal
+    );
+    assert_eq!(
+        compare_sequence_numbers(
+            "49590338271490256608559692538361571095921575989136588898",
+            "49590338271490256608559692540925702759324208523137515618"
+        ),
+        Ordering::Less
+    );
+    // Lexicographically "9" is greater than "10"
+    assert_eq!(compare_sequence_numbers("9", "10"), Ordering::Less);
+    assert_eq!(compare_sequence_numbers("100", "99"), Ordering::Greater);
+}
+
+#[test]
+fn test_compare_record_positions() {
+    assert_eq!(
+        compare_record_positions(("100", Some(2)), ("100", Some(3))),
+        Ordering::Less
+    );
+    // The whole record follows all user records aggregated in it
+    assert_eq!(
+        compare_record_positions(("100", None), ("100", Some(3))),
+        Ordering::Greater
+    );
+    assert_eq!(
+        compare_record_positions(("99", None), ("100", Some(0))),
+        Ordering::Less
+    );
+    assert_eq!(
+        compare_record_positions(("100", None), ("100", None)),
+        Ordering::Equal
+    );
+}
+
+#[test]
+fn test_deaggregate_records() {
+    let data = aggregated_record(&["alice", "bob"], &[(0, "one"), (1, "two"), (0, "three")]);
+    assert_eq!(
+        deaggregate_records(&data),
+        Some(vec![
+            user_record("alice", "one"),
+            user_record("bob", "two"),
+            user_record("alice", "three"),
+        ])
+    );
+}
+
+#[test]
+fn test_deaggregate_plain_records() {
+    assert_eq!(deaggregate_records(b"{\"user\": \"alice\"}"), None);
+    assert_eq!(deaggregate_records(&KPL_AGGREGATED_RECORD_MAGIC), None);
+
+    // A record starting with the magic bytes is read as is if the checksum
+    // doesn't match
+    let mut data = aggregated_record(&["alice"], &[(0, "one")]);
+    let last_byte = data.last_mut().unwrap();
+    *last_byte = last_byte.wrapping_add(1);
+    assert_eq!(deaggregate_records(&data), None);
+
+    // Or if a user record refers to a missing partition key
+    let data = aggregated_record(&["alice"], &[(0, "one"), (1, "two")]);
+    assert_eq!(deaggregate_records(&data), None);
+}
+
+#[test]
+fn test_children_wait_for_parent() {
+    let mut tracker = ShardTracker::new(0, 1);
+    let shards = vec![
+        shard("shardId-000000000000", &[]),
+        shard("shardId-000000000001", &["shardId-000000000000"]),
+        shard("shardId-000000000002", &["shardId-000000000000"]),
+    ];
+    assert_eq!(tracker.ready_shards(&shards), vec!["shardId-000000000000"]);
+    assert!(tracker.ready_shards(&shards).is_empty());
+
+    tracker.finish("shardId-000000000000");
+    assert_eq!(
+        tracker.ready_shards(&shards),
+        vec!["shardId-000000000001", "shardId-000000000002"]
+    );
+    assert!(tracker.ready_shards(&shards).is_empty());
+}
+
+#[test]
+fn test_merged_shard_waits_for_both_parents() {
+    let mut tracker = ShardTracker::new(0, 1);
+    let shards = vec![
+        shard("shardId-000000000000", &[]),
+        shard("shardId-000000000001", &[]),
+        shard(
+            "shardId-000000000002",
+            &["shardId-000000000000", "shardId-000000000001"],
+        ),
+    ];
+    assert_eq!(
+        tracker.ready_shards(&shards),
+        vec!["shardId-000000000000", "shardId-000000000001"]
+    );
+    tracker.finish("shardId-000000000001");
+    assert!(tracker.ready_shards(&shards).is_empty());
+    tracker.finish("shardId-000000000000");
+    assert_eq!(tracker.ready_shards(&shards), vec!["shardId-000000000002"]);
+}
+
+#[test]
+fn test_expired_parent_does_not_block() {
+    let mut tracker = ShardTracker::new(0, 1);
+    let shards = vec![shard("shardId-000000000001", &["shardId-000000000000"])];
+    assert_eq!(tracker.ready_shards(&shards), vec!["shardId-000000000001"]);
+}
+
+#[test]
+fn test_shards_are_distributed_by_lineage() {
+    let shards = vec![
+        shard("shardId-000000000000", &[]),
+        shard("shardId-000000000001", &[]),
+        shard("shardId-000000000002", &[]),
+        shard("shardId-000000000003", &[]),
+        shard("shardId-000000000004", &["shardId-000000000000"]),
+        shard("shardId-000000000005", &["shardId-000000000000"]),
+    ];
+    let mut trackers: Vec<_> = (0..3).map(|index| ShardTracker::new(index, 3)).collect();
+    for tracker in &mut trackers {
+        tracker.ready_shards(&shards);
+    }
+    for shard in &shards {
+        let owners = trackers
+            .iter()
+            .filter(|tracker| tracker.is_assigned(&shard.shard_id))
+            .count();
+        assert_eq!(owners, 1);
+    }
+    for tracker in &trackers {
+        assert_eq!(
+            tracker.is_assigned("shardId-000000000004"),
+            tracker.is_assigned("shardId-000000000000")
+        );
+        assert_eq!(
+            tracker.is_assigned("shardId-000000000005"),
+            tracker.is_assigned("shardId-000000000000")
+        );
+    }
+}
+
+#[test]
+fn test_kinesis_metadata_serialization() {
+    let metadata: SourceMetadata = KinesisMetadata::new(
+        "orders".to_string(),
+        "shardId-000000000000".to_string(),
+        "49590338271490256608559692538361571095921575989136588898".to_string(),
+        "alice".to_string(),
+        Some(1_700_000_000_000),
+    )
+    .into();
+    assert_eq!(
+        metadata.serialize(),
+        serde_json::json!({
+            "stream_name": "orders",
+            "shard_id": "shardId-000000000000",
+            "sequence_number": "49590338271490256608559692538361571095921575989136588898",
+            "partition_key": "alice",
+            "approximate_arrival_timestamp_millis": 1_700_000_000_000_i64,
+        })
+    );
+    assert!(metadata.commits_allowed_in_between());
+}
@@ -0,0 +1,296 @@
+// Copyright © 2024 Pathway
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bytes::BytesMut;
+use rumqttc::mqttbytes::v4::{
+    read as read_packet, ConnAck, ConnectReturnCode, Packet, PingResp, PubAck, Publish, SubAck,
+    SubscribeReasonCode,
+};
+use rumqttc::mqttbytes::{matches as topic_matches, Error as PacketError, QoS};
+
+use pathway_engine::connectors::data_format::FormatterContext;
+use pathway_engine::connectors::data_storage::{
+    MqttReader, MqttWriter, ReadResult, Reader, ReaderContext, WriteError, Writer,
+};
+use pathway_engine::connectors::metadata::{MqttMetadata, SourceMetadata};
+use pathway_engine::connectors::mqtt::{connect, ProtocolVersion, QualityOfService};
+use pathway_engine::engine::{Key, Timestamp, Value};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BrokerBehavior {
+    /// Acknowledges the publishes and forwards them to the subscribers.
+    Normal,
+
+    /// Never acknowledges the publishes.
+    NoAcks,
+
+    /// Closes the connection upon the first publish and stops accepting
+    /// the new connections.
+    CloseOnPublish,
+}
+
+#[derive(Default)]
+struct BrokerState {
+    messages: Vec<Publish>,
+    subscribers: Vec<(String, TcpStream)>,
+}
+
+/// Starts a minimal MQTT 3.1.1 broker on a random local port. The broker keeps
+/// all published messages and also delivers the earlier ones to a new
+/// subscriber, so that the order of subscribing and publishing doesn't matter.
+fn start_fake_broker(behavior: BrokerBehavior) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let state = Arc::new(Mutex::new(BrokerState::default()));
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let state = state.clone();
+            let handler = thread::spawn(move || serve_client(stream.unwrap(), &state, behavior));
+            if behavior == BrokerBehavior::CloseOnPublish {
+                handler.join().unwrap();
+                break;
+            }
+        }
+    });
+    address
+}
+
+fn serve_client(mut stream: TcpStream, state: &Mutex<BrokerState>, behavior: BrokerBehavior) {
+    let mut buffer = BytesMut::new();
+    let mut chunk = [0; 4096];
+    loop {
+        let packet = match read_packet(&mut buffer, 1 << 20) {
+            Ok(packet) => packet,
+            Err(PacketError::InsufficientBytes(_)) => {
+                match stream.read(&mut chunk) {
+                    Ok(0) | Err(_) => return,
+                    Ok(n_bytes) => buffer.extend_from_slice(&chunk[..n_bytes]),
+                }
+                continue;
+            }
+            Err(e) => panic!("malformed MQTT packet: {e:?}"),
+        };
+        let mut response = BytesMut::new();
+        match packet {
+            Packet::Connect(_) => {
+                ConnAck::new(ConnectReturnCode::Success, false)
+                    .write(&mut response)
+                    .unwrap();
+            }
+            Packet::Subscribe(subscribe) => {
+                let return_codes = subscribe
+                    .filters
+                    .iter()
+                    .map(|filter| SubscribeReasonCode::Success(filter.qos))
+                    .collect();
+                SubAck::new(subscribe.pkid, return_codes)
+                    .write(&mut response)
+                    .unwrap();
+                let mut state = state.lock().unwrap();
+                for filter in subscribe.filters {
+                    for message in &state.messages {
+                        if topic_matches(&message.topic, &filter.path) {
+                            forwarded_message(message).write(&mut response).unwrap();
+                        }
+                    }
+                    state
+                        .subscribers
+                        .push((filter.path, stream.try_clone().unwrap()));
+                }
+            }
+            Packet::Publish(publish) => match behavior {
+                BrokerBehavior::CloseOnPublish => return,
+                BrokerBehavior::NoAcks => {}
+                BrokerBehavior::Normal => {
+                    if publish.qos != QoS::AtMostOnce {
+                        PubAck::new(publish.pkid).write(&mut response).unwrap();
+                    }
+                    let mut state = state.lock().unwrap();
+                    for (filter, subscriber) in &mut state.subscribers {
+                        if topic_matches(&publish.topic, filter) {
+                            let mut forwarded = BytesMut::new();
+                            forwarded_message(&publish).write(&mut forwarded).unwrap();
+                            let _ = subscriber.write_all(&forwarded);
+                        }
+                    }
+                    state.messages.push(publish);
+                }
+            },
+            Packet::PingReq => {
+                PingResp.write(&mut response).unwrap();
+            }
+            Packet::Disconnect => return,
+            _ => {}
+        }
+        if stream.write_all(&response).is_err() {
+            return;
+        }
+    }
+}
+
+/// The messages are delivered to the subscribers at most once, so that
+/// the broker doesn't need to track their acknowledgements.
+fn forwarded_message(message: &Publish) -> Publish {
+    Publish::new(
+        message.topic.clone(),
+        QoS::AtMostOnce,
+        message.payload.to_vec(),
+    )
+}
+
+fn create_writer(
+    address: SocketAddr,
+    qos: QualityOfService,
+    flush_timeout: Duration,
+) -> eyre::Result<MqttWriter> {
+    let (client, connection) = connect(
+        &format!("mqtt://{address}?client_id=writer"),
+        ProtocolVersion::V311,
+    )?;
+    Ok(MqttWriter::new(
+        client,
+        connection,
+        "sensors/kitchen".to_string(),
+        None,
+        qos,
+        false,
+        flush_timeout,
+    )?)
+}
+
+fn payload_context(payload: &str) -> FormatterContext {
+    FormatterContext::new_single_payload(
+        payload.as_bytes().to_vec(),
+        Key::for_value(&Value::from(payload)),
+        vec![Value::from(payload)],
+        Timestamp(0),
+        1,
+    )
+}
+
+#[test]
+fn test_mqtt_metadata_serialization() {
+    let metadata: SourceMetadata =
+        MqttMetadata::new("sensors/kitchen/temperature".to_string(), 1, true).into();
+    assert_eq!(
+        metadata.serialize(),
+        serde_json::json!({
+            "topic": "sensors/kitchen/temperature",
+            "qos": 1,
+            "retain": true,
+        })
+    );
+    assert!(metadata.commits_allowed_in_between());
+}
+
+#[test]
+fn test_mqtt_settings_parsing() {
+    assert_eq!(
+        ProtocolVersion::try_from("3.1.1").unwrap(),
+        ProtocolVersion::V311
+    );
+    assert_eq!(ProtocolVersion::try_from("5").unwrap(), ProtocolVersion::V5);
+    assert!(ProtocolVersion::try_from("3").is_err());
+
+    for level in 0..=2 {
+        let qos = QualityOfService::try_from(level).unwrap();
+        assert_eq!(qos.level(), level);
+    }
+    assert!(QualityOfService::try_from(3).is_err());
+}
+
+#[test]
+fn test_mqtt_uri_parsing() {
+    for protocol_version in [ProtocolVersion::V311, ProtocolVersion::V5] {
+        assert!(connect("mqtt://localhost:1883?client_id=pathway", protocol_version).is_ok());
+        assert!(connect("mqtt://localhost:1883", protocol_version).is_err());
+        assert!(connect("http://localhost:1883?client_id=pathway", protocol_version).is_err());
+    }
+}
+
+#[test]
+fn test_mqtt_write_and_read() -> eyre::Result<()> {
+    let address = start_fake_broker(BrokerBehavior::Normal);
+
+    let (client, connection) = connect(
+        &format!("mqtt://{address}?client_id=reader"),
+        ProtocolVersion::V311,
+    )?;
+    let mut reader = MqttReader::new(
+        client,
+        connection,
+        "sensors/#".to_string(),
+        QualityOfService::AtLeastOnce,
+        None,
+    );
+    let reader_thread = thread::spawn(move || {
+        let mut payloads = Vec::new();
+        while payloads.len() < 3 {
+            if let ReadResult::Data(ReaderContext::RawBytes(_, payload), _) = reader.read().unwrap()
+            {
+                payloads.push(String::from_utf8(payload).unwrap());
+            }
+        }
+        payloads
+    });
+
+    let mut writer = create_writer(
+        address,
+        QualityOfService::AtLeastOnce,
+        Duration::from_secs(30),
+    )?;
+    for payload in ["one", "two", "three"] {
+        writer.write(payload_context(payload))?;
+    }
+    writer.flush(true)?;
+    drop(writer);
+
+    assert_eq!(reader_thread.join().unwrap(), vec!["one", "two", "three"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_mqtt_flush_fails_on_lost_connection() -> eyre::Result<()> {
+    let address = start_fake_broker(BrokerBehavior::CloseOnPublish);
+
+    let mut writer = create_writer(
+        address,
+        QualityOfService::AtLeastOnce,
+        Duration::from_secs(60),
+    )?;
+    writer.write(payload_context("one"))?;
+    let started_at = Instant::now();
+    assert!(matches!(
+        writer.flush(true),
+        Err(WriteError::MqttConnectionLost(_))
+    ));
+    drop(writer);
+    assert!(started_at.elapsed() < Duration::from_secs(30));
+
+    Ok(())
+}
+
+#[test]
+fn test_mqtt_flush_timeout() -> eyre::Result<()> {
+    let address = start_fake_broker(BrokerBehavior::NoAcks);
+
+    let mut writer = create_writer(
+        address,
+        QualityOfService::AtLeastOnce,
+        Duration::from_millis(500),
+    )?;
+    writer.write(payload_context("one"))?;
+    writer.write(payload_context("two"))?;
+    assert!(matches!(
+        writer.flush(true),
+        Err(WriteError::MqttDeliveryTimeout(2, _))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+// Copyright © 2024 Pathway
+
+use assert_matches::assert_matches;
+
+use mysql::binlog::value::BinlogValue;
+use mysql::Value as MysqlValue;
+
+use pathway_engine::connectors::mysql_binlog::{
+    binlog_file_index, format_source_id, parse_binlog_value, Error as MysqlBinlogError, GtidSet,
+};
+use pathway_engine::connectors::{OffsetKey, OffsetValue, StorageType};
+use pathway_engine::engine::{DateTimeNaive, DateTimeUtc, Duration, Type, Value};
+use pathway_engine::persistence::frontier::OffsetAntichain;
+
+const SOURCE_ID: &str = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+const OTHER_SOURCE_ID: &str = "4e11fa47-71ca-11e1-9e33-c80aa9429562";
+
+#[test]
+fn test_gtid_set_parsing() -> eyre::Result<()> {
+    let gtid_set: GtidSet = format!("{SOURCE_ID}:1-5:7,\n{OTHER_SOURCE_ID}:3").parse()?;
+    assert_eq!(
+        gtid_set.to_string(),
+        format!("{SOURCE_ID}:1-5:7,{OTHER_SOURCE_ID}:3")
+    );
+    assert_eq!(gtid_set.transactions_count(), 7);
+    assert!(gtid_set.contains(SOURCE_ID, 3));
+    assert!(!gtid_set.contains(SOURCE_ID, 6));
+    assert!(!gtid_set.contains(OTHER_SOURCE_ID, 1));
+    assert_eq!(gtid_set.to_sids().len(), 2);
+
+    let empty: GtidSet = "".parse()?;
+    assert!(empty.is_empty());
+    assert_eq!(empty.to_string(), "");
+    assert!(empty.to_sids().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_gtid_set_malformed() {
+    assert_matches!(
+        "abc:1-5".parse::<GtidSet>(),
+        Err(MysqlBinlogError::InvalidGtidSet(_))
+    );
+    assert_matches!(
+        format!("{SOURCE_ID}:5-1").parse::<GtidSet>(),
+        Err(MysqlBinlogError::InvalidGtidSet(_))
+    );
+    assert_matches!(
+        format!("{SOURCE_ID}:0").parse::<GtidSet>(),
+        Err(MysqlBinlogError::InvalidGtidSet(_))
+    );
+    assert_matches!(
+        format!("{SOURCE_ID}:x").parse::<GtidSet>(),
+        Err(MysqlBinlogError::InvalidGtidSet(_))
+    );
+}
+
+#[test]
+fn test_gtid_set_add() -> eyre::Result<()> {
+    let mut gtid_set: GtidSet = format!("{SOURCE_ID}:1-5:7").parse()?;
+    gtid_set.add(SOURCE_ID, 6);
+    assert_eq!(gtid_set.to_string(), format!("{SOURCE_ID}:1-7"));
+    gtid_set.add(SOURCE_ID, 3);
+    assert_eq!(gtid_set.to_string(), format!("{SOURCE_ID}:1-7"));
+    gtid_set.add(&OTHER_SOURCE_ID.to_uppercase(), 10);
+    assert_eq!(
+        gtid_set.to_string(),
+        format!("{SOURCE_ID}:1-7,{OTHER_SOURCE_ID}:10")
+    );
+    assert_eq!(gtid_set.transactions_count(), 8);
+    Ok(())
+}
+
+#[test]
+fn test_source_id_and_file_formatting() {
+    let sid = [
+        0x3e, 0x11, 0xfa, 0x47, 0x71, 0xca, 0x11, 0xe1, 0x9e, 0x33, 0xc8, 0x0a, 0xa9, 0x42, 0x95,
+        0x62,
+    ];
+    assert_eq!(format_source_id(sid), SOURCE_ID);
+    assert_eq!(binlog_file_index("binlog.000012"), Some(12));
+    assert_eq!(binlog_file_index("mysql-bin.1000000"), Some(1_000_000));
+    assert_eq!(binlog_file_index("binlog"), None);
+}
+
+#[test]
+fn test_parse_binlog_values() -> eyre::Result<()> {
+    let value = |value| BinlogValue::Value(value);
+
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::Int(-42)), &Type::Int),
+        Some(Value::Int(-42))
+    );
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::UInt(42)), &Type::Int),
+        Some(Value::Int(42))
+    );
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::UInt(u64::MAX)), &Type::Int),
+        None
+    );
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::Int(1)), &Type::Bool),
+        Some(Value::Bool(true))
+    );
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::Double(1.5)), &Type::Float),
+        Some(Value::from(1.5))
+    );
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::Bytes(b"12.50".to_vec())), &Type::Float),
+        Some(Value::from(12.5))
+    );
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::Bytes(b"abc".to_vec())), &Type::String),
+        Some(Value::from("abc"))
+    );
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::Bytes(vec![0, 255])), &Type::Bytes),
+        Some(Value::Bytes(vec![0, 255].into()))
+    );
+    assert_eq!(
+        parse_binlog_value(
+            &value(MysqlValue::Bytes(br#"{"a": [1, 2]}"#.to_vec())),
+            &Type::Json
+        ),
+        Some(Value::from(serde_json::json!({"a": [1, 2]})))
+    );
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::NULL), &Type::Optional(Type::Int.into())),
+        Some(Value::None)
+    );
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::NULL), &Type::Int),
+        None
+    );
+    assert_eq!(
+        parse_binlog_value(&value(MysqlValue::Bytes(b"abc".to_vec())), &Type::Int),
+        None
+    );
+
+    assert_eq!(
+        parse_binlog_value(
+            &value(MysqlValue::Date(2024, 1, 2, 3, 4, 5, 6)),
+            &Type::DateTimeNaive
+        ),
+        Some(Value::from(DateTimeNaive::from_timestamp(
+            1_704_164_645_000_006,
+            "us"
+        )?))
+    );
+    assert_eq!(
+        parse_binlog_value(
+            &value(MysqlValue::Date(2024, 1, 2, 3, 4, 5, 6)),
+            &Type::DateTimeUtc
+        ),
+        Some(Value::from(DateTimeUtc::from_timestamp(
+            1_704_164_645_000_006,
+            "us"
+        )?))
+    );
+    assert_eq!(
+        parse_binlog_value(
+            &value(MysqlValue::Bytes(b"1704164645.5".to_vec())),
+            &Type::DateTimeUtc
+        ),
+        Some(Value::from(DateTimeUtc::from_timestamp(
+            1_704_164_645_500_000,
+            "us"
+        )?))
+    );
+    assert_eq!(
+        parse_binlog_value(
+            &value(MysqlValue::Time(true, 1, 2, 3, 4, 5)),
+            &Type::Duration
+        ),
+        Some(Value::from(Duration::new_with_unit(-93_784_000_005, "us")?))
+    );
+
+    Ok(())
+}
+
+fn binlog_frontier(
+    file_name: &str,
+    transaction_position: u64,
+    gtid_set: Option<&str>,
+    entries_read_within_transaction: u64,
+) -> OffsetAntichain {
+    let mut frontier = OffsetAntichain::new();
+    frontier.advance_offset(
+        OffsetKey::Empty,
+        OffsetValue::MysqlBinlogPosition {
+            file_name: file_name.into(),
+            transaction_position,
+            gtid_set: gtid_set.map(Into::into),
+            entries_read_within_transaction,
+        },
+    );
+    frontier
+}
+
+#[test]
+fn test_merge_binlog_frontiers() {
+    let storage_type = StorageType::MysqlBinlog;
+
+    let earlier = binlog_frontier("binlog.000009", 1000, None, 5);
+    let later = binlog_frontier("binlog.000010", 4, None, 1);
+    assert_eq!(storage_type.merge_two_frontiers(&earlier, &later), later);
+    assert_eq!(storage_type.merge_two_frontiers(&later, &earlier), later);
+
+    let earlier = binlog_frontier("binlog.000010", 4, None, 1);
+    let later = binlog_frontier("binlog.000010", 4, None, 2);
+    assert_eq!(storage_type.merge_two_frontiers(&earlier, &later), later);
+
+    let gtid_earlier = format!("{SOURCE_ID}:1-5");
+    let gtid_later = format!("{SOURCE_ID}:1-6");
+    let earlier = binlog_frontier("binlog.000010", 4000, Some(&gtid_earlier), 1);
+    let later = binlog_frontier("binlog.000002", 4, Some(&gtid_later), 1);
+    assert_eq!(storage_type.merge_two_frontiers(&later, &earlier), later);
+    assert_eq!(storage_type.merge_two_frontiers(&earlier, &later), later);
+}
@@ -0,0 +1,333 @@
+// Copyright © 2024 Pathway
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Arc;
+
+use assert_matches::assert_matches;
+use deltalake::arrow::array::{Int64Array, RecordBatch as ArrowRecordBatch, StringArray};
+use deltalake::arrow::datatypes::{
+    DataType as ArrowDataType, Field as ArrowField, Schema as ArrowSchema,
+};
+use deltalake::parquet::arrow::ArrowWriter;
+use deltalake::parquet::file::properties::WriterProperties;
+use tempfile::tempdir;
+
+use pathway_engine::connectors::data_format::{InnerSchemaField, ParsedEvent, TransparentParser};
+use pathway_engine::connectors::data_storage::{
+    new_parquet_filesystem_reader, ConnectorMode, DataEventType, ReadResult, Reader, ReaderContext,
+};
+use pathway_engine::connectors::data_tokenize::{ParquetTokenizer, Tokenize};
+use pathway_engine::connectors::SessionType;
+use pathway_engine::engine::{Type, Value};
+use pathway_engine::persistence::frontier::OffsetAntichain;
+
+use crate::helpers::{create_persistence_manager, read_data_from_reader};
+
+const ROWS_PER_ROW_GROUP: usize = 2;
+
+fn write_parquet_file(path: &Path, numbers: &[i64]) -> eyre::Result<()> {
+    let schema = Arc::new(ArrowSchema::new(vec![
+        ArrowField::new("number", ArrowDataType::Int64, false),
+        ArrowField::new("name", ArrowDataType::Utf8, false),
+    ]));
+    let names: Vec<String> = numbers
+        .iter()
+        .map(|number| format!("row {number}"))
+        .collect();
+    let batch = ArrowRecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int64Array::from(numbers.to_vec())),
+            Arc::new(StringArray::from(names)),
+        ],
+    )?;
+    let properties = WriterProperties::builder()
+        .set_max_row_group_size(ROWS_PER_ROW_GROUP)
+        .build();
+    let mut writer = ArrowWriter::try_new(File::create(path)?, schema, Some(properties))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+fn column_types() -> HashMap<String, Type> {
+    HashMap::from([
+        ("number".to_string(), Type::Int),
+        ("name".to_string(), Type::String),
+    ])
+}
+
+fn parser(value_field_names: &[&str]) -> eyre::Result<TransparentParser> {
+    let schema = [
+        ("number".to_string(), InnerSchemaField::new(Type::Int, None)),
+        (
+            "name".to_string(),
+            InnerSchemaField::new(Type::String, None),
+        ),
+        (
+            "_metadata".to_string(),
+            InnerSchemaField::new(Type::Json, None),
+        ),
+    ];
+    Ok(TransparentParser::new(
+        None,
+        value_field_names.iter().map(ToString::to_string).collect(),
+        schema.into(),
+        SessionType::Native,
+    )?)
+}
+
+fn read_numbers(reader: Box<dyn Reader>) -> eyre::Result<Vec<i64>> {
+    let entries = read_data_from_reader(reader, Box::new(parser(&["number"])?))?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| match entry {
+            ParsedEvent::Insert((_, values)) => match values.as_slice() {
+                [Value::Int(number)] => *number,
+                other => panic!("unexpected values: {other:?}"),
+            },
+            other => panic!("unexpected event: {other:?}"),
+        })
+        .collect())
+}
+
+fn row_position(row_group: u64, rows_read: u64) -> u64 {
+    (row_group << 32) | rows_read
+}
+
+#[test]
+fn test_parquet_tokenizer_positions() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let path = test_storage.path().join("input.parquet");
+    write_parquet_file(&path, &[1, 2, 3, 4, 5])?;
+
+    let mut tokenizer = ParquetTokenizer::new(column_types());
+    assert!(tokenizer.supports_resuming());
+    tokenizer.set_new_reader(
+        Box::new(Cursor::new(std::fs::read(&path)?)),
+        DataEventType::Insert,
+    )?;
+
+    let mut positions = Vec::new();
+    let mut resumable_positions = Vec::new();
+    while let Some((context, position)) = tokenizer.next_entry()? {
+        assert_matches!(
+            context,
+            ReaderContext::Diff((DataEventType::Insert, None, _))
+        );
+        positions.push(position);
+        if tokenizer.is_at_resumable_position() {
+            resumable_positions.push(position);
+        }
+    }
+    assert_eq!(
+        positions,
+        vec![
+            row_position(0, 1),
+            row_position(0, 2),
+            row_position(1, 1),
+            row_position(1, 2),
+            row_position(2, 1),
+        ]
+    );
+    assert_eq!(
+        resumable_positions,
+        vec![row_position(0, 2), row_position(1, 2), row_position(2, 1)]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parquet_tokenizer_resume() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let path = test_storage.path().join("input.parquet");
+    write_parquet_file(&path, &[1, 2, 3, 4, 5, 6])?;
+    let contents = std::fs::read(&path)?;
+
+    let mut tokenizer = ParquetTokenizer::new(column_types());
+    let mut remaining_positions = |position| -> eyre::Result<Option<Vec<u64>>> {
+        let has_entries =
+            tokenizer.set_resumed_reader(Box::new(Cursor::new(contents.clone())), position)?;
+        if !has_entries {
+            return Ok(None);
+        }
+        let mut positions = Vec::new();
+        while let Some((_, position)) = tokenizer.next_entry()? {
+            positions.push(position);
+        }
+        Ok(Some(positions))
+    };
+
+    assert_eq!(
+        remaining_positions(row_position(0, 2))?,
+        Some(vec![
+            row_position(1, 1),
+            row_position(1, 2),
+            row_position(2, 1),
+            row_position(2, 2),
+        ])
+    );
+    assert_eq!(
+        remaining_positions(row_position(1, 1))?,
+        Some(vec![
+            row_position(1, 2),
+            row_position(2, 1),
+            row_position(2, 2)
+        ])
+    );
+    assert_eq!(remaining_positions(row_position(2, 2))?, None);
+    Ok(())
+}
+
+#[test]
+fn test_parquet_tokenizer_invalid_file() -> eyre::Result<()> {
+    let mut tokenizer = ParquetTokenizer::new(column_types());
+    tokenizer.set_new_reader(
+        Box::new(Cursor::new(b"not a parquet file".to_vec())),
+        DataEventType::Insert,
+    )?;
+    assert!(tokenizer.next_entry()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_parquet_reader_commits_by_row_groups() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let path = test_storage.path().join("input.parquet");
+    write_parquet_file(&path, &[1, 2, 3, 4, 5])?;
+
+    let mut reader = new_parquet_filesystem_reader(
+        path.to_str().unwrap(),
+        column_types(),
+        ConnectorMode::Static,
+        Some(1),
+        "*",
+    )?;
+    let mut events = Vec::new();
+    loop {
+        let event = match reader.read()? {
+            ReadResult::Finished => break,
+            ReadResult::NewSource(_) => "new_source",
+            ReadResult::Data(..) => "data",
+            ReadResult::FinishedSource {
+                commit_allowed: true,
+            } => "commit",
+            ReadResult::FinishedSource {
+                commit_allowed: false,
+            } => "finished_source",
+        };
+        events.push(event);
+    }
+    assert_eq!(
+        events,
+        vec![
+            "new_source",
+            "data",
+            "data",
+            "commit",
+            "new_source",
+            "data",
+            "data",
+            "commit",
+            "new_source",
+            "data",
+            "commit",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parquet_reader_metadata_column() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let path = test_storage.path().join("input.parquet");
+    write_parquet_file(&path, &[1])?;
+
+    let reader = new_parquet_filesystem_reader(
+        path.to_str().unwrap(),
+        column_types(),
+        ConnectorMode::Static,
+        None,
+        "*",
+    )?;
+    let entries = read_data_from_reader(
+        Box::new(reader),
+        Box::new(parser(&["number", "name", "_metadata"])?),
+    )?;
+    let [ParsedEvent::Insert((_, values))] = entries.as_slice() else {
+        panic!("unexpected entries: {entries:?}");
+    };
+    assert_eq!(values[0], Value::Int(1));
+    assert_eq!(values[1], Value::from("row 1"));
+    let Value::Json(metadata) = &values[2] else {
+        panic!("unexpected metadata: {:?}", values[2]);
+    };
+    let metadata_path = metadata["path"].as_str().unwrap();
+    assert!(metadata_path.ends_with("input.parquet"), "{metadata_path}");
+    Ok(())
+}
+
+#[test]
+fn test_parquet_reader_resumes_from_row_group() -> eyre::Result<()> {
+    let test_storage = tempdir()?;
+    let test_storage_path = test_storage.path();
+    let pstorage_root_path = test_storage_path.join("pstorage");
+    let input_path = test_storage_path.join("input.parquet");
+    write_parquet_file(&input_path, &[1, 2, 3, 4, 5, 6])?;
+    let tracker = create_persistence_manager(&pstorage_root_path, true);
+
+    let create_reader = || -> eyre::Result<Box<dyn Reader>> {
+        let mut reader = new_parquet_filesystem_reader(
+            input_path.to_str().unwrap(),
+            column_types(),
+            ConnectorMode::Static,
+            Some(1),
+            "*",
+        )?;
+        reader.initialize_cached_objects_storage(&tracker.lock().unwrap(), 1)?;
+        Ok(Box::new(reader))
+    };
+
+    // Read the first row group and stop at the commit that follows it
+    let mut frontier = OffsetAntichain::new();
+    {
+        let mut reader = create_reader()?;
+        loop {
+            match reader.read()? {
+                ReadResult::Data(_, (offset_key, offset_value)) => {
+                    frontier.advance_offset(offset_key, offset_value);
+                }
+                ReadResult::FinishedSource {
+                    commit_allowed: true,
+                } => break,
+                ReadResult::NewSource(_) => {}
+                other => panic!("unexpected read result: {other:?}"),
+            }
+        }
+    }
+
+    let mut reader = create_reader()?;
+    reader.seek(&frontier)?;
+    assert_eq!(read_numbers(reader)?, vec![3, 4, 5, 6]);
+
+    // Once the file is read in full, nothing is left to resume
+    let mut reader = create_reader()?;
+    reader.seek(&frontier)?;
+    let mut last_frontier = frontier.clone();
+    loop {
+        match reader.read()? {
+            ReadResult::Data(_, (offset_key, offset_value)) => {
+                last_frontier.advance_offset(offset_key, offset_value);
+            }
+            ReadResult::Finished => break,
+            _ => {}
+        }
+    }
+    let mut reader = create_reader()?;
+    reader.seek(&last_frontier)?;
+    assert_eq!(read_numbers(reader)?, Vec::<i64>::new());
+    Ok(())
+}
@@ -4,6 +4,8 @@ use crate::helpers::ErrorPlacement;
 
 use super::helpers::assert_error_shown_for_reader_context;
 
+use std::collections::HashMap;
+
 use pathway_engine::connectors::data_format::{DebeziumDBType, DebeziumMessageParser};
 use pathway_engine::connectors::data_storage::{DataEventType, ReaderContext};
 
@@ -14,6 +16,7 @@ fn test_utf8_decode_error() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
 
     let invalid_utf8_bytes: &[u8] = &[0xC0, 0x80, 0xE0, 0x80, 0x80];
@@ -34,6 +37,7 @@ fn test_empty_payload() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
 
     assert_error_shown_for_reader_context(
@@ -53,6 +57,7 @@ fn test_unsupported_context() -> eyre::Result<()> {
         vec!["first_name".to_string()],
         "        ".to_string(),
         DebeziumDBType::Postgres,
+        HashMap::new(),
     );
 
     assert_error_shown_for_reader_context(
@@ -0,0 +1,237 @@
+// Copyright © 2024 Pathway
+
+use assert_matches::assert_matches;
+
+use pathway_engine::connectors::pgoutput::{
+    format_lsn, parse_lsn, parse_text_value, Error as PgoutputError, Message as PgoutputMessage,
+    Relation, RelationColumn, ReplicaIdentity, TupleColumn,
+};
+use pathway_engine::engine::{Type, Value};
+
+fn encode_tuple(columns: &[Option<&str>]) -> Vec<u8> {
+    let mut result = Vec::new();
+    result.extend_from_slice(&i16::try_from(columns.len()).unwrap().to_be_bytes());
+    for column in columns {
+        match column {
+            Some(value) => {
+                result.push(b't');
+                result.extend_from_slice(&u32::try_from(value.len()).unwrap().to_be_bytes());
+                result.extend_from_slice(value.as_bytes());
+            }
+            None => result.push(b'n'),
+        }
+    }
+    result
+}
+
+#[test]
+fn test_parse_begin_and_commit() -> eyre::Result<()> {
+    let mut begin = vec![b'B'];
+    begin.extend_from_slice(&0x16_B374_D848_u64.to_be_bytes());
+    begin.extend_from_slice(&0_i64.to_be_bytes());
+    begin.extend_from_slice(&42_u32.to_be_bytes());
+    assert_eq!(
+        PgoutputMessage::parse(&begin)?,
+        PgoutputMessage::Begin {
+            final_lsn: 0x16_B374_D848
+        }
+    );
+
+    let mut commit = vec![b'C', 0];
+    commit.extend_from_slice(&0x16_B374_D848_u64.to_be_bytes());
+    commit.extend_from_slice(&0x16_B374_D900_u64.to_be_bytes());
+    commit.extend_from_slice(&0_i64.to_be_bytes());
+    assert_eq!(
+        PgoutputMessage::parse(&commit)?,
+        PgoutputMessage::Commit {
+            commit_lsn: 0x16_B374_D848,
+            end_lsn: 0x16_B374_D900,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_relation() -> eyre::Result<()> {
+    let mut message = vec![b'R'];
+    message.extend_from_slice(&16384_u32.to_be_bytes());
+    message.extend_from_slice(b"public\0users\0f");
+    message.extend_from_slice(&2_i16.to_be_bytes());
+    for (flags, name, type_id) in [(1_u8, "id", 23_u32), (0, "name", 25)] {
+        message.push(flags);
+        message.extend_from_slice(name.as_bytes());
+        message.push(0);
+        message.extend_from_slice(&type_id.to_be_bytes());
+        message.extend_from_slice(&(-1_i32).to_be_bytes());
+    }
+
+    let relation = Relation {
+        id: 16384,
+        namespace: "public".to_string(),
+        name: "users".to_string(),
+        replica_identity: ReplicaIdentity::Full,
+        columns: vec![
+            RelationColumn {
+                name: "id".to_string(),
+                is_key: true,
+            },
+            RelationColumn {
+                name: "name".to_string(),
+                is_key: false,
+            },
+        ],
+    };
+    assert_eq!(
+        PgoutputMessage::parse(&message)?,
+        PgoutputMessage::Relation(relation.clone())
+    );
+    assert!(relation.matches("users"));
+    assert!(relation.matches("public.users"));
+    assert!(!relation.matches("private.users"));
+    assert!(!relation.matches("orders"));
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_row_changes() -> eyre::Result<()> {
+    let mut insert = vec![b'I'];
+    insert.extend_from_slice(&16384_u32.to_be_bytes());
+    insert.push(b'N');
+    insert.extend(encode_tuple(&[Some("1"), Some("Alice")]));
+    assert_eq!(
+        PgoutputMessage::parse(&insert)?,
+        PgoutputMessage::Insert {
+            relation_id: 16384,
+            new_tuple: vec![
+                TupleColumn::Text("1".to_string()),
+                TupleColumn::Text("Alice".to_string())
+            ],
+        }
+    );
+
+    let mut update = vec![b'U'];
+    update.extend_from_slice(&16384_u32.to_be_bytes());
+    update.push(b'O');
+    update.extend(encode_tuple(&[Some("1"), Some("Alice")]));
+    update.push(b'N');
+    update.extend(encode_tuple(&[Some("1"), None]));
+    assert_eq!(
+        PgoutputMessage::parse(&update)?,
+        PgoutputMessage::Update {
+            relation_id: 16384,
+            old_tuple: Some(vec![
+                TupleColumn::Text("1".to_string()),
+                TupleColumn::Text("Alice".to_string())
+            ]),
+            new_tuple: vec![TupleColumn::Text("1".to_string()), TupleColumn::Null],
+        }
+    );
+
+    let mut update_without_old_tuple = vec![b'U'];
+    update_without_old_tuple.extend_from_slice(&16384_u32.to_be_bytes());
+    update_without_old_tuple.push(b'N');
+    update_without_old_tuple.extend_from_slice(&2_i16.to_be_bytes());
+    update_without_old_tuple.extend_from_slice(&[b't', 0, 0, 0, 1, b'2', b'u']);
+    assert_eq!(
+        PgoutputMessage::parse(&update_without_old_tuple)?,
+        PgoutputMessage::Update {
+            relation_id: 16384,
+            old_tuple: None,
+            new_tuple: vec![
+                TupleColumn::Text("2".to_string()),
+                TupleColumn::UnchangedToast
+            ],
+        }
+    );
+
+    let mut delete = vec![b'D'];
+    delete.extend_from_slice(&16384_u32.to_be_bytes());
+    delete.push(b'K');
+    delete.extend(encode_tuple(&[Some("1"), None]));
+    assert_eq!(
+        PgoutputMessage::parse(&delete)?,
+        PgoutputMessage::Delete {
+            relation_id: 16384,
+            old_tuple: vec![TupleColumn::Text("1".to_string()), TupleColumn::Null],
+        }
+    );
+
+    let mut truncate = vec![b'T'];
+    truncate.extend_from_slice(&2_u32.to_be_bytes());
+    truncate.push(0);
+    truncate.extend_from_slice(&16384_u32.to_be_bytes());
+    truncate.extend_from_slice(&16390_u32.to_be_bytes());
+    assert_eq!(
+        PgoutputMessage::parse(&truncate)?,
+        PgoutputMessage::Truncate {
+            relation_ids: vec![16384, 16390]
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_malformed_messages() {
+    assert_matches!(
+        PgoutputMessage::parse(&[b'B', 0, 0]),
+        Err(PgoutputError::UnexpectedEnd)
+    );
+    assert_matches!(
+        PgoutputMessage::parse(b"Z"),
+        Err(PgoutputError::UnknownMessageType('Z'))
+    );
+    assert_matches!(
+        PgoutputMessage::parse(&[b'I', 0, 0, 64, 0, b'X']),
+        Err(PgoutputError::UnexpectedTupleMarker('X'))
+    );
+    assert_matches!(
+        PgoutputMessage::parse(&[b'I', 0, 0, 64, 0, b'N', 0, 1, b'q']),
+        Err(PgoutputError::UnknownColumnKind('q'))
+    );
+}
+
+#[test]
+fn test_lsn_formatting() -> eyre::Result<()> {
+    assert_eq!(format_lsn(0x16_B374_D848), "16/B374D848");
+    assert_eq!(format_lsn(0), "0/0");
+    assert_eq!(parse_lsn("16/B374D848")?, 0x16_B374_D848);
+    assert_eq!(parse_lsn(&format_lsn(u64::MAX))?, u64::MAX);
+    assert_matches!(parse_lsn("16B374D848"), Err(PgoutputError::InvalidLsn(_)));
+    Ok(())
+}
+
+#[test]
+fn test_parse_text_values() {
+    assert_eq!(parse_text_value("t", &Type::Bool), Some(Value::Bool(true)));
+    assert_eq!(parse_text_value("f", &Type::Bool), Some(Value::Bool(false)));
+    assert_eq!(parse_text_value("-42", &Type::Int), Some(Value::Int(-42)));
+    assert_eq!(
+        parse_text_value("1.5", &Type::Float),
+        Some(Value::Float(1.5.into()))
+    );
+    assert_eq!(
+        parse_text_value("abc", &Type::Optional(Type::String.into())),
+        Some(Value::from("abc"))
+    );
+    assert_eq!(
+        parse_text_value("\\x0aff", &Type::Bytes),
+        Some(Value::Bytes(vec![0x0a, 0xff].into()))
+    );
+    assert_eq!(
+        parse_text_value(r#"{"a": [1, 2]}"#, &Type::Json),
+        Some(Value::from(serde_json::json!({"a": [1, 2]})))
+    );
+    assert!(matches!(
+        parse_text_value("2024-01-02 03:04:05.123456", &Type::DateTimeNaive),
+        Some(Value::DateTimeNaive(_))
+    ));
+    assert!(matches!(
+        parse_text_value("2024-01-02 03:04:05.123456+02", &Type::DateTimeUtc),
+        Some(Value::DateTimeUtc(_))
+    ));
+    assert_eq!(parse_text_value("abc", &Type::Int), None);
+    assert_eq!(parse_text_value("yes", &Type::Bool), None);
+}
@@ -0,0 +1,157 @@
+// Copyright © 2024 Pathway
+
+use crate::helpers::ReplaceErrors;
+
+use std::collections::{BTreeMap, HashMap};
+
+use pathway_engine::connectors::data_format::{
+    InnerSchemaField, JsonLinesParser, MessageMetadataField, ParseError, ParsedEvent, Parser,
+};
+use pathway_engine::connectors::data_storage::{DataEventType, ReaderContext};
+use pathway_engine::connectors::metadata::{PubSubMetadata, SourceMetadata};
+use pathway_engine::connectors::SessionType;
+use pathway_engine::engine::{DateTimeUtc, Type, Value};
+
+fn pubsub_metadata(
+    publish_time_millis: Option<i64>,
+    attributes: &[(&str, &str)],
+) -> PubSubMetadata {
+    let attributes: BTreeMap<String, String> = attributes
+        .iter()
+        .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+        .collect();
+    PubSubMetadata::new(
+        "projects/my-project/subscriptions/orders".to_string(),
+        "1234567890".to_string(),
+        publish_time_millis,
+        Some("alice".to_string()),
+        None,
+        attributes,
+    )
+}
+
+fn attributes_parser() -> eyre::Result<JsonLinesParser> {
+    let schema = [
+        ("amount".to_string(), InnerSchemaField::new(Type::Int, None)),
+        (
+            "store".to_string(),
+            InnerSchemaField::new(Type::String, Some(Value::from("unknown"))),
+        ),
+        (
+            "priority".to_string(),
+            InnerSchemaField::new(Type::Optional(Type::Int.into()), None),
+        ),
+        (
+            "published_at".to_string(),
+            InnerSchemaField::new(Type::Optional(Type::DateTimeUtc.into()), None),
+        ),
+    ];
+    let parser = JsonLinesParser::new(
+        None,
+        vec![
+            "amount".to_string(),
+            "store".to_string(),
+            "priority".to_string(),
+            "published_at".to_string(),
+        ],
+        HashMap::new(),
+        true,
+        schema.into(),
+        SessionType::Native,
+    )?
+    .with_message_metadata_fields(vec![
+        (
+            "store".to_string(),
+            MessageMetadataField::PubSubAttribute("store".to_string()),
+        ),
+        (
+            "priority".to_string(),
+            MessageMetadataField::PubSubAttribute("priority".to_string()),
+        ),
+        (
+            "published_at".to_string(),
+            MessageMetadataField::PubSubPublishTime,
+        ),
+    ])?;
+    Ok(parser)
+}
+
+fn parse_message(
+    parser: &mut dyn Parser,
+    metadata: &SourceMetadata,
+    payload: &str,
+) -> eyre::Result<Vec<ParsedEvent>> {
+    parser.on_new_source_started(metadata);
+    let context = ReaderContext::from_raw_bytes(DataEventType::Insert, payload.as_bytes().to_vec());
+    let parsed = parser.parse(&context).map_err(ParseError::from)?;
+    Ok(parsed
+        .into_iter()
+        .map(|event| event.replace_errors())
+        .collect())
+}
+
+#[test]
+fn test_pubsub_metadata_serialization() {
+    let metadata: SourceMetadata =
+        pubsub_metadata(Some(1_700_000_000_000), &[("store", "berlin")]).into();
+    assert_eq!(
+        metadata.serialize(),
+        serde_json::json!({
+            "subscription": "projects/my-project/subscriptions/orders",
+            "message_id": "1234567890",
+            "publish_time_millis": 1_700_000_000_000_i64,
+            "ordering_key": "alice",
+            "delivery_attempt": null,
+            "attributes": {"store": "berlin"},
+        })
+    );
+    assert!(metadata.commits_allowed_in_between());
+}
+
+#[test]
+fn test_pubsub_attributes_and_publish_time_as_fields() -> eyre::Result<()> {
+    let mut parser = attributes_parser()?;
+    let metadata = pubsub_metadata(
+        Some(1_704_164_645_123),
+        &[("store", "berlin"), ("priority", "2")],
+    )
+    .into();
+
+    let events = parse_message(&mut parser, &metadata, r#"{"amount": 10}"#)?;
+    assert_eq!(
+        events,
+        vec![ParsedEvent::Insert((
+            None,
+            vec![
+                Value::Int(10),
+                Value::from("berlin"),
+                Value::Int(2),
+                Value::from(DateTimeUtc::from_timestamp(1_704_164_645_123, "ms")?),
+            ]
+        ))]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_pubsub_missing_attributes() -> eyre::Result<()> {
+    let mut parser = attributes_parser()?;
+    let metadata = pubsub_metadata(None, &[]).into();
+
+    let events = parse_message(&mut parser, &metadata, r#"{"amount": 10}"#)?;
+    assert_eq!(
+        events,
+        vec![ParsedEvent::Insert((
+            None,
+            vec![
+                Value::Int(10),
+                Value::from("unknown"),
+                Value::None,
+                Value::None,
+            ]
+        ))]
+    );
+
+    Ok(())
+}
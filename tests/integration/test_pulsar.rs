@@ -0,0 +1,74 @@
+// Copyright © 2024 Pathway
+
+use pathway_engine::connectors::metadata::{PulsarMetadata, SourceMetadata};
+use pathway_engine::connectors::pulsar::{java_string_hash, key_partition, message_key, MessageId};
+
+#[test]
+fn test_java_string_hash() {
+    assert_eq!(java_string_hash(""), 0);
+    assert_eq!(java_string_hash("hello"), 99_162_322);
+    // The hash of the key is negative in Java, so its sign bit is cleared
+    assert_eq!(
+        java_string_hash("The quick brown fox jumps over the lazy dog"),
+        1_538_055_507
+    );
+    assert_eq!(java_string_hash("polygenelubricants"), 0);
+    // The characters outside of the Basic Multilingual Plane take two code units
+    assert_eq!(java_string_hash("\u{1F600}"), 1_772_899);
+}
+
+#[test]
+fn test_key_partition() {
+    assert_eq!(key_partition("hello", 1), 0);
+    assert_eq!(key_partition("hello", 3), 1);
+    assert_eq!(key_partition("hello", 5), 2);
+    assert_eq!(
+        key_partition("The quick brown fox jumps over the lazy dog", 7),
+        2
+    );
+}
+
+#[test]
+fn test_message_key() {
+    assert_eq!(message_key(b"alice".to_vec()), "alice");
+    assert_eq!(message_key(vec![0xff, 0x00, 0x01]), "/wAB");
+}
+
+#[test]
+fn test_message_id_ordering() {
+    let id = |ledger_id, entry_id, batch_index| MessageId {
+        ledger_id,
+        entry_id,
+        batch_index,
+    };
+    assert!(id(1, 5, -1) < id(1, 6, -1));
+    assert!(id(1, 5, 0) < id(1, 5, 1));
+    assert!(id(1, 9, 3) < id(2, 0, 0));
+}
+
+#[test]
+fn test_pulsar_metadata_serialization() {
+    let metadata: SourceMetadata = PulsarMetadata::new(
+        "persistent://public/default/orders-partition-0".to_string(),
+        Some("alice".to_string()),
+        1_700_000_000_000,
+        None,
+        12,
+        34,
+        -1,
+    )
+    .into();
+    assert_eq!(
+        metadata.serialize(),
+        serde_json::json!({
+            "topic": "persistent://public/default/orders-partition-0",
+            "key": "alice",
+            "publish_time_millis": 1_700_000_000_000_u64,
+            "event_time_millis": null,
+            "ledger_id": 12,
+            "entry_id": 34,
+            "batch_index": -1,
+        })
+    );
+    assert!(metadata.commits_allowed_in_between());
+}
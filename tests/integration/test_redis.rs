@@ -0,0 +1,80 @@
+// Copyright © 2024 Pathway
+
+use pathway_engine::connectors::redis_stream::{format_field_value, parse_field_value, EntryId};
+use pathway_engine::engine::{DateTimeNaive, Type, Value};
+
+#[test]
+fn test_entry_id_parsing() -> eyre::Result<()> {
+    let entry_id: EntryId = "1526919030474-55".parse()?;
+    assert_eq!(
+        entry_id,
+        EntryId {
+            milliseconds: 1_526_919_030_474,
+            sequence: 55
+        }
+    );
+    assert_eq!(entry_id.to_string(), "1526919030474-55");
+    assert!(entry_id < "1526919030474-56".parse()?);
+    assert!(entry_id < "1526919030475-0".parse()?);
+
+    assert!("1526919030474".parse::<EntryId>().is_err());
+    assert!("abc-1".parse::<EntryId>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_field_value() -> eyre::Result<()> {
+    assert_eq!(parse_field_value(b"42", &Type::Int), Some(Value::Int(42)));
+    assert_eq!(
+        parse_field_value(b"12.5", &Type::Optional(Type::Float.into())),
+        Some(Value::Float(12.5.into()))
+    );
+    assert_eq!(
+        parse_field_value(b"1", &Type::Bool),
+        Some(Value::Bool(true))
+    );
+    assert_eq!(
+        parse_field_value(b"False", &Type::Bool),
+        Some(Value::Bool(false))
+    );
+    assert_eq!(
+        parse_field_value(b"alice", &Type::String),
+        Some(Value::from("alice"))
+    );
+    assert_eq!(
+        parse_field_value(&[0, 255], &Type::Bytes),
+        Some(Value::Bytes(vec![0, 255].into()))
+    );
+    assert_eq!(
+        parse_field_value(b"2024-01-02T03:04:05", &Type::DateTimeNaive),
+        Some(Value::DateTimeNaive(DateTimeNaive::strptime(
+            "2024-01-02T03:04:05",
+            "%Y-%m-%dT%H:%M:%S"
+        )?))
+    );
+    assert_eq!(parse_field_value(b"abc", &Type::Int), None);
+    assert_eq!(parse_field_value(&[0, 255], &Type::String), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_format_field_value() -> eyre::Result<()> {
+    assert_eq!(
+        format_field_value(&Value::from("alice"))?,
+        Some(b"alice".to_vec())
+    );
+    assert_eq!(format_field_value(&Value::Int(42))?, Some(b"42".to_vec()));
+    assert_eq!(
+        format_field_value(&Value::Bytes(vec![0, 255].into()))?,
+        Some(vec![0, 255])
+    );
+    assert_eq!(
+        format_field_value(&Value::from(serde_json::json!({"a": [1, 2]})))?,
+        Some(br#"{"a":[1,2]}"#.to_vec())
+    );
+    assert_eq!(format_field_value(&Value::None)?, None);
+
+    Ok(())
+}
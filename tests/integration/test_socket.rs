@@ -0,0 +1,383 @@
+// Copyright © 2024 Pathway
+
+use crate::helpers::ReplaceErrors;
+
+use std::io::Write;
+use std::net::{SocketAddr, TcpStream};
+use std::sync::mpsc;
+
+use chrono::{DateTime, Utc};
+
+use pathway_engine::connectors::data_format::{
+    InnerSchemaField, ParseError, ParsedEvent, Parser, SyslogParser,
+};
+use pathway_engine::connectors::data_storage::{
+    DataEventType, ReadResult, Reader, ReaderContext, SocketReader,
+};
+use pathway_engine::connectors::metadata::{SocketMetadata, SourceMetadata};
+use pathway_engine::connectors::socket::{
+    FrameDecoder, Framing, FramingError, ListenerHandle, Protocol, SOCKET_READER_QUEUE_SIZE,
+};
+use pathway_engine::connectors::syslog::{Error as SyslogParseError, Message as SyslogMessage};
+use pathway_engine::connectors::{OffsetKey, OffsetValue};
+use pathway_engine::engine::{DateTimeUtc, Type, Value};
+
+fn decode_all(decoder: &mut FrameDecoder, data: &[u8]) -> Result<Vec<Vec<u8>>, FramingError> {
+    decoder.extend(data);
+    let mut messages = Vec::new();
+    while let Some(message) = decoder.next_message()? {
+        messages.push(message);
+    }
+    Ok(messages)
+}
+
+fn utc(timestamp: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .expect("timestamp must be valid")
+        .to_utc()
+}
+
+#[test]
+fn test_line_framing() -> eyre::Result<()> {
+    let mut decoder = FrameDecoder::new(Framing::Lines, 16);
+    assert_eq!(
+        decode_all(&mut decoder, b"first\r\n\nsec")?,
+        vec![b"first".to_vec()]
+    );
+    assert_eq!(
+        decode_all(&mut decoder, b"ond\nthi")?,
+        vec![b"second".to_vec()]
+    );
+    assert!(decode_all(&mut decoder, b"rd")?.is_empty());
+    assert_eq!(decoder.finish(), Some(b"third".to_vec()));
+
+    let mut decoder = FrameDecoder::new(Framing::Lines, 4);
+    assert_eq!(
+        decode_all(&mut decoder, b"too long"),
+        Err(FramingError::TooLong {
+            length: 8,
+            limit: 4
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_length_prefixed_framing() -> eyre::Result<()> {
+    let mut decoder = FrameDecoder::new(Framing::LengthPrefixed, 16);
+    assert_eq!(
+        decode_all(&mut decoder, b"\0\0\0\x03abc\0\0\0\0\0\0")?,
+        vec![b"abc".to_vec(), Vec::new()]
+    );
+    assert_eq!(decode_all(&mut decoder, b"\0\x02x")?, Vec::<Vec<u8>>::new());
+    assert_eq!(decode_all(&mut decoder, b"y")?, vec![b"xy".to_vec()]);
+    assert_eq!(decoder.finish(), None);
+
+    let mut decoder = FrameDecoder::new(Framing::LengthPrefixed, 16);
+    assert_eq!(
+        decode_all(&mut decoder, b"\0\0\x01\0"),
+        Err(FramingError::TooLong {
+            length: 256,
+            limit: 16
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_octet_counting_framing() -> eyre::Result<()> {
+    let mut decoder = FrameDecoder::new(Framing::OctetCounting, 64);
+    assert_eq!(
+        decode_all(&mut decoder, b"5 <13>a11 <13>hello")?,
+        vec![b"<13>a".to_vec()]
+    );
+    assert_eq!(
+        decode_all(&mut decoder, b" w")?,
+        vec![b"<13>hello w".to_vec()]
+    );
+    assert_eq!(
+        decode_all(&mut decoder, b"orld"),
+        Err(FramingError::InvalidOctetCount("orld".to_string()))
+    );
+
+    let mut decoder = FrameDecoder::new(Framing::OctetCounting, 64);
+    assert_eq!(
+        decode_all(&mut decoder, b"<13>a\n"),
+        Err(FramingError::InvalidOctetCount("<13>a\n".to_string()))
+    );
+    let mut decoder = FrameDecoder::new(Framing::OctetCounting, 64);
+    assert_eq!(
+        decode_all(&mut decoder, b"05 <13>a"),
+        Err(FramingError::InvalidOctetCount("05".to_string()))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_rfc5424_syslog_message() -> eyre::Result<()> {
+    let line = "<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 \
+        [exampleSDID@32473 iut=\"3\" eventSource=\"Application\" eventID=\"1011\"]\
+        [examplePriority@32473 class=\"high\" note=\"a \\\"quoted\\\" \\] value\"] \
+        \u{feff}An application event log entry...";
+    let message = SyslogMessage::parse(line, Utc::now())?;
+    assert_eq!(message.facility, 20);
+    assert_eq!(message.severity, 5);
+    assert_eq!(message.version, Some(1));
+    assert_eq!(
+        message.timestamp,
+        Some(DateTime::parse_from_rfc3339("2003-10-11T22:14:15.003Z")?)
+    );
+    assert_eq!(message.hostname.as_deref(), Some("mymachine.example.com"));
+    assert_eq!(message.app_name.as_deref(), Some("evntslog"));
+    assert_eq!(message.procid, None);
+    assert_eq!(message.msgid.as_deref(), Some("ID47"));
+    assert_eq!(
+        serde_json::Value::Object(message.structured_data),
+        serde_json::json!({
+            "exampleSDID@32473": {"iut": "3", "eventSource": "Application", "eventID": "1011"},
+            "examplePriority@32473": {"class": "high", "note": "a \"quoted\" ] value"},
+        })
+    );
+    assert_eq!(message.message, "An application event log entry...");
+
+    let message = SyslogMessage::parse("<34>1 - - - - - -", Utc::now())?;
+    assert_eq!(message.timestamp, None);
+    assert_eq!(message.hostname, None);
+    assert!(message.structured_data.is_empty());
+    assert_eq!(message.message, "");
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_rfc3164_syslog_message() -> eyre::Result<()> {
+    let now = utc("2024-03-10T12:00:00Z");
+    let message = SyslogMessage::parse(
+        "<34>Oct 11 22:14:15 mymachine su[230]: 'su root' failed for lonvick on /dev/pts/8",
+        now,
+    )?;
+    assert_eq!(message.facility, 4);
+    assert_eq!(message.severity, 2);
+    assert_eq!(message.version, None);
+    // October is closer to March when it's in the previous year
+    assert_eq!(
+        message.timestamp,
+        Some(utc("2023-10-11T22:14:15Z").fixed_offset())
+    );
+    assert_eq!(message.hostname.as_deref(), Some("mymachine"));
+    assert_eq!(message.app_name.as_deref(), Some("su"));
+    assert_eq!(message.procid.as_deref(), Some("230"));
+    assert_eq!(
+        message.message,
+        "'su root' failed for lonvick on /dev/pts/8"
+    );
+
+    let message = SyslogMessage::parse("<13>Mar  2 08:00:01 cron: job started", now)?;
+    assert_eq!(
+        message.timestamp,
+        Some(utc("2024-03-02T08:00:01Z").fixed_offset())
+    );
+    assert_eq!(message.hostname, None);
+    assert_eq!(message.app_name.as_deref(), Some("cron"));
+    assert_eq!(message.procid, None);
+    assert_eq!(message.message, "job started");
+
+    let message = SyslogMessage::parse("<13>no header at all", now)?;
+    assert_eq!(message.timestamp, None);
+    assert_eq!(message.app_name, None);
+    assert_eq!(message.message, "no header at all");
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_rfc3164_syslog_message_starting_with_number() -> eyre::Result<()> {
+    let now = utc("2024-03-10T12:00:00Z");
+    // Only the version 1 followed by a timestamp or the nil value starts
+    // an RFC 5424 header
+    for line in [
+        "<13>1 disk is full",
+        "<13>12 workers started",
+        "<13>1 yesterday - - - - -",
+        "<13>2 2024-01-02T03:04:05Z host app - - - hello",
+    ] {
+        let message = SyslogMessage::parse(line, now)?;
+        assert_eq!(message.version, None);
+        assert_eq!(message.timestamp, None);
+        assert_eq!(message.hostname, None);
+        assert_eq!(message.message, line[4..]);
+    }
+
+    let message = SyslogMessage::parse("<13>1 2024-01-02T03:04:05Z host app - - - hello", now)?;
+    assert_eq!(message.version, Some(1));
+    assert_eq!(
+        message.timestamp,
+        Some(utc("2024-01-02T03:04:05Z").fixed_offset())
+    );
+    assert_eq!(message.hostname.as_deref(), Some("host"));
+    assert_eq!(message.message, "hello");
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_invalid_syslog_messages() {
+    let now = Utc::now();
+    assert_eq!(
+        SyslogMessage::parse("no priority", now),
+        Err(SyslogParseError::MissingPriority)
+    );
+    assert_eq!(
+        SyslogMessage::parse("<192>1 - - - - - -", now),
+        Err(SyslogParseError::InvalidPriority("192".to_string()))
+    );
+    assert_eq!(
+        SyslogMessage::parse("<13>1 - host", now),
+        Err(SyslogParseError::MissingHeaderField("HOSTNAME"))
+    );
+    assert!(matches!(
+        SyslogMessage::parse("<13>1 - - - - - [id x=\"1\"", now),
+        Err(SyslogParseError::InvalidStructuredData(_))
+    ));
+}
+
+#[test]
+fn test_syslog_parser() -> eyre::Result<()> {
+    let schema = [
+        (
+            "severity".to_string(),
+            InnerSchemaField::new(Type::Int, None),
+        ),
+        (
+            "timestamp".to_string(),
+            InnerSchemaField::new(Type::Optional(Type::DateTimeUtc.into()), None),
+        ),
+        (
+            "hostname".to_string(),
+            InnerSchemaField::new(Type::Optional(Type::String.into()), None),
+        ),
+        (
+            "procid".to_string(),
+            InnerSchemaField::new(Type::Optional(Type::String.into()), None),
+        ),
+        (
+            "structured_data".to_string(),
+            InnerSchemaField::new(Type::Json, None),
+        ),
+        (
+            "message".to_string(),
+            InnerSchemaField::new(Type::String, None),
+        ),
+        (
+            "_metadata".to_string(),
+            InnerSchemaField::new(Type::Json, None),
+        ),
+    ];
+    let field_names = schema.iter().map(|(name, _)| name.clone()).collect();
+    let mut parser = SyslogParser::new(field_names, schema.into())?;
+    let metadata: SourceMetadata =
+        SocketMetadata::new("udp", SocketAddr::from(([10, 0, 0, 7], 51514))).into();
+    parser.on_new_source_started(&metadata);
+
+    let context = ReaderContext::from_raw_bytes(
+        DataEventType::Insert,
+        b"<11>1 2024-01-02T03:04:05.5+02:00 web-1 nginx - - [meta@1 k=\"v\"] upstream timed out\n"
+            .to_vec(),
+    );
+    let events: Vec<ParsedEvent> = parser
+        .parse(&context)
+        .map_err(ParseError::from)?
+        .into_iter()
+        .map(|event| event.replace_errors())
+        .collect();
+    assert_eq!(
+        events,
+        vec![ParsedEvent::Insert((
+            None,
+            vec![
+                Value::Int(3),
+                Value::from(DateTimeUtc::from_timestamp(1_704_157_445_500, "ms")?),
+                Value::from("web-1"),
+                Value::None,
+                Value::from(serde_json::json!({"meta@1": {"k": "v"}})),
+                Value::from("upstream timed out"),
+                Value::from(serde_json::json!({
+                    "protocol": "udp",
+                    "source_address": "10.0.0.7",
+                    "source_port": 51514,
+                })),
+            ]
+        ))]
+    );
+
+    let context = ReaderContext::from_raw_bytes(DataEventType::Insert, b"not syslog".to_vec());
+    assert!(parser.parse(&context).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_socket_metadata_serialization() {
+    let metadata: SourceMetadata =
+        SocketMetadata::new("tcp", "[::1]:40000".parse().expect("address must be valid")).into();
+    assert_eq!(
+        metadata.serialize(),
+        serde_json::json!({
+            "protocol": "tcp",
+            "source_address": "::1",
+            "source_port": 40000,
+        })
+    );
+    assert!(metadata.commits_allowed_in_between());
+}
+
+#[test]
+fn test_tcp_socket_reader() -> eyre::Result<()> {
+    let (message_sender, message_receiver) = mpsc::sync_channel(SOCKET_READER_QUEUE_SIZE);
+    let listener =
+        ListenerHandle::start_tcp("127.0.0.1:0".parse()?, Framing::Lines, 1024, message_sender)?;
+    let address = listener.local_address();
+    let mut reader = SocketReader::new(Protocol::Tcp, Some(listener), message_receiver, None);
+
+    let mut stream = TcpStream::connect(address)?;
+    let client_port = stream.local_addr()?.port();
+    stream.write_all(b"first\nsecond\n")?;
+
+    for (index, expected_payload) in ["first", "second"].into_iter().enumerate() {
+        let ReadResult::NewSource(metadata) = reader.read()? else {
+            panic!("the metadata must be read before the message");
+        };
+        assert_eq!(
+            metadata.serialize(),
+            serde_json::json!({
+                "protocol": "tcp",
+                "source_address": "127.0.0.1",
+                "source_port": client_port,
+            })
+        );
+        let read_result = reader.read()?;
+        let ReadResult::Data(ReaderContext::RawBytes(DataEventType::Insert, payload), offset) =
+            read_result
+        else {
+            panic!("unexpected read result: {read_result:?}");
+        };
+        assert_eq!(payload, expected_payload.as_bytes());
+        assert_eq!(
+            offset,
+            (
+                OffsetKey::Socket,
+                OffsetValue::SocketReadMessagesCount(index + 1)
+            )
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_socket_reader_without_listener_finishes() -> eyre::Result<()> {
+    let (_, message_receiver) = mpsc::sync_channel(SOCKET_READER_QUEUE_SIZE);
+    let mut reader = SocketReader::new(Protocol::Udp, None, message_receiver, None);
+    assert!(matches!(reader.read()?, ReadResult::Finished));
+    Ok(())
+}
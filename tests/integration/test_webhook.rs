@@ -0,0 +1,262 @@
+// Copyright © 2024 Pathway
+
+use std::sync::mpsc as std_mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::sync::{mpsc, oneshot};
+
+use pathway_engine::async_runtime::create_async_tokio_runtime;
+use pathway_engine::connectors::data_storage::{
+    DataEventType, ReadResult, Reader, ReaderContext, WebhookReader, WebhookRetryPolicy,
+};
+use pathway_engine::connectors::metadata::{SourceMetadata, WebhookMetadata};
+use pathway_engine::connectors::webhook::{
+    parse_retry_after, sign_body, split_body_into_rows, BatchFormat, ReceivedRequest, UrlTemplate,
+    UrlTemplateError, WEBHOOK_READER_QUEUE_SIZE,
+};
+use pathway_engine::connectors::{OffsetKey, OffsetValue};
+use pathway_engine::engine::{Timestamp, Value};
+use pathway_engine::persistence::frontier::OffsetAntichain;
+
+fn request(rows: &[&str]) -> (ReceivedRequest, oneshot::Receiver<()>) {
+    let (accepted, accepted_receiver) = oneshot::channel();
+    let request = ReceivedRequest {
+        route: "/orders".to_string(),
+        peer_address: None,
+        rows: rows.iter().map(|row| row.as_bytes().to_vec()).collect(),
+        accepted,
+    };
+    (request, accepted_receiver)
+}
+
+fn requests_frontier(count: usize) -> OffsetAntichain {
+    let mut frontier = OffsetAntichain::new();
+    frontier.advance_offset(
+        OffsetKey::Webhook,
+        OffsetValue::WebhookReadRequestsCount(count),
+    );
+    frontier
+}
+
+fn read_request(reader: &mut WebhookReader, expected_rows: &[&str]) -> eyre::Result<()> {
+    assert!(matches!(reader.read()?, ReadResult::NewSource(_)));
+    for expected_row in expected_rows {
+        let read_result = reader.read()?;
+        let ReadResult::Data(ReaderContext::RawBytes(DataEventType::Insert, row), _) = read_result
+        else {
+            panic!("unexpected read result: {read_result:?}");
+        };
+        assert_eq!(row, expected_row.as_bytes());
+    }
+    assert!(matches!(
+        reader.read()?,
+        ReadResult::FinishedSource {
+            commit_allowed: true
+        }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_split_json_lines_body() -> eyre::Result<()> {
+    let body = b"{\"a\": 1}\r\n\n  {\"a\": 2}\n";
+    assert_eq!(
+        split_body_into_rows(body, false)?,
+        vec![br#"{"a": 1}"#.to_vec(), br#"{"a": 2}"#.to_vec()]
+    );
+    assert!(split_body_into_rows(b"\n\n", false)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_split_json_document_body() -> eyre::Result<()> {
+    let body = br#"[{"a": 1}, {"a": 2, "b": [true]}]"#;
+    assert_eq!(
+        split_body_into_rows(body, true)?,
+        vec![br#"{"a":1}"#.to_vec(), br#"{"a":2,"b":[true]}"#.to_vec()]
+    );
+
+    let body = b"{\n  \"a\": 1\n}";
+    assert_eq!(
+        split_body_into_rows(body, true)?,
+        vec![br#"{"a":1}"#.to_vec()]
+    );
+
+    assert!(split_body_into_rows(b"{\"a\": ", true).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_webhook_metadata_serialization() {
+    let metadata: SourceMetadata =
+        WebhookMetadata::new("/orders".to_string(), Some("127.0.0.1:53412".to_string())).into();
+    assert_eq!(
+        metadata.serialize(),
+        serde_json::json!({
+            "route": "/orders",
+            "peer_address": "127.0.0.1:53412",
+        })
+    );
+    assert!(!metadata.commits_allowed_in_between());
+}
+
+#[test]
+fn test_webhook_reader_responds_after_reading() -> eyre::Result<()> {
+    let (request_sender, request_receiver) = mpsc::channel(WEBHOOK_READER_QUEUE_SIZE);
+    let mut reader = WebhookReader::new(
+        create_async_tokio_runtime()?,
+        None,
+        request_receiver,
+        None,
+        false,
+    );
+    assert!(!reader.processed_frontier_needed());
+
+    let (first_request, mut first_accepted) = request(&[r#"{"a": 1}"#, r#"{"a": 2}"#]);
+    request_sender.try_send(first_request)?;
+    read_request(&mut reader, &[r#"{"a": 1}"#, r#"{"a": 2}"#])?;
+    // The time advancement closing the request hasn't been passed to the engine yet
+    assert!(first_accepted.try_recv().is_err());
+
+    let (second_request, mut second_accepted) = request(&[r#"{"a": 3}"#]);
+    request_sender.try_send(second_request)?;
+    assert!(matches!(reader.read()?, ReadResult::NewSource(_)));
+    assert!(first_accepted.try_recv().is_ok());
+    assert!(second_accepted.try_recv().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_webhook_reader_responds_after_processing() -> eyre::Result<()> {
+    let (request_sender, request_receiver) = mpsc::channel(WEBHOOK_READER_QUEUE_SIZE);
+    let mut reader = WebhookReader::new(
+        create_async_tokio_runtime()?,
+        None,
+        request_receiver,
+        None,
+        true,
+    );
+    assert!(reader.processed_frontier_needed());
+    let (processed_frontier_sender, processed_frontier_receiver) = std_mpsc::channel();
+    reader.set_processed_frontier_receiver(processed_frontier_receiver);
+
+    let (first_request, mut first_accepted) = request(&[r#"{"a": 1}"#]);
+    let (second_request, mut second_accepted) = request(&[r#"{"a": 2}"#]);
+    request_sender.try_send(first_request)?;
+    request_sender.try_send(second_request)?;
+    read_request(&mut reader, &[r#"{"a": 1}"#])?;
+    read_request(&mut reader, &[r#"{"a": 2}"#])?;
+
+    // Both requests have been passed to the engine, but only the first one
+    // has been processed
+    processed_frontier_sender.send(requests_frontier(1))?;
+    let (third_request, mut third_accepted) = request(&[r#"{"a": 3}"#]);
+    request_sender.try_send(third_request)?;
+    assert!(matches!(reader.read()?, ReadResult::NewSource(_)));
+    assert!(first_accepted.try_recv().is_ok());
+    assert!(second_accepted.try_recv().is_err());
+    assert!(third_accepted.try_recv().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_url_template() -> eyre::Result<()> {
+    let column_names = vec!["user".to_string(), "total".to_string()];
+    let template = UrlTemplate::parse(
+        "https://example.com/users/{table.user}/totals?value={table.total}&time={table.time}&diff={table.diff}",
+        &column_names,
+    )?;
+    assert_eq!(
+        template.render(
+            &[Value::from("alice smith/2"), Value::from(12_i64)],
+            Timestamp(1_700_000_000_000),
+            -1
+        ),
+        "https://example.com/users/alice%20smith%2F2/totals?value=12&time=1700000000000&diff=-1"
+    );
+
+    let constant_template = UrlTemplate::parse("https://example.com/totals", &column_names)?;
+    assert_eq!(
+        constant_template.render(&[Value::from("bob"), Value::from(3_i64)], Timestamp(2), 1),
+        "https://example.com/totals"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_url_template_errors() {
+    let column_names = vec!["user".to_string()];
+    assert!(matches!(
+        UrlTemplate::parse("https://example.com/{table.owner}", &column_names),
+        Err(UrlTemplateError::UnknownColumn(name)) if name == "owner"
+    ));
+    assert!(matches!(
+        UrlTemplate::parse("https://example.com/{table.user", &column_names),
+        Err(UrlTemplateError::UnclosedWildcard)
+    ));
+}
+
+#[test]
+fn test_batch_formats() {
+    let payloads = vec![br#"{"a":1}"#.to_vec(), br#"{"a":2}"#.to_vec()];
+    assert_eq!(
+        BatchFormat::JsonLines.join(&payloads),
+        b"{\"a\":1}\n{\"a\":2}\n"
+    );
+    assert_eq!(
+        BatchFormat::JsonArray.join(&payloads),
+        br#"[{"a":1},{"a":2}]"#
+    );
+    assert_eq!(BatchFormat::JsonArray.join(&[]), b"[]");
+    assert_eq!(
+        BatchFormat::JsonLines.content_type(),
+        "application/x-ndjson"
+    );
+    assert_eq!(BatchFormat::JsonArray.content_type(), "application/json");
+}
+
+#[test]
+fn test_sign_body() {
+    // The test case 2 from RFC 4231
+    assert_eq!(
+        sign_body(b"Jefe", b"what do ya want for nothing?"),
+        "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[test]
+fn test_parse_retry_after() {
+    let now = UNIX_EPOCH + Duration::from_secs(1_445_412_480); // Wed, 21 Oct 2015 07:28:00 GMT
+    assert_eq!(
+        parse_retry_after("120", now),
+        Some(Duration::from_secs(120))
+    );
+    assert_eq!(
+        parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+        Some(Duration::from_secs(30))
+    );
+    assert_eq!(
+        parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+        Some(Duration::ZERO)
+    );
+    assert_eq!(parse_retry_after("soon", SystemTime::now()), None);
+}
+
+#[test]
+fn test_webhook_retry_policy_max_delay() {
+    let retry_policy = |max_retries, backoff_factor| WebhookRetryPolicy {
+        max_retries,
+        initial_delay: Duration::from_secs(2),
+        backoff_factor,
+        jitter: Duration::from_millis(1),
+    };
+    assert_eq!(
+        retry_policy(3, 1.5).max_delay(),
+        Duration::from_millis(4500)
+    );
+    assert_eq!(retry_policy(1, 1.5).max_delay(), Duration::from_secs(2));
+    assert_eq!(retry_policy(0, 1.5).max_delay(), Duration::from_secs(2));
+    assert_eq!(retry_policy(1000, 10.0).max_delay(), Duration::MAX);
+}